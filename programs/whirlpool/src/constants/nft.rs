@@ -29,3 +29,7 @@ pub const WPB_METADATA_URI: &str =
 pub const WP_2022_METADATA_NAME_PREFIX: &str = "OWP";
 pub const WP_2022_METADATA_SYMBOL: &str = "OWP";
 pub const WP_2022_METADATA_URI_BASE: &str = "https://position-nft.orca.so/meta";
+
+pub const WPB_2022_METADATA_NAME_PREFIX: &str = "OPB";
+pub const WPB_2022_METADATA_SYMBOL: &str = "OPB";
+pub const WPB_2022_METADATA_URI: &str = WPB_METADATA_URI;
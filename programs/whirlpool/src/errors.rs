@@ -146,6 +146,117 @@ pub enum ErrorCode {
 
     #[msg("Trade resulted in partial fill")]
     PartialFillError, // 0x17a9 (6057)
+
+    #[msg("Invalid fee tier index")]
+    InvalidFeeTierIndex, // 0x17aa (6058)
+    #[msg("Invalid adaptive fee constants")]
+    InvalidAdaptiveFeeConstants, // 0x17ab (6059)
+    #[msg("Oracle account is not initialized for this whirlpool")]
+    OracleNotInitialized, // 0x17ac (6060)
+    #[msg("No oracle observation is available for the requested timestamp")]
+    ObservationUnavailable, // 0x17ad (6061)
+    #[msg("Invalid oracle observation capacity")]
+    InvalidObservationCapacity, // 0x17ae (6062)
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow, // 0x17af (6063)
+
+    #[msg("Limit order amount must be greater than zero")]
+    ZeroLimitOrderAmount, // 0x17b0 (6064)
+    #[msg("Limit order tick index is not on the opposite side of the current price")]
+    InvalidLimitOrderTickIndex, // 0x17b1 (6065)
+    #[msg("Limit order does not belong to the provided tick array")]
+    LimitOrderTickArrayMismatch, // 0x17b2 (6066)
+
+    #[msg("TickArray is not empty. It cannot be closed")]
+    CloseTickArrayNotEmpty, // 0x17b3 (6067)
+    #[msg("Signer is not the payer of the TickArray or the protocol authority")]
+    InvalidTickArrayPayer, // 0x17b4 (6068)
+
+    #[msg("DynamicTickArray must be resized to initialize or uninitialize a tick")]
+    DynamicTickArrayResizeRequired, // 0x17b5 (6069)
+
+    #[msg("Did not meet liquidity min")]
+    LiquidityMinSubceeded, // 0x17b6 (6070)
+
+    #[msg("Invalid multi hop swap route")]
+    InvalidMultiHopSwapRoute, // 0x17b7 (6071)
+    #[msg("Duplicate pool in multi hop swap route")]
+    DuplicateMultiHopPool, // 0x17b8 (6072)
+
+    #[msg("Reward accounts do not match the initialized rewards")]
+    RewardAccountsMismatch, // 0x17b9 (6073)
+
+    #[msg("Reward extension is not initialized for the Whirlpool")]
+    RewardExtensionNotInitialized, // 0x17ba (6074)
+    #[msg("Reward extension is already initialized")]
+    RewardExtensionAlreadyInitialized, // 0x17bb (6075)
+    #[msg("Reward extension is not allocated for the TickArray or Position")]
+    RewardExtensionNotAllocated, // 0x17bc (6076)
+    #[msg("Accounts with a reward extension are not supported by this instruction")]
+    RewardExtensionNotSupported, // 0x17bd (6077)
+
+    #[msg("Invalid reward emission schedule")]
+    InvalidRewardSchedule, // 0x17be (6078)
+
+    #[msg("Whirlpool is paused")]
+    WhirlpoolPaused, // 0x17bf (6079)
+
+    #[msg("Referral fee rate exceeds the maximum allowed")]
+    ReferralFeeRateMaxExceeded, // 0x17c0 (6080)
+    #[msg("Invalid referral fee account")]
+    InvalidReferralFeeAccount, // 0x17c1 (6081)
+
+    #[msg("Token badge of a mint with a transfer hook or permanent delegate is missing")]
+    MissingTokenBadge, // 0x17c2 (6082)
+    #[msg("Transfer hook program is not allowed by the token badge")]
+    TransferHookProgramNotAllowed, // 0x17c3 (6083)
+    #[msg("Permanent delegate is not allowed by the token badge")]
+    PermanentDelegateNotAllowed, // 0x17c4 (6084)
+
+    #[msg("Token mint is paused")]
+    TokenMintPaused, // 0x17c5 (6085)
+
+    #[msg("Flash swap input was not repaid to the vault")]
+    FlashSwapNotRepaid, // 0x17c6 (6086)
+    #[msg("Invalid flash swap callback program")]
+    InvalidFlashSwapCallbackProgram, // 0x17c7 (6087)
+
+    #[msg("Position cannot be locked")]
+    PositionNotLockable, // 0x17c8 (6088)
+    #[msg("Unlock timestamp must be in the future")]
+    InvalidUnlockTimestamp, // 0x17c9 (6089)
+    #[msg("Operation not allowed on a locked position")]
+    PositionLocked, // 0x17ca (6090)
+
+    #[msg("The new tick range is the same as the current tick range")]
+    SameTickRangeNotAllowed, // 0x17cb (6091)
+
+    #[msg("Whirlpool sqrt price is out of the range specified by the user")]
+    SqrtPriceOutOfRange, // 0x17cc (6092)
+
+    #[msg("Oracle account must be writable")]
+    OracleNotWritable, // 0x17cd (6093)
+    #[msg("Oracle account does not belong to this whirlpool")]
+    InvalidOracle, // 0x17ce (6094)
+
+    #[msg("Limit orders are only supported on fixed TickArrays of pools with Token program mints")]
+    LimitOrderNotSupported, // 0x17cf (6095)
+
+    #[msg("DynamicTickArray growth requires a writable position authority and the System program")]
+    TickArrayRentNotFunded, // 0x17d0 (6096)
+
+    #[msg("Reward schedule is already initialized")]
+    RewardScheduleAlreadyInitialized, // 0x17d1 (6097)
+    #[msg("Reward schedule is not initialized")]
+    RewardScheduleNotInitialized, // 0x17d2 (6098)
+
+    #[msg("The recorded payer of the tick rent must be passed as a writable account")]
+    TickArrayRentPayerMissing, // 0x17d3 (6099)
+
+    #[msg("TickArray has no LimitOrderBook, initialize_limit_order_book must be invoked first")]
+    LimitOrderBookNotInitialized, // 0x17d4 (6100)
+    #[msg("LimitOrderBook is already initialized")]
+    LimitOrderBookAlreadyInitialized, // 0x17d5 (6101)
 }
 
 impl From<TryFromIntError> for ErrorCode {
@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+
+// Events are emitted through a self-CPI (`emit_cpi!`), so that indexers can still read them
+// from the inner instructions when the logs are truncated or the instruction is invoked by CPI.
+// The instructions emitting events take the event_authority and program accounts at the end
+// of their account lists.
+//
+// Amounts are the amounts moved in or out of the pool vaults (transfer fee included).
+// The transfer fee fields hold the portion withheld by the Token-2022 TransferFee extension
+// and are always zero for mints owned by the Token program.
+
+#[event]
+pub struct PoolInitialized {
+    pub whirlpool: Pubkey,
+    pub whirlpools_config: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub tick_spacing: u16,
+    pub token_program_a: Pubkey,
+    pub token_program_b: Pubkey,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    pub initial_sqrt_price: u128,
+}
+
+#[event]
+pub struct Traded {
+    pub whirlpool: Pubkey,
+    pub a_to_b: bool,
+    pub pre_sqrt_price: u128,
+    pub post_sqrt_price: u128,
+    pub pre_tick_index: i32,
+    pub post_tick_index: i32,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+}
+
+// Emitted after Traded when swap_v2_with_referral_fee charges a referral fee.
+// The amount is sent to the referral fee account, on top of the input or out of the output amount.
+#[event]
+pub struct ReferralFeePaid {
+    pub whirlpool: Pubkey,
+    pub referral_fee_account: Pubkey,
+    pub referral_fee_mint: Pubkey,
+    pub amount: u64,
+    pub transfer_fee: u64,
+}
+
+#[event]
+pub struct LiquidityIncreased {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub token_a_transfer_fee: u64,
+    pub token_b_transfer_fee: u64,
+}
+
+#[event]
+pub struct LiquidityDecreased {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub token_a_transfer_fee: u64,
+    pub token_b_transfer_fee: u64,
+}
+
+#[event]
+pub struct FeesCollected {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub fee_a_amount: u64,
+    pub fee_b_amount: u64,
+    pub fee_a_transfer_fee: u64,
+    pub fee_b_transfer_fee: u64,
+}
+
+#[event]
+pub struct RewardCollected {
+    pub whirlpool: Pubkey,
+    pub position: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub transfer_fee: u64,
+}
+
+#[event]
+pub struct LimitOrderOpened {
+    pub whirlpool: Pubkey,
+    pub limit_order: Pubkey,
+    pub owner: Pubkey,
+    pub tick_index: i32,
+    pub a_to_b: bool,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LimitOrderCollected {
+    pub whirlpool: Pubkey,
+    pub limit_order: Pubkey,
+    pub a_to_b: bool,
+    pub output_amount: u64,
+}
+
+#[event]
+pub struct LimitOrderCancelled {
+    pub whirlpool: Pubkey,
+    pub limit_order: Pubkey,
+    pub a_to_b: bool,
+    pub input_amount: u64,
+    pub output_amount: u64,
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::*;
+    use anchor_lang::{Discriminator, Event, ToAccountMetas};
+
+    fn traded() -> Traded {
+        Traded {
+            whirlpool: Pubkey::new_unique(),
+            a_to_b: true,
+            pre_sqrt_price: 1 << 64,
+            post_sqrt_price: 1 << 63,
+            pre_tick_index: 0,
+            post_tick_index: -100,
+            input_amount: 1000,
+            output_amount: 900,
+            input_transfer_fee: 10,
+            output_transfer_fee: 9,
+        }
+    }
+
+    #[test]
+    fn test_event_data_is_prefixed_with_discriminator() {
+        let event = traded();
+        let data = event.data();
+        assert_eq!(data[..8], Traded::discriminator());
+
+        let decoded = Traded::try_from_slice(&data[8..]).unwrap();
+        assert_eq!(decoded.whirlpool, event.whirlpool);
+        assert_eq!(decoded.post_sqrt_price, event.post_sqrt_price);
+        assert_eq!(decoded.output_transfer_fee, event.output_transfer_fee);
+    }
+
+    #[test]
+    fn test_event_discriminators_are_unique() {
+        let mut discriminators = vec![
+            PoolInitialized::discriminator(),
+            Traded::discriminator(),
+            ReferralFeePaid::discriminator(),
+            LiquidityIncreased::discriminator(),
+            LiquidityDecreased::discriminator(),
+            FeesCollected::discriminator(),
+            RewardCollected::discriminator(),
+            LimitOrderOpened::discriminator(),
+            LimitOrderCollected::discriminator(),
+            LimitOrderCancelled::discriminator(),
+        ];
+        let len = discriminators.len();
+        discriminators.sort();
+        discriminators.dedup();
+        assert_eq!(discriminators.len(), len);
+    }
+
+    // Instructions emitting events take the event_authority and program accounts
+    // after their own accounts.
+    #[test]
+    fn test_swap_accounts_end_with_event_cpi_accounts() {
+        let event_authority = Pubkey::find_program_address(&[b"__event_authority"], &crate::ID).0;
+        let accounts = crate::accounts::Swap {
+            token_program: Pubkey::new_unique(),
+            token_authority: Pubkey::new_unique(),
+            whirlpool: Pubkey::new_unique(),
+            token_owner_account_a: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_owner_account_b: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_array_0: Pubkey::new_unique(),
+            tick_array_1: Pubkey::new_unique(),
+            tick_array_2: Pubkey::new_unique(),
+            oracle: Pubkey::new_unique(),
+            whirlpools_config_extension: Pubkey::new_unique(),
+            whirlpool_pause: Pubkey::new_unique(),
+            whirlpool_rewards: Pubkey::new_unique(),
+            event_authority,
+            program: crate::ID,
+        };
+        let account_metas = accounts.to_account_metas(None);
+        assert_eq!(account_metas.len(), 16);
+        assert_eq!(account_metas[14].pubkey, event_authority);
+        assert_eq!(account_metas[15].pubkey, crate::ID);
+    }
+
+    #[test]
+    fn test_modify_liquidity_accounts_end_with_event_cpi_accounts() {
+        let accounts = crate::accounts::ModifyLiquidity {
+            whirlpool: Pubkey::new_unique(),
+            token_program: Pubkey::new_unique(),
+            position_authority: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            position_token_account: Pubkey::new_unique(),
+            token_owner_account_a: Pubkey::new_unique(),
+            token_owner_account_b: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            tick_array_lower: Pubkey::new_unique(),
+            tick_array_upper: Pubkey::new_unique(),
+            oracle: Pubkey::new_unique(),
+            whirlpools_config_extension: Pubkey::new_unique(),
+            whirlpool_pause: Pubkey::new_unique(),
+            whirlpool_rewards: Pubkey::new_unique(),
+            event_authority: Pubkey::new_unique(),
+            program: crate::ID,
+        };
+        assert_eq!(accounts.to_account_metas(None).len(), 17);
+    }
+
+    #[test]
+    fn test_collect_fees_accounts_end_with_event_cpi_accounts() {
+        let accounts = crate::accounts::CollectFees {
+            whirlpool: Pubkey::new_unique(),
+            position_authority: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            position_token_account: Pubkey::new_unique(),
+            token_owner_account_a: Pubkey::new_unique(),
+            token_vault_a: Pubkey::new_unique(),
+            token_owner_account_b: Pubkey::new_unique(),
+            token_vault_b: Pubkey::new_unique(),
+            token_program: Pubkey::new_unique(),
+            event_authority: Pubkey::new_unique(),
+            program: crate::ID,
+        };
+        assert_eq!(accounts.to_account_metas(None).len(), 11);
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    events::LimitOrderCancelled,
+    state::*,
+    util::{get_limit_order_offset, load_limit_order_book_mut, transfer_from_vault_to_owner},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = whirlpool, has_one = owner, close = receiver)]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CancelLimitOrder>) -> Result<()> {
+    let limit_order = &mut ctx.accounts.limit_order;
+    let a_to_b = limit_order.a_to_b;
+
+    let offset = get_limit_order_offset(
+        &ctx.accounts.tick_array.to_account_info(),
+        &ctx.accounts.whirlpool.key(),
+        limit_order.tick_index,
+        ctx.accounts.whirlpool.tick_spacing,
+    )?;
+
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+    let mut limit_order_book = load_limit_order_book_mut(&tick_array_info)?;
+    let level = limit_order_book.ticks[offset].level_mut(a_to_b);
+
+    // The unfilled amount is returned and the output of the filled amount is collected
+    let input_amount = limit_order.get_amount_remaining(level)?;
+    let output_amount = limit_order.collect_filled(level)?;
+    level.withdraw(input_amount)?;
+    drop(limit_order_book);
+
+    let (amount_a, amount_b) = if a_to_b {
+        (input_amount, output_amount)
+    } else {
+        (output_amount, input_amount)
+    };
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program,
+        amount_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program,
+        amount_b,
+    )?;
+
+    emit_cpi!(LimitOrderCancelled {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        limit_order: ctx.accounts.limit_order.key(),
+        a_to_b,
+        input_amount,
+        output_amount,
+    });
+
+    Ok(())
+}
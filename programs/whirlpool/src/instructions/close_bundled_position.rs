@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 
 use crate::errors::ErrorCode;
-use crate::{state::*, util::verify_position_bundle_authority};
+use crate::{
+    state::*,
+    util::{is_position_reward_extension_empty, verify_position_bundle_authority_interface},
+};
 
 #[derive(Accounts)]
 #[instruction(bundle_index: u16)]
@@ -26,7 +29,7 @@ pub struct CloseBundledPosition<'info> {
         constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
         constraint = position_bundle_token_account.amount == 1
     )]
-    pub position_bundle_token_account: Box<Account<'info, TokenAccount>>,
+    pub position_bundle_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
 
     pub position_bundle_authority: Signer<'info>,
 
@@ -39,12 +42,14 @@ pub fn handler(ctx: Context<CloseBundledPosition>, bundle_index: u16) -> Result<
     let position_bundle = &mut ctx.accounts.position_bundle;
 
     // Allow delegation
-    verify_position_bundle_authority(
+    verify_position_bundle_authority_interface(
         &ctx.accounts.position_bundle_token_account,
         &ctx.accounts.position_bundle_authority,
     )?;
 
-    if !Position::is_position_empty(&ctx.accounts.bundled_position) {
+    if !Position::is_position_empty(&ctx.accounts.bundled_position)
+        || !is_position_reward_extension_empty(&ctx.accounts.bundled_position.to_account_info())?
+    {
         return Err(ErrorCode::ClosePositionNotEmpty.into());
     }
 
@@ -54,3 +59,116 @@ pub fn handler(ctx: Context<CloseBundledPosition>, bundle_index: u16) -> Result<
 
     Ok(())
 }
+
+#[cfg(test)]
+mod close_bundled_position_tests {
+    use super::*;
+    use crate::util::test_utils::{anchor_account_data, leaked_account_info, token_account_data};
+    use anchor_spl::token::spl_token;
+    use anchor_spl::token_2022::spl_token_2022;
+    use std::collections::BTreeSet;
+
+    const BUNDLE_INDEX: u16 = 7;
+
+    struct Fixture {
+        account_infos: Vec<AccountInfo<'static>>,
+    }
+
+    impl Fixture {
+        fn new(token_program: Pubkey, position_liquidity: u128) -> Self {
+            let position_bundle_mint = Pubkey::new_unique();
+            let authority = Pubkey::new_unique();
+
+            let (bundled_position_key, _) = Pubkey::find_program_address(
+                &[
+                    b"bundled_position".as_ref(),
+                    position_bundle_mint.as_ref(),
+                    BUNDLE_INDEX.to_string().as_bytes(),
+                ],
+                &crate::ID,
+            );
+            let bundled_position = Position {
+                position_mint: position_bundle_mint,
+                liquidity: position_liquidity,
+                ..Default::default()
+            };
+
+            let mut position_bundle = PositionBundle::default();
+            position_bundle.initialize(position_bundle_mint).unwrap();
+            position_bundle.open_bundled_position(BUNDLE_INDEX).unwrap();
+
+            let account_infos = vec![
+                leaked_account_info(
+                    bundled_position_key,
+                    crate::ID,
+                    false,
+                    true,
+                    anchor_account_data(&bundled_position, Position::LEN),
+                ),
+                leaked_account_info(
+                    Pubkey::new_unique(),
+                    crate::ID,
+                    false,
+                    true,
+                    anchor_account_data(&position_bundle, PositionBundle::LEN),
+                ),
+                leaked_account_info(
+                    Pubkey::new_unique(),
+                    token_program,
+                    false,
+                    false,
+                    token_account_data(position_bundle_mint, authority, 1, None),
+                ),
+                leaked_account_info(authority, System::id(), true, false, vec![]),
+                leaked_account_info(Pubkey::new_unique(), System::id(), false, true, vec![]),
+            ];
+            Self { account_infos }
+        }
+
+        // Returns the position bitmap of the PositionBundle after the close
+        fn close(&self) -> Result<[u8; 32]> {
+            let account_infos: &'static [AccountInfo<'static>] =
+                Box::leak(self.account_infos.clone().into_boxed_slice());
+            let mut accounts_iter = account_infos;
+            let mut bumps = CloseBundledPositionBumps::default();
+            let mut accounts = CloseBundledPosition::try_accounts(
+                &crate::ID,
+                &mut accounts_iter,
+                &BUNDLE_INDEX.to_le_bytes(),
+                &mut bumps,
+                &mut BTreeSet::new(),
+            )?;
+
+            handler(
+                Context::new(&crate::ID, &mut accounts, &[], bumps),
+                BUNDLE_INDEX,
+            )?;
+            Ok(accounts.position_bundle.position_bitmap)
+        }
+    }
+
+    #[test]
+    fn test_close_with_token_account() {
+        let position_bitmap = Fixture::new(spl_token::ID, 0).close().unwrap();
+        assert_eq!(position_bitmap, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_close_with_token_2022_account() {
+        let position_bitmap = Fixture::new(spl_token_2022::ID, 0).close().unwrap();
+        assert_eq!(position_bitmap, [0u8; 32]);
+    }
+
+    #[test]
+    fn test_close_with_token_account_of_other_program() {
+        assert!(Fixture::new(Pubkey::new_unique(), 0).close().is_err());
+    }
+
+    #[test]
+    fn test_close_not_empty_position() {
+        assert_eq!(
+            Fixture::new(spl_token_2022::ID, 1).close().unwrap_err(),
+            ErrorCode::ClosePositionNotEmpty.into()
+        );
+    }
+}
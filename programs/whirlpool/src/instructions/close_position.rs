@@ -3,7 +3,10 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 use crate::errors::ErrorCode;
 use crate::state::*;
-use crate::util::{burn_and_close_user_position_token, verify_position_authority};
+use crate::util::{
+    burn_and_close_user_position_token, is_position_reward_extension_empty,
+    verify_position_authority,
+};
 
 #[derive(Accounts)]
 pub struct ClosePosition<'info> {
@@ -38,7 +41,9 @@ pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
         &ctx.accounts.position_authority,
     )?;
 
-    if !Position::is_position_empty(&ctx.accounts.position) {
+    if !Position::is_position_empty(&ctx.accounts.position)
+        || !is_position_reward_extension_empty(&ctx.accounts.position.to_account_info())?
+    {
         return Err(ErrorCode::ClosePositionNotEmpty.into());
     }
 
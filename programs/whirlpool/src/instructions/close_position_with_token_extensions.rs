@@ -4,7 +4,10 @@ use anchor_spl::token_interface::{Mint, TokenAccount};
 
 use crate::errors::ErrorCode;
 use crate::state::*;
-use crate::util::{burn_and_close_user_position_token_2022, verify_position_authority_interface};
+use crate::util::{
+    burn_and_close_user_position_token_2022, is_position_reward_extension_empty,
+    verify_position_authority_interface, verify_position_not_locked,
+};
 
 #[derive(Accounts)]
 pub struct ClosePositionWithTokenExtensions<'info> {
@@ -40,7 +43,11 @@ pub fn handler(ctx: Context<ClosePositionWithTokenExtensions>) -> Result<()> {
         &ctx.accounts.position_authority,
     )?;
 
-    if !Position::is_position_empty(&ctx.accounts.position) {
+    verify_position_not_locked(&ctx.accounts.position_token_account)?;
+
+    if !Position::is_position_empty(&ctx.accounts.position)
+        || !is_position_reward_extension_empty(&ctx.accounts.position.to_account_info())?
+    {
         return Err(ErrorCode::ClosePositionNotEmpty.into());
     }
 
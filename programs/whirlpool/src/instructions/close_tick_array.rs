@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::{load_limit_order_book, load_tick_array_mut, LoadedTickArrayMut},
+};
+
+#[derive(Accounts)]
+pub struct CloseTickArray<'info> {
+    #[account(has_one = whirlpools_config)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    pub whirlpools_config: Account<'info, WhirlpoolsConfig>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    /// CHECK: TickArray or DynamicTickArray of the whirlpool, checked in the handler
+    #[account(mut)]
+    pub tick_array: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"tick_array_rent_payers", tick_array.key().as_ref()], bump)]
+    /// CHECK: TickArrayRentPayers of a TickArray, not initialized for DynamicTickArrays
+    /// and TickArrays initialized before the record was introduced
+    pub rent_payers: UncheckedAccount<'info>,
+    // remaining accounts
+    // - payers of the LimitOrderBook and TickArrayRewardExtension rent, as recorded in the TickArray
+}
+
+pub fn handler(ctx: Context<CloseTickArray>) -> Result<()> {
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+
+    let (payer, section_refunds) = {
+        let tick_array = load_tick_array_mut(&tick_array_info, &ctx.accounts.whirlpool.key())?;
+        match tick_array {
+            LoadedTickArrayMut::Fixed(ref array) => {
+                if !array.is_empty() {
+                    return Err(ErrorCode::CloseTickArrayNotEmpty.into());
+                }
+                drop(tick_array);
+
+                if LimitOrderBook::is_allocated(tick_array_info.data_len())
+                    && !load_limit_order_book(&tick_array_info)?.is_empty()
+                {
+                    return Err(ErrorCode::CloseTickArrayNotEmpty.into());
+                }
+
+                // TickArrays initialized before the payers were recorded may have no record
+                let rent_payers = close_rent_payers(
+                    &ctx.accounts.rent_payers.to_account_info(),
+                    &tick_array_info,
+                )?
+                .unwrap_or_default();
+                (
+                    rent_payers.tick_array_payer,
+                    rent_payers.section_refunds().to_vec(),
+                )
+            }
+            LoadedTickArrayMut::Dynamic(ref array) => {
+                if !array.is_empty() {
+                    return Err(ErrorCode::CloseTickArrayNotEmpty.into());
+                }
+                (array.funder(), vec![])
+            }
+        }
+    };
+
+    // The rent of TickArrays without a recorded payer is reclaimed by the protocol
+    let authority = if payer == Pubkey::default() {
+        ctx.accounts
+            .whirlpools_config
+            .collect_protocol_fees_authority
+    } else {
+        payer
+    };
+    if ctx.accounts.authority.key() != authority {
+        return Err(ErrorCode::InvalidTickArrayPayer.into());
+    }
+
+    // Refund the rent of each section to its payer, the remaining lamports go to the receiver
+    for (section_payer, rent) in section_refunds {
+        if section_payer == Pubkey::default() || rent == 0 {
+            continue;
+        }
+        let section_payer_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|account_info| account_info.key() == section_payer)
+            .ok_or(ErrorCode::RemainingAccountsInsufficient)?;
+        let amount = rent.min(tick_array_info.lamports());
+        **tick_array_info.try_borrow_mut_lamports()? -= amount;
+        **section_payer_info.try_borrow_mut_lamports()? += amount;
+    }
+
+    let receiver_info = ctx.accounts.receiver.to_account_info();
+    let amount = tick_array_info.lamports();
+    **tick_array_info.try_borrow_mut_lamports()? -= amount;
+    **receiver_info.try_borrow_mut_lamports()? += amount;
+
+    tick_array_info.assign(&System::id());
+    tick_array_info.realloc(0, false)?;
+
+    Ok(())
+}
+
+/// Closes the TickArrayRentPayers of the TickArray, if it exists, and returns its content.
+/// The rent of the record is moved to the TickArray, to be refunded with the rent of the TickArray.
+fn close_rent_payers(
+    rent_payers_info: &AccountInfo<'_>,
+    tick_array_info: &AccountInfo<'_>,
+) -> Result<Option<TickArrayRentPayers>> {
+    if *rent_payers_info.owner != crate::ID {
+        return Ok(None);
+    }
+    let rent_payers =
+        TickArrayRentPayers::try_deserialize(&mut rent_payers_info.try_borrow_data()?.as_ref())?;
+
+    let amount = rent_payers_info.lamports();
+    **rent_payers_info.try_borrow_mut_lamports()? -= amount;
+    **tick_array_info.try_borrow_mut_lamports()? += amount;
+
+    rent_payers_info.assign(&System::id());
+    rent_payers_info.realloc(0, false)?;
+
+    Ok(Some(rent_payers))
+}
+
+#[cfg(test)]
+mod close_tick_array_tests {
+    use super::*;
+    use crate::util::test_utils::{anchor_account_data, leaked_account_info};
+
+    #[test]
+    fn test_close_rent_payers() {
+        let tick_array_info = leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            true,
+            vec![0u8; TickArray::LEN],
+        );
+        let mut rent_payers = TickArrayRentPayers::default();
+        rent_payers.initialize(tick_array_info.key(), Pubkey::new_unique());
+        rent_payers.limit_order_book_payer = Pubkey::new_unique();
+        rent_payers.limit_order_book_rent = 500;
+        let rent_payers_info = leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            true,
+            anchor_account_data(&rent_payers, TickArrayRentPayers::LEN),
+        );
+        let rent_payers_lamports = rent_payers_info.lamports();
+        let tick_array_lamports = tick_array_info.lamports();
+
+        let closed = close_rent_payers(&rent_payers_info, &tick_array_info).unwrap();
+        assert_eq!(closed, Some(rent_payers));
+        assert_eq!(rent_payers_info.lamports(), 0);
+        assert_eq!(rent_payers_info.data_len(), 0);
+        assert_eq!(*rent_payers_info.owner, System::id());
+        assert_eq!(
+            tick_array_info.lamports(),
+            tick_array_lamports + rent_payers_lamports
+        );
+    }
+
+    #[test]
+    fn test_close_rent_payers_not_initialized() {
+        // DynamicTickArrays and TickArrays initialized before the record was introduced
+        let tick_array_info = leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            true,
+            vec![0u8; TickArray::LEN],
+        );
+        let rent_payers_info =
+            leaked_account_info(Pubkey::new_unique(), System::id(), false, true, vec![]);
+
+        let closed = close_rent_payers(&rent_payers_info, &tick_array_info).unwrap();
+        assert_eq!(closed, None);
+    }
+}
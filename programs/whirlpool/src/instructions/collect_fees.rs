@@ -3,10 +3,12 @@ use anchor_spl::token::{self, Token, TokenAccount};
 use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 
 use crate::{
+    events::FeesCollected,
     state::*,
     util::{transfer_from_vault_to_owner, verify_position_authority_interface},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
     pub whirlpool: Box<Account<'info, Whirlpool>>,
@@ -65,5 +67,14 @@ pub fn handler(ctx: Context<CollectFees>) -> Result<()> {
         fee_owed_b,
     )?;
 
+    emit_cpi!(FeesCollected {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        fee_a_amount: fee_owed_a,
+        fee_b_amount: fee_owed_b,
+        fee_a_transfer_fee: 0,
+        fee_b_transfer_fee: 0,
+    });
+
     Ok(())
 }
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    events::LimitOrderCollected,
+    state::*,
+    util::{get_limit_order_offset, load_limit_order_book_mut, transfer_from_vault_to_owner},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectLimitOrder<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool, has_one = owner)]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    #[account(mut, constraint = token_owner_account.mint == if limit_order.a_to_b { whirlpool.token_mint_b } else { whirlpool.token_mint_a })]
+    pub token_owner_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = if limit_order.a_to_b { whirlpool.token_vault_b } else { whirlpool.token_vault_a })]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CollectLimitOrder>) -> Result<()> {
+    let limit_order = &mut ctx.accounts.limit_order;
+
+    let offset = get_limit_order_offset(
+        &ctx.accounts.tick_array.to_account_info(),
+        &ctx.accounts.whirlpool.key(),
+        limit_order.tick_index,
+        ctx.accounts.whirlpool.tick_spacing,
+    )?;
+
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+    let mut limit_order_book = load_limit_order_book_mut(&tick_array_info)?;
+    let level = limit_order_book.ticks[offset].level_mut(limit_order.a_to_b);
+    let output_amount = limit_order.collect_filled(level)?;
+    drop(limit_order_book);
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_owner_account,
+        &ctx.accounts.token_program,
+        output_amount,
+    )?;
+
+    emit_cpi!(LimitOrderCollected {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        limit_order: ctx.accounts.limit_order.key(),
+        a_to_b: ctx.accounts.limit_order.a_to_b,
+        output_amount,
+    });
+
+    Ok(())
+}
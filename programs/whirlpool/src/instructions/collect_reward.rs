@@ -3,10 +3,14 @@ use anchor_spl::token::{self, Token, TokenAccount};
 use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 
 use crate::{
+    events::RewardCollected,
     state::*,
-    util::{transfer_from_vault_to_owner, verify_position_authority_interface},
+    util::{
+        record_reward_collected, transfer_from_vault_to_owner, verify_position_authority_interface,
+    },
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct CollectReward<'info> {
@@ -32,6 +36,10 @@ pub struct CollectReward<'info> {
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
 }
 
 /// Collects all harvestable tokens for a specified reward.
@@ -62,6 +70,7 @@ pub fn handler(ctx: Context<CollectReward>, reward_index: u8) -> Result<()> {
     );
 
     position.update_reward_owed(index, updated_amount_owed);
+    record_reward_collected(&ctx.accounts.whirlpool_rewards, index, transfer_amount)?;
 
     transfer_from_vault_to_owner(
         &ctx.accounts.whirlpool,
@@ -69,7 +78,18 @@ pub fn handler(ctx: Context<CollectReward>, reward_index: u8) -> Result<()> {
         &ctx.accounts.reward_owner_account,
         &ctx.accounts.token_program,
         transfer_amount,
-    )
+    )?;
+
+    emit_cpi!(RewardCollected {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        reward_index,
+        reward_mint: ctx.accounts.whirlpool.reward_infos[index].mint,
+        amount: transfer_amount,
+        transfer_fee: 0,
+    });
+
+    Ok(())
 }
 
 fn calculate_collect_reward(position_reward: PositionRewardInfo, vault_amount: u64) -> (u64, u64) {
@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+
+use crate::errors::ErrorCode;
+use crate::events::LiquidityIncreased;
+use crate::manager::liquidity_manager::{
+    calculate_fee_and_reward_growths, calculate_liquidity_token_deltas, calculate_modify_liquidity,
+    sync_modify_liquidity_values,
+};
+use crate::math::{convert_to_liquidity_delta, get_liquidity_from_token_amounts};
+use crate::state::*;
+use crate::util::{
+    store_whirlpool_reward_schedule, to_timestamp_u64, verify_position_authority_interface,
+    verify_whirlpool_not_paused, TickArrayRentFunder,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompoundFees<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, updated if initialized
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+    // remaining accounts
+    // - System program, if a DynamicTickArray grows
+}
+
+/*
+  Adds the largest liquidity which fits in the fees owed to the position back to the position.
+  The fee tokens never leave the vaults, so no token transfer (and no transfer fee) is involved.
+  The remainder which cannot be used at the current price stays owed to the position.
+*/
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CompoundFees<'info>>) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension,
+        &ctx.accounts.whirlpool_pause,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Fees can only accrue to a position with liquidity.
+    if ctx.accounts.position.liquidity > 0 {
+        let (position_update, reward_infos, reward_schedule) = calculate_fee_and_reward_growths(
+            &ctx.accounts.whirlpool,
+            &ctx.accounts.whirlpool_rewards,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+
+        ctx.accounts
+            .whirlpool
+            .update_rewards(reward_infos, timestamp);
+        ctx.accounts.position.update(&position_update);
+
+        if let Some(reward_schedule) = &reward_schedule {
+            store_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards, reward_schedule)?;
+        }
+    }
+
+    // The price does not change when liquidity is added
+    let (liquidity_amount, delta_a, delta_b) = calculate_compound_liquidity(
+        ctx.accounts.whirlpool.tick_current_index,
+        ctx.accounts.whirlpool.sqrt_price,
+        &ctx.accounts.position,
+    )?;
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    update_oracle_on_liquidity_change(
+        &ctx.accounts.whirlpool.key(),
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.oracle,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        update,
+        timestamp,
+        &TickArrayRentFunder::new(
+            ctx.accounts.position_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    position.fee_owed_a -= delta_a;
+    position.fee_owed_b -= delta_b;
+
+    emit_cpi!(LiquidityIncreased {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        tick_lower_index: ctx.accounts.position.tick_lower_index,
+        tick_upper_index: ctx.accounts.position.tick_upper_index,
+        liquidity: liquidity_amount,
+        token_a_amount: delta_a,
+        token_b_amount: delta_b,
+        token_a_transfer_fee: 0,
+        token_b_transfer_fee: 0,
+    });
+
+    Ok(())
+}
+
+/// Returns the largest liquidity which can be added with the fees owed to the position at the
+/// current price, and the token amounts it requires.
+///
+/// # Errors
+/// - `LiquidityZero` - If the fees owed are not enough to add any liquidity
+/// - `TokenMaxExceeded` - If the required token amounts exceed the fees owed
+fn calculate_compound_liquidity(
+    tick_current_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+) -> Result<(u128, u64, u64)> {
+    let liquidity_amount = get_liquidity_from_token_amounts(
+        tick_current_index,
+        sqrt_price,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        position.fee_owed_a,
+        position.fee_owed_b,
+    )?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        tick_current_index,
+        sqrt_price,
+        position,
+        liquidity_delta,
+    )?;
+    if delta_a > position.fee_owed_a || delta_b > position.fee_owed_b {
+        return Err(ErrorCode::TokenMaxExceeded.into());
+    }
+
+    Ok((liquidity_amount, delta_a, delta_b))
+}
+
+#[cfg(test)]
+mod compound_fees_tests {
+    use super::*;
+    use crate::math::sqrt_price_from_tick_index;
+    use crate::state::position_builder::PositionBuilder;
+
+    fn position(fee_owed_a: u64, fee_owed_b: u64) -> Position {
+        PositionBuilder::new(-1280, 1280)
+            .liquidity(1_000_000)
+            .fee_owed_a(fee_owed_a)
+            .fee_owed_b(fee_owed_b)
+            .build()
+    }
+
+    fn compound(tick_current_index: i32, position: &Position) -> Result<(u128, u64, u64)> {
+        calculate_compound_liquidity(
+            tick_current_index,
+            sqrt_price_from_tick_index(tick_current_index),
+            position,
+        )
+    }
+
+    #[test]
+    fn test_compound_in_range() {
+        let position = position(1_000_000, 1_000_000);
+        let (liquidity, delta_a, delta_b) = compound(64, &position).unwrap();
+        assert!(liquidity > 0);
+        assert!(delta_a <= 1_000_000 && delta_b <= 1_000_000);
+        // one of the tokens is the limiting factor and is almost fully used
+        assert!(delta_a > 999_000 || delta_b > 999_000);
+    }
+
+    #[test]
+    fn test_compound_below_range_uses_only_token_a() {
+        let position = position(500_000, 700_000);
+        let (liquidity, delta_a, delta_b) = compound(-2000, &position).unwrap();
+        assert!(liquidity > 0);
+        assert!(delta_a <= 500_000 && delta_a > 499_000);
+        // token B stays owed to the position
+        assert_eq!(delta_b, 0);
+    }
+
+    #[test]
+    fn test_compound_above_range_uses_only_token_b() {
+        let position = position(500_000, 700_000);
+        let (liquidity, delta_a, delta_b) = compound(2000, &position).unwrap();
+        assert!(liquidity > 0);
+        assert_eq!(delta_a, 0);
+        assert!(delta_b <= 700_000 && delta_b > 699_000);
+    }
+
+    #[test]
+    fn test_compound_never_exceeds_fees_owed() {
+        for tick_current_index in [-1281, -1280, -1279, -640, 0, 1, 640, 1279, 1280, 1281] {
+            for (fee_owed_a, fee_owed_b) in [(1, 1), (7, 3), (1_000, 1), (123_456_789, 987_654_321)]
+            {
+                let position = position(fee_owed_a, fee_owed_b);
+                match compound(tick_current_index, &position) {
+                    Ok((_, delta_a, delta_b)) => {
+                        assert!(delta_a <= fee_owed_a && delta_b <= fee_owed_b);
+                    }
+                    Err(error) => assert_eq!(error, ErrorCode::LiquidityZero.into()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compound_without_fees() {
+        let position = position(0, 0);
+        assert_eq!(
+            compound(0, &position).unwrap_err(),
+            ErrorCode::LiquidityZero.into()
+        );
+    }
+}
@@ -1,12 +1,16 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
+use crate::events::LiquidityDecreased;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
+use crate::return_data::{set_return_data, ModifyLiquidityResult};
+use crate::state::update_oracle_on_liquidity_change;
 use crate::util::{
     to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority_interface,
+    verify_position_not_locked, TickArrayRentFunder,
 };
 
 use super::increase_liquidity::ModifyLiquidity;
@@ -14,8 +18,8 @@ use super::increase_liquidity::ModifyLiquidity;
 /*
   Removes liquidity from an existing Whirlpool Position.
 */
-pub fn handler(
-    ctx: Context<ModifyLiquidity>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_min_a: u64,
     token_min_b: u64,
@@ -25,6 +29,8 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
+    verify_position_not_locked(&ctx.accounts.position_token_account)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -35,6 +41,7 @@ pub fn handler(
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
@@ -42,13 +49,25 @@ pub fn handler(
         timestamp,
     )?;
 
+    update_oracle_on_liquidity_change(
+        &ctx.accounts.whirlpool.key(),
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.oracle,
+        timestamp,
+    )?;
+
     sync_modify_liquidity_values(
         &mut ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &mut ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
         update,
         timestamp,
+        &TickArrayRentFunder::new(
+            ctx.accounts.position_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
     )?;
 
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
@@ -78,5 +97,23 @@ pub fn handler(
         delta_b,
     )?;
 
-    Ok(())
+    emit_cpi!(LiquidityDecreased {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        tick_lower_index: ctx.accounts.position.tick_lower_index,
+        tick_upper_index: ctx.accounts.position.tick_upper_index,
+        liquidity: liquidity_amount,
+        token_a_amount: delta_a,
+        token_b_amount: delta_b,
+        token_a_transfer_fee: 0,
+        token_b_transfer_fee: 0,
+    });
+
+    set_return_data(&ModifyLiquidityResult::new(
+        liquidity_amount,
+        delta_a,
+        0,
+        delta_b,
+        0,
+    ))
 }
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::burn_and_close_position_bundle_token_2022;
+
+#[derive(Accounts)]
+pub struct DeletePositionBundleWithTokenExtensions<'info> {
+    #[account(mut,
+        close = receiver,
+        seeds = [b"position_bundle".as_ref(), position_bundle_mint.key().as_ref()],
+        bump,
+    )]
+    pub position_bundle: Account<'info, PositionBundle>,
+
+    #[account(mut,
+        address = position_bundle.position_bundle_mint,
+        owner = token_2022_program.key(),
+    )]
+    pub position_bundle_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,
+        constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
+        constraint = position_bundle_token_account.owner == position_bundle_owner.key(),
+        constraint = position_bundle_token_account.amount == 1,
+    )]
+    pub position_bundle_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub position_bundle_owner: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(address = token_2022::ID)]
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+/*
+  Deletes a PositionBundle with Mint and TokenAccount owned by Token-2022.
+  The Mint account is closed as well, so all the rent is returned to the receiver.
+*/
+pub fn handler(ctx: Context<DeletePositionBundleWithTokenExtensions>) -> Result<()> {
+    let position_bundle = &ctx.accounts.position_bundle;
+
+    if !position_bundle.is_deletable() {
+        return Err(ErrorCode::PositionBundleNotDeletable.into());
+    }
+
+    burn_and_close_position_bundle_token_2022(
+        &ctx.accounts.position_bundle_owner,
+        &ctx.accounts.receiver,
+        &ctx.accounts.position_bundle_mint,
+        &ctx.accounts.position_bundle_token_account,
+        &ctx.accounts.token_2022_program,
+        position_bundle,
+        &[
+            b"position_bundle".as_ref(),
+            ctx.accounts.position_bundle_mint.key().as_ref(),
+            &[ctx.bumps.position_bundle],
+        ],
+    )
+}
+
+#[cfg(test)]
+mod delete_position_bundle_with_token_extensions_tests {
+    use super::*;
+    use crate::util::test_utils::{
+        anchor_account_data, leaked_account_info, leaked_program_account_info, mint_data,
+        token_account_data,
+    };
+    use anchor_spl::token::spl_token;
+    use anchor_spl::token_2022::spl_token_2022;
+    use std::collections::BTreeSet;
+
+    fn delete(mint_program: Pubkey, open_bundle_index: Option<u16>) -> Result<()> {
+        let position_bundle_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        let (position_bundle_key, _) = Pubkey::find_program_address(
+            &[b"position_bundle".as_ref(), position_bundle_mint.as_ref()],
+            &crate::ID,
+        );
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(position_bundle_mint).unwrap();
+        if let Some(bundle_index) = open_bundle_index {
+            position_bundle.open_bundled_position(bundle_index).unwrap();
+        }
+
+        let account_infos: &'static [AccountInfo<'static>] = Box::leak(
+            vec![
+                leaked_account_info(
+                    position_bundle_key,
+                    crate::ID,
+                    false,
+                    true,
+                    anchor_account_data(&position_bundle, PositionBundle::LEN),
+                ),
+                leaked_account_info(
+                    position_bundle_mint,
+                    mint_program,
+                    false,
+                    true,
+                    mint_data(1),
+                ),
+                leaked_account_info(
+                    Pubkey::new_unique(),
+                    mint_program,
+                    false,
+                    true,
+                    token_account_data(position_bundle_mint, owner, 1, None),
+                ),
+                leaked_account_info(owner, System::id(), true, false, vec![]),
+                leaked_account_info(Pubkey::new_unique(), System::id(), false, true, vec![]),
+                leaked_program_account_info(spl_token_2022::ID),
+            ]
+            .into_boxed_slice(),
+        );
+        let mut accounts_iter = account_infos;
+        let mut bumps = DeletePositionBundleWithTokenExtensionsBumps::default();
+        let mut accounts = DeletePositionBundleWithTokenExtensions::try_accounts(
+            &crate::ID,
+            &mut accounts_iter,
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+
+        handler(Context::new(&crate::ID, &mut accounts, &[], bumps))
+    }
+
+    #[test]
+    fn test_delete_with_token_2022_mint() {
+        assert!(delete(spl_token_2022::ID, None).is_ok());
+    }
+
+    #[test]
+    fn test_delete_with_token_mint() {
+        // The legacy position bundles are deleted by delete_position_bundle
+        assert_eq!(
+            delete(spl_token::ID, None).unwrap_err(),
+            anchor_lang::error::ErrorCode::ConstraintOwner.into()
+        );
+    }
+
+    #[test]
+    fn test_delete_not_empty_bundle() {
+        assert_eq!(
+            delete(spl_token_2022::ID, Some(0)).unwrap_err(),
+            ErrorCode::PositionBundleNotDeletable.into()
+        );
+    }
+}
@@ -3,15 +3,19 @@ use anchor_spl::token::{self, Token, TokenAccount};
 use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 
 use crate::errors::ErrorCode;
+use crate::events::LiquidityIncreased;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
+use crate::return_data::{set_return_data, ModifyLiquidityResult};
 use crate::state::*;
 use crate::util::{
     to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority_interface,
+    verify_whirlpool_not_paused, TickArrayRentFunder,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ModifyLiquidity<'info> {
     #[account(mut)]
@@ -40,14 +44,32 @@ pub struct ModifyLiquidity<'info> {
     #[account(mut, constraint = token_vault_b.key() == whirlpool.token_vault_b)]
     pub token_vault_b: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut, has_one = whirlpool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(mut, has_one = whirlpool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, updated if initialized
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked on increase only
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked on increase only
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
 }
 
-pub fn handler(
-    ctx: Context<ModifyLiquidity>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_max_a: u64,
     token_max_b: u64,
@@ -57,6 +79,11 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension,
+        &ctx.accounts.whirlpool_pause,
+    )?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -67,6 +94,7 @@ pub fn handler(
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
@@ -74,13 +102,25 @@ pub fn handler(
         timestamp,
     )?;
 
+    update_oracle_on_liquidity_change(
+        &ctx.accounts.whirlpool.key(),
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.oracle,
+        timestamp,
+    )?;
+
     sync_modify_liquidity_values(
         &mut ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &mut ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
         update,
         timestamp,
+        &TickArrayRentFunder::new(
+            ctx.accounts.position_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
     )?;
 
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
@@ -110,5 +150,23 @@ pub fn handler(
         delta_b,
     )?;
 
-    Ok(())
+    emit_cpi!(LiquidityIncreased {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        tick_lower_index: ctx.accounts.position.tick_lower_index,
+        tick_upper_index: ctx.accounts.position.tick_upper_index,
+        liquidity: liquidity_amount,
+        token_a_amount: delta_a,
+        token_b_amount: delta_b,
+        token_a_transfer_fee: 0,
+        token_b_transfer_fee: 0,
+    });
+
+    set_return_data(&ModifyLiquidityResult::new(
+        liquidity_amount,
+        delta_a,
+        0,
+        delta_b,
+        0,
+    ))
 }
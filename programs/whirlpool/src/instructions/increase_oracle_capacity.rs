@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    errors::ErrorCode,
+    state::{Oracle, Whirlpool},
+};
+
+#[derive(Accounts)]
+pub struct IncreaseOracleCapacity<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<IncreaseOracleCapacity>, observation_capacity: u16) -> Result<()> {
+    let oracle_account_info = ctx.accounts.oracle.to_account_info();
+
+    let current_observation_capacity = Oracle::observation_capacity(oracle_account_info.data_len());
+    if observation_capacity <= current_observation_capacity {
+        return Err(ErrorCode::InvalidObservationCapacity.into());
+    }
+
+    let space = Oracle::space(observation_capacity);
+    let required_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(oracle_account_info.lamports());
+    if required_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: oracle_account_info.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+
+    // New slots are used once the ring buffer reaches its current end
+    oracle_account_info.realloc(space, false)?;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(fee_tier_index: u16)]
+pub struct InitializeAdaptiveFeeTier<'info> {
+    pub config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    // Shares the seed space with FeeTier so that a fee_tier_index cannot collide with a tick_spacing
+    #[account(init,
+      payer = funder,
+      seeds = [b"fee_tier", config.key().as_ref(),
+               fee_tier_index.to_le_bytes().as_ref()],
+      bump,
+      space = AdaptiveFeeTier::LEN)]
+    pub adaptive_fee_tier: Account<'info, AdaptiveFeeTier>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(address = config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeAdaptiveFeeTier>,
+    fee_tier_index: u16,
+    tick_spacing: u16,
+    default_base_fee_rate: u16,
+    adaptive_fee_constants: AdaptiveFeeConstants,
+) -> Result<()> {
+    ctx.accounts.adaptive_fee_tier.initialize(
+        &ctx.accounts.config,
+        fee_tier_index,
+        tick_spacing,
+        default_base_fee_rate,
+        adaptive_fee_constants,
+    )
+}
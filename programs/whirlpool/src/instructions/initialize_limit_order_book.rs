@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ErrorCode, state::*, util::allocate_limit_order_book};
+
+#[derive(Accounts)]
+pub struct InitializeLimitOrderBook<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    #[account(mut, seeds = [b"tick_array_rent_payers", tick_array.key().as_ref()], bump)]
+    /// CHECK: TickArrayRentPayers of the TickArray, created in the handler if it does not exist
+    pub rent_payers: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Appends a LimitOrderBook to the TickArray account data.
+
+  open_limit_order allocates it on the first order, but the TickArrayRewardExtension is stored
+  after it and an account can only grow by MAX_PERMITTED_DATA_INCREASE in a single instruction,
+  so the LimitOrderBook must be allocated on its own before the extension.
+*/
+pub fn handler(ctx: Context<InitializeLimitOrderBook>) -> Result<()> {
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+    if LimitOrderBook::is_allocated(tick_array_info.data_len()) {
+        return Err(ErrorCode::LimitOrderBookAlreadyInitialized.into());
+    }
+
+    allocate_limit_order_book(
+        &tick_array_info,
+        &ctx.accounts.rent_payers,
+        ctx.bumps.rent_payers,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )
+}
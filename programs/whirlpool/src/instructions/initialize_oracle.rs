@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{AdaptiveFeeConstants, Oracle, Whirlpool},
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(init,
+      seeds = [b"oracle", whirlpool.key().as_ref()],
+      bump,
+      payer = funder,
+      space = Oracle::space(1))]
+    pub oracle: Account<'info, Oracle>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeOracle>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let whirlpool = &ctx.accounts.whirlpool;
+    let oracle = &mut ctx.accounts.oracle;
+
+    // Pools initialized with an AdaptiveFeeTier already have an Oracle,
+    // so adaptive fee is always disabled here.
+    oracle.initialize(whirlpool.key(), AdaptiveFeeConstants::default())?;
+
+    let oracle_account_info = oracle.to_account_info();
+    oracle.write_observation(
+        &mut oracle_account_info.try_borrow_mut_data()?,
+        timestamp,
+        whirlpool.tick_current_index,
+        whirlpool.liquidity,
+    );
+
+    Ok(())
+}
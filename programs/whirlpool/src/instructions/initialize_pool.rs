@@ -1,7 +1,9 @@
+use crate::events::PoolInitialized;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
+#[event_cpi]
 #[derive(Accounts)]
 // now we don't use bumps, but we must list args in the same order to use tick_spacing arg.
 #[instruction(bumps: WhirlpoolBumps, tick_spacing: u16)]
@@ -69,11 +71,27 @@ pub fn handler(
         whirlpools_config,
         bump,
         tick_spacing,
+        tick_spacing,
         initial_sqrt_price,
         default_fee_rate,
         token_mint_a,
         ctx.accounts.token_vault_a.key(),
         token_mint_b,
         ctx.accounts.token_vault_b.key(),
-    )
+    )?;
+
+    emit_cpi!(PoolInitialized {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        whirlpools_config: ctx.accounts.whirlpools_config.key(),
+        token_mint_a,
+        token_mint_b,
+        tick_spacing,
+        token_program_a: ctx.accounts.token_program.key(),
+        token_program_b: ctx.accounts.token_program.key(),
+        decimals_a: ctx.accounts.token_mint_a.decimals,
+        decimals_b: ctx.accounts.token_mint_b.decimals,
+        initial_sqrt_price,
+    });
+
+    Ok(())
 }
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::ErrorCode,
+    events::PoolInitialized,
+    state::*,
+    util::{load_token_badge, to_timestamp_u64, v2::is_supported_token_mint},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializePoolWithAdaptiveFee<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(seeds = [b"token_badge", whirlpools_config.key().as_ref(), token_mint_a.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub token_badge_a: UncheckedAccount<'info>,
+    #[account(seeds = [b"token_badge", whirlpools_config.key().as_ref(), token_mint_b.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub token_badge_b: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(has_one = whirlpools_config)]
+    pub adaptive_fee_tier: Box<Account<'info, AdaptiveFeeTier>>,
+
+    #[account(init,
+      seeds = [
+        b"whirlpool".as_ref(),
+        whirlpools_config.key().as_ref(),
+        token_mint_a.key().as_ref(),
+        token_mint_b.key().as_ref(),
+        adaptive_fee_tier.fee_tier_index.to_le_bytes().as_ref()
+      ],
+      bump,
+      payer = funder,
+      space = Whirlpool::LEN)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(init,
+      seeds = [b"oracle", whirlpool.key().as_ref()],
+      bump,
+      payer = funder,
+      space = Oracle::space(1))]
+    pub oracle: Box<Account<'info, Oracle>>,
+
+    #[account(init,
+      payer = funder,
+      token::token_program = token_program_a,
+      token::mint = token_mint_a,
+      token::authority = whirlpool)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+      payer = funder,
+      token::token_program = token_program_b,
+      token::mint = token_mint_b,
+      token::authority = whirlpool)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<InitializePoolWithAdaptiveFee>,
+    initial_sqrt_price: u128,
+) -> Result<()> {
+    let token_mint_a = ctx.accounts.token_mint_a.key();
+    let token_mint_b = ctx.accounts.token_mint_b.key();
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let whirlpools_config = &ctx.accounts.whirlpools_config;
+    let adaptive_fee_tier = &ctx.accounts.adaptive_fee_tier;
+
+    let tick_spacing = adaptive_fee_tier.tick_spacing;
+
+    let bump = ctx.bumps.whirlpool;
+
+    // Don't allow creating a pool with unsupported token mints
+    let token_badge_a = load_token_badge(
+        whirlpools_config.key(),
+        token_mint_a,
+        &ctx.accounts.token_badge_a,
+    )?;
+
+    if !is_supported_token_mint(&ctx.accounts.token_mint_a, token_badge_a.as_ref()).unwrap() {
+        return Err(ErrorCode::UnsupportedTokenMint.into());
+    }
+
+    let token_badge_b = load_token_badge(
+        whirlpools_config.key(),
+        token_mint_b,
+        &ctx.accounts.token_badge_b,
+    )?;
+
+    if !is_supported_token_mint(&ctx.accounts.token_mint_b, token_badge_b.as_ref()).unwrap() {
+        return Err(ErrorCode::UnsupportedTokenMint.into());
+    }
+
+    // The base fee rate is stored as the fee rate of the Whirlpool
+    whirlpool.initialize(
+        whirlpools_config,
+        bump,
+        tick_spacing,
+        adaptive_fee_tier.fee_tier_index,
+        initial_sqrt_price,
+        adaptive_fee_tier.default_base_fee_rate,
+        token_mint_a,
+        ctx.accounts.token_vault_a.key(),
+        token_mint_b,
+        ctx.accounts.token_vault_b.key(),
+    )?;
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let tick_current_index = whirlpool.tick_current_index;
+    let liquidity = whirlpool.liquidity;
+
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.initialize(whirlpool.key(), adaptive_fee_tier.adaptive_fee_constants)?;
+
+    let oracle_account_info = oracle.to_account_info();
+    oracle.write_observation(
+        &mut oracle_account_info.try_borrow_mut_data()?,
+        timestamp,
+        tick_current_index,
+        liquidity,
+    );
+
+    emit_cpi!(PoolInitialized {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        whirlpools_config: ctx.accounts.whirlpools_config.key(),
+        token_mint_a,
+        token_mint_b,
+        tick_spacing,
+        token_program_a: ctx.accounts.token_program_a.key(),
+        token_program_b: ctx.accounts.token_program_b.key(),
+        decimals_a: ctx.accounts.token_mint_a.decimals,
+        decimals_b: ctx.accounts.token_mint_b.decimals,
+        initial_sqrt_price,
+    });
+
+    Ok(())
+}
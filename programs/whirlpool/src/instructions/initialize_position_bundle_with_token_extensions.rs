@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::Token2022;
+
+use crate::constants::nft::whirlpool_nft_update_auth::ID as WP_NFT_UPDATE_AUTH;
+use crate::state::*;
+use crate::util::{
+    build_position_bundle_token_metadata, initialize_position_bundle_mint_2022,
+    initialize_position_bundle_token_metadata_extension, initialize_position_token_account_2022,
+    mint_position_bundle_token_2022_and_remove_authority,
+};
+
+#[derive(Accounts)]
+pub struct InitializePositionBundleWithTokenExtensions<'info> {
+    #[account(init,
+        payer = funder,
+        space = PositionBundle::LEN,
+        seeds = [b"position_bundle".as_ref(), position_bundle_mint.key().as_ref()],
+        bump,
+    )]
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+
+    /// CHECK: initialized in the handler
+    #[account(mut)]
+    pub position_bundle_mint: Signer<'info>,
+
+    /// CHECK: initialized in the handler
+    #[account(mut)]
+    pub position_bundle_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: safe, the account that will be the owner of the position bundle can be arbitrary
+    pub position_bundle_owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(address = spl_token_2022::ID)]
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: checked via account constraints
+    #[account(address = WP_NFT_UPDATE_AUTH)]
+    pub metadata_update_auth: UncheckedAccount<'info>,
+}
+
+/*
+  Initializes a PositionBundle with Mint and TokenAccount owned by Token-2022.
+  The metadata is stored in the Mint account with TokenMetadata extension.
+*/
+pub fn handler(ctx: Context<InitializePositionBundleWithTokenExtensions>) -> Result<()> {
+    let position_bundle_mint = &ctx.accounts.position_bundle_mint;
+    let position_bundle = &mut ctx.accounts.position_bundle;
+
+    position_bundle.initialize(position_bundle_mint.key())?;
+
+    let position_bundle_seeds = [
+        b"position_bundle".as_ref(),
+        position_bundle_mint.key.as_ref(),
+        &[ctx.bumps.position_bundle],
+    ];
+
+    initialize_position_bundle_mint_2022(
+        position_bundle_mint,
+        &ctx.accounts.funder,
+        position_bundle,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_2022_program,
+    )?;
+
+    let (name, symbol, uri) = build_position_bundle_token_metadata(position_bundle_mint);
+
+    initialize_position_bundle_token_metadata_extension(
+        name,
+        symbol,
+        uri,
+        position_bundle_mint,
+        position_bundle,
+        &ctx.accounts.metadata_update_auth,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_2022_program,
+        &position_bundle_seeds,
+    )?;
+
+    initialize_position_token_account_2022(
+        &ctx.accounts.position_bundle_token_account,
+        position_bundle_mint,
+        &ctx.accounts.funder,
+        &ctx.accounts.position_bundle_owner,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.associated_token_program,
+    )?;
+
+    mint_position_bundle_token_2022_and_remove_authority(
+        position_bundle,
+        position_bundle_mint,
+        &ctx.accounts.position_bundle_token_account,
+        &ctx.accounts.token_2022_program,
+        &position_bundle_seeds,
+    )
+}
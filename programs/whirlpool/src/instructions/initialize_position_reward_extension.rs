@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+
+use crate::{
+    errors::ErrorCode,
+    manager::reward_extension_manager::calculate_position_reward_extension_init,
+    state::*,
+    util::{
+        allocate_reward_extension, get_tick_from_tick_array, get_tick_reward_growths_outside,
+        load_whirlpool_reward_extension, store_position_reward_extension,
+        store_whirlpool_reward_extension, to_timestamp_u64, verify_position_authority_interface,
+    },
+};
+
+#[derive(Accounts)]
+pub struct InitializePositionRewardExtension<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+/*
+  Appends a PositionRewardExtension to the Position account data, so that the position accrues the
+  extension rewards of the Whirlpool from now on. Both TickArrays of the position must carry a
+  TickArrayRewardExtension.
+*/
+pub fn handler(ctx: Context<InitializePositionRewardExtension>) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let position = &ctx.accounts.position;
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    let position_info = position.to_account_info();
+
+    if PositionRewardExtension::is_allocated(position_info.data_len()) {
+        return Err(ErrorCode::RewardExtensionAlreadyInitialized.into());
+    }
+
+    let whirlpool_reward_extension = load_whirlpool_reward_extension(&whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+
+    let tick_lower = get_tick_from_tick_array(
+        &ctx.accounts.tick_array_lower,
+        &whirlpool.key(),
+        position.tick_lower_index,
+        whirlpool.tick_spacing,
+    )?;
+    let tick_lower_reward_growths_outside = get_tick_reward_growths_outside(
+        &ctx.accounts.tick_array_lower,
+        &whirlpool.key(),
+        position.tick_lower_index,
+        whirlpool.tick_spacing,
+    )?
+    .ok_or(ErrorCode::RewardExtensionNotAllocated)?;
+
+    let tick_upper = get_tick_from_tick_array(
+        &ctx.accounts.tick_array_upper,
+        &whirlpool.key(),
+        position.tick_upper_index,
+        whirlpool.tick_spacing,
+    )?;
+    let tick_upper_reward_growths_outside = get_tick_reward_growths_outside(
+        &ctx.accounts.tick_array_upper,
+        &whirlpool.key(),
+        position.tick_upper_index,
+        whirlpool.tick_spacing,
+    )?
+    .ok_or(ErrorCode::RewardExtensionNotAllocated)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let (next_whirlpool_reward_extension, position_reward_extension) =
+        calculate_position_reward_extension_init(
+            whirlpool,
+            &whirlpool_reward_extension,
+            position,
+            &tick_lower,
+            &tick_lower_reward_growths_outside,
+            &tick_upper,
+            &tick_upper_reward_growths_outside,
+            timestamp,
+        )?;
+
+    allocate_reward_extension(
+        &position_info,
+        PositionRewardExtension::OFFSET + PositionRewardExtension::LEN,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    store_whirlpool_reward_extension(&whirlpool_rewards, &next_whirlpool_reward_extension)?;
+    store_position_reward_extension(&position_info, &position_reward_extension)
+}
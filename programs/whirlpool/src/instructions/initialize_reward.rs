@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 use crate::state::Whirlpool;
+use crate::util::create_whirlpool_rewards;
 
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
@@ -29,6 +30,10 @@ pub struct InitializeReward<'info> {
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: WhirlpoolRewards of the Whirlpool, created in the handler for the first reward
+    pub whirlpool_rewards: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<InitializeReward>, reward_index: u8) -> Result<()> {
@@ -38,5 +43,19 @@ pub fn handler(ctx: Context<InitializeReward>, reward_index: u8) -> Result<()> {
         reward_index as usize,
         ctx.accounts.reward_mint.key(),
         ctx.accounts.reward_vault.key(),
+    )?;
+
+    // Track the amounts emitted and collected for the rewards from the start. The ledgers of the
+    // rewards initialized before are seeded from their vaults by initialize_reward_schedule.
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    if reward_index != 0 || *whirlpool_rewards.owner == crate::ID {
+        return Ok(());
+    }
+    create_whirlpool_rewards(
+        &whirlpool_rewards,
+        whirlpool.key(),
+        ctx.bumps.whirlpool_rewards,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
     )
 }
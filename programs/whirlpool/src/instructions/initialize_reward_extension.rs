@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::{allocate_reward_extension, store_whirlpool_reward_extension, to_timestamp_u64},
+};
+
+#[derive(Accounts)]
+pub struct InitializeRewardExtension<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = whirlpools_config.reward_emissions_super_authority)]
+    pub reward_emissions_super_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+/*
+  Appends a WhirlpoolRewardExtension to the WhirlpoolRewards account data, which adds
+  NUM_EXTENSION_REWARDS rewards on top of the rewards stored in the Whirlpool.
+  The authority of all extension rewards is initially the reward emissions super authority.
+  The Whirlpool must have a WhirlpoolRewardSchedule, which precedes the extension.
+*/
+pub fn handler(ctx: Context<InitializeRewardExtension>) -> Result<()> {
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    if WhirlpoolRewardExtension::is_allocated(whirlpool_rewards.data_len()) {
+        return Err(ErrorCode::RewardExtensionAlreadyInitialized.into());
+    }
+    // The extension follows the schedules, whose ledgers must have been seeded
+    if !WhirlpoolRewardSchedule::is_allocated(whirlpool_rewards.data_len()) {
+        return Err(ErrorCode::RewardScheduleNotInitialized.into());
+    }
+
+    allocate_reward_extension(
+        &whirlpool_rewards,
+        WhirlpoolRewardExtension::OFFSET + WhirlpoolRewardExtension::LEN,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    store_whirlpool_reward_extension(
+        &whirlpool_rewards,
+        &WhirlpoolRewardExtension::new(
+            ctx.accounts
+                .whirlpools_config
+                .reward_emissions_super_authority,
+            timestamp,
+        ),
+    )
+}
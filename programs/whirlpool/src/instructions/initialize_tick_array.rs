@@ -18,10 +18,24 @@ pub struct InitializeTickArray<'info> {
       space = TickArray::LEN)]
     pub tick_array: AccountLoader<'info, TickArray>,
 
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"tick_array_rent_payers", tick_array.key().as_ref()],
+      bump,
+      space = TickArrayRentPayers::LEN)]
+    pub rent_payers: Account<'info, TickArrayRentPayers>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<InitializeTickArray>, start_tick_index: i32) -> Result<()> {
     let mut tick_array = ctx.accounts.tick_array.load_init()?;
-    tick_array.initialize(&ctx.accounts.whirlpool, start_tick_index)
+    tick_array.initialize(&ctx.accounts.whirlpool, start_tick_index)?;
+
+    // Record the payer so that the rent can be refunded when the TickArray is closed
+    ctx.accounts
+        .rent_payers
+        .initialize(ctx.accounts.tick_array.key(), ctx.accounts.funder.key());
+    Ok(())
 }
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    state::*,
+    util::{
+        allocate_tick_array_section, load_tick_array_reward_extension_mut,
+        load_whirlpool_reward_extension, TickArraySection,
+    },
+};
+
+#[derive(Accounts)]
+pub struct InitializeTickArrayRewardExtension<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    #[account(mut, seeds = [b"tick_array_rent_payers", tick_array.key().as_ref()], bump)]
+    /// CHECK: TickArrayRentPayers of the TickArray, created in the handler if it does not exist
+    pub rent_payers: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_extension
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+/*
+  Appends a TickArrayRewardExtension to the TickArray account data, after the LimitOrderBook.
+
+  An account can only grow by MAX_PERMITTED_DATA_INCREASE in a single instruction,
+  which is less than the LimitOrderBook and the extension together.
+  A TickArray without a LimitOrderBook is rejected, initialize_limit_order_book allocates it.
+*/
+pub fn handler(ctx: Context<InitializeTickArrayRewardExtension>) -> Result<()> {
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+    if TickArrayRewardExtension::is_allocated(tick_array_info.data_len()) {
+        return Err(ErrorCode::RewardExtensionAlreadyInitialized.into());
+    }
+
+    if !LimitOrderBook::is_allocated(tick_array_info.data_len()) {
+        return Err(ErrorCode::LimitOrderBookNotInitialized.into());
+    }
+
+    allocate_tick_array_section(
+        &tick_array_info,
+        TickArraySection::RewardExtension,
+        &ctx.accounts.rent_payers,
+        ctx.bumps.rent_payers,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let reward_extension = match load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards)? {
+        Some(reward_extension) => reward_extension,
+        // All growths are zero until the Whirlpool has a reward extension
+        None => return Ok(()),
+    };
+
+    // By convention, assume all prior growth happened below the ticks which are already initialized
+    let reward_growths = WhirlpoolRewardInfo::to_reward_growths(&reward_extension.reward_infos);
+    let (tick_array, mut tick_array_reward_extension) =
+        load_tick_array_reward_extension_mut(&tick_array_info, &whirlpool.key())?;
+    for offset in 0..TICK_ARRAY_SIZE_USIZE {
+        let tick_index =
+            tick_array.start_tick_index + offset as i32 * whirlpool.tick_spacing as i32;
+        if tick_array.ticks[offset].initialized && whirlpool.tick_current_index >= tick_index {
+            tick_array_reward_extension.reward_growths_outside[offset] = reward_growths;
+        }
+    }
+
+    Ok(())
+}
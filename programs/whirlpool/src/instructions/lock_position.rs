@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use solana_program::program_option::COption;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{
+    freeze_user_position_token_2022, to_timestamp_u64, verify_position_owner_interface,
+};
+
+#[derive(Accounts)]
+pub struct LockPosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// Owner of the position token, a delegate cannot lock the position
+    pub position_authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+        has_one = whirlpool,
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(address = position.position_mint, owner = token_2022_program.key())]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = funder,
+        space = LockConfig::LEN,
+        seeds = [b"lock_config".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub lock_config: Account<'info, LockConfig>,
+
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = token_2022::ID)]
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Locks a Position with a Mint owned by Token-2022, so that its liquidity cannot be withdrawn
+  until the lock expires. The position token account is frozen by the Position account,
+  the freeze authority of the position mint, which also prevents transferring the position.
+
+  A lock can be permanent, so only the owner of the position token can lock the position.
+  The funder is recorded in the LockConfig to be refunded with the rent on unlock.
+*/
+pub fn handler(ctx: Context<LockPosition>, lock_type: LockType) -> Result<()> {
+    verify_position_owner_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let position = &ctx.accounts.position;
+    if position.liquidity == 0
+        || ctx.accounts.position_mint.freeze_authority != COption::Some(position.key())
+    {
+        return Err(ErrorCode::PositionNotLockable.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    ctx.accounts.lock_config.initialize(
+        position.key(),
+        ctx.accounts.position_token_account.owner,
+        ctx.accounts.whirlpool.key(),
+        timestamp,
+        lock_type,
+        ctx.accounts.funder.key(),
+    )?;
+
+    freeze_user_position_token_2022(
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_2022_program,
+        position,
+        &[
+            b"position".as_ref(),
+            ctx.accounts.position_mint.key().as_ref(),
+            &[ctx.bumps.position],
+        ],
+    )
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+
+use crate::constants::nft::whirlpool_nft_update_auth::ID as WP_NFT_UPDATE_AUTH;
+use crate::state::*;
+use crate::util::{
+    build_position_token_metadata, initialize_position_mint_2022,
+    initialize_position_token_account_2022, initialize_token_metadata_extension,
+    mint_position_token_2022_and_remove_authority, verify_no_reward_extension,
+    verify_position_bundle_authority_interface,
+};
+
+#[derive(Accounts)]
+#[instruction(bundle_index: u16)]
+pub struct MigrateBundledPositionOutOfBundle<'info> {
+    #[account(mut,
+        close = receiver,
+        seeds = [
+            b"bundled_position".as_ref(),
+            position_bundle.position_bundle_mint.key().as_ref(),
+            bundle_index.to_string().as_bytes()
+        ],
+        bump,
+    )]
+    pub bundled_position: Box<Account<'info, Position>>,
+
+    #[account(mut)]
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+
+    #[account(
+        constraint = position_bundle_token_account.mint == bundled_position.position_mint,
+        constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
+        constraint = position_bundle_token_account.amount == 1
+    )]
+    pub position_bundle_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    pub position_bundle_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: safe, the account that will be the owner of the position can be arbitrary
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+      bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// CHECK: initialized in the handler
+    #[account(mut)]
+    pub position_mint: Signer<'info>,
+
+    /// CHECK: initialized in the handler
+    #[account(mut)]
+    pub position_token_account: UncheckedAccount<'info>,
+
+    #[account(address = bundled_position.whirlpool)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = spl_token_2022::ID)]
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: checked via account constraints
+    #[account(address = WP_NFT_UPDATE_AUTH)]
+    pub metadata_update_auth: UncheckedAccount<'info>,
+}
+
+/*
+  Moves a bundled position out of its PositionBundle into a new Position
+  with Mint and TokenAccount owned by Token-2022.
+  The liquidity, fee and reward checkpoints and amounts owed are kept by the new position.
+*/
+pub fn handler(
+    ctx: Context<MigrateBundledPositionOutOfBundle>,
+    bundle_index: u16,
+    with_token_metadata: bool,
+) -> Result<()> {
+    // Allow delegation
+    verify_position_bundle_authority_interface(
+        &ctx.accounts.position_bundle_token_account,
+        &ctx.accounts.position_bundle_authority,
+    )?;
+
+    // The reward extension appended to the Position account cannot be migrated
+    verify_no_reward_extension(
+        &ctx.accounts.bundled_position.to_account_info(),
+        Position::LEN,
+    )?;
+
+    ctx.accounts
+        .position_bundle
+        .close_bundled_position(bundle_index)?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+    let position_mint = &ctx.accounts.position_mint;
+    let position = &mut ctx.accounts.position;
+
+    let position_seeds = [
+        b"position".as_ref(),
+        position_mint.key.as_ref(),
+        &[ctx.bumps.position],
+    ];
+
+    position.migrate_position(&ctx.accounts.bundled_position, position_mint.key());
+
+    initialize_position_mint_2022(
+        position_mint,
+        &ctx.accounts.funder,
+        position,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_2022_program,
+        with_token_metadata,
+    )?;
+
+    if with_token_metadata {
+        let (name, symbol, uri) = build_position_token_metadata(position_mint, position, whirlpool);
+
+        initialize_token_metadata_extension(
+            name,
+            symbol,
+            uri,
+            position_mint,
+            position,
+            &ctx.accounts.metadata_update_auth,
+            &ctx.accounts.funder,
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_2022_program,
+            &position_seeds,
+        )?;
+    }
+
+    initialize_position_token_account_2022(
+        &ctx.accounts.position_token_account,
+        position_mint,
+        &ctx.accounts.funder,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.associated_token_program,
+    )?;
+
+    mint_position_token_2022_and_remove_authority(
+        position,
+        position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_2022_program,
+        &position_seeds,
+    )?;
+
+    // Anchor will close the bundled Position account
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+
+use crate::state::*;
+use crate::util::{
+    burn_and_close_user_position_token, verify_no_reward_extension, verify_position_authority,
+    verify_position_bundle_authority_interface,
+};
+
+#[derive(Accounts)]
+#[instruction(bundle_index: u16)]
+pub struct MigratePositionIntoBundle<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut,
+        close = receiver,
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut, address = position.position_mint)]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut,
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint)]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+        payer = funder,
+        space = Position::LEN,
+        seeds = [
+            b"bundled_position".as_ref(),
+            position_bundle.position_bundle_mint.key().as_ref(),
+            bundle_index.to_string().as_bytes()
+        ],
+        bump,
+    )]
+    pub bundled_position: Box<Account<'info, Position>>,
+
+    #[account(mut)]
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+
+    #[account(
+        constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
+        constraint = position_bundle_token_account.amount == 1
+    )]
+    pub position_bundle_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    pub position_bundle_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Moves a Position with Mint and TokenAccount owned by Token program into a PositionBundle.
+  The liquidity, fee and reward checkpoints and amounts owed are kept by the bundled position,
+  and the position token is burned.
+*/
+pub fn handler(ctx: Context<MigratePositionIntoBundle>, bundle_index: u16) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    // Allow delegation
+    verify_position_bundle_authority_interface(
+        &ctx.accounts.position_bundle_token_account,
+        &ctx.accounts.position_bundle_authority,
+    )?;
+
+    // The reward extension appended to the Position account cannot be migrated
+    verify_no_reward_extension(&ctx.accounts.position.to_account_info(), Position::LEN)?;
+
+    let position_bundle = &mut ctx.accounts.position_bundle;
+    position_bundle.open_bundled_position(bundle_index)?;
+
+    ctx.accounts
+        .bundled_position
+        .migrate_position(&ctx.accounts.position, position_bundle.position_bundle_mint);
+
+    burn_and_close_user_position_token(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.receiver,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )
+
+    // Anchor will close the Position account
+}
@@ -1,28 +1,50 @@
 #![allow(ambiguous_glob_reexports)]
 
+pub mod cancel_limit_order;
 pub mod close_bundled_position;
 pub mod close_position;
 pub mod close_position_with_token_extensions;
+pub mod close_tick_array;
 pub mod collect_fees;
+pub mod collect_limit_order;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
+pub mod compound_fees;
 pub mod decrease_liquidity;
 pub mod delete_position_bundle;
+pub mod delete_position_bundle_with_token_extensions;
 pub mod increase_liquidity;
+pub mod increase_oracle_capacity;
+pub mod initialize_adaptive_fee_tier;
 pub mod initialize_config;
+pub mod initialize_dynamic_tick_array;
 pub mod initialize_fee_tier;
+pub mod initialize_limit_order_book;
+pub mod initialize_oracle;
 pub mod initialize_pool;
+pub mod initialize_pool_with_adaptive_fee;
 pub mod initialize_position_bundle;
 pub mod initialize_position_bundle_with_metadata;
+pub mod initialize_position_bundle_with_token_extensions;
+pub mod initialize_position_reward_extension;
 pub mod initialize_reward;
+pub mod initialize_reward_extension;
 pub mod initialize_tick_array;
+pub mod initialize_tick_array_reward_extension;
+pub mod lock_position;
+pub mod migrate_bundled_position_out_of_bundle;
+pub mod migrate_position_into_bundle;
+pub mod migrate_position_with_token_extensions_into_bundle;
 pub mod open_bundled_position;
+pub mod open_limit_order;
 pub mod open_position;
 pub mod open_position_with_metadata;
 pub mod open_position_with_token_extensions;
+pub mod reset_position_range;
 pub mod set_collect_protocol_fees_authority;
 pub mod set_default_fee_rate;
 pub mod set_default_protocol_fee_rate;
+pub mod set_extension_reward_authority;
 pub mod set_fee_authority;
 pub mod set_fee_rate;
 pub mod set_protocol_fee_rate;
@@ -32,31 +54,54 @@ pub mod set_reward_emissions;
 pub mod set_reward_emissions_super_authority;
 pub mod swap;
 pub mod two_hop_swap;
+pub mod unlock_position;
 pub mod update_fees_and_rewards;
 
+pub use cancel_limit_order::*;
 pub use close_bundled_position::*;
 pub use close_position::*;
 pub use close_position_with_token_extensions::*;
+pub use close_tick_array::*;
 pub use collect_fees::*;
+pub use collect_limit_order::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
+pub use compound_fees::*;
 
 pub use delete_position_bundle::*;
+pub use delete_position_bundle_with_token_extensions::*;
 pub use increase_liquidity::*;
+pub use increase_oracle_capacity::*;
+pub use initialize_adaptive_fee_tier::*;
 pub use initialize_config::*;
+pub use initialize_dynamic_tick_array::*;
 pub use initialize_fee_tier::*;
+pub use initialize_limit_order_book::*;
+pub use initialize_oracle::*;
 pub use initialize_pool::*;
+pub use initialize_pool_with_adaptive_fee::*;
 pub use initialize_position_bundle::*;
 pub use initialize_position_bundle_with_metadata::*;
+pub use initialize_position_bundle_with_token_extensions::*;
+pub use initialize_position_reward_extension::*;
 pub use initialize_reward::*;
+pub use initialize_reward_extension::*;
 pub use initialize_tick_array::*;
+pub use initialize_tick_array_reward_extension::*;
+pub use lock_position::*;
+pub use migrate_bundled_position_out_of_bundle::*;
+pub use migrate_position_into_bundle::*;
+pub use migrate_position_with_token_extensions_into_bundle::*;
 pub use open_bundled_position::*;
+pub use open_limit_order::*;
 pub use open_position::*;
 pub use open_position_with_metadata::*;
 pub use open_position_with_token_extensions::*;
+pub use reset_position_range::*;
 pub use set_collect_protocol_fees_authority::*;
 pub use set_default_fee_rate::*;
 pub use set_default_protocol_fee_rate::*;
+pub use set_extension_reward_authority::*;
 pub use set_fee_authority::*;
 pub use set_fee_rate::*;
 pub use set_protocol_fee_rate::*;
@@ -66,6 +111,7 @@ pub use set_reward_emissions::*;
 pub use set_reward_emissions_super_authority::*;
 pub use swap::*;
 pub use two_hop_swap::*;
+pub use unlock_position::*;
 pub use update_fees_and_rewards::*;
 
 pub mod v2;
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
 
-use crate::{state::*, util::verify_position_bundle_authority};
+use crate::{state::*, util::verify_position_bundle_authority_interface};
 
 #[derive(Accounts)]
 #[instruction(bundle_index: u16)]
@@ -25,7 +25,7 @@ pub struct OpenBundledPosition<'info> {
         constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
         constraint = position_bundle_token_account.amount == 1
     )]
-    pub position_bundle_token_account: Box<Account<'info, TokenAccount>>,
+    pub position_bundle_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
 
     pub position_bundle_authority: Signer<'info>,
 
@@ -49,7 +49,7 @@ pub fn handler(
     let position = &mut ctx.accounts.bundled_position;
 
     // Allow delegation
-    verify_position_bundle_authority(
+    verify_position_bundle_authority_interface(
         &ctx.accounts.position_bundle_token_account,
         &ctx.accounts.position_bundle_authority,
     )?;
@@ -65,3 +65,147 @@ pub fn handler(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod open_bundled_position_tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+    use crate::util::test_utils::{
+        anchor_account_data, leaked_account_info, leaked_program_account_info, token_account_data,
+    };
+    use anchor_spl::token::spl_token;
+    use anchor_spl::token_2022::spl_token_2022;
+
+    const BUNDLE_INDEX: u16 = 3;
+
+    fn leak(account_info: AccountInfo<'static>) -> &'static AccountInfo<'static> {
+        Box::leak(Box::new(account_info))
+    }
+
+    // The init constraint of bundled_position requires the runtime,
+    // so the accounts are built as if it had been initialized
+    fn open(token_program: Pubkey, signer: Option<Pubkey>) -> Result<Position> {
+        let position_bundle_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let signer = signer.unwrap_or(owner);
+
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(position_bundle_mint).unwrap();
+        let whirlpool = Whirlpool {
+            tick_spacing: 64,
+            ..Default::default()
+        };
+
+        let rent_data = [
+            Rent::default()
+                .lamports_per_byte_year
+                .to_le_bytes()
+                .as_ref(),
+            Rent::default().exemption_threshold.to_le_bytes().as_ref(),
+            &[Rent::default().burn_percent],
+        ]
+        .concat();
+
+        let mut accounts = OpenBundledPosition {
+            bundled_position: Box::new(Account::try_from_unchecked(leak(leaked_account_info(
+                Pubkey::new_unique(),
+                crate::ID,
+                false,
+                true,
+                vec![0u8; Position::LEN],
+            )))?),
+            position_bundle: Box::new(Account::try_from(leak(leaked_account_info(
+                Pubkey::new_unique(),
+                crate::ID,
+                false,
+                true,
+                anchor_account_data(&position_bundle, PositionBundle::LEN),
+            )))?),
+            position_bundle_token_account: Box::new(InterfaceAccount::try_from(leak(
+                leaked_account_info(
+                    Pubkey::new_unique(),
+                    token_program,
+                    false,
+                    false,
+                    token_account_data(position_bundle_mint, owner, 1, None),
+                ),
+            ))?),
+            position_bundle_authority: Signer::try_from(leak(leaked_account_info(
+                signer,
+                System::id(),
+                true,
+                false,
+                vec![],
+            )))?,
+            whirlpool: Box::new(Account::try_from(leak(leaked_account_info(
+                Pubkey::new_unique(),
+                crate::ID,
+                false,
+                false,
+                anchor_account_data(&whirlpool, Whirlpool::LEN),
+            )))?),
+            funder: Signer::try_from(leak(leaked_account_info(
+                Pubkey::new_unique(),
+                System::id(),
+                true,
+                true,
+                vec![],
+            )))?,
+            system_program: Program::try_from(leak(leaked_program_account_info(System::id())))?,
+            rent: Sysvar::from_account_info(leak(leaked_account_info(
+                solana_program::sysvar::rent::ID,
+                solana_program::sysvar::ID,
+                false,
+                false,
+                rent_data,
+            )))?,
+        };
+
+        handler(
+            Context::new(
+                &crate::ID,
+                &mut accounts,
+                &[],
+                OpenBundledPositionBumps::default(),
+            ),
+            BUNDLE_INDEX,
+            -128,
+            128,
+        )?;
+
+        assert_eq!(
+            accounts.position_bundle.position_bitmap[0],
+            1 << BUNDLE_INDEX
+        );
+        Ok(Position::clone(&accounts.bundled_position))
+    }
+
+    #[test]
+    fn test_open_with_token_account() {
+        let position = open(spl_token::ID, None).unwrap();
+        assert_eq!(position.tick_lower_index, -128);
+        assert_eq!(position.tick_upper_index, 128);
+    }
+
+    #[test]
+    fn test_open_with_token_2022_account() {
+        let position = open(spl_token_2022::ID, None).unwrap();
+        assert_eq!(position.tick_lower_index, -128);
+        assert_eq!(position.tick_upper_index, 128);
+    }
+
+    #[test]
+    fn test_open_with_token_account_of_other_program() {
+        assert!(open(Pubkey::new_unique(), None).is_err());
+    }
+
+    #[test]
+    fn test_open_by_other_signer() {
+        for token_program in [spl_token::ID, spl_token_2022::ID] {
+            assert_eq!(
+                open(token_program, Some(Pubkey::new_unique())).err(),
+                Some(ErrorCode::MissingOrInvalidDelegate.into())
+            );
+        }
+    }
+}
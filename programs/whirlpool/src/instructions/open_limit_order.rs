@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Transfer};
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+
+use crate::{
+    errors::ErrorCode,
+    events::LimitOrderOpened,
+    state::*,
+    util::{
+        allocate_limit_order_book, get_limit_order_offset, load_limit_order_book_mut,
+        load_tick_array_mut, verify_limit_order_supported, verify_whirlpool_not_paused,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(tick_index: i32, a_to_b: bool)]
+pub struct OpenLimitOrder<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(init, payer = funder, space = LimitOrder::LEN)]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler, DynamicTickArrays are not supported
+    pub tick_array: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"tick_array_rent_payers", tick_array.key().as_ref()], bump)]
+    /// CHECK: TickArrayRentPayers of the TickArray, created in the handler if it does not exist
+    pub rent_payers: UncheckedAccount<'info>,
+
+    // Token-2022 accounts are accepted here to be rejected with LimitOrderNotSupported in the handler
+    #[account(mut, constraint = token_owner_account.mint == if a_to_b { whirlpool.token_mint_a } else { whirlpool.token_mint_b })]
+    pub token_owner_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+    #[account(mut, address = if a_to_b { whirlpool.token_vault_a } else { whirlpool.token_vault_b })]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<OpenLimitOrder>,
+    tick_index: i32,
+    a_to_b: bool,
+    amount: u64,
+) -> Result<()> {
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension,
+        &ctx.accounts.whirlpool_pause,
+    )?;
+
+    let whirlpool = &ctx.accounts.whirlpool;
+
+    // Orders selling token A are filled when the price rises to the tick,
+    // and orders selling token B are filled when the price falls to the tick.
+    let is_valid_tick_index = if a_to_b {
+        tick_index > whirlpool.tick_current_index
+    } else {
+        tick_index <= whirlpool.tick_current_index
+    };
+    if !is_valid_tick_index {
+        return Err(ErrorCode::InvalidLimitOrderTickIndex.into());
+    }
+
+    let tick_array_info = ctx.accounts.tick_array.to_account_info();
+    verify_limit_order_supported(
+        &tick_array_info,
+        &ctx.accounts.token_vault.to_account_info(),
+    )?;
+
+    // has_one = whirlpool constraint equivalent check
+    load_tick_array_mut(&tick_array_info, &whirlpool.key())?;
+
+    allocate_limit_order_book(
+        &tick_array_info,
+        &ctx.accounts.rent_payers,
+        ctx.bumps.rent_payers,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    let offset = get_limit_order_offset(
+        &tick_array_info,
+        &whirlpool.key(),
+        tick_index,
+        whirlpool.tick_spacing,
+    )?;
+
+    let mut limit_order_book = load_limit_order_book_mut(&tick_array_info)?;
+    ctx.accounts.limit_order.initialize(
+        whirlpool.key(),
+        ctx.accounts.owner.key(),
+        tick_index,
+        a_to_b,
+        amount,
+        limit_order_book.ticks[offset].level_mut(a_to_b),
+    )?;
+    drop(limit_order_book);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.token_owner_account.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit_cpi!(LimitOrderOpened {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        limit_order: ctx.accounts.limit_order.key(),
+        owner: ctx.accounts.owner.key(),
+        tick_index,
+        a_to_b,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{
+    load_position_reward_extension, store_position_reward_extension,
+    verify_position_authority_interface,
+};
+
+#[derive(Accounts)]
+pub struct ResetPositionRange<'info> {
+    pub position_authority: Signer<'info>,
+
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+}
+
+/*
+  Changes the tick range of an empty position in place, so that the position keeps its mint.
+  The PositionRewardExtension appended to the Position account, if any, must be empty as well.
+*/
+pub fn handler(
+    ctx: Context<ResetPositionRange>,
+    new_tick_lower_index: i32,
+    new_tick_upper_index: i32,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let position_info = ctx.accounts.position.to_account_info();
+    if let Some(mut position_reward_extension) = load_position_reward_extension(&position_info)? {
+        if !position_reward_extension.is_empty() {
+            return Err(ErrorCode::ClosePositionNotEmpty.into());
+        }
+        position_reward_extension.reset_checkpoints();
+        store_position_reward_extension(&position_info, &position_reward_extension)?;
+    }
+
+    ctx.accounts.position.reset_position_range(
+        ctx.accounts.whirlpool.tick_spacing,
+        new_tick_lower_index,
+        new_tick_upper_index,
+    )
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::ErrorCode,
+    state::Whirlpool,
+    util::{load_whirlpool_reward_extension, store_whirlpool_reward_extension},
+};
+
+#[derive(Accounts)]
+pub struct SetExtensionRewardAuthority<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    pub reward_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be new authority can be arbitrary
+    pub new_reward_authority: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetExtensionRewardAuthority>, reward_index: u8) -> Result<()> {
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    let mut reward_extension = load_whirlpool_reward_extension(&whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+
+    // address constraint equivalent check
+    let index = reward_index as usize;
+    match reward_extension.reward_infos.get(index) {
+        Some(reward_info) if reward_info.authority == ctx.accounts.reward_authority.key() => {}
+        Some(_) => return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into()),
+        None => return Err(ErrorCode::InvalidRewardIndex.into()),
+    }
+
+    reward_extension.update_reward_authority(index, ctx.accounts.new_reward_authority.key())?;
+    store_whirlpool_reward_extension(&whirlpool_rewards, &reward_extension)
+}
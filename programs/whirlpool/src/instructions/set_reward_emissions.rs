@@ -4,8 +4,10 @@ use anchor_spl::token::TokenAccount;
 use crate::errors::ErrorCode;
 use crate::manager::whirlpool_manager::next_whirlpool_reward_infos;
 use crate::math::checked_mul_shift_right;
-use crate::state::Whirlpool;
-use crate::util::to_timestamp_u64;
+use crate::state::{RewardSchedule, Whirlpool};
+use crate::util::{
+    load_whirlpool_reward_schedule, store_whirlpool_reward_schedule, to_timestamp_u64,
+};
 
 const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
 
@@ -20,6 +22,10 @@ pub struct SetRewardEmissions<'info> {
 
     #[account(address = whirlpool.reward_infos[reward_index as usize].vault)]
     pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
 }
 
 pub fn handler(
@@ -37,12 +43,37 @@ pub fn handler(
 
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
-    let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    let reward_schedule = load_whirlpool_reward_schedule(&whirlpool_rewards)?;
+    let (next_reward_infos, next_reward_schedule) =
+        next_whirlpool_reward_infos(whirlpool, &reward_schedule, timestamp)?;
+
+    // An increase of the emissions must be covered by the rewards which are not owed yet,
+    // which are only known if the ledgers of the reward schedules are tracked
+    let index = reward_index as usize;
+    if emissions_per_second_x64 > whirlpool.reward_infos[index].emissions_per_second_x64 {
+        let next_reward_schedule = next_reward_schedule
+            .as_ref()
+            .ok_or(ErrorCode::RewardScheduleNotInitialized)?;
+        if next_reward_schedule.ledgers[index].amount_available(reward_vault.amount)
+            < emissions_per_day
+        {
+            return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+        }
+    }
 
     ctx.accounts.whirlpool.update_emissions(
         reward_index as usize,
         next_reward_infos,
         timestamp,
         emissions_per_second_x64,
-    )
+    )?;
+
+    // A constant emissions rate replaces the schedule of the reward
+    if let Some(mut next_reward_schedule) = next_reward_schedule {
+        next_reward_schedule.schedules[reward_index as usize] = RewardSchedule::default();
+        store_whirlpool_reward_schedule(&whirlpool_rewards, &next_reward_schedule)?;
+    }
+
+    Ok(())
 }
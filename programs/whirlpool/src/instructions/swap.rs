@@ -3,11 +3,17 @@ use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::{
     errors::ErrorCode,
+    events::Traded,
     manager::swap_manager::*,
-    state::Whirlpool,
-    util::{to_timestamp_u64, update_and_swap_whirlpool, SparseSwapTickSequenceBuilder},
+    return_data::{set_return_data, SwapResult},
+    state::{OracleAccessor, Whirlpool},
+    util::{
+        load_whirlpool_reward_extension, load_whirlpool_reward_schedule, to_timestamp_u64,
+        update_and_swap_whirlpool, verify_whirlpool_not_paused, SparseSwapTickSequenceBuilder,
+    },
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Swap<'info> {
     #[account(address = token::ID)]
@@ -40,9 +46,21 @@ pub struct Swap<'info> {
     /// CHECK: checked in the handler
     pub tick_array_2: UncheckedAccount<'info>,
 
-    #[account(seeds = [b"oracle", whirlpool.key().as_ref()],bump)]
-    /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()],bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
     pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
 }
 
 pub fn handler(
@@ -53,6 +71,11 @@ pub fn handler(
     amount_specified_is_input: bool,
     a_to_b: bool, // Zero for one
 ) -> Result<()> {
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension,
+        &ctx.accounts.whirlpool_pause,
+    )?;
+
     let whirlpool = &mut ctx.accounts.whirlpool;
     let clock = Clock::get()?;
     // Update the global reward growth which increases as a function of time.
@@ -70,6 +93,10 @@ pub fn handler(
     )?;
     let mut swap_tick_sequence = builder.build()?;
 
+    let oracle_accessor = OracleAccessor::new(whirlpool, ctx.accounts.oracle.to_account_info())?;
+    let reward_schedule = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards)?;
+    let reward_extension = load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards)?;
+
     let swap_update = swap(
         whirlpool,
         &mut swap_tick_sequence,
@@ -78,6 +105,9 @@ pub fn handler(
         amount_specified_is_input,
         a_to_b,
         timestamp,
+        &oracle_accessor.get_adaptive_fee_info()?,
+        &reward_schedule,
+        &reward_extension,
     )?;
 
     if amount_specified_is_input {
@@ -92,16 +122,42 @@ pub fn handler(
         return Err(ErrorCode::AmountInAboveMaximum.into());
     }
 
+    let pre_sqrt_price = whirlpool.sqrt_price;
+    let pre_tick_index = whirlpool.tick_current_index;
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    let swap_result = SwapResult::new(&swap_update, a_to_b, 0, 0);
+
     update_and_swap_whirlpool(
         whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &ctx.accounts.token_authority,
         &ctx.accounts.token_owner_account_a,
         &ctx.accounts.token_owner_account_b,
         &ctx.accounts.token_vault_a,
         &ctx.accounts.token_vault_b,
         &ctx.accounts.token_program,
+        &oracle_accessor,
         swap_update,
         a_to_b,
         timestamp,
-    )
+    )?;
+
+    emit_cpi!(Traded {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        a_to_b,
+        pre_sqrt_price,
+        post_sqrt_price: ctx.accounts.whirlpool.sqrt_price,
+        pre_tick_index,
+        post_tick_index: ctx.accounts.whirlpool.tick_current_index,
+        input_amount,
+        output_amount,
+        input_transfer_fee: 0,
+        output_transfer_fee: 0,
+    });
+
+    set_return_data(&swap_result)
 }
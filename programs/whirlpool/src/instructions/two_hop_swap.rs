@@ -3,11 +3,17 @@ use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::{
     errors::ErrorCode,
+    events::Traded,
     manager::swap_manager::*,
-    state::Whirlpool,
-    util::{to_timestamp_u64, update_and_swap_whirlpool, SparseSwapTickSequenceBuilder},
+    return_data::{set_return_data, SwapResult},
+    state::{OracleAccessor, Whirlpool},
+    util::{
+        load_whirlpool_reward_extension, load_whirlpool_reward_schedule, to_timestamp_u64,
+        update_and_swap_whirlpool, verify_whirlpool_not_paused, SparseSwapTickSequenceBuilder,
+    },
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct TwoHopSwap<'info> {
     #[account(address = token::ID)]
@@ -65,13 +71,37 @@ pub struct TwoHopSwap<'info> {
     /// CHECK: checked in the handler
     pub tick_array_two_2: UncheckedAccount<'info>,
 
-    #[account(seeds = [b"oracle", whirlpool_one.key().as_ref()],bump)]
-    /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
+    #[account(mut, seeds = [b"oracle", whirlpool_one.key().as_ref()],bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
     pub oracle_one: UncheckedAccount<'info>,
 
-    #[account(seeds = [b"oracle", whirlpool_two.key().as_ref()],bump)]
-    /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
+    #[account(mut, seeds = [b"oracle", whirlpool_two.key().as_ref()],bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
     pub oracle_two: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool_one.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension_one: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool_one.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause_one: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool_two.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension_two: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool_two.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause_two: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool_one.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards_one: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool_two.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards_two: UncheckedAccount<'info>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -85,6 +115,15 @@ pub fn handler(
     sqrt_price_limit_one: u128,
     sqrt_price_limit_two: u128,
 ) -> Result<()> {
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension_one,
+        &ctx.accounts.whirlpool_pause_one,
+    )?;
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension_two,
+        &ctx.accounts.whirlpool_pause_two,
+    )?;
+
     let clock = Clock::get()?;
     // Update the global reward growth which increases as a function of time.
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
@@ -136,6 +175,18 @@ pub fn handler(
     )?;
     let mut swap_tick_sequence_two = builder_two.build()?;
 
+    let oracle_accessor_one =
+        OracleAccessor::new(whirlpool_one, ctx.accounts.oracle_one.to_account_info())?;
+    let oracle_accessor_two =
+        OracleAccessor::new(whirlpool_two, ctx.accounts.oracle_two.to_account_info())?;
+
+    let reward_schedule_one = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards_one)?;
+    let reward_schedule_two = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards_two)?;
+    let reward_extension_one =
+        load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards_one)?;
+    let reward_extension_two =
+        load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards_two)?;
+
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
     // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
     let (swap_update_one, swap_update_two) = if amount_specified_is_input {
@@ -150,6 +201,9 @@ pub fn handler(
             amount_specified_is_input, // true
             a_to_b_one,
             timestamp,
+            &oracle_accessor_one.get_adaptive_fee_info()?,
+            &reward_schedule_one,
+            &reward_extension_one,
         )?;
 
         // Swap two input is the output of swap one
@@ -167,6 +221,9 @@ pub fn handler(
             amount_specified_is_input, // true
             a_to_b_two,
             timestamp,
+            &oracle_accessor_two.get_adaptive_fee_info()?,
+            &reward_schedule_two,
+            &reward_extension_two,
         )?;
         (swap_calc_one, swap_calc_two)
     } else {
@@ -181,6 +238,9 @@ pub fn handler(
             amount_specified_is_input, // false
             a_to_b_two,
             timestamp,
+            &oracle_accessor_two.get_adaptive_fee_info()?,
+            &reward_schedule_two,
+            &reward_extension_two,
         )?;
 
         // The output of swap 1 is input of swap_calc_two
@@ -198,6 +258,9 @@ pub fn handler(
             amount_specified_is_input, // false
             a_to_b_one,
             timestamp,
+            &oracle_accessor_one.get_adaptive_fee_info()?,
+            &reward_schedule_one,
+            &reward_extension_one,
         )?;
         (swap_calc_one, swap_calc_two)
     };
@@ -243,14 +306,36 @@ pub fn handler(
         }
     }
 
+    let pre_sqrt_price_one = whirlpool_one.sqrt_price;
+    let pre_tick_index_one = whirlpool_one.tick_current_index;
+    let (input_amount_one, output_amount_one) = if a_to_b_one {
+        (swap_update_one.amount_a, swap_update_one.amount_b)
+    } else {
+        (swap_update_one.amount_b, swap_update_one.amount_a)
+    };
+
+    let pre_sqrt_price_two = whirlpool_two.sqrt_price;
+    let pre_tick_index_two = whirlpool_two.tick_current_index;
+    let (input_amount_two, output_amount_two) = if a_to_b_two {
+        (swap_update_two.amount_a, swap_update_two.amount_b)
+    } else {
+        (swap_update_two.amount_b, swap_update_two.amount_a)
+    };
+    let swap_results = vec![
+        SwapResult::new(&swap_update_one, a_to_b_one, 0, 0),
+        SwapResult::new(&swap_update_two, a_to_b_two, 0, 0),
+    ];
+
     update_and_swap_whirlpool(
         whirlpool_one,
+        &ctx.accounts.whirlpool_rewards_one,
         &ctx.accounts.token_authority,
         &ctx.accounts.token_owner_account_one_a,
         &ctx.accounts.token_owner_account_one_b,
         &ctx.accounts.token_vault_one_a,
         &ctx.accounts.token_vault_one_b,
         &ctx.accounts.token_program,
+        &oracle_accessor_one,
         swap_update_one,
         a_to_b_one,
         timestamp,
@@ -258,14 +343,44 @@ pub fn handler(
 
     update_and_swap_whirlpool(
         whirlpool_two,
+        &ctx.accounts.whirlpool_rewards_two,
         &ctx.accounts.token_authority,
         &ctx.accounts.token_owner_account_two_a,
         &ctx.accounts.token_owner_account_two_b,
         &ctx.accounts.token_vault_two_a,
         &ctx.accounts.token_vault_two_b,
         &ctx.accounts.token_program,
+        &oracle_accessor_two,
         swap_update_two,
         a_to_b_two,
         timestamp,
-    )
+    )?;
+
+    emit_cpi!(Traded {
+        whirlpool: ctx.accounts.whirlpool_one.key(),
+        a_to_b: a_to_b_one,
+        pre_sqrt_price: pre_sqrt_price_one,
+        post_sqrt_price: ctx.accounts.whirlpool_one.sqrt_price,
+        pre_tick_index: pre_tick_index_one,
+        post_tick_index: ctx.accounts.whirlpool_one.tick_current_index,
+        input_amount: input_amount_one,
+        output_amount: output_amount_one,
+        input_transfer_fee: 0,
+        output_transfer_fee: 0,
+    });
+
+    emit_cpi!(Traded {
+        whirlpool: ctx.accounts.whirlpool_two.key(),
+        a_to_b: a_to_b_two,
+        pre_sqrt_price: pre_sqrt_price_two,
+        post_sqrt_price: ctx.accounts.whirlpool_two.sqrt_price,
+        pre_tick_index: pre_tick_index_two,
+        post_tick_index: ctx.accounts.whirlpool_two.tick_current_index,
+        input_amount: input_amount_two,
+        output_amount: output_amount_two,
+        input_transfer_fee: 0,
+        output_transfer_fee: 0,
+    });
+
+    set_return_data(&swap_results)
 }
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::{
+    thaw_user_position_token_2022, to_timestamp_u64, verify_position_authority_interface,
+};
+
+#[derive(Accounts)]
+pub struct UnlockPosition<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only, checked against the LockConfig in the handler
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(address = position.position_mint, owner = token_2022_program.key())]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut,
+        seeds = [b"lock_config".as_ref(), position.key().as_ref()],
+        bump,
+        has_one = position,
+    )]
+    pub lock_config: Account<'info, LockConfig>,
+
+    #[account(address = token_2022::ID)]
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+/*
+  Unlocks a Position whose lock has expired, thawing the position token account.
+  The rent of the LockConfig is refunded to the funder recorded at lock time.
+*/
+pub fn handler(ctx: Context<UnlockPosition>) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    if ctx.accounts.lock_config.is_locked(timestamp) {
+        return Err(ErrorCode::PositionLocked.into());
+    }
+
+    // address constraint equivalent check
+    if ctx.accounts.funder.key() != ctx.accounts.lock_config.rent_receiver() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+
+    thaw_user_position_token_2022(
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.position,
+        &[
+            b"position".as_ref(),
+            ctx.accounts.position_mint.key().as_ref(),
+            &[ctx.bumps.position],
+        ],
+    )?;
+
+    ctx.accounts
+        .lock_config
+        .close(ctx.accounts.funder.to_account_info())
+}
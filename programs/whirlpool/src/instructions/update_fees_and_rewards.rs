@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    manager::liquidity_manager::calculate_fee_and_reward_growths, state::*, util::to_timestamp_u64,
+    manager::liquidity_manager::{
+        calculate_fee_and_reward_growths, calculate_reward_extension_growths,
+        sync_reward_extension_values,
+    },
+    state::*,
+    util::{store_whirlpool_reward_schedule, to_timestamp_u64},
 };
 
 #[derive(Accounts)]
@@ -12,10 +17,14 @@ pub struct UpdateFeesAndRewards<'info> {
     #[account(mut, has_one = whirlpool)]
     pub position: Account<'info, Position>,
 
-    #[account(has_one = whirlpool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(has_one = whirlpool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
@@ -24,8 +33,20 @@ pub fn handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
-    let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    let (position_update, reward_infos, reward_schedule) = calculate_fee_and_reward_growths(
+        whirlpool,
+        &whirlpool_rewards,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        timestamp,
+    )?;
+
+    // Rewards of the reward extension accrue along with the rewards of the Whirlpool
+    let reward_extension_update = calculate_reward_extension_growths(
         whirlpool,
+        &whirlpool_rewards,
         position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
@@ -35,5 +56,13 @@ pub fn handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
     whirlpool.update_rewards(reward_infos, timestamp);
     position.update(&position_update);
 
+    if let Some(reward_schedule) = &reward_schedule {
+        store_whirlpool_reward_schedule(&whirlpool_rewards, reward_schedule)?;
+    }
+
+    if let Some(reward_extension_update) = &reward_extension_update {
+        sync_reward_extension_values(&whirlpool_rewards, position, reward_extension_update)?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,514 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use super::collect_reward::calculate_collect_reward;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, to_timestamp_u64,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::{FeesCollected, RewardCollected},
+    manager::liquidity_manager::{
+        calculate_fee_and_reward_growths, calculate_reward_extension_growths,
+        sync_reward_extension_values,
+    },
+    state::*,
+    util::{
+        load_position_reward_extension, load_whirlpool_reward_extension,
+        record_extension_reward_collected, record_reward_collected,
+        store_position_reward_extension, store_whirlpool_reward_schedule,
+        v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface,
+    },
+};
+
+// reward mint, reward vault, reward token program, reward owner account
+const REWARD_FIXED_ACCOUNTS_LEN: usize = 4;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectAllV2<'info> {
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked in the handler
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == whirlpool.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == whirlpool.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - accounts for each initialized reward, then for each initialized extension reward if the
+    //   position has a reward extension (Reward, in the order of reward index)
+    //   reward mint, reward vault, reward token program, reward owner account,
+    //   accounts for transfer hook program of the reward mint
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
+}
+
+struct RewardAccounts<'info> {
+    reward_mint: Box<InterfaceAccount<'info, Mint>>,
+    reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    reward_token_program: Interface<'info, TokenInterface>,
+    reward_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    transfer_hook_accounts: Option<Vec<AccountInfo<'info>>>,
+}
+
+impl<'info> RewardAccounts<'info> {
+    fn load(
+        accounts: &'info [AccountInfo<'info>],
+        reward_info: &WhirlpoolRewardInfo,
+    ) -> Result<Self> {
+        if accounts.len() < REWARD_FIXED_ACCOUNTS_LEN {
+            return Err(ErrorCode::RewardAccountsMismatch.into());
+        }
+
+        let reward_mint = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[0])?);
+        let reward_vault = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?);
+        let reward_token_program = Interface::<TokenInterface>::try_from(&accounts[2])?;
+        let reward_owner_account =
+            Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?);
+
+        // address constraint equivalent checks
+        if reward_mint.key() != reward_info.mint
+            || reward_vault.key() != reward_info.vault
+            || reward_token_program.key() != *accounts[0].owner
+        {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+        }
+        if reward_owner_account.mint != reward_info.mint {
+            return Err(anchor_lang::error::ErrorCode::ConstraintRaw.into());
+        }
+
+        let transfer_hook_accounts = if accounts.len() > REWARD_FIXED_ACCOUNTS_LEN {
+            Some(accounts[REWARD_FIXED_ACCOUNTS_LEN..].to_vec())
+        } else {
+            None
+        };
+
+        Ok(Self {
+            reward_mint,
+            reward_vault,
+            reward_token_program,
+            reward_owner_account,
+            transfer_hook_accounts,
+        })
+    }
+}
+
+/// Collects the fees and all initialized rewards of a position in a single instruction.
+///
+/// The fee and reward growth checkpoints of the position are updated first, equivalent to
+/// update_fees_and_rewards. Positions without liquidity cannot accrue anything, so the update
+/// is skipped for them.
+///
+/// The Reward slices are ordered by reward index: one per initialized reward, followed by one per
+/// initialized extension reward if the position has a reward extension.
+///
+/// As with collect_reward, if a reward vault does not have enough tokens, the maximum number of
+/// available tokens will be debited to the user and the unharvested amount remains tracked.
+///
+/// # Returns
+/// - `Ok`: Fees and rewards have been successfully harvested
+/// - `Err`: `RewardAccountsMismatch` if the Reward slices do not match the initialized rewards
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CollectAllV2<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::Reward,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+
+    let whirlpool_reward_extension =
+        load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards)?;
+    let position_has_reward_extension =
+        PositionRewardExtension::is_allocated(ctx.accounts.position.to_account_info().data_len());
+    let (reward_infos, extension_reward_infos) = collectable_reward_infos(
+        &ctx.accounts.whirlpool.reward_infos,
+        &whirlpool_reward_extension,
+        position_has_reward_extension,
+    );
+    if remaining_accounts.rewards.len() != reward_infos.len() + extension_reward_infos.len() {
+        return Err(ErrorCode::RewardAccountsMismatch.into());
+    }
+
+    let reward_accounts = remaining_accounts
+        .rewards
+        .iter()
+        .zip(reward_infos.iter().chain(extension_reward_infos.iter()))
+        .map(|(accounts, reward_info)| RewardAccounts::load(accounts, reward_info))
+        .collect::<Result<Vec<_>>>()?;
+    let (reward_accounts, extension_reward_accounts) = reward_accounts.split_at(reward_infos.len());
+
+    let token_mints = [&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b]
+        .into_iter()
+        .chain(
+            reward_accounts
+                .iter()
+                .map(|accounts| &*accounts.reward_mint),
+        )
+        .chain(
+            extension_reward_accounts
+                .iter()
+                .map(|accounts| &*accounts.reward_mint),
+        )
+        .collect::<Vec<_>>();
+    verify_token_badge_allowlists(
+        &ctx.accounts.whirlpool.whirlpools_config,
+        &token_mints,
+        remaining_accounts.token_badges,
+    )?;
+
+    if ctx.accounts.position.liquidity > 0 {
+        let clock = Clock::get()?;
+        let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+        let (position_update, reward_infos, reward_schedule) = calculate_fee_and_reward_growths(
+            &ctx.accounts.whirlpool,
+            &ctx.accounts.whirlpool_rewards,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+
+        // Rewards of the reward extension accrue along with the rewards of the Whirlpool
+        let reward_extension_update = calculate_reward_extension_growths(
+            &ctx.accounts.whirlpool,
+            &ctx.accounts.whirlpool_rewards,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+
+        ctx.accounts
+            .whirlpool
+            .update_rewards(reward_infos, timestamp);
+        ctx.accounts.position.update(&position_update);
+
+        if let Some(reward_schedule) = &reward_schedule {
+            store_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards, reward_schedule)?;
+        }
+
+        if let Some(reward_extension_update) = &reward_extension_update {
+            sync_reward_extension_values(
+                &ctx.accounts.whirlpool_rewards,
+                &ctx.accounts.position,
+                reward_extension_update,
+            )?;
+        }
+    }
+
+    let position = &mut ctx.accounts.position;
+
+    // Store the fees owed to use as transfer amounts.
+    let fee_owed_a = position.fee_owed_a;
+    let fee_owed_b = position.fee_owed_b;
+
+    position.reset_fees_owed();
+
+    let reward_transfer_amounts = collect_rewards_owed(
+        &mut position.reward_infos[..reward_accounts.len()],
+        &vault_amounts(reward_accounts),
+    );
+
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    for (index, transfer_amount) in reward_transfer_amounts.iter().enumerate() {
+        record_reward_collected(&whirlpool_rewards, index, *transfer_amount)?;
+    }
+
+    let extension_reward_transfer_amounts = if extension_reward_accounts.is_empty() {
+        vec![]
+    } else {
+        let position_info = position.to_account_info();
+        let mut position_reward_extension = load_position_reward_extension(&position_info)?
+            .ok_or(ErrorCode::RewardExtensionNotAllocated)?;
+        let transfer_amounts = collect_rewards_owed(
+            &mut position_reward_extension.reward_infos[..extension_reward_accounts.len()],
+            &vault_amounts(extension_reward_accounts),
+        );
+        store_position_reward_extension(&position_info, &position_reward_extension)?;
+        for (index, transfer_amount) in transfer_amounts.iter().enumerate() {
+            record_extension_reward_collected(&whirlpool_rewards, index, *transfer_amount)?;
+        }
+        transfer_amounts
+    };
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        fee_owed_a,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        fee_owed_b,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+
+    // Extension rewards are reported with their reward index offset by NUM_REWARDS
+    let collected_rewards = reward_accounts
+        .iter()
+        .zip(reward_transfer_amounts.iter())
+        .enumerate()
+        .chain(
+            extension_reward_accounts
+                .iter()
+                .zip(extension_reward_transfer_amounts.iter())
+                .enumerate()
+                .map(|(index, collected)| (NUM_REWARDS + index, collected)),
+        )
+        .collect::<Vec<_>>();
+
+    for (_, (accounts, transfer_amount)) in collected_rewards.iter() {
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.whirlpool,
+            &accounts.reward_mint,
+            &accounts.reward_vault,
+            &accounts.reward_owner_account,
+            &accounts.reward_token_program,
+            &ctx.accounts.memo_program,
+            &accounts.transfer_hook_accounts,
+            **transfer_amount,
+            transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+        )?;
+    }
+
+    emit_cpi!(FeesCollected {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        fee_a_amount: fee_owed_a,
+        fee_b_amount: fee_owed_b,
+        fee_a_transfer_fee: calculate_transfer_fee_excluded_amount(
+            &ctx.accounts.token_mint_a,
+            fee_owed_a
+        )?
+        .transfer_fee,
+        fee_b_transfer_fee: calculate_transfer_fee_excluded_amount(
+            &ctx.accounts.token_mint_b,
+            fee_owed_b
+        )?
+        .transfer_fee,
+    });
+
+    for (reward_index, (accounts, transfer_amount)) in collected_rewards.iter() {
+        emit_cpi!(RewardCollected {
+            whirlpool: ctx.accounts.whirlpool.key(),
+            position: ctx.accounts.position.key(),
+            reward_index: *reward_index as u8,
+            reward_mint: accounts.reward_mint.key(),
+            amount: **transfer_amount,
+            transfer_fee: calculate_transfer_fee_excluded_amount(
+                &accounts.reward_mint,
+                **transfer_amount
+            )?
+            .transfer_fee,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the initialized rewards and extension rewards which have a Reward slice.
+/// Extension rewards can only be owed to positions with a reward extension.
+fn collectable_reward_infos(
+    reward_infos: &[WhirlpoolRewardInfo; NUM_REWARDS],
+    whirlpool_reward_extension: &Option<WhirlpoolRewardExtension>,
+    position_has_reward_extension: bool,
+) -> (Vec<WhirlpoolRewardInfo>, Vec<WhirlpoolRewardInfo>) {
+    let initialized = |reward_infos: &[WhirlpoolRewardInfo]| {
+        reward_infos
+            .iter()
+            .filter(|reward_info| reward_info.initialized())
+            .copied()
+            .collect::<Vec<_>>()
+    };
+
+    let extension_reward_infos = match whirlpool_reward_extension {
+        Some(reward_extension) if position_has_reward_extension => {
+            initialized(&reward_extension.reward_infos)
+        }
+        _ => vec![],
+    };
+    (initialized(reward_infos), extension_reward_infos)
+}
+
+fn vault_amounts(reward_accounts: &[RewardAccounts]) -> Vec<u64> {
+    reward_accounts
+        .iter()
+        .map(|accounts| accounts.reward_vault.amount)
+        .collect()
+}
+
+/// Debits the amounts owed to the position, capped by the vault amounts, and returns the transfer amounts.
+fn collect_rewards_owed(
+    reward_infos: &mut [PositionRewardInfo],
+    vault_amounts: &[u64],
+) -> Vec<u64> {
+    reward_infos
+        .iter_mut()
+        .zip(vault_amounts.iter())
+        .map(|(reward_info, vault_amount)| {
+            let (transfer_amount, updated_amount_owed) =
+                calculate_collect_reward(*reward_info, *vault_amount);
+            reward_info.amount_owed = updated_amount_owed;
+            transfer_amount
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod collect_all_tests {
+    use super::*;
+
+    fn reward_info(initialized: bool) -> WhirlpoolRewardInfo {
+        WhirlpoolRewardInfo {
+            mint: if initialized {
+                Pubkey::new_unique()
+            } else {
+                Pubkey::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn reward_extension(initialized_count: usize) -> WhirlpoolRewardExtension {
+        let mut reward_extension = WhirlpoolRewardExtension::default();
+        for (index, extension_reward_info) in reward_extension.reward_infos.iter_mut().enumerate() {
+            *extension_reward_info = reward_info(index < initialized_count);
+        }
+        reward_extension
+    }
+
+    fn position_reward(amount_owed: u64) -> PositionRewardInfo {
+        PositionRewardInfo {
+            amount_owed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_collectable_reward_infos_without_extension() {
+        let reward_infos = [reward_info(true), reward_info(true), reward_info(false)];
+        let (rewards, extension_rewards) = collectable_reward_infos(&reward_infos, &None, false);
+        assert_eq!(rewards, reward_infos[..2].to_vec());
+        assert!(extension_rewards.is_empty());
+    }
+
+    #[test]
+    fn test_collectable_reward_infos_with_extension() {
+        let reward_infos = [reward_info(true), reward_info(false), reward_info(false)];
+        let reward_extension = reward_extension(2);
+        let (rewards, extension_rewards) =
+            collectable_reward_infos(&reward_infos, &Some(reward_extension), true);
+        assert_eq!(rewards, reward_infos[..1].to_vec());
+        assert_eq!(
+            extension_rewards,
+            reward_extension.reward_infos[..2].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_collectable_reward_infos_position_without_extension() {
+        // nothing can be owed to a position without reward extension
+        let reward_infos = [reward_info(true), reward_info(true), reward_info(true)];
+        let (rewards, extension_rewards) =
+            collectable_reward_infos(&reward_infos, &Some(reward_extension(3)), false);
+        assert_eq!(rewards.len(), NUM_REWARDS);
+        assert!(extension_rewards.is_empty());
+    }
+
+    #[test]
+    fn test_collect_rewards_owed() {
+        let mut reward_infos = [
+            position_reward(100),
+            position_reward(50),
+            position_reward(0),
+        ];
+        let transfer_amounts = collect_rewards_owed(&mut reward_infos, &[1000, 20, 10]);
+        assert_eq!(transfer_amounts, vec![100, 20, 0]);
+        // the unharvested amount remains tracked
+        assert_eq!(
+            reward_infos.map(|reward_info| reward_info.amount_owed),
+            [0, 30, 0]
+        );
+    }
+
+    #[test]
+    fn test_collect_extension_rewards_owed() {
+        let mut position_reward_extension = PositionRewardExtension::default();
+        position_reward_extension.reward_infos[0] = position_reward(7);
+        position_reward_extension.reward_infos[1] = position_reward(9);
+        position_reward_extension.reward_infos[2] = position_reward(11);
+
+        // only the rewards with a Reward slice are collected
+        let transfer_amounts =
+            collect_rewards_owed(&mut position_reward_extension.reward_infos[..2], &[7, 5]);
+        assert_eq!(transfer_amounts, vec![7, 5]);
+        assert_eq!(position_reward_extension.reward_infos[0].amount_owed, 0);
+        assert_eq!(position_reward_extension.reward_infos[1].amount_owed, 4);
+        assert_eq!(position_reward_extension.reward_infos[2].amount_owed, 11);
+    }
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::RewardCollected,
+    instructions::v2::collect_reward::calculate_collect_reward,
+    state::*,
+    util::{
+        load_position_reward_extension, load_whirlpool_reward_extension,
+        record_extension_reward_collected, store_position_reward_extension,
+        v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectExtensionReward<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = whirlpool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub reward_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *reward_mint.to_account_info().owner)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of reward_mint
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
+}
+
+/// Collects all harvestable tokens for a specified extension reward.
+///
+/// As with collect_reward, if the reward vault does not have enough tokens, the maximum number of
+/// available tokens will be debited to the user and the unharvested amount remains tracked.
+/// The RewardCollected event reports the reward index offset by NUM_REWARDS.
+///
+/// # Parameters
+/// - `reward_index` - The extension reward to harvest, from 0 to NUM_EXTENSION_REWARDS - 1.
+///
+/// # Returns
+/// - `Ok`: Reward tokens at the specified reward index have been successfully harvested
+/// - `Err`: `RewardExtensionNotInitialized` if the Whirlpool has no reward extension,
+///   `RewardExtensionNotAllocated` if the position has no reward extension,
+///   `InvalidRewardIndex` if the extension reward is not initialized
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectExtensionReward<'info>>,
+    reward_index: u8,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let whirlpool_reward_extension =
+        load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards)?
+            .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+
+    let index = reward_index as usize;
+    let reward_info = whirlpool_reward_extension.get_initialized_reward(index)?;
+
+    // address constraint equivalent checks
+    if ctx.accounts.reward_mint.key() != reward_info.mint
+        || ctx.accounts.reward_vault.key() != reward_info.vault
+    {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+    if ctx.accounts.reward_owner_account.mint != reward_info.mint {
+        return Err(anchor_lang::error::ErrorCode::ConstraintRaw.into());
+    }
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookReward, AccountsType::TokenBadges],
+    )?;
+    verify_token_badge_allowlists(
+        &ctx.accounts.whirlpool.whirlpools_config,
+        &[&ctx.accounts.reward_mint],
+        remaining_accounts.token_badges,
+    )?;
+
+    let position_info = ctx.accounts.position.to_account_info();
+    let mut position_reward_extension = load_position_reward_extension(&position_info)?
+        .ok_or(ErrorCode::RewardExtensionNotAllocated)?;
+
+    let (transfer_amount, updated_amount_owed) = calculate_collect_reward(
+        position_reward_extension.reward_infos[index],
+        ctx.accounts.reward_vault.amount,
+    );
+
+    position_reward_extension.update_reward_owed(index, updated_amount_owed);
+    store_position_reward_extension(&position_info, &position_reward_extension)?;
+    record_extension_reward_collected(&ctx.accounts.whirlpool_rewards, index, transfer_amount)?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_owner_account,
+        &ctx.accounts.reward_token_program,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_reward,
+        transfer_amount,
+        transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+    )?;
+
+    emit_cpi!(RewardCollected {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        reward_index: (NUM_REWARDS + index) as u8,
+        reward_mint: ctx.accounts.reward_mint.key(),
+        amount: transfer_amount,
+        transfer_fee: calculate_transfer_fee_excluded_amount(
+            &ctx.accounts.reward_mint,
+            transfer_amount
+        )?
+        .transfer_fee,
+    });
+
+    Ok(())
+}
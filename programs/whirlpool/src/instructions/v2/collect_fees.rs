@@ -2,13 +2,18 @@ use anchor_lang::prelude::*;
 use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
+};
 use crate::{
     constants::transfer_memo,
+    events::FeesCollected,
     state::*,
     util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CollectFeesV2<'info> {
     pub whirlpool: Box<Account<'info, Whirlpool>>,
@@ -46,6 +51,7 @@ pub struct CollectFeesV2<'info> {
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
 }
 
 pub fn handler<'info>(
@@ -61,7 +67,16 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+    verify_token_badge_allowlists(
+        &ctx.accounts.whirlpool.whirlpools_config,
+        &[&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b],
+        remaining_accounts.token_badges,
     )?;
 
     let position = &mut ctx.accounts.position;
@@ -96,5 +111,22 @@ pub fn handler<'info>(
         transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
     )?;
 
+    emit_cpi!(FeesCollected {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        fee_a_amount: fee_owed_a,
+        fee_b_amount: fee_owed_b,
+        fee_a_transfer_fee: calculate_transfer_fee_excluded_amount(
+            &ctx.accounts.token_mint_a,
+            fee_owed_a
+        )?
+        .transfer_fee,
+        fee_b_transfer_fee: calculate_transfer_fee_excluded_amount(
+            &ctx.accounts.token_mint_b,
+            fee_owed_b
+        )?
+        .transfer_fee,
+    });
+
     Ok(())
 }
@@ -1,4 +1,6 @@
-use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{
+    parse_remaining_accounts, verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
+};
 use crate::{constants::transfer_memo, state::*, util::v2::transfer_from_vault_to_owner_v2};
 use anchor_lang::prelude::*;
 use anchor_spl::memo::Memo;
@@ -39,6 +41,7 @@ pub struct CollectProtocolFeesV2<'info> {
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
 }
 
 pub fn handler<'info>(
@@ -51,7 +54,16 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+    verify_token_badge_allowlists(
+        &whirlpool.whirlpools_config,
+        &[&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b],
+        remaining_accounts.token_badges,
     )?;
 
     transfer_from_vault_to_owner_v2(
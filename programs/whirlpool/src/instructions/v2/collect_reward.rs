@@ -2,13 +2,21 @@ use anchor_lang::prelude::*;
 use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
+};
 use crate::{
     constants::transfer_memo,
+    events::RewardCollected,
     state::*,
-    util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface},
+    util::{
+        record_reward_collected, v2::transfer_from_vault_to_owner_v2,
+        verify_position_authority_interface,
+    },
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct CollectRewardV2<'info> {
@@ -38,8 +46,13 @@ pub struct CollectRewardV2<'info> {
     #[account(address = *reward_mint.to_account_info().owner)]
     pub reward_token_program: Interface<'info, TokenInterface>,
     pub memo_program: Program<'info, Memo>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
     // remaining accounts
     // - accounts for transfer hook program of reward_mint
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
 }
 
 /// Collects all harvestable tokens for a specified reward.
@@ -69,7 +82,12 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookReward],
+        &[AccountsType::TransferHookReward, AccountsType::TokenBadges],
+    )?;
+    verify_token_badge_allowlists(
+        &ctx.accounts.whirlpool.whirlpools_config,
+        &[&ctx.accounts.reward_mint],
+        remaining_accounts.token_badges,
     )?;
 
     let index = reward_index as usize;
@@ -81,6 +99,7 @@ pub fn handler<'info>(
     );
 
     position.update_reward_owed(index, updated_amount_owed);
+    record_reward_collected(&ctx.accounts.whirlpool_rewards, index, transfer_amount)?;
 
     transfer_from_vault_to_owner_v2(
         &ctx.accounts.whirlpool,
@@ -92,11 +111,29 @@ pub fn handler<'info>(
         &remaining_accounts.transfer_hook_reward,
         transfer_amount,
         transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
-    )
+    )?;
+
+    emit_cpi!(RewardCollected {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        reward_index,
+        reward_mint: ctx.accounts.reward_mint.key(),
+        amount: transfer_amount,
+        transfer_fee: calculate_transfer_fee_excluded_amount(
+            &ctx.accounts.reward_mint,
+            transfer_amount
+        )?
+        .transfer_fee,
+    });
+
+    Ok(())
 }
 
 // TODO: refactor (remove (dup))
-fn calculate_collect_reward(position_reward: PositionRewardInfo, vault_amount: u64) -> (u64, u64) {
+pub(crate) fn calculate_collect_reward(
+    position_reward: PositionRewardInfo,
+    vault_amount: u64,
+) -> (u64, u64) {
     let amount_owed = position_reward.amount_owed;
     let (transfer_amount, updated_amount_owed) = if amount_owed > vault_amount {
         (vault_amount, amount_owed - vault_amount)
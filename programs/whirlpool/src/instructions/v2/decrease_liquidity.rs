@@ -2,16 +2,20 @@ use anchor_lang::prelude::*;
 
 use crate::constants::transfer_memo;
 use crate::errors::ErrorCode;
+use crate::events::LiquidityDecreased;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
+use crate::return_data::{set_return_data, ModifyLiquidityResult};
+use crate::state::update_oracle_on_liquidity_change;
 use crate::util::{
-    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
-    RemainingAccountsInfo,
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
 };
 use crate::util::{
     to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface,
+    verify_position_not_locked, verify_sqrt_price_in_range, TickArrayRentFunder,
 };
 
 use super::increase_liquidity::ModifyLiquidityV2;
@@ -24,6 +28,8 @@ pub fn handler<'info>(
     liquidity_amount: u128,
     token_min_a: u64,
     token_min_b: u64,
+    sqrt_price_min: Option<u128>,
+    sqrt_price_max: Option<u128>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
     verify_position_authority_interface(
@@ -31,17 +37,34 @@ pub fn handler<'info>(
         &ctx.accounts.position_authority,
     )?;
 
-    let clock = Clock::get()?;
+    verify_position_not_locked(&ctx.accounts.position_token_account)?;
 
     if liquidity_amount == 0 {
         return Err(ErrorCode::LiquidityZero.into());
     }
 
+    verify_sqrt_price_in_range(
+        ctx.accounts.whirlpool.sqrt_price,
+        sqrt_price_min,
+        sqrt_price_max,
+    )?;
+
+    let clock = Clock::get()?;
+
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+    verify_token_badge_allowlists(
+        &ctx.accounts.whirlpool.whirlpools_config,
+        &[&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b],
+        remaining_accounts.token_badges,
     )?;
 
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
@@ -49,6 +72,7 @@ pub fn handler<'info>(
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
@@ -56,13 +80,25 @@ pub fn handler<'info>(
         timestamp,
     )?;
 
+    update_oracle_on_liquidity_change(
+        &ctx.accounts.whirlpool.key(),
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.oracle,
+        timestamp,
+    )?;
+
     sync_modify_liquidity_values(
         &mut ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &mut ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
         update,
         timestamp,
+        &TickArrayRentFunder::new(
+            ctx.accounts.position_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
     )?;
 
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
@@ -109,5 +145,23 @@ pub fn handler<'info>(
         transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
     )?;
 
-    Ok(())
+    emit_cpi!(LiquidityDecreased {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        tick_lower_index: ctx.accounts.position.tick_lower_index,
+        tick_upper_index: ctx.accounts.position.tick_upper_index,
+        liquidity: liquidity_amount,
+        token_a_amount: delta_a,
+        token_b_amount: delta_b,
+        token_a_transfer_fee: transfer_fee_excluded_delta_a.transfer_fee,
+        token_b_transfer_fee: transfer_fee_excluded_delta_b.transfer_fee,
+    });
+
+    set_return_data(&ModifyLiquidityResult::new(
+        liquidity_amount,
+        delta_a,
+        transfer_fee_excluded_delta_a.transfer_fee,
+        delta_b,
+        transfer_fee_excluded_delta_b.transfer_fee,
+    ))
 }
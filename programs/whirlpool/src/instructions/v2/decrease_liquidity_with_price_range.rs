@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use crate::util::RemainingAccountsInfo;
+
+use super::increase_liquidity::ModifyLiquidityV2;
+
+/*
+  Performs decrease_liquidity_v2 only if the sqrt price of the Whirlpool is within the given range.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+    sqrt_price_min: Option<u128>,
+    sqrt_price_max: Option<u128>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    super::decrease_liquidity::handler(
+        ctx,
+        liquidity_amount,
+        token_min_a,
+        token_min_b,
+        sqrt_price_min,
+        sqrt_price_max,
+        remaining_accounts_info,
+    )
+}
+
+#[cfg(test)]
+mod decrease_liquidity_with_price_range_tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+    use crate::instruction;
+    use crate::instructions::v2::ModifyLiquidityV2Bumps;
+    use crate::util::test_utils::modify_liquidity_v2_accounts;
+    use anchor_lang::Discriminator;
+    use solana_program::program_error::ProgramError;
+
+    const SQRT_PRICE: u128 = 1 << 64;
+
+    fn decrease_liquidity(
+        sqrt_price_min: Option<u128>,
+        sqrt_price_max: Option<u128>,
+    ) -> Result<()> {
+        let mut accounts = modify_liquidity_v2_accounts(SQRT_PRICE, 0);
+        handler(
+            Context::new(
+                &crate::ID,
+                &mut accounts,
+                &[],
+                ModifyLiquidityV2Bumps::default(),
+            ),
+            1000,
+            0,
+            0,
+            sqrt_price_min,
+            sqrt_price_max,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_decrease_liquidity_v2_data_has_no_price_range() {
+        let data = instruction::DecreaseLiquidityV2 {
+            liquidity_amount: 1,
+            token_min_a: 2,
+            token_min_b: 3,
+            remaining_accounts_info: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        // liquidity_amount, token_min_a, token_min_b, None
+        assert_eq!(data.len(), 16 + 8 + 8 + 1);
+    }
+
+    #[test]
+    fn test_decrease_liquidity_v2_with_price_range_data() {
+        let data = instruction::DecreaseLiquidityV2WithPriceRange {
+            liquidity_amount: 1,
+            token_min_a: 2,
+            token_min_b: 3,
+            sqrt_price_min: None,
+            sqrt_price_max: Some(4),
+            remaining_accounts_info: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(data.len(), 16 + 8 + 8 + 1 + 17 + 1);
+        assert_ne!(
+            instruction::DecreaseLiquidityV2::DISCRIMINATOR,
+            instruction::DecreaseLiquidityV2WithPriceRange::DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn test_price_out_of_range() {
+        assert_eq!(
+            decrease_liquidity(Some(SQRT_PRICE + 1), None).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+        assert_eq!(
+            decrease_liquidity(None, Some(SQRT_PRICE - 1)).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+    }
+
+    #[test]
+    fn test_price_in_range() {
+        // the handler proceeds past the price check up to the Clock sysvar
+        for (sqrt_price_min, sqrt_price_max) in [
+            (None, None),
+            (Some(SQRT_PRICE), Some(SQRT_PRICE)),
+            (Some(SQRT_PRICE - 1), Some(SQRT_PRICE + 1)),
+        ] {
+            assert_eq!(
+                decrease_liquidity(sqrt_price_min, sqrt_price_max).unwrap_err(),
+                ProgramError::UnsupportedSysvar.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,427 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::instruction::{AccountMeta, Instruction};
+
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::Traded,
+    instructions::v2::swap::swap_with_transfer_fee_extension,
+    return_data::{set_return_data, SwapResult},
+    state::{OracleAccessor, Whirlpool},
+    util::{
+        load_whirlpool_reward_extension, load_whirlpool_reward_schedule, to_timestamp_u64,
+        v2::{transfer_from_vault_to_owner_v2, update_whirlpool_after_swap_v2},
+        verify_whirlpool_not_paused, SparseSwapTickSequenceBuilder,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FlashSwap<'info> {
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(mut)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = whirlpool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = whirlpool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub token_destination_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler
+    pub tick_array_0: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler
+    pub tick_array_1: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+
+    #[account(executable)]
+    /// CHECK: any program except the Whirlpool program, checked in the handler
+    pub callback_program: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - supplemental TickArray accounts
+    // - accounts for callback program
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
+}
+
+/// Swaps in the Whirlpool, sending the output tokens before receiving the input tokens.
+///
+/// The Whirlpool is updated and the output tokens are transferred to the destination account first.
+/// The callback program is then invoked with `callback_data` followed by the input amount to repay
+/// and the output amount (transfer fee included, little-endian u64), and with the FlashSwapCallback
+/// accounts. Before the callback returns, the input vault must have received the input amount
+/// (transfer fee excluded), including the swap fee.
+///
+/// The runtime does not allow the callback program to reenter the Whirlpool program, and the
+/// Whirlpool program itself is rejected as the callback program, so the input vault can only be
+/// repaid through the callback.
+///
+/// # Returns
+/// - `Ok`: The swap has been repaid
+/// - `Err`: `InvalidFlashSwapCallbackProgram` if the callback program is the Whirlpool program,
+///   `FlashSwapNotRepaid` if the input vault did not receive the input amount
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, FlashSwap<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+    callback_data: Vec<u8>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    if ctx.accounts.callback_program.key() == crate::ID {
+        return Err(ErrorCode::InvalidFlashSwapCallbackProgram.into());
+    }
+
+    let (input_token_mint, output_token_mint) = if a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a)
+    };
+    if ctx.accounts.token_destination_account.mint != output_token_mint.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintRaw.into());
+    }
+
+    let whirlpool = &mut ctx.accounts.whirlpool;
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::FlashSwapCallback,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+    verify_token_badge_allowlists(
+        &whirlpool.whirlpools_config,
+        &[&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b],
+        remaining_accounts.token_badges,
+    )?;
+
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension,
+        &ctx.accounts.whirlpool_pause,
+    )?;
+
+    let builder = SparseSwapTickSequenceBuilder::try_from(
+        whirlpool,
+        a_to_b,
+        vec![
+            ctx.accounts.tick_array_0.to_account_info(),
+            ctx.accounts.tick_array_1.to_account_info(),
+            ctx.accounts.tick_array_2.to_account_info(),
+        ],
+        remaining_accounts.supplemental_tick_arrays,
+    )?;
+    let mut swap_tick_sequence = builder.build()?;
+
+    let oracle_accessor = OracleAccessor::new(whirlpool, ctx.accounts.oracle.to_account_info())?;
+    let reward_schedule = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards)?;
+    let reward_extension = load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards)?;
+
+    let swap_update = swap_with_transfer_fee_extension(
+        whirlpool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        &oracle_accessor.get_adaptive_fee_info()?,
+        &reward_schedule,
+        &reward_extension,
+    )?;
+
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+
+    if amount_specified_is_input {
+        let transfer_fee_excluded_output_amount =
+            calculate_transfer_fee_excluded_amount(output_token_mint, output_amount)?.amount;
+        if transfer_fee_excluded_output_amount < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimum.into());
+        }
+    } else {
+        let transfer_fee_included_input_amount = input_amount;
+        if transfer_fee_included_input_amount > other_amount_threshold {
+            return Err(ErrorCode::AmountInAboveMaximum.into());
+        }
+    }
+
+    let pre_sqrt_price = whirlpool.sqrt_price;
+    let pre_tick_index = whirlpool.tick_current_index;
+    let input_transfer_fee_excluded =
+        calculate_transfer_fee_excluded_amount(input_token_mint, input_amount)?;
+    let output_transfer_fee =
+        calculate_transfer_fee_excluded_amount(output_token_mint, output_amount)?.transfer_fee;
+    let swap_result = SwapResult::new(
+        &swap_update,
+        a_to_b,
+        input_transfer_fee_excluded.transfer_fee,
+        output_transfer_fee,
+    );
+
+    update_whirlpool_after_swap_v2(
+        whirlpool,
+        &ctx.accounts.whirlpool_rewards,
+        &oracle_accessor,
+        &swap_update,
+        a_to_b,
+        timestamp,
+    )?;
+
+    let (input_vault, output_vault, output_token_program, output_transfer_hook_accounts) = if a_to_b
+    {
+        (
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &remaining_accounts.transfer_hook_b,
+        )
+    } else {
+        (
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &remaining_accounts.transfer_hook_a,
+        )
+    };
+    let input_vault_amount_before = input_vault.amount;
+
+    transfer_from_vault_to_owner_v2(
+        whirlpool,
+        output_token_mint,
+        output_vault,
+        &ctx.accounts.token_destination_account,
+        output_token_program,
+        &ctx.accounts.memo_program,
+        output_transfer_hook_accounts,
+        output_amount,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    // The callback must read the state of the Whirlpool after the swap from the account data,
+    // and the TickArray data must not be borrowed while it runs
+    drop(swap_tick_sequence);
+    whirlpool.exit(&crate::ID)?;
+
+    invoke_flash_swap_callback(
+        &ctx.accounts.callback_program,
+        remaining_accounts.flash_swap_callback.unwrap_or_default(),
+        callback_data,
+        input_amount,
+        output_amount,
+    )?;
+
+    let input_vault = if a_to_b {
+        &mut ctx.accounts.token_vault_a
+    } else {
+        &mut ctx.accounts.token_vault_b
+    };
+    verify_flash_swap_repaid(
+        input_vault,
+        input_vault_amount_before,
+        input_transfer_fee_excluded.amount,
+    )?;
+
+    emit_cpi!(Traded {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        a_to_b,
+        pre_sqrt_price,
+        post_sqrt_price: ctx.accounts.whirlpool.sqrt_price,
+        pre_tick_index,
+        post_tick_index: ctx.accounts.whirlpool.tick_current_index,
+        input_amount,
+        output_amount,
+        input_transfer_fee: input_transfer_fee_excluded.transfer_fee,
+        output_transfer_fee,
+    });
+
+    set_return_data(&swap_result)
+}
+
+fn invoke_flash_swap_callback<'info>(
+    callback_program: &UncheckedAccount<'info>,
+    callback_accounts: &[AccountInfo<'info>],
+    callback_data: Vec<u8>,
+    input_amount: u64,
+    output_amount: u64,
+) -> Result<()> {
+    let mut data = callback_data;
+    data.extend_from_slice(&input_amount.to_le_bytes());
+    data.extend_from_slice(&output_amount.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: callback_program.key(),
+        accounts: callback_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+
+    let mut account_infos = callback_accounts.to_vec();
+    account_infos.push(callback_program.to_account_info());
+
+    // The Whirlpool does not sign the callback
+    solana_program::program::invoke(&instruction, &account_infos)?;
+
+    Ok(())
+}
+
+/// Verifies that the input vault received at least the input amount (transfer fee excluded)
+/// since the output was sent.
+fn verify_flash_swap_repaid(
+    input_vault: &mut InterfaceAccount<'_, TokenAccount>,
+    input_vault_amount_before: u64,
+    input_amount: u64,
+) -> Result<()> {
+    input_vault.reload()?;
+    let input_vault_amount_received = input_vault.amount.saturating_sub(input_vault_amount_before);
+    if input_vault_amount_received < input_amount {
+        return Err(ErrorCode::FlashSwapNotRepaid.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod flash_swap_tests {
+    use super::*;
+    use crate::util::test_utils::{anchor_account_data, leaked_account_info, token_account_data};
+    use anchor_spl::token::spl_token;
+
+    fn input_vault(amount: u64) -> InterfaceAccount<'static, TokenAccount> {
+        let account_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            spl_token::ID,
+            false,
+            true,
+            token_account_data(Pubkey::new_unique(), Pubkey::new_unique(), amount, None),
+        )));
+        InterfaceAccount::try_from(&*account_info).unwrap()
+    }
+
+    // Simulates a transfer to the vault by the callback program
+    fn repay(input_vault: &InterfaceAccount<'_, TokenAccount>, amount: u64) {
+        let account_info = input_vault.to_account_info();
+        let data = token_account_data(
+            input_vault.mint,
+            input_vault.owner,
+            input_vault.amount + amount,
+            None,
+        );
+        account_info
+            .try_borrow_mut_data()
+            .unwrap()
+            .copy_from_slice(&data);
+    }
+
+    #[test]
+    fn test_repaid() {
+        let mut vault = input_vault(1000);
+        repay(&vault, 500);
+        assert!(verify_flash_swap_repaid(&mut vault, 1000, 500).is_ok());
+        assert_eq!(vault.amount, 1500);
+
+        // repaying more than the input amount is accepted
+        let mut vault = input_vault(1000);
+        repay(&vault, 501);
+        assert!(verify_flash_swap_repaid(&mut vault, 1000, 500).is_ok());
+    }
+
+    #[test]
+    fn test_under_repaid() {
+        let mut vault = input_vault(1000);
+        repay(&vault, 499);
+        assert_eq!(
+            verify_flash_swap_repaid(&mut vault, 1000, 500).unwrap_err(),
+            ErrorCode::FlashSwapNotRepaid.into()
+        );
+
+        let mut vault = input_vault(1000);
+        assert_eq!(
+            verify_flash_swap_repaid(&mut vault, 1000, 500).unwrap_err(),
+            ErrorCode::FlashSwapNotRepaid.into()
+        );
+    }
+
+    #[test]
+    fn test_whirlpool_is_stored_before_callback() {
+        let account_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            true,
+            anchor_account_data(&Whirlpool::default(), Whirlpool::LEN),
+        )));
+        let mut whirlpool = Account::<Whirlpool>::try_from(&*account_info).unwrap();
+        whirlpool.sqrt_price = 1 << 64;
+        whirlpool.tick_current_index = 10;
+        whirlpool.exit(&crate::ID).unwrap();
+
+        // the callback program reads the Whirlpool from the account data
+        let stored = Whirlpool::try_deserialize(&mut &account_info.data.borrow()[..]).unwrap();
+        assert_eq!(stored.sqrt_price, 1 << 64);
+        assert_eq!(stored.tick_current_index, 10);
+    }
+}
@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::manager::whirlpool_manager::{
+    next_whirlpool_reward_extension, next_whirlpool_reward_infos,
+};
+use crate::return_data::{set_return_data, RewardSolvency};
+use crate::state::{RewardLedger, Whirlpool, NUM_REWARDS};
+use crate::util::{
+    load_whirlpool_reward_extension, load_whirlpool_reward_schedule, to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct GetRewardSolvency<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    /// Vault of the reward, checked in the handler
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+/// Publishes the RewardSolvency of a reward as return data, without updating any account.
+///
+/// The reward index of an extension reward is offset by NUM_REWARDS, as in the RewardCollected event.
+pub fn handler(ctx: Context<GetRewardSolvency>, reward_index: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let (reward_vault, ledger) = next_reward_ledger(
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
+        reward_index as usize,
+        timestamp,
+    )?;
+
+    // address constraint equivalent check
+    if ctx.accounts.reward_vault.key() != reward_vault {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+
+    set_return_data(&RewardSolvency::new(
+        &ledger,
+        ctx.accounts.reward_vault.amount,
+    ))
+}
+
+/// Returns the vault of the reward at the given index and its ledger as of the given timestamp.
+fn next_reward_ledger(
+    whirlpool: &Account<Whirlpool>,
+    whirlpool_rewards: &AccountInfo,
+    index: usize,
+    timestamp: u64,
+) -> Result<(Pubkey, RewardLedger)> {
+    if index < NUM_REWARDS {
+        let reward_info = &whirlpool.reward_infos[index];
+        if !reward_info.initialized() {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+
+        let reward_schedule = load_whirlpool_reward_schedule(whirlpool_rewards)?;
+        let (_, next_reward_schedule) =
+            next_whirlpool_reward_infos(whirlpool, &reward_schedule, timestamp)?;
+        let next_reward_schedule =
+            next_reward_schedule.ok_or(ErrorCode::RewardScheduleNotInitialized)?;
+        return Ok((reward_info.vault, next_reward_schedule.ledgers[index]));
+    }
+
+    let index = index - NUM_REWARDS;
+    let reward_extension = load_whirlpool_reward_extension(whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+    let reward_vault = reward_extension.get_initialized_reward(index)?.vault;
+    let next_reward_extension =
+        next_whirlpool_reward_extension(whirlpool, &reward_extension, timestamp)?;
+    Ok((reward_vault, next_reward_extension.ledgers[index]))
+}
+
+#[cfg(test)]
+mod get_reward_solvency_tests {
+    use super::*;
+    use crate::state::{
+        RewardSchedule, WhirlpoolRewardExtension, WhirlpoolRewardInfo, WhirlpoolRewardSchedule,
+    };
+    use crate::util::test_utils::{anchor_account_data, leaked_account_info};
+    use crate::util::{store_whirlpool_reward_extension, store_whirlpool_reward_schedule};
+
+    fn reward_info(emissions_per_second_x64: u128) -> WhirlpoolRewardInfo {
+        WhirlpoolRewardInfo {
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            emissions_per_second_x64,
+            ..Default::default()
+        }
+    }
+
+    fn whirlpool_accounts(
+        whirlpool: &Whirlpool,
+        reward_schedule: Option<&WhirlpoolRewardSchedule>,
+        reward_extension: Option<&WhirlpoolRewardExtension>,
+    ) -> (Account<'static, Whirlpool>, AccountInfo<'static>) {
+        let whirlpool_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            false,
+            anchor_account_data(whirlpool, Whirlpool::LEN),
+        )));
+
+        let len = if reward_extension.is_some() {
+            WhirlpoolRewardExtension::OFFSET + WhirlpoolRewardExtension::LEN
+        } else if reward_schedule.is_some() {
+            WhirlpoolRewardSchedule::OFFSET + WhirlpoolRewardSchedule::LEN
+        } else {
+            0
+        };
+        let whirlpool_rewards_info =
+            leaked_account_info(Pubkey::new_unique(), crate::ID, false, true, vec![0; len]);
+        if let Some(reward_schedule) = reward_schedule {
+            store_whirlpool_reward_schedule(&whirlpool_rewards_info, reward_schedule).unwrap();
+        }
+        if let Some(reward_extension) = reward_extension {
+            store_whirlpool_reward_extension(&whirlpool_rewards_info, reward_extension).unwrap();
+        }
+        (
+            Account::try_from(&*whirlpool_info).unwrap(),
+            whirlpool_rewards_info,
+        )
+    }
+
+    fn whirlpool(reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS]) -> Whirlpool {
+        Whirlpool {
+            liquidity: 1000,
+            reward_last_updated_timestamp: 100,
+            reward_infos,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_next_reward_ledger() {
+        let reward_infos = [
+            reward_info(2 << 64),
+            WhirlpoolRewardInfo::default(),
+            WhirlpoolRewardInfo::default(),
+        ];
+        let reward_schedule = WhirlpoolRewardSchedule {
+            ledgers: [
+                RewardLedger::new(500),
+                Default::default(),
+                Default::default(),
+            ],
+            ..Default::default()
+        };
+        let (whirlpool, whirlpool_rewards) =
+            whirlpool_accounts(&whirlpool(reward_infos), Some(&reward_schedule), None);
+
+        // 10 seconds of 2 rewards per second are owed on top of the seeded amount
+        let (reward_vault, ledger) =
+            next_reward_ledger(&whirlpool, &whirlpool_rewards, 0, 110).unwrap();
+        assert_eq!(reward_vault, reward_infos[0].vault);
+        assert_eq!(ledger.amount_outstanding(), 520);
+    }
+
+    #[test]
+    fn test_next_reward_ledger_extension_reward() {
+        let reward_infos = [
+            reward_info(0),
+            WhirlpoolRewardInfo::default(),
+            WhirlpoolRewardInfo::default(),
+        ];
+        let mut reward_extension = WhirlpoolRewardExtension::new(Pubkey::new_unique(), 100);
+        reward_extension.reward_infos[0] = reward_info(0);
+        reward_extension.schedules[0] = RewardSchedule::new(100, 200, 100);
+        reward_extension.record_collected(0, 3);
+        let (whirlpool, whirlpool_rewards) = whirlpool_accounts(
+            &whirlpool(reward_infos),
+            Some(&WhirlpoolRewardSchedule::default()),
+            Some(&reward_extension),
+        );
+
+        let (reward_vault, ledger) =
+            next_reward_ledger(&whirlpool, &whirlpool_rewards, NUM_REWARDS, 150).unwrap();
+        assert_eq!(reward_vault, reward_extension.reward_infos[0].vault);
+        assert_eq!(ledger.amount_outstanding(), 47);
+    }
+
+    #[test]
+    fn test_next_reward_ledger_without_schedule() {
+        let reward_infos = [
+            reward_info(0),
+            WhirlpoolRewardInfo::default(),
+            WhirlpoolRewardInfo::default(),
+        ];
+        let (whirlpool, whirlpool_rewards) =
+            whirlpool_accounts(&whirlpool(reward_infos), None, None);
+
+        assert_eq!(
+            next_reward_ledger(&whirlpool, &whirlpool_rewards, 0, 110).unwrap_err(),
+            ErrorCode::RewardScheduleNotInitialized.into()
+        );
+        assert_eq!(
+            next_reward_ledger(&whirlpool, &whirlpool_rewards, NUM_REWARDS, 110).unwrap_err(),
+            ErrorCode::RewardExtensionNotInitialized.into()
+        );
+    }
+
+    #[test]
+    fn test_next_reward_ledger_uninitialized_reward() {
+        let (whirlpool, whirlpool_rewards) = whirlpool_accounts(
+            &whirlpool([WhirlpoolRewardInfo::default(); NUM_REWARDS]),
+            Some(&WhirlpoolRewardSchedule::default()),
+            None,
+        );
+
+        assert_eq!(
+            next_reward_ledger(&whirlpool, &whirlpool_rewards, 1, 110).unwrap_err(),
+            ErrorCode::InvalidRewardIndex.into()
+        );
+    }
+}
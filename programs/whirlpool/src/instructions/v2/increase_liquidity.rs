@@ -3,19 +3,23 @@ use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::ErrorCode;
+use crate::events::LiquidityIncreased;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
+use crate::return_data::{set_return_data, ModifyLiquidityResult};
 use crate::state::*;
 use crate::util::{
-    calculate_transfer_fee_included_amount, parse_remaining_accounts, AccountsType,
-    RemainingAccountsInfo,
+    calculate_transfer_fee_included_amount, parse_remaining_accounts,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
 };
 use crate::util::{
     to_timestamp_u64, v2::transfer_from_owner_to_vault_v2, verify_position_authority_interface,
+    verify_sqrt_price_in_range, verify_whirlpool_not_paused, TickArrayRentFunder,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ModifyLiquidityV2<'info> {
     #[account(mut)]
@@ -53,13 +57,32 @@ pub struct ModifyLiquidityV2<'info> {
     #[account(mut, constraint = token_vault_b.key() == whirlpool.token_vault_b)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut, has_one = whirlpool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(mut, has_one = whirlpool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: checked in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, updated if initialized
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked on increase only
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked on increase only
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
 }
 
 pub fn handler<'info>(
@@ -67,24 +90,45 @@ pub fn handler<'info>(
     liquidity_amount: u128,
     token_max_a: u64,
     token_max_b: u64,
+    sqrt_price_min: Option<u128>,
+    sqrt_price_max: Option<u128>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
     verify_position_authority_interface(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
     )?;
-
-    let clock = Clock::get()?;
-
     if liquidity_amount == 0 {
         return Err(ErrorCode::LiquidityZero.into());
     }
 
+    verify_sqrt_price_in_range(
+        ctx.accounts.whirlpool.sqrt_price,
+        sqrt_price_min,
+        sqrt_price_max,
+    )?;
+
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension,
+        &ctx.accounts.whirlpool_pause,
+    )?;
+
+    let clock = Clock::get()?;
+
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+    verify_token_badge_allowlists(
+        &ctx.accounts.whirlpool.whirlpools_config,
+        &[&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b],
+        remaining_accounts.token_badges,
     )?;
 
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
@@ -92,6 +136,7 @@ pub fn handler<'info>(
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
@@ -99,13 +144,25 @@ pub fn handler<'info>(
         timestamp,
     )?;
 
+    update_oracle_on_liquidity_change(
+        &ctx.accounts.whirlpool.key(),
+        &ctx.accounts.whirlpool,
+        &ctx.accounts.oracle,
+        timestamp,
+    )?;
+
     sync_modify_liquidity_values(
         &mut ctx.accounts.whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &mut ctx.accounts.position,
         &ctx.accounts.tick_array_lower,
         &ctx.accounts.tick_array_upper,
         update,
         timestamp,
+        &TickArrayRentFunder::new(
+            ctx.accounts.position_authority.as_ref(),
+            ctx.remaining_accounts,
+        ),
     )?;
 
     let (delta_a, delta_b) = calculate_liquidity_token_deltas(
@@ -150,5 +207,80 @@ pub fn handler<'info>(
         transfer_fee_included_delta_b.amount,
     )?;
 
-    Ok(())
+    emit_cpi!(LiquidityIncreased {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        position: ctx.accounts.position.key(),
+        tick_lower_index: ctx.accounts.position.tick_lower_index,
+        tick_upper_index: ctx.accounts.position.tick_upper_index,
+        liquidity: liquidity_amount,
+        token_a_amount: transfer_fee_included_delta_a.amount,
+        token_b_amount: transfer_fee_included_delta_b.amount,
+        token_a_transfer_fee: transfer_fee_included_delta_a.transfer_fee,
+        token_b_transfer_fee: transfer_fee_included_delta_b.transfer_fee,
+    });
+
+    set_return_data(&ModifyLiquidityResult::new(
+        liquidity_amount,
+        transfer_fee_included_delta_a.amount,
+        transfer_fee_included_delta_a.transfer_fee,
+        transfer_fee_included_delta_b.amount,
+        transfer_fee_included_delta_b.transfer_fee,
+    ))
+}
+
+#[cfg(test)]
+mod increase_liquidity_tests {
+    use super::*;
+    use crate::instructions::v2::ModifyLiquidityV2Bumps;
+    use crate::util::test_utils::{
+        anchor_account_data, leaked_account_info, modify_liquidity_v2_accounts,
+    };
+    use solana_program::program_error::ProgramError;
+
+    fn increase_liquidity(accounts: &mut ModifyLiquidityV2<'static>) -> Result<()> {
+        handler(
+            Context::new(&crate::ID, accounts, &[], ModifyLiquidityV2Bumps::default()),
+            1000,
+            u64::MAX,
+            u64::MAX,
+            None,
+            None,
+            None,
+        )
+    }
+
+    fn whirlpool_pause(paused: bool) -> UncheckedAccount<'static> {
+        let whirlpool_pause = WhirlpoolPause {
+            whirlpool: Pubkey::new_unique(),
+            paused,
+        };
+        UncheckedAccount::try_from(Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            false,
+            anchor_account_data(&whirlpool_pause, WhirlpoolPause::LEN),
+        ))))
+    }
+
+    #[test]
+    fn test_whirlpool_paused() {
+        let mut accounts = modify_liquidity_v2_accounts(1 << 64, 0);
+        accounts.whirlpool_pause = whirlpool_pause(true);
+        assert_eq!(
+            increase_liquidity(&mut accounts).unwrap_err(),
+            ErrorCode::WhirlpoolPaused.into()
+        );
+    }
+
+    #[test]
+    fn test_whirlpool_not_paused() {
+        // the handler proceeds past the pause check up to the Clock sysvar
+        let mut accounts = modify_liquidity_v2_accounts(1 << 64, 0);
+        accounts.whirlpool_pause = whirlpool_pause(false);
+        assert_eq!(
+            increase_liquidity(&mut accounts).unwrap_err(),
+            ProgramError::UnsupportedSysvar.into()
+        );
+    }
 }
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::get_liquidity_from_token_amounts;
+use crate::util::{calculate_transfer_fee_excluded_amount, RemainingAccountsInfo};
+
+use super::increase_liquidity::ModifyLiquidityV2;
+
+/*
+  Adds the largest liquidity which fits in the given token amounts at the current price.
+  The transfer fee is deducted from the token amounts before computing the liquidity,
+  so token_max_a and token_max_b are the amounts sent from the owner accounts.
+  The current price is checked against the price range as in increase_liquidity_v2_with_price_range.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+    token_max_a: u64,
+    token_max_b: u64,
+    liquidity_min: u128,
+    sqrt_price_min: Option<u128>,
+    sqrt_price_max: Option<u128>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let transfer_fee_excluded_max_a =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, token_max_a)?;
+    let transfer_fee_excluded_max_b =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, token_max_b)?;
+
+    let liquidity_amount = get_liquidity_from_token_amounts(
+        ctx.accounts.whirlpool.tick_current_index,
+        ctx.accounts.whirlpool.sqrt_price,
+        ctx.accounts.position.tick_lower_index,
+        ctx.accounts.position.tick_upper_index,
+        transfer_fee_excluded_max_a.amount,
+        transfer_fee_excluded_max_b.amount,
+    )?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    if liquidity_amount < liquidity_min {
+        return Err(ErrorCode::LiquidityMinSubceeded.into());
+    }
+
+    super::increase_liquidity::handler(
+        ctx,
+        liquidity_amount,
+        token_max_a,
+        token_max_b,
+        sqrt_price_min,
+        sqrt_price_max,
+        remaining_accounts_info,
+    )
+}
+
+#[cfg(test)]
+mod increase_liquidity_by_token_amounts_tests {
+    use super::*;
+    use crate::instructions::v2::ModifyLiquidityV2Bumps;
+    use crate::util::test_utils::modify_liquidity_v2_accounts;
+    use solana_program::program_error::ProgramError;
+
+    const SQRT_PRICE: u128 = 1 << 64;
+
+    fn increase_liquidity_by_token_amounts(
+        liquidity_min: u128,
+        sqrt_price_min: Option<u128>,
+        sqrt_price_max: Option<u128>,
+    ) -> Result<()> {
+        let mut accounts = modify_liquidity_v2_accounts(SQRT_PRICE, 0);
+        handler(
+            Context::new(
+                &crate::ID,
+                &mut accounts,
+                &[],
+                ModifyLiquidityV2Bumps::default(),
+            ),
+            1000,
+            1000,
+            liquidity_min,
+            sqrt_price_min,
+            sqrt_price_max,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_price_out_of_range() {
+        assert_eq!(
+            increase_liquidity_by_token_amounts(0, Some(SQRT_PRICE + 1), None).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+        assert_eq!(
+            increase_liquidity_by_token_amounts(0, None, Some(SQRT_PRICE - 1)).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+    }
+
+    #[test]
+    fn test_price_in_range() {
+        // the handler proceeds past the price check up to the Clock sysvar
+        assert_eq!(
+            increase_liquidity_by_token_amounts(0, Some(SQRT_PRICE), Some(SQRT_PRICE)).unwrap_err(),
+            ProgramError::UnsupportedSysvar.into()
+        );
+    }
+
+    #[test]
+    fn test_liquidity_min_subceeded() {
+        assert_eq!(
+            increase_liquidity_by_token_amounts(u128::MAX, None, None).unwrap_err(),
+            ErrorCode::LiquidityMinSubceeded.into()
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use crate::util::RemainingAccountsInfo;
+
+use super::increase_liquidity::ModifyLiquidityV2;
+
+/*
+  Performs increase_liquidity_v2 only if the sqrt price of the Whirlpool is within the given range.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+    sqrt_price_min: Option<u128>,
+    sqrt_price_max: Option<u128>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    super::increase_liquidity::handler(
+        ctx,
+        liquidity_amount,
+        token_max_a,
+        token_max_b,
+        sqrt_price_min,
+        sqrt_price_max,
+        remaining_accounts_info,
+    )
+}
+
+#[cfg(test)]
+mod increase_liquidity_with_price_range_tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+    use crate::instruction;
+    use crate::instructions::v2::ModifyLiquidityV2Bumps;
+    use crate::util::test_utils::modify_liquidity_v2_accounts;
+    use anchor_lang::Discriminator;
+    use solana_program::program_error::ProgramError;
+
+    const SQRT_PRICE: u128 = 1 << 64;
+
+    fn increase_liquidity(
+        sqrt_price_min: Option<u128>,
+        sqrt_price_max: Option<u128>,
+    ) -> Result<()> {
+        let mut accounts = modify_liquidity_v2_accounts(SQRT_PRICE, 0);
+        handler(
+            Context::new(
+                &crate::ID,
+                &mut accounts,
+                &[],
+                ModifyLiquidityV2Bumps::default(),
+            ),
+            1000,
+            u64::MAX,
+            u64::MAX,
+            sqrt_price_min,
+            sqrt_price_max,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_increase_liquidity_v2_data_has_no_price_range() {
+        let data = instruction::IncreaseLiquidityV2 {
+            liquidity_amount: 1,
+            token_max_a: 2,
+            token_max_b: 3,
+            remaining_accounts_info: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        // liquidity_amount, token_max_a, token_max_b, None
+        assert_eq!(data.len(), 16 + 8 + 8 + 1);
+    }
+
+    #[test]
+    fn test_increase_liquidity_v2_with_price_range_data() {
+        let data = instruction::IncreaseLiquidityV2WithPriceRange {
+            liquidity_amount: 1,
+            token_max_a: 2,
+            token_max_b: 3,
+            sqrt_price_min: Some(4),
+            sqrt_price_max: None,
+            remaining_accounts_info: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(data.len(), 16 + 8 + 8 + 17 + 1 + 1);
+        assert_ne!(
+            instruction::IncreaseLiquidityV2::DISCRIMINATOR,
+            instruction::IncreaseLiquidityV2WithPriceRange::DISCRIMINATOR
+        );
+    }
+
+    #[test]
+    fn test_price_out_of_range() {
+        assert_eq!(
+            increase_liquidity(Some(SQRT_PRICE + 1), None).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+        assert_eq!(
+            increase_liquidity(None, Some(SQRT_PRICE - 1)).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+    }
+
+    #[test]
+    fn test_price_in_range() {
+        // the handler proceeds past the price check up to the Clock sysvar
+        for (sqrt_price_min, sqrt_price_max) in [
+            (None, None),
+            (Some(SQRT_PRICE), Some(SQRT_PRICE)),
+            (Some(SQRT_PRICE - 1), Some(SQRT_PRICE + 1)),
+        ] {
+            assert_eq!(
+                increase_liquidity(sqrt_price_min, sqrt_price_max).unwrap_err(),
+                ProgramError::UnsupportedSysvar.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::ErrorCode,
+    state::Whirlpool,
+    util::{
+        load_token_badge, load_whirlpool_reward_extension, store_whirlpool_reward_extension,
+        v2::is_supported_token_mint,
+    },
+};
+
+#[derive(Accounts)]
+pub struct InitializeExtensionReward<'info> {
+    pub reward_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(seeds = [b"token_badge", whirlpool.whirlpools_config.as_ref(), reward_mint.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub reward_token_badge: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        token::token_program = reward_token_program,
+        token::mint = reward_mint,
+        token::authority = whirlpool
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *reward_mint.to_account_info().owner)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<InitializeExtensionReward>, reward_index: u8) -> Result<()> {
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    let mut reward_extension = load_whirlpool_reward_extension(&whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+
+    // address constraint equivalent check
+    let index = reward_index as usize;
+    match reward_extension.reward_infos.get(index) {
+        Some(reward_info) if reward_info.authority == ctx.accounts.reward_authority.key() => {}
+        Some(_) => return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into()),
+        None => return Err(ErrorCode::InvalidRewardIndex.into()),
+    }
+
+    // Don't allow initializing a reward with an unsupported token mint
+    let token_badge = load_token_badge(
+        ctx.accounts.whirlpool.whirlpools_config,
+        ctx.accounts.reward_mint.key(),
+        &ctx.accounts.reward_token_badge,
+    )?;
+
+    if !is_supported_token_mint(&ctx.accounts.reward_mint, token_badge.as_ref()).unwrap() {
+        return Err(ErrorCode::UnsupportedTokenMint.into());
+    }
+
+    reward_extension.initialize_reward(
+        index,
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.reward_vault.key(),
+    )?;
+    store_whirlpool_reward_extension(&whirlpool_rewards, &reward_extension)
+}
@@ -3,10 +3,12 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     errors::ErrorCode,
+    events::PoolInitialized,
     state::*,
-    util::{is_token_badge_initialized, v2::is_supported_token_mint},
+    util::{load_token_badge, v2::is_supported_token_mint},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(tick_spacing: u16)]
 pub struct InitializePoolV2<'info> {
@@ -80,23 +82,23 @@ pub fn handler(
     let bump = ctx.bumps.whirlpool;
 
     // Don't allow creating a pool with unsupported token mints
-    let is_token_badge_initialized_a = is_token_badge_initialized(
+    let token_badge_a = load_token_badge(
         whirlpools_config.key(),
         token_mint_a,
         &ctx.accounts.token_badge_a,
     )?;
 
-    if !is_supported_token_mint(&ctx.accounts.token_mint_a, is_token_badge_initialized_a).unwrap() {
+    if !is_supported_token_mint(&ctx.accounts.token_mint_a, token_badge_a.as_ref()).unwrap() {
         return Err(ErrorCode::UnsupportedTokenMint.into());
     }
 
-    let is_token_badge_initialized_b = is_token_badge_initialized(
+    let token_badge_b = load_token_badge(
         whirlpools_config.key(),
         token_mint_b,
         &ctx.accounts.token_badge_b,
     )?;
 
-    if !is_supported_token_mint(&ctx.accounts.token_mint_b, is_token_badge_initialized_b).unwrap() {
+    if !is_supported_token_mint(&ctx.accounts.token_mint_b, token_badge_b.as_ref()).unwrap() {
         return Err(ErrorCode::UnsupportedTokenMint.into());
     }
 
@@ -104,11 +106,27 @@ pub fn handler(
         whirlpools_config,
         bump,
         tick_spacing,
+        tick_spacing,
         initial_sqrt_price,
         default_fee_rate,
         token_mint_a,
         ctx.accounts.token_vault_a.key(),
         token_mint_b,
         ctx.accounts.token_vault_b.key(),
-    )
+    )?;
+
+    emit_cpi!(PoolInitialized {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        whirlpools_config: ctx.accounts.whirlpools_config.key(),
+        token_mint_a,
+        token_mint_b,
+        tick_spacing,
+        token_program_a: ctx.accounts.token_program_a.key(),
+        token_program_b: ctx.accounts.token_program_b.key(),
+        decimals_a: ctx.accounts.token_mint_a.decimals,
+        decimals_b: ctx.accounts.token_mint_b.decimals,
+        initial_sqrt_price,
+    });
+
+    Ok(())
 }
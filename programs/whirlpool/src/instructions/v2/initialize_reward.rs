@@ -4,7 +4,7 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
     errors::ErrorCode,
     state::Whirlpool,
-    util::{is_token_badge_initialized, v2::is_supported_token_mint},
+    util::{create_whirlpool_rewards, load_token_badge, v2::is_supported_token_mint},
 };
 
 #[derive(Accounts)]
@@ -38,19 +38,23 @@ pub struct InitializeRewardV2<'info> {
     pub reward_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: WhirlpoolRewards of the Whirlpool, created in the handler for the first reward
+    pub whirlpool_rewards: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<InitializeRewardV2>, reward_index: u8) -> Result<()> {
     let whirlpool = &mut ctx.accounts.whirlpool;
 
     // Don't allow initializing a reward with an unsupported token mint
-    let is_token_badge_initialized = is_token_badge_initialized(
+    let token_badge = load_token_badge(
         whirlpool.whirlpools_config,
         ctx.accounts.reward_mint.key(),
         &ctx.accounts.reward_token_badge,
     )?;
 
-    if !is_supported_token_mint(&ctx.accounts.reward_mint, is_token_badge_initialized).unwrap() {
+    if !is_supported_token_mint(&ctx.accounts.reward_mint, token_badge.as_ref()).unwrap() {
         return Err(ErrorCode::UnsupportedTokenMint.into());
     }
 
@@ -58,5 +62,19 @@ pub fn handler(ctx: Context<InitializeRewardV2>, reward_index: u8) -> Result<()>
         reward_index as usize,
         ctx.accounts.reward_mint.key(),
         ctx.accounts.reward_vault.key(),
+    )?;
+
+    // Track the amounts emitted and collected for the rewards from the start. The ledgers of the
+    // rewards initialized before are seeded from their vaults by initialize_reward_schedule.
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    if reward_index != 0 || *whirlpool_rewards.owner == crate::ID {
+        return Ok(());
+    }
+    create_whirlpool_rewards(
+        &whirlpool_rewards,
+        whirlpool.key(),
+        ctx.bumps.whirlpool_rewards,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
     )
 }
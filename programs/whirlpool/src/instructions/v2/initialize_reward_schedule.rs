@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::state::{
+    RewardLedger, Whirlpool, WhirlpoolRewardInfo, WhirlpoolRewardSchedule, WhirlpoolsConfig,
+    NUM_REWARDS,
+};
+use crate::util::{create_whirlpool_rewards, store_whirlpool_reward_schedule};
+
+#[derive(Accounts)]
+pub struct InitializeRewardSchedule<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = whirlpools_config.reward_emissions_super_authority)]
+    pub reward_emissions_super_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: WhirlpoolRewards of the Whirlpool, created in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+    // remaining accounts
+    // - reward vaults of the initialized rewards, in the order of the reward indexes
+}
+
+/*
+  Creates the WhirlpoolRewards PDA of the Whirlpool with a WhirlpoolRewardSchedule.
+
+  The amounts emitted to the positions before the ledgers exist are unknown, so the ledgers are
+  seeded with the amounts held by the reward vaults, as if all of them were owed to the positions.
+  The budgets of the schedules must be deposited to the vaults after this instruction.
+*/
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InitializeRewardSchedule<'info>>,
+) -> Result<()> {
+    let ledgers =
+        seed_reward_ledgers(&ctx.accounts.whirlpool.reward_infos, ctx.remaining_accounts)?;
+
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    create_whirlpool_rewards(
+        &whirlpool_rewards,
+        ctx.accounts.whirlpool.key(),
+        ctx.bumps.whirlpool_rewards,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    store_whirlpool_reward_schedule(
+        &whirlpool_rewards,
+        &WhirlpoolRewardSchedule {
+            ledgers,
+            ..Default::default()
+        },
+    )
+}
+
+fn seed_reward_ledgers<'info>(
+    reward_infos: &[WhirlpoolRewardInfo; NUM_REWARDS],
+    reward_vaults: &'info [AccountInfo<'info>],
+) -> Result<[RewardLedger; NUM_REWARDS]> {
+    let mut ledgers = [RewardLedger::default(); NUM_REWARDS];
+    let mut reward_vaults = reward_vaults.iter();
+    for (ledger, reward_info) in ledgers.iter_mut().zip(reward_infos.iter()) {
+        if !reward_info.initialized() {
+            continue;
+        }
+
+        let reward_vault_info = reward_vaults
+            .next()
+            .ok_or(ErrorCode::RemainingAccountsInsufficient)?;
+        // address constraint equivalent check
+        if reward_vault_info.key() != reward_info.vault {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+        }
+        let reward_vault = InterfaceAccount::<TokenAccount>::try_from(reward_vault_info)?;
+        *ledger = RewardLedger::new(reward_vault.amount);
+    }
+    Ok(ledgers)
+}
+
+#[cfg(test)]
+mod initialize_reward_schedule_tests {
+    use super::*;
+    use crate::util::test_utils::{leaked_account_info, token_account_data};
+    use anchor_spl::token::spl_token;
+
+    fn reward_info(vault: Pubkey) -> WhirlpoolRewardInfo {
+        WhirlpoolRewardInfo {
+            mint: Pubkey::new_unique(),
+            vault,
+            ..Default::default()
+        }
+    }
+
+    fn reward_vault(key: Pubkey, amount: u64) -> AccountInfo<'static> {
+        leaked_account_info(
+            key,
+            spl_token::ID,
+            false,
+            false,
+            token_account_data(Pubkey::new_unique(), Pubkey::new_unique(), amount, None),
+        )
+    }
+
+    #[test]
+    fn test_seed_reward_ledgers() {
+        let (vault_0, vault_1) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let reward_infos = [
+            reward_info(vault_0),
+            reward_info(vault_1),
+            WhirlpoolRewardInfo::default(),
+        ];
+
+        let ledgers = seed_reward_ledgers(
+            &reward_infos,
+            Box::leak(Box::new([
+                reward_vault(vault_0, 1000),
+                reward_vault(vault_1, 0),
+            ])),
+        )
+        .unwrap();
+        assert_eq!(ledgers[0], RewardLedger::new(1000));
+        assert_eq!(ledgers[0].amount_outstanding(), 1000);
+        assert_eq!(ledgers[1], RewardLedger::default());
+        assert_eq!(ledgers[2], RewardLedger::default());
+    }
+
+    #[test]
+    fn test_seed_reward_ledgers_missing_vault() {
+        let reward_infos = [
+            reward_info(Pubkey::new_unique()),
+            reward_info(Pubkey::new_unique()),
+            WhirlpoolRewardInfo::default(),
+        ];
+
+        let result = seed_reward_ledgers(
+            &reward_infos,
+            Box::leak(Box::new([reward_vault(reward_infos[0].vault, 1000)])),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::RemainingAccountsInsufficient.into()
+        );
+    }
+
+    #[test]
+    fn test_seed_reward_ledgers_wrong_vault() {
+        let reward_infos = [
+            reward_info(Pubkey::new_unique()),
+            WhirlpoolRewardInfo::default(),
+            WhirlpoolRewardInfo::default(),
+        ];
+
+        let result = seed_reward_ledgers(
+            &reward_infos,
+            Box::leak(Box::new([reward_vault(Pubkey::new_unique(), 0)])),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            anchor_lang::error::ErrorCode::ConstraintAddress.into()
+        );
+    }
+}
@@ -1,29 +1,59 @@
 #![allow(ambiguous_glob_reexports)]
 
+pub mod collect_all;
+pub mod collect_extension_reward;
 pub mod collect_fees;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
 pub mod decrease_liquidity;
+pub mod decrease_liquidity_with_price_range;
+pub mod flash_swap;
+pub mod get_reward_solvency;
 pub mod increase_liquidity;
+pub mod increase_liquidity_by_token_amounts;
+pub mod increase_liquidity_with_price_range;
+pub mod initialize_extension_reward;
 pub mod initialize_pool;
 pub mod initialize_reward;
+pub mod initialize_reward_schedule;
+pub mod multi_hop_swap;
+pub mod set_extension_reward_emissions;
+pub mod set_extension_reward_emissions_schedule;
 pub mod set_reward_emissions;
+pub mod set_reward_emissions_schedule;
+pub mod simulate_swap;
 pub mod swap;
+pub mod swap_with_referral_fee;
 pub mod two_hop_swap;
 
 pub mod delete_token_badge;
 pub mod initialize_config_extension;
 pub mod initialize_token_badge;
 pub mod set_config_extension_authority;
+pub mod set_config_paused;
+pub mod set_max_referral_fee_rate;
+pub mod set_token_badge_allowlist;
 pub mod set_token_badge_authority;
+pub mod set_whirlpool_paused;
 
+pub use collect_all::*;
+pub use collect_extension_reward::*;
 pub use collect_fees::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
+pub use flash_swap::*;
+pub use get_reward_solvency::*;
 pub use increase_liquidity::*;
+pub use initialize_extension_reward::*;
 pub use initialize_pool::*;
 pub use initialize_reward::*;
+pub use initialize_reward_schedule::*;
+pub use multi_hop_swap::*;
+pub use set_extension_reward_emissions::*;
+pub use set_extension_reward_emissions_schedule::*;
 pub use set_reward_emissions::*;
+pub use set_reward_emissions_schedule::*;
+pub use simulate_swap::*;
 pub use swap::*;
 pub use two_hop_swap::*;
 
@@ -31,4 +61,8 @@ pub use delete_token_badge::*;
 pub use initialize_config_extension::*;
 pub use initialize_token_badge::*;
 pub use set_config_extension_authority::*;
+pub use set_config_paused::*;
+pub use set_max_referral_fee_rate::*;
+pub use set_token_badge_allowlist::*;
 pub use set_token_badge_authority::*;
+pub use set_whirlpool_paused::*;
@@ -0,0 +1,514 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::swap_with_transfer_fee_extension;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, load_whirlpool_reward_extension,
+    load_whirlpool_reward_schedule, parse_remaining_accounts, store_whirlpool_reward_extension,
+    store_whirlpool_reward_schedule, transfer_from_owner_to_vault_v2,
+    transfer_from_vault_to_owner_v2, verify_token_badge_allowlists, AccountsType,
+    RemainingAccountsInfo, MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::Traded,
+    manager::swap_manager::PostSwapUpdate,
+    math::NO_EXPLICIT_SQRT_PRICE_LIMIT,
+    return_data::{set_return_data, SwapResult},
+    state::{OracleAccessor, Whirlpool},
+    util::{to_timestamp_u64, verify_whirlpool_not_paused, SparseSwapTickSequenceBuilder},
+};
+
+pub const MIN_MULTI_HOP_SWAP_HOPS: usize = 2;
+pub const MAX_MULTI_HOP_SWAP_HOPS: usize = 4;
+
+// whirlpool, input token vault, output token vault, oracle, WhirlpoolRewards (writable)
+const SWAP_HOP_WRITABLE_ACCOUNTS_LEN: usize = 5;
+// and WhirlpoolsConfigExtension, WhirlpoolPause
+const SWAP_HOP_FIXED_ACCOUNTS_LEN: usize = SWAP_HOP_WRITABLE_ACCOUNTS_LEN + 2;
+const SWAP_HOP_MIN_TICK_ARRAYS_LEN: usize = 1;
+const SWAP_HOP_MAX_TICK_ARRAYS_LEN: usize = 3 + MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MultiHopSwapV2<'info> {
+    pub token_mint_input: InterfaceAccount<'info, Mint>,
+    pub token_mint_output: InterfaceAccount<'info, Mint>,
+
+    #[account(address = *token_mint_input.to_account_info().owner)]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_output.to_account_info().owner)]
+    pub token_program_output: Interface<'info, TokenInterface>,
+
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_output.mint == token_mint_output.key())]
+    pub token_owner_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_authority: Signer<'info>,
+
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_input
+    // - accounts for transfer hook program of token_mint_output
+    // - accounts for each hop of the route (SwapHop, in order)
+    //   whirlpool, input token vault, output token vault, oracle, WhirlpoolRewards,
+    //   WhirlpoolsConfigExtension, WhirlpoolPause, TickArray accounts
+    // - accounts for each intermediate token of the route (IntermediateToken, in order)
+    //   mint, token program, accounts for transfer hook program of the mint
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
+}
+
+struct SwapHop<'info> {
+    whirlpool: Box<Account<'info, Whirlpool>>,
+    a_to_b: bool,
+    token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_vault_output: Box<InterfaceAccount<'info, TokenAccount>>,
+    oracle: AccountInfo<'info>,
+    whirlpool_rewards: AccountInfo<'info>,
+    tick_arrays: Vec<AccountInfo<'info>>,
+}
+
+struct IntermediateToken<'info> {
+    mint: InterfaceAccount<'info, Mint>,
+    token_program: Interface<'info, TokenInterface>,
+    transfer_hook_accounts: Option<Vec<AccountInfo<'info>>>,
+}
+
+impl<'info> IntermediateToken<'info> {
+    fn load(accounts: &'info [AccountInfo<'info>]) -> Result<Self> {
+        if accounts.len() < 2 {
+            return Err(ErrorCode::InvalidMultiHopSwapRoute.into());
+        }
+
+        let mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+        let token_program = Interface::<TokenInterface>::try_from(&accounts[1])?;
+        if token_program.key() != *accounts[0].owner {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+        }
+
+        let transfer_hook_accounts = if accounts.len() > 2 {
+            Some(accounts[2..].to_vec())
+        } else {
+            None
+        };
+
+        Ok(Self {
+            mint,
+            token_program,
+            transfer_hook_accounts,
+        })
+    }
+}
+
+impl<'info> SwapHop<'info> {
+    fn load(
+        accounts: &'info [AccountInfo<'info>],
+        token_mint_input: &Pubkey,
+        token_mint_output: &Pubkey,
+    ) -> Result<Self> {
+        if accounts.len() < SWAP_HOP_FIXED_ACCOUNTS_LEN + SWAP_HOP_MIN_TICK_ARRAYS_LEN {
+            return Err(ErrorCode::InvalidMultiHopSwapRoute.into());
+        }
+        if accounts.len() > SWAP_HOP_FIXED_ACCOUNTS_LEN + SWAP_HOP_MAX_TICK_ARRAYS_LEN {
+            return Err(ErrorCode::TooManySupplementalTickArrays.into());
+        }
+        for account in accounts[..SWAP_HOP_WRITABLE_ACCOUNTS_LEN].iter() {
+            if !account.is_writable {
+                return Err(anchor_lang::error::ErrorCode::ConstraintMut.into());
+            }
+        }
+
+        let whirlpool = Box::new(Account::<Whirlpool>::try_from(&accounts[0])?);
+
+        let a_to_b = whirlpool.token_mint_a == *token_mint_input;
+        if whirlpool.input_token_mint(a_to_b) != *token_mint_input
+            || whirlpool.output_token_mint(a_to_b) != *token_mint_output
+        {
+            return Err(ErrorCode::InvalidIntermediaryMint.into());
+        }
+
+        let token_vault_input = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?);
+        let token_vault_output =
+            Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?);
+        if token_vault_input.key() != whirlpool.input_token_vault(a_to_b)
+            || token_vault_output.key() != whirlpool.output_token_vault(a_to_b)
+        {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+        }
+
+        // seeds constraint equivalent check
+        let (oracle_address, _) =
+            Pubkey::find_program_address(&[b"oracle", whirlpool.key().as_ref()], &crate::ID);
+        if accounts[3].key() != oracle_address {
+            return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+        }
+
+        // seeds constraint equivalent check
+        let (whirlpool_rewards_address, _) = Pubkey::find_program_address(
+            &[b"whirlpool_rewards", whirlpool.key().as_ref()],
+            &crate::ID,
+        );
+        let (whirlpools_config_extension_address, _) = Pubkey::find_program_address(
+            &[b"config_extension", whirlpool.whirlpools_config.as_ref()],
+            &crate::ID,
+        );
+        let (whirlpool_pause_address, _) = Pubkey::find_program_address(
+            &[b"whirlpool_pause", whirlpool.key().as_ref()],
+            &crate::ID,
+        );
+        if accounts[4].key() != whirlpool_rewards_address
+            || accounts[5].key() != whirlpools_config_extension_address
+            || accounts[6].key() != whirlpool_pause_address
+        {
+            return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+        }
+
+        verify_whirlpool_not_paused(&accounts[5], &accounts[6])?;
+
+        Ok(Self {
+            whirlpool,
+            a_to_b,
+            token_vault_input,
+            token_vault_output,
+            oracle: accounts[3].clone(),
+            whirlpool_rewards: accounts[4].clone(),
+            tick_arrays: accounts[SWAP_HOP_FIXED_ACCOUNTS_LEN..].to_vec(),
+        })
+    }
+}
+
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, MultiHopSwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookInput,
+            AccountsType::TransferHookOutput,
+            AccountsType::SwapHop,
+            AccountsType::IntermediateToken,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+
+    let hop_count = remaining_accounts.swap_hops.len();
+    if !(MIN_MULTI_HOP_SWAP_HOPS..=MAX_MULTI_HOP_SWAP_HOPS).contains(&hop_count)
+        || remaining_accounts.intermediate_tokens.len() != hop_count - 1
+    {
+        return Err(ErrorCode::InvalidMultiHopSwapRoute.into());
+    }
+
+    let intermediate_tokens = remaining_accounts
+        .intermediate_tokens
+        .iter()
+        .map(|accounts| IntermediateToken::load(accounts))
+        .collect::<Result<Vec<_>>>()?;
+
+    // mints[i] is the input token of hop i and the output token of hop i - 1
+    let mints: Vec<&InterfaceAccount<'info, Mint>> =
+        std::iter::once(&ctx.accounts.token_mint_input)
+            .chain(intermediate_tokens.iter().map(|token| &token.mint))
+            .chain(std::iter::once(&ctx.accounts.token_mint_output))
+            .collect();
+    let token_programs: Vec<&Interface<'info, TokenInterface>> =
+        std::iter::once(&ctx.accounts.token_program_input)
+            .chain(intermediate_tokens.iter().map(|token| &token.token_program))
+            .chain(std::iter::once(&ctx.accounts.token_program_output))
+            .collect();
+    let transfer_hook_accounts: Vec<&Option<Vec<AccountInfo<'info>>>> =
+        std::iter::once(&remaining_accounts.transfer_hook_input)
+            .chain(
+                intermediate_tokens
+                    .iter()
+                    .map(|token| &token.transfer_hook_accounts),
+            )
+            .chain(std::iter::once(&remaining_accounts.transfer_hook_output))
+            .collect();
+
+    let mut hops = Vec::with_capacity(hop_count);
+    for (i, accounts) in remaining_accounts.swap_hops.iter().enumerate() {
+        let hop = SwapHop::load(accounts, &mints[i].key(), &mints[i + 1].key())?;
+        verify_token_badge_allowlists(
+            &hop.whirlpool.whirlpools_config,
+            &[mints[i], mints[i + 1]],
+            remaining_accounts.token_badges,
+        )?;
+
+        // Don't allow swaps on the same whirlpool
+        if hops
+            .iter()
+            .any(|prev: &SwapHop| prev.whirlpool.key() == hop.whirlpool.key())
+        {
+            return Err(ErrorCode::DuplicateMultiHopPool.into());
+        }
+
+        hops.push(hop);
+    }
+
+    let builders = hops
+        .iter()
+        .map(|hop| {
+            SparseSwapTickSequenceBuilder::try_from(
+                &hop.whirlpool,
+                hop.a_to_b,
+                hop.tick_arrays.clone(),
+                None,
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut swap_tick_sequences = builders
+        .iter()
+        .map(|builder| builder.build())
+        .collect::<Result<Vec<_>>>()?;
+
+    let oracle_accessors = hops
+        .iter()
+        .map(|hop| OracleAccessor::new(&hop.whirlpool, hop.oracle.clone()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let reward_schedules = hops
+        .iter()
+        .map(|hop| load_whirlpool_reward_schedule(&hop.whirlpool_rewards))
+        .collect::<Result<Vec<_>>>()?;
+
+    let reward_extensions = hops
+        .iter()
+        .map(|hop| load_whirlpool_reward_extension(&hop.whirlpool_rewards))
+        .collect::<Result<Vec<_>>>()?;
+
+    let swap_hop =
+        |i: usize, swap_tick_sequences: &mut Vec<_>, amount: u64| -> Result<PostSwapUpdate> {
+            let hop = &hops[i];
+            let (token_mint_a, token_mint_b) = if hop.a_to_b {
+                (mints[i], mints[i + 1])
+            } else {
+                (mints[i + 1], mints[i])
+            };
+            swap_with_transfer_fee_extension(
+                &hop.whirlpool,
+                token_mint_a,
+                token_mint_b,
+                &mut swap_tick_sequences[i],
+                amount,
+                NO_EXPLICIT_SQRT_PRICE_LIMIT,
+                amount_specified_is_input,
+                hop.a_to_b,
+                timestamp,
+                &oracle_accessors[i].get_adaptive_fee_info()?,
+                &reward_schedules[i],
+                &reward_extensions[i],
+            )
+        };
+
+    let mut swap_updates: Vec<PostSwapUpdate> = Vec::with_capacity(hop_count);
+    if amount_specified_is_input {
+        // Exact-in: the swap calculations occur from the first hop to the last hop,
+        // and the output of each hop is the input of the next hop.
+        // We use vault to vault transfer, so transfer fee will be collected once.
+        let mut hop_input_amount = amount;
+        for (i, hop) in hops.iter().enumerate() {
+            let swap_update = swap_hop(i, &mut swap_tick_sequences, hop_input_amount)?;
+            hop_input_amount = if hop.a_to_b {
+                swap_update.amount_b
+            } else {
+                swap_update.amount_a
+            };
+            swap_updates.push(swap_update);
+        }
+    } else {
+        // Exact-out: the swap calculations occur from the last hop to the first hop,
+        // but the actual swaps occur from the first hop to the last hop
+        // (to ensure that the intermediate tokens exist in the vaults)
+        let mut hop_output_amount = amount;
+        for i in (0..hop_count).rev() {
+            let swap_update = swap_hop(i, &mut swap_tick_sequences, hop_output_amount)?;
+            let hop_input_amount = if hops[i].a_to_b {
+                swap_update.amount_a
+            } else {
+                swap_update.amount_b
+            };
+            // The output of the previous hop is the input of this hop
+            hop_output_amount =
+                calculate_transfer_fee_excluded_amount(mints[i], hop_input_amount)?.amount;
+            swap_updates.push(swap_update);
+        }
+        swap_updates.reverse();
+    }
+
+    // (input amount, output amount) of each hop
+    let hop_amounts: Vec<(u64, u64)> = hops
+        .iter()
+        .zip(swap_updates.iter())
+        .map(|(hop, swap_update)| {
+            if hop.a_to_b {
+                (swap_update.amount_a, swap_update.amount_b)
+            } else {
+                (swap_update.amount_b, swap_update.amount_a)
+            }
+        })
+        .collect();
+
+    // All output token of each hop should be consumed by the next hop
+    if hop_amounts.windows(2).any(|pair| pair[0].1 != pair[1].0) {
+        return Err(ErrorCode::IntermediateTokenAmountMismatch.into());
+    }
+
+    let input_amount = hop_amounts[0].0;
+    let output_amount = hop_amounts[hop_count - 1].1;
+    if amount_specified_is_input {
+        // If amount_specified_is_input == true, then we have a variable amount of output
+        // The slippage we care about is the output of the last hop.
+        let output_amount =
+            calculate_transfer_fee_excluded_amount(mints[hop_count], output_amount)?.amount;
+
+        // If we have received less than the minimum out, throw an error
+        if output_amount < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimum.into());
+        }
+    } else {
+        // amount_specified_is_input == false, then we have a variable amount of input
+        // The slippage we care about is the input of the first hop
+        if input_amount > other_amount_threshold {
+            return Err(ErrorCode::AmountInAboveMaximum.into());
+        }
+    }
+
+    // Each token is transferred once (from owner to vault, from vault to vault, or from vault to owner),
+    // so the transfer fee of an intermediate token is reported on both of its hops.
+    let transfer_fees = mints
+        .iter()
+        .enumerate()
+        .map(|(i, mint)| {
+            let amount = if i < hop_count {
+                hop_amounts[i].0
+            } else {
+                output_amount
+            };
+            Ok(calculate_transfer_fee_excluded_amount(mint, amount)?.transfer_fee)
+        })
+        .collect::<Result<Vec<u64>>>()?;
+
+    let swap_results: Vec<SwapResult> = hops
+        .iter()
+        .zip(swap_updates.iter())
+        .enumerate()
+        .map(|(i, (hop, swap_update))| {
+            SwapResult::new(
+                swap_update,
+                hop.a_to_b,
+                transfer_fees[i],
+                transfer_fees[i + 1],
+            )
+        })
+        .collect();
+
+    let pre_swap_states: Vec<(u128, i32)> = hops
+        .iter()
+        .map(|hop| (hop.whirlpool.sqrt_price, hop.whirlpool.tick_current_index))
+        .collect();
+
+    // Record the state of the Whirlpools before the swap
+    for (oracle_accessor, (hop, swap_update)) in oracle_accessors
+        .iter()
+        .zip(hops.iter().zip(swap_updates.iter()))
+    {
+        oracle_accessor.update_on_swap(
+            &hop.whirlpool,
+            &swap_update.next_adaptive_fee_info,
+            timestamp,
+        )?;
+    }
+
+    for (hop, swap_update) in hops.iter_mut().zip(swap_updates) {
+        hop.whirlpool.update_after_swap(
+            swap_update.next_liquidity,
+            swap_update.next_tick_index,
+            swap_update.next_sqrt_price,
+            swap_update.next_fee_growth_global,
+            swap_update.next_reward_infos,
+            swap_update.next_protocol_fee,
+            hop.a_to_b,
+            timestamp,
+        );
+
+        if let Some(next_reward_schedule) = &swap_update.next_reward_schedule {
+            store_whirlpool_reward_schedule(&hop.whirlpool_rewards, next_reward_schedule)?;
+        }
+
+        if let Some(next_reward_extension) = &swap_update.next_reward_extension {
+            store_whirlpool_reward_extension(&hop.whirlpool_rewards, next_reward_extension)?;
+        }
+    }
+
+    transfer_from_owner_to_vault_v2(
+        &ctx.accounts.token_authority,
+        mints[0],
+        &ctx.accounts.token_owner_account_input,
+        &hops[0].token_vault_input,
+        token_programs[0],
+        &ctx.accounts.memo_program,
+        transfer_hook_accounts[0],
+        input_amount,
+    )?;
+
+    // Transfer from pool to pool
+    for i in 0..hop_count - 1 {
+        transfer_from_vault_to_owner_v2(
+            &hops[i].whirlpool,
+            mints[i + 1],
+            &hops[i].token_vault_output,
+            &hops[i + 1].token_vault_input,
+            token_programs[i + 1],
+            &ctx.accounts.memo_program,
+            transfer_hook_accounts[i + 1],
+            hop_amounts[i].1,
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?;
+    }
+
+    transfer_from_vault_to_owner_v2(
+        &hops[hop_count - 1].whirlpool,
+        mints[hop_count],
+        &hops[hop_count - 1].token_vault_output,
+        &ctx.accounts.token_owner_account_output,
+        token_programs[hop_count],
+        &ctx.accounts.memo_program,
+        transfer_hook_accounts[hop_count],
+        output_amount,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    for (i, hop) in hops.iter().enumerate() {
+        // Whirlpools in remaining accounts are not persisted by Anchor
+        hop.whirlpool.exit(&crate::ID)?;
+
+        let (pre_sqrt_price, pre_tick_index) = pre_swap_states[i];
+        emit_cpi!(Traded {
+            whirlpool: hop.whirlpool.key(),
+            a_to_b: hop.a_to_b,
+            pre_sqrt_price,
+            post_sqrt_price: hop.whirlpool.sqrt_price,
+            pre_tick_index,
+            post_tick_index: hop.whirlpool.tick_current_index,
+            input_amount: hop_amounts[i].0,
+            output_amount: hop_amounts[i].1,
+            input_transfer_fee: transfer_fees[i],
+            output_transfer_fee: transfer_fees[i + 1],
+        });
+    }
+
+    set_return_data(&swap_results)
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{WhirlpoolsConfig, WhirlpoolsConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetConfigPaused<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(mut, has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Account<'info, WhirlpoolsConfigExtension>,
+
+    #[account(address = whirlpools_config_extension.config_extension_authority)]
+    pub config_extension_authority: Signer<'info>,
+}
+
+/// Pause or unpause all of the Whirlpools in the WhirlpoolsConfig. Only the config extension authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetConfigPaused>, paused: bool) -> Result<()> {
+    ctx.accounts
+        .whirlpools_config_extension
+        .update_paused(paused);
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::manager::whirlpool_manager::next_whirlpool_reward_extension;
+use crate::math::checked_mul_shift_right;
+use crate::state::{RewardSchedule, Whirlpool};
+use crate::util::{
+    load_whirlpool_reward_extension, store_whirlpool_reward_extension, to_timestamp_u64,
+};
+
+const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
+
+#[derive(Accounts)]
+pub struct SetExtensionRewardEmissions<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    pub reward_authority: Signer<'info>,
+
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetExtensionRewardEmissions>,
+    reward_index: u8,
+    emissions_per_second_x64: u128,
+) -> Result<()> {
+    let whirlpool = &ctx.accounts.whirlpool;
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    let reward_vault = &ctx.accounts.reward_vault;
+
+    let reward_extension = load_whirlpool_reward_extension(&whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+
+    // address constraint equivalent checks
+    let index = reward_index as usize;
+    match reward_extension.reward_infos.get(index) {
+        Some(reward_info)
+            if reward_info.authority == ctx.accounts.reward_authority.key()
+                && reward_info.vault == reward_vault.key() => {}
+        Some(_) => return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into()),
+        None => return Err(ErrorCode::InvalidRewardIndex.into()),
+    }
+
+    let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)?;
+    if reward_vault.amount < emissions_per_day {
+        return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let mut next_reward_extension =
+        next_whirlpool_reward_extension(whirlpool, &reward_extension, timestamp)?;
+
+    // An increase of the emissions must be covered by the rewards which are not owed yet
+    if emissions_per_second_x64 > reward_extension.reward_infos[index].emissions_per_second_x64
+        && next_reward_extension.ledgers[index].amount_available(reward_vault.amount)
+            < emissions_per_day
+    {
+        return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+    }
+
+    // A constant emissions rate replaces the schedule of the reward
+    next_reward_extension.schedules[index] = RewardSchedule::default();
+    next_reward_extension.update_emissions(
+        index,
+        next_reward_extension.reward_infos,
+        timestamp,
+        emissions_per_second_x64,
+    )?;
+    store_whirlpool_reward_extension(&whirlpool_rewards, &next_reward_extension)
+}
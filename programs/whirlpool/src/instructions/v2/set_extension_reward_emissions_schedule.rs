@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::instructions::v2::set_reward_emissions_schedule::scheduled_emissions_per_second_x64;
+use crate::manager::whirlpool_manager::next_whirlpool_reward_extension;
+use crate::state::{RewardSchedule, Whirlpool};
+use crate::util::{
+    load_whirlpool_reward_extension, store_whirlpool_reward_extension, to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct SetExtensionRewardEmissionsSchedule<'info> {
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    pub reward_authority: Signer<'info>,
+
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+/*
+  Emits the budget of an extension reward evenly between the start and end timestamps,
+  as set_reward_emissions_schedule does for the rewards of the Whirlpool.
+*/
+pub fn handler(
+    ctx: Context<SetExtensionRewardEmissionsSchedule>,
+    reward_index: u8,
+    start_timestamp: u64,
+    end_timestamp: u64,
+    emissions_budget: u64,
+) -> Result<()> {
+    let whirlpool = &ctx.accounts.whirlpool;
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+
+    let reward_extension = load_whirlpool_reward_extension(&whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+
+    // address constraint equivalent checks
+    let index = reward_index as usize;
+    match reward_extension.reward_infos.get(index) {
+        Some(reward_info)
+            if reward_info.authority == ctx.accounts.reward_authority.key()
+                && reward_info.vault == ctx.accounts.reward_vault.key() => {}
+        Some(_) => return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into()),
+        None => return Err(ErrorCode::InvalidRewardIndex.into()),
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Emissions cannot start in the past
+    let start_timestamp = start_timestamp.max(timestamp);
+    if start_timestamp >= end_timestamp {
+        return Err(ErrorCode::InvalidRewardSchedule.into());
+    }
+
+    let mut next_reward_extension =
+        next_whirlpool_reward_extension(whirlpool, &reward_extension, timestamp)?;
+
+    // The whole budget must be deposited up front, on top of the rewards which are still owed
+    if next_reward_extension.ledgers[index].amount_available(ctx.accounts.reward_vault.amount)
+        < emissions_budget
+    {
+        return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+    }
+
+    next_reward_extension.update_emissions(
+        index,
+        next_reward_extension.reward_infos,
+        timestamp,
+        scheduled_emissions_per_second_x64(start_timestamp, end_timestamp, emissions_budget),
+    )?;
+    next_reward_extension.schedules[index] =
+        RewardSchedule::new(start_timestamp, end_timestamp, emissions_budget);
+    store_whirlpool_reward_extension(&whirlpool_rewards, &next_reward_extension)
+}
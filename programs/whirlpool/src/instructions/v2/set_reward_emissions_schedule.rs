@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::manager::whirlpool_manager::next_whirlpool_reward_infos;
+use crate::math::Q64_RESOLUTION;
+use crate::state::{RewardSchedule, Whirlpool};
+use crate::util::{
+    load_whirlpool_reward_schedule, store_whirlpool_reward_schedule, to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardEmissionsSchedule<'info> {
+    #[account(mut)]
+    pub whirlpool: Account<'info, Whirlpool>,
+
+    #[account(address = whirlpool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(address = whirlpool.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, checked in the handler
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+}
+
+/*
+  Emits the budget evenly between the start and end timestamps.
+  The schedules must have been created by initialize_reward_schedule.
+*/
+pub fn handler(
+    ctx: Context<SetRewardEmissionsSchedule>,
+    reward_index: u8,
+    start_timestamp: u64,
+    end_timestamp: u64,
+    emissions_budget: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Emissions cannot start in the past
+    let start_timestamp = start_timestamp.max(timestamp);
+    if start_timestamp >= end_timestamp {
+        return Err(ErrorCode::InvalidRewardSchedule.into());
+    }
+
+    let whirlpool_rewards = ctx.accounts.whirlpool_rewards.to_account_info();
+    let reward_schedule = load_whirlpool_reward_schedule(&whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardScheduleNotInitialized)?;
+    let (next_reward_infos, next_reward_schedule) =
+        next_whirlpool_reward_infos(&ctx.accounts.whirlpool, &Some(reward_schedule), timestamp)?;
+    let mut next_reward_schedule = next_reward_schedule.unwrap_or(reward_schedule);
+
+    // The whole budget must be deposited up front, on top of the rewards which are still owed
+    let index = reward_index as usize;
+    if next_reward_schedule.ledgers[index].amount_available(ctx.accounts.reward_vault.amount)
+        < emissions_budget
+    {
+        return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+    }
+
+    ctx.accounts.whirlpool.update_emissions(
+        index,
+        next_reward_infos,
+        timestamp,
+        scheduled_emissions_per_second_x64(start_timestamp, end_timestamp, emissions_budget),
+    )?;
+
+    next_reward_schedule.schedules[index] =
+        RewardSchedule::new(start_timestamp, end_timestamp, emissions_budget);
+    store_whirlpool_reward_schedule(&whirlpool_rewards, &next_reward_schedule)
+}
+
+// The scheduled emissions only depend on the remaining budget and seconds of the schedule,
+// so the emissions rate of the reward is informational.
+pub fn scheduled_emissions_per_second_x64(
+    start_timestamp: u64,
+    end_timestamp: u64,
+    emissions_budget: u64,
+) -> u128 {
+    (u128::from(emissions_budget) << Q64_RESOLUTION) / u128::from(end_timestamp - start_timestamp)
+}
@@ -0,0 +1,40 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetTokenBadgeAllowlist<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Box<Account<'info, WhirlpoolsConfigExtension>>,
+
+    #[account(address = whirlpools_config_extension.token_badge_authority)]
+    pub token_badge_authority: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+      mut,
+      seeds = [
+        b"token_badge",
+        whirlpools_config.key().as_ref(),
+        token_mint.key().as_ref(),
+      ],
+      bump,
+      has_one = whirlpools_config,
+    )]
+    pub token_badge: Account<'info, TokenBadge>,
+}
+
+/// Set the transfer hook program and permanent delegate allowed for the badged token mint. Only the token badge authority has permission to invoke this instruction.
+pub fn handler(
+    ctx: Context<SetTokenBadgeAllowlist>,
+    transfer_hook_program_id: Pubkey,
+    permanent_delegate: Pubkey,
+) -> Result<()> {
+    ctx.accounts
+        .token_badge
+        .update_allowlist(transfer_hook_program_id, permanent_delegate);
+    Ok(())
+}
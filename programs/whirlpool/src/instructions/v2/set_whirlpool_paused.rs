@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+use crate::state::{Whirlpool, WhirlpoolPause, WhirlpoolsConfig, WhirlpoolsConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetWhirlpoolPaused<'info> {
+    pub whirlpools_config: Box<Account<'info, WhirlpoolsConfig>>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpools_config_extension: Account<'info, WhirlpoolsConfigExtension>,
+
+    #[account(address = whirlpools_config_extension.config_extension_authority)]
+    pub config_extension_authority: Signer<'info>,
+
+    #[account(has_one = whirlpools_config)]
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(mut, seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: WhirlpoolPause of the Whirlpool, created in the handler if it does not exist
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Pause or unpause a Whirlpool. Only the config extension authority has permission to invoke this instruction.
+
+  The flag is stored in the WhirlpoolPause PDA of the Whirlpool, which is created when pausing
+  a Whirlpool for the first time.
+*/
+pub fn handler(ctx: Context<SetWhirlpoolPaused>, paused: bool) -> Result<()> {
+    let whirlpool_pause_info = ctx.accounts.whirlpool_pause.to_account_info();
+    let mut whirlpool_pause = if *whirlpool_pause_info.owner == crate::ID {
+        WhirlpoolPause::try_deserialize(&mut whirlpool_pause_info.try_borrow_data()?.as_ref())?
+    } else {
+        // Whirlpools without a WhirlpoolPause are never paused
+        if !paused {
+            return Ok(());
+        }
+        let whirlpool_key = ctx.accounts.whirlpool.key();
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: whirlpool_pause_info.clone(),
+                },
+                &[&[
+                    b"whirlpool_pause",
+                    whirlpool_key.as_ref(),
+                    &[ctx.bumps.whirlpool_pause],
+                ]],
+            ),
+            Rent::get()?.minimum_balance(WhirlpoolPause::LEN),
+            WhirlpoolPause::LEN as u64,
+            &crate::ID,
+        )?;
+        let mut whirlpool_pause = WhirlpoolPause::default();
+        whirlpool_pause.initialize(whirlpool_key);
+        whirlpool_pause
+    };
+
+    whirlpool_pause.update_paused(paused);
+
+    let mut data = whirlpool_pause_info.try_borrow_mut_data()?;
+    let mut dst: &mut [u8] = &mut data;
+    whirlpool_pause.try_serialize(&mut dst)
+}
@@ -0,0 +1,438 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::{
+    instructions::v2::swap::{get_max_referral_fee_rate, swap_with_transfer_fee_extension},
+    manager::swap_manager::PostSwapUpdate,
+    return_data::{set_return_data, SwapResult},
+    state::{OracleAccessor, Whirlpool},
+    util::{
+        load_whirlpool_reward_extension, load_whirlpool_reward_schedule, to_timestamp_u64,
+        v2::ReferralFee, SparseSwapTickSequenceBuilder,
+    },
+};
+
+#[derive(Accounts)]
+pub struct SimulateSwap<'info> {
+    pub whirlpool: Box<Account<'info, Whirlpool>>,
+
+    #[account(address = whirlpool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = whirlpool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_0: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_1: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, caps the referral fee rate
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
+    // remaining accounts
+    // - supplemental TickArray accounts
+    // - referral fee token account (optional, required to quote a referral fee)
+}
+
+/// Runs the swap loop of swap_v2 without transferring tokens or updating any account,
+/// and publishes the resulting SwapResult as return data.
+///
+/// The swap loop updates the crossed ticks and fills the limit orders in the TickArray data,
+/// so it runs on in-memory copies of the TickArray accounts, which can be passed as read-only.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SimulateSwap<'info>>,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+    referral_fee_rate: u16,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let whirlpool = &ctx.accounts.whirlpool;
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::SupplementalTickArrays,
+            AccountsType::ReferralFee,
+        ],
+    )?;
+
+    let mut tick_array_account_infos = vec![
+        ctx.accounts.tick_array_0.to_account_info(),
+        ctx.accounts.tick_array_1.to_account_info(),
+        ctx.accounts.tick_array_2.to_account_info(),
+    ];
+    if let Some(supplemental_tick_arrays) = &remaining_accounts.supplemental_tick_arrays {
+        tick_array_account_infos.extend(supplemental_tick_arrays.iter().cloned());
+    }
+    let mut tick_array_copies = TickArrayCopy::copy_all(&tick_array_account_infos)?;
+
+    let builder = SparseSwapTickSequenceBuilder::try_from(
+        whirlpool,
+        a_to_b,
+        tick_array_copies
+            .iter_mut()
+            .map(TickArrayCopy::as_account_info)
+            .collect(),
+        None,
+    )?;
+    let mut swap_tick_sequence = builder.build()?;
+
+    let oracle_accessor =
+        OracleAccessor::new_read_only(whirlpool, ctx.accounts.oracle.to_account_info())?;
+    let reward_schedule = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards)?;
+    let reward_extension = load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards)?;
+
+    let swap_update = swap_with_transfer_fee_extension(
+        whirlpool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        &oracle_accessor.get_adaptive_fee_info()?,
+        &reward_schedule,
+        &reward_extension,
+    )?;
+
+    let (input_token_mint, output_token_mint) = if a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a)
+    };
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    let referral_fee = ReferralFee::calculate(
+        remaining_accounts.referral_fee,
+        referral_fee_rate,
+        get_max_referral_fee_rate(referral_fee_rate, &ctx.accounts.whirlpools_config_extension)?,
+        &input_token_mint.key(),
+        &output_token_mint.key(),
+        input_amount,
+        output_amount,
+        amount_specified_is_input,
+    )?;
+
+    set_return_data(&simulated_swap_result(
+        &swap_update,
+        a_to_b,
+        input_token_mint,
+        output_token_mint,
+        &referral_fee,
+    )?)
+}
+
+/// Builds the SwapResult returned by swap_v2 (or swap_v2_with_referral_fee) for the same swap.
+fn simulated_swap_result(
+    swap_update: &PostSwapUpdate,
+    a_to_b: bool,
+    input_token_mint: &InterfaceAccount<'_, Mint>,
+    output_token_mint: &InterfaceAccount<'_, Mint>,
+    referral_fee: &Option<ReferralFee>,
+) -> Result<SwapResult> {
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    let owner_output_amount = output_amount - ReferralFee::output_amount(referral_fee);
+
+    let input_transfer_fee =
+        calculate_transfer_fee_excluded_amount(input_token_mint, input_amount)?.transfer_fee;
+    let output_transfer_fee =
+        calculate_transfer_fee_excluded_amount(output_token_mint, owner_output_amount)?
+            .transfer_fee;
+
+    // The referral fee on the output is not received by the token owner account
+    let mut swap_result = SwapResult::new(
+        swap_update,
+        a_to_b,
+        input_transfer_fee,
+        output_transfer_fee + ReferralFee::output_amount(referral_fee),
+    );
+    swap_result.referral_fee_amount = referral_fee
+        .as_ref()
+        .map_or(0, |referral_fee| referral_fee.amount);
+    Ok(swap_result)
+}
+
+/// In-memory copy of a TickArray account, updated by the swap loop instead of the account data.
+struct TickArrayCopy {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TickArrayCopy {
+    /// Copies the given accounts, deduplicated by key so that each TickArray is copied once.
+    fn copy_all(account_infos: &[AccountInfo]) -> Result<Vec<Self>> {
+        let mut copies: Vec<Self> = Vec::with_capacity(account_infos.len());
+        for account_info in account_infos {
+            if copies.iter().any(|copy| copy.key == *account_info.key) {
+                continue;
+            }
+            copies.push(Self {
+                key: *account_info.key,
+                owner: *account_info.owner,
+                lamports: account_info.lamports(),
+                data: account_info.try_borrow_data()?.to_vec(),
+            });
+        }
+        Ok(copies)
+    }
+
+    /// The copy is writable, as SparseSwapTickSequenceBuilder requires.
+    fn as_account_info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            false,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+#[cfg(test)]
+mod simulate_swap_tests {
+    use super::*;
+    use crate::state::{TickArray, TickUpdate, WhirlpoolRewardInfo, NUM_REWARDS};
+    use crate::util::test_utils::{
+        anchor_account_data, leaked_account_info, mint_data, token_account_data,
+    };
+    use anchor_lang::Discriminator;
+    use anchor_spl::token::spl_token;
+    use anchor_spl::token_interface::TokenAccount;
+
+    const TICK_SPACING: u16 = 64;
+
+    fn whirlpool_account() -> Account<'static, Whirlpool> {
+        let whirlpool = Whirlpool {
+            tick_spacing: TICK_SPACING,
+            tick_current_index: 0,
+            ..Default::default()
+        };
+        let account_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            false,
+            anchor_account_data(&whirlpool, Whirlpool::LEN),
+        )));
+        Account::try_from(account_info).unwrap()
+    }
+
+    // read-only as passed to simulate_swap
+    fn tick_array_account_info(whirlpool: Pubkey, start_tick_index: i32) -> AccountInfo<'static> {
+        let mut data = vec![0u8; TickArray::LEN];
+        data[0..8].copy_from_slice(&TickArray::discriminator());
+        data[8..12].copy_from_slice(&start_tick_index.to_le_bytes());
+        data[9956..9988].copy_from_slice(&whirlpool.to_bytes());
+        leaked_account_info(Pubkey::new_unique(), crate::ID, false, false, data)
+    }
+
+    fn mint() -> InterfaceAccount<'static, Mint> {
+        let account_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            spl_token::ID,
+            false,
+            false,
+            mint_data(0),
+        )));
+        InterfaceAccount::try_from(account_info).unwrap()
+    }
+
+    fn referral_fee(mint: Pubkey, is_input: bool, amount: u64) -> Option<ReferralFee<'static>> {
+        let account_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            spl_token::ID,
+            false,
+            false,
+            token_account_data(mint, Pubkey::new_unique(), 0, None),
+        )));
+        Some(ReferralFee {
+            token_account: Box::new(
+                InterfaceAccount::<TokenAccount>::try_from(&*account_info).unwrap(),
+            ),
+            is_input,
+            amount,
+        })
+    }
+
+    fn post_swap_update(amount_a: u64, amount_b: u64) -> PostSwapUpdate {
+        PostSwapUpdate {
+            amount_a,
+            amount_b,
+            next_liquidity: 1_000_000,
+            next_tick_index: 100,
+            next_sqrt_price: 1 << 64,
+            next_fee_growth_global: 0,
+            next_reward_infos: [WhirlpoolRewardInfo::default(); NUM_REWARDS],
+            next_reward_schedule: None,
+            next_reward_extension: None,
+            next_protocol_fee: 0,
+            next_adaptive_fee_info: None,
+            fee_amount: 3,
+        }
+    }
+
+    #[test]
+    fn test_copy_all_deduplicates_accounts() {
+        let whirlpool = Pubkey::new_unique();
+        let tick_array_0 = tick_array_account_info(whirlpool, 0);
+        let tick_array_1 = tick_array_account_info(whirlpool, 5632);
+
+        let copies = TickArrayCopy::copy_all(&[
+            tick_array_0.clone(),
+            tick_array_1.clone(),
+            tick_array_0.clone(),
+        ])
+        .unwrap();
+        assert_eq!(copies.len(), 2);
+        assert_eq!(copies[0].key, tick_array_0.key());
+        assert_eq!(copies[0].owner, crate::ID);
+        assert_eq!(copies[0].data, *tick_array_0.try_borrow_data().unwrap());
+        assert_eq!(copies[1].key, tick_array_1.key());
+    }
+
+    #[test]
+    fn test_swap_tick_sequence_from_read_only_tick_arrays() {
+        let whirlpool = whirlpool_account();
+        let tick_array = tick_array_account_info(whirlpool.key(), 0);
+        let tick_array_data = tick_array.try_borrow_data().unwrap().to_vec();
+
+        // the TickArray accounts are not writable, so they cannot be used directly
+        assert_eq!(
+            SparseSwapTickSequenceBuilder::try_from(
+                &whirlpool,
+                false,
+                vec![tick_array.clone()],
+                None
+            )
+            .err()
+            .unwrap(),
+            anchor_lang::error::ErrorCode::AccountNotMutable.into()
+        );
+
+        // the same TickArray passed as all three tick arrays is copied once
+        let mut copies =
+            TickArrayCopy::copy_all(&[tick_array.clone(), tick_array.clone(), tick_array.clone()])
+                .unwrap();
+        assert_eq!(copies.len(), 1);
+        {
+            let builder = SparseSwapTickSequenceBuilder::try_from(
+                &whirlpool,
+                false,
+                copies
+                    .iter_mut()
+                    .map(TickArrayCopy::as_account_info)
+                    .collect(),
+                None,
+            )
+            .unwrap();
+            let mut swap_tick_sequence = builder.build().unwrap();
+            swap_tick_sequence
+                .update_tick(
+                    0,
+                    TICK_SPACING as i32,
+                    TICK_SPACING,
+                    &TickUpdate {
+                        initialized: true,
+                        liquidity_net: 1000,
+                        liquidity_gross: 1000,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert!(
+                swap_tick_sequence
+                    .get_tick(0, TICK_SPACING as i32, TICK_SPACING)
+                    .unwrap()
+                    .initialized
+            );
+        }
+
+        // the update is only applied to the copy
+        assert_ne!(copies[0].data, tick_array_data);
+        assert_eq!(*tick_array.try_borrow_data().unwrap(), tick_array_data);
+    }
+
+    #[test]
+    fn test_simulated_swap_result() {
+        let (mint_a, mint_b) = (mint(), mint());
+        let swap_result =
+            simulated_swap_result(&post_swap_update(1000, 900), true, &mint_a, &mint_b, &None)
+                .unwrap();
+        assert_eq!(
+            swap_result,
+            SwapResult::new(&post_swap_update(1000, 900), true, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_simulated_swap_result_with_input_referral_fee() {
+        let (mint_a, mint_b) = (mint(), mint());
+        let swap_result = simulated_swap_result(
+            &post_swap_update(1000, 900),
+            true,
+            &mint_a,
+            &mint_b,
+            &referral_fee(mint_a.key(), true, 10),
+        )
+        .unwrap();
+        // the referral fee on the input is paid on top of amount_in
+        assert_eq!(swap_result.amount_in, 1000);
+        assert_eq!(swap_result.amount_out_after_transfer_fee, 900);
+        assert_eq!(swap_result.referral_fee_amount, 10);
+    }
+
+    #[test]
+    fn test_simulated_swap_result_with_output_referral_fee() {
+        let (mint_a, mint_b) = (mint(), mint());
+        let swap_result = simulated_swap_result(
+            &post_swap_update(900, 1000),
+            false,
+            &mint_a,
+            &mint_b,
+            &referral_fee(mint_a.key(), false, 9),
+        )
+        .unwrap();
+        // the referral fee on the output is not received by the token owner account
+        assert_eq!(swap_result.amount_in, 1000);
+        assert_eq!(swap_result.amount_out, 900);
+        assert_eq!(swap_result.amount_out_after_transfer_fee, 891);
+        assert_eq!(swap_result.referral_fee_amount, 9);
+    }
+}
@@ -4,19 +4,27 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::util::{
     calculate_transfer_fee_excluded_amount, calculate_transfer_fee_included_amount,
-    parse_remaining_accounts, AccountsType, RemainingAccountsInfo,
+    parse_remaining_accounts, verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
 };
 use crate::{
     constants::transfer_memo,
     errors::ErrorCode,
+    events::{ReferralFeePaid, Traded},
     manager::swap_manager::*,
-    state::Whirlpool,
+    return_data::{set_return_data, SwapResult},
+    state::{
+        AdaptiveFeeInfo, OracleAccessor, Whirlpool, WhirlpoolRewardExtension,
+        WhirlpoolRewardSchedule,
+    },
     util::{
-        to_timestamp_u64, v2::update_and_swap_whirlpool_v2, SparseSwapTickSequenceBuilder,
-        SwapTickSequence,
+        load_whirlpool_reward_extension, load_whirlpool_reward_schedule,
+        load_whirlpools_config_extension, to_timestamp_u64,
+        v2::{update_and_swap_whirlpool_v2, ReferralFee},
+        verify_whirlpool_not_paused, SparseSwapTickSequenceBuilder, SwapTickSequence,
     },
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SwapV2<'info> {
     #[account(address = *token_mint_a.to_account_info().owner)]
@@ -59,21 +67,37 @@ pub struct SwapV2<'info> {
     pub tick_array_2: UncheckedAccount<'info>,
 
     #[account(mut, seeds = [b"oracle", whirlpool.key().as_ref()], bump)]
-    /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
     pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards: UncheckedAccount<'info>,
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
     // - supplemental TickArray accounts
+    // - referral fee token account (swap_v2_with_referral_fee only)
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
 }
 
-pub fn handler<'info>(
-    ctx: Context<'_, '_, '_, 'info, SwapV2<'info>>,
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SwapV2<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     sqrt_price_limit: u128,
     amount_specified_is_input: bool,
     a_to_b: bool, // Zero for one
+    referral_fee_rate: u16,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
     let whirlpool = &mut ctx.accounts.whirlpool;
@@ -89,8 +113,20 @@ pub fn handler<'info>(
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
             AccountsType::SupplementalTickArrays,
+            AccountsType::ReferralFee,
+            AccountsType::TokenBadges,
         ],
     )?;
+    verify_token_badge_allowlists(
+        &whirlpool.whirlpools_config,
+        &[&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b],
+        remaining_accounts.token_badges,
+    )?;
+
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension,
+        &ctx.accounts.whirlpool_pause,
+    )?;
 
     let builder = SparseSwapTickSequenceBuilder::try_from(
         whirlpool,
@@ -104,6 +140,10 @@ pub fn handler<'info>(
     )?;
     let mut swap_tick_sequence = builder.build()?;
 
+    let oracle_accessor = OracleAccessor::new(whirlpool, ctx.accounts.oracle.to_account_info())?;
+    let reward_schedule = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards)?;
+    let reward_extension = load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards)?;
+
     let swap_update = swap_with_transfer_fee_extension(
         whirlpool,
         &ctx.accounts.token_mint_a,
@@ -114,38 +154,93 @@ pub fn handler<'info>(
         amount_specified_is_input,
         a_to_b,
         timestamp,
+        &oracle_accessor.get_adaptive_fee_info()?,
+        &reward_schedule,
+        &reward_extension,
+    )?;
+
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    let (input_token_mint, output_token_mint) = if a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a)
+    };
+
+    let referral_fee = ReferralFee::calculate(
+        remaining_accounts.referral_fee,
+        referral_fee_rate,
+        get_max_referral_fee_rate(referral_fee_rate, &ctx.accounts.whirlpools_config_extension)?,
+        &input_token_mint.key(),
+        &output_token_mint.key(),
+        input_amount,
+        output_amount,
+        amount_specified_is_input,
     )?;
+    // The amounts transferred from and to the token owner accounts
+    let owner_input_amount = input_amount
+        .checked_add(ReferralFee::input_amount(&referral_fee))
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let owner_output_amount = output_amount - ReferralFee::output_amount(&referral_fee);
 
     if amount_specified_is_input {
-        let transfer_fee_excluded_output_amount = if a_to_b {
-            calculate_transfer_fee_excluded_amount(
-                &ctx.accounts.token_mint_b,
-                swap_update.amount_b,
-            )?
-            .amount
-        } else {
-            calculate_transfer_fee_excluded_amount(
-                &ctx.accounts.token_mint_a,
-                swap_update.amount_a,
-            )?
-            .amount
-        };
+        let transfer_fee_excluded_output_amount =
+            calculate_transfer_fee_excluded_amount(output_token_mint, owner_output_amount)?.amount;
         if transfer_fee_excluded_output_amount < other_amount_threshold {
             return Err(ErrorCode::AmountOutBelowMinimum.into());
         }
     } else {
-        let transfer_fee_included_input_amount = if a_to_b {
-            swap_update.amount_a
-        } else {
-            swap_update.amount_b
-        };
+        let transfer_fee_included_input_amount = owner_input_amount;
         if transfer_fee_included_input_amount > other_amount_threshold {
             return Err(ErrorCode::AmountInAboveMaximum.into());
         }
     }
 
+    let pre_sqrt_price = whirlpool.sqrt_price;
+    let pre_tick_index = whirlpool.tick_current_index;
+    let input_transfer_fee =
+        calculate_transfer_fee_excluded_amount(input_token_mint, input_amount)?.transfer_fee;
+    let output_transfer_fee =
+        calculate_transfer_fee_excluded_amount(output_token_mint, owner_output_amount)?
+            .transfer_fee;
+    let referral_fee_paid = match &referral_fee {
+        Some(referral_fee) => {
+            let referral_fee_mint = if referral_fee.is_input {
+                input_token_mint
+            } else {
+                output_token_mint
+            };
+            Some(ReferralFeePaid {
+                whirlpool: whirlpool.key(),
+                referral_fee_account: referral_fee.token_account.key(),
+                referral_fee_mint: referral_fee_mint.key(),
+                amount: referral_fee.amount,
+                transfer_fee: calculate_transfer_fee_excluded_amount(
+                    referral_fee_mint,
+                    referral_fee.amount,
+                )?
+                .transfer_fee,
+            })
+        }
+        None => None,
+    };
+    // The referral fee on the output is not received by the token owner account
+    let mut swap_result = SwapResult::new(
+        &swap_update,
+        a_to_b,
+        input_transfer_fee,
+        output_transfer_fee + ReferralFee::output_amount(&referral_fee),
+    );
+    swap_result.referral_fee_amount = referral_fee
+        .as_ref()
+        .map_or(0, |referral_fee| referral_fee.amount);
+
     update_and_swap_whirlpool_v2(
         whirlpool,
+        &ctx.accounts.whirlpool_rewards,
         &ctx.accounts.token_authority,
         &ctx.accounts.token_mint_a,
         &ctx.accounts.token_mint_b,
@@ -158,10 +253,45 @@ pub fn handler<'info>(
         &ctx.accounts.token_program_a,
         &ctx.accounts.token_program_b,
         &ctx.accounts.memo_program,
+        &oracle_accessor,
         swap_update,
         a_to_b,
         timestamp,
+        &referral_fee,
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    emit_cpi!(Traded {
+        whirlpool: ctx.accounts.whirlpool.key(),
+        a_to_b,
+        pre_sqrt_price,
+        post_sqrt_price: ctx.accounts.whirlpool.sqrt_price,
+        pre_tick_index,
+        post_tick_index: ctx.accounts.whirlpool.tick_current_index,
+        input_amount,
+        output_amount,
+        input_transfer_fee,
+        output_transfer_fee,
+    });
+
+    if let Some(referral_fee_paid) = referral_fee_paid {
+        emit_cpi!(referral_fee_paid);
+    }
+
+    set_return_data(&swap_result)
+}
+
+/// Returns the cap of the referral fee rate, set in the WhirlpoolsConfigExtension if it is initialized.
+pub fn get_max_referral_fee_rate(
+    referral_fee_rate: u16,
+    whirlpools_config_extension: &AccountInfo,
+) -> Result<u16> {
+    if referral_fee_rate == 0 {
+        return Ok(0);
+    }
+    Ok(
+        load_whirlpools_config_extension(whirlpools_config_extension)?
+            .map_or(0, |config_extension| config_extension.max_referral_fee_rate),
     )
 }
 
@@ -176,6 +306,9 @@ pub fn swap_with_transfer_fee_extension<'info>(
     amount_specified_is_input: bool,
     a_to_b: bool,
     timestamp: u64,
+    adaptive_fee_info: &Option<AdaptiveFeeInfo>,
+    reward_schedule: &Option<WhirlpoolRewardSchedule>,
+    reward_extension: &Option<WhirlpoolRewardExtension>,
 ) -> Result<PostSwapUpdate> {
     let (input_token_mint, output_token_mint) = if a_to_b {
         (token_mint_a, token_mint_b)
@@ -198,6 +331,9 @@ pub fn swap_with_transfer_fee_extension<'info>(
             amount_specified_is_input,
             a_to_b,
             timestamp,
+            adaptive_fee_info,
+            reward_schedule,
+            reward_extension,
         )?;
 
         let (swap_update_amount_input, swap_update_amount_output) = if a_to_b {
@@ -236,7 +372,11 @@ pub fn swap_with_transfer_fee_extension<'info>(
             next_sqrt_price: swap_update.next_sqrt_price,
             next_fee_growth_global: swap_update.next_fee_growth_global,
             next_reward_infos: swap_update.next_reward_infos,
+            next_reward_schedule: swap_update.next_reward_schedule,
+            next_reward_extension: swap_update.next_reward_extension,
             next_protocol_fee: swap_update.next_protocol_fee,
+            next_adaptive_fee_info: swap_update.next_adaptive_fee_info,
+            fee_amount: swap_update.fee_amount,
         });
     }
 
@@ -254,6 +394,9 @@ pub fn swap_with_transfer_fee_extension<'info>(
         amount_specified_is_input,
         a_to_b,
         timestamp,
+        adaptive_fee_info,
+        reward_schedule,
+        reward_extension,
     )?;
 
     let (swap_update_amount_input, swap_update_amount_output) = if a_to_b {
@@ -286,6 +429,10 @@ pub fn swap_with_transfer_fee_extension<'info>(
         next_sqrt_price: swap_update.next_sqrt_price,
         next_fee_growth_global: swap_update.next_fee_growth_global,
         next_reward_infos: swap_update.next_reward_infos,
+        next_reward_schedule: swap_update.next_reward_schedule,
+        next_reward_extension: swap_update.next_reward_extension,
         next_protocol_fee: swap_update.next_protocol_fee,
+        next_adaptive_fee_info: swap_update.next_adaptive_fee_info,
+        fee_amount: swap_update.fee_amount,
     })
 }
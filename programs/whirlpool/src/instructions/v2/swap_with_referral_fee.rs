@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::util::RemainingAccountsInfo;
+
+use super::swap::SwapV2;
+
+/*
+  Performs swap_v2 and charges a referral fee for the integrator.
+  The fee is sent to the referral fee account passed in the ReferralFee slice of the remaining accounts,
+  and its rate is capped by the WhirlpoolsConfigExtension of the WhirlpoolsConfig.
+*/
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    referral_fee_rate: u16,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    super::swap::handler(
+        ctx,
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        referral_fee_rate,
+        remaining_accounts_info,
+    )
+}
+
+#[cfg(test)]
+mod swap_with_referral_fee_tests {
+    use crate::instruction;
+    use anchor_lang::{AnchorSerialize, Discriminator};
+
+    #[test]
+    fn test_swap_v2_data_has_no_referral_fee_rate() {
+        let data = instruction::SwapV2 {
+            amount: 1,
+            other_amount_threshold: 2,
+            sqrt_price_limit: 3,
+            amount_specified_is_input: true,
+            a_to_b: false,
+            remaining_accounts_info: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        // amount, other_amount_threshold, sqrt_price_limit, 2 bools, None
+        assert_eq!(data.len(), 8 + 8 + 16 + 1 + 1 + 1);
+    }
+
+    #[test]
+    fn test_swap_v2_with_referral_fee_data() {
+        let data = instruction::SwapV2WithReferralFee {
+            amount: 1,
+            other_amount_threshold: 2,
+            sqrt_price_limit: 3,
+            amount_specified_is_input: true,
+            a_to_b: false,
+            referral_fee_rate: 25,
+            remaining_accounts_info: None,
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(data.len(), 8 + 8 + 16 + 1 + 1 + 2 + 1);
+        assert_eq!(&data[34..36], &25u16.to_le_bytes());
+        assert_ne!(
+            instruction::SwapV2::DISCRIMINATOR,
+            instruction::SwapV2WithReferralFee::DISCRIMINATOR
+        );
+    }
+}
@@ -4,16 +4,20 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::swap_with_transfer_fee_extension;
 use crate::util::{
-    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
-    update_and_two_hop_swap_whirlpool_v2, AccountsType, RemainingAccountsInfo,
+    calculate_transfer_fee_excluded_amount, load_whirlpool_reward_extension,
+    load_whirlpool_reward_schedule, parse_remaining_accounts, update_and_two_hop_swap_whirlpool_v2,
+    verify_token_badge_allowlists, AccountsType, RemainingAccountsInfo,
 };
 use crate::{
     constants::transfer_memo,
     errors::ErrorCode,
-    state::Whirlpool,
-    util::{to_timestamp_u64, SparseSwapTickSequenceBuilder},
+    events::Traded,
+    return_data::{set_return_data, SwapResult},
+    state::{OracleAccessor, Whirlpool},
+    util::{to_timestamp_u64, verify_whirlpool_not_paused, SparseSwapTickSequenceBuilder},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
     amount: u64,
@@ -83,20 +87,45 @@ pub struct TwoHopSwapV2<'info> {
     pub tick_array_two_2: UncheckedAccount<'info>,
 
     #[account(mut, seeds = [b"oracle", whirlpool_one.key().as_ref()], bump)]
-    /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
     pub oracle_one: UncheckedAccount<'info>,
 
     #[account(mut, seeds = [b"oracle", whirlpool_two.key().as_ref()], bump)]
-    /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
+    /// CHECK: initialized only for pools with adaptive fee or price oracle, checked by OracleAccessor
     pub oracle_two: UncheckedAccount<'info>,
 
+    #[account(seeds = [b"config_extension", whirlpool_one.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension_one: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool_one.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause_one: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", whirlpool_two.whirlpools_config.as_ref()], bump)]
+    /// CHECK: not initialized if the WhirlpoolsConfig has no extension, checked by verify_whirlpool_not_paused
+    pub whirlpools_config_extension_two: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"whirlpool_pause", whirlpool_two.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool was never paused, checked by verify_whirlpool_not_paused
+    pub whirlpool_pause_two: UncheckedAccount<'info>,
+
     pub memo_program: Program<'info, Memo>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool_one.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards_one: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"whirlpool_rewards", whirlpool_two.key().as_ref()], bump)]
+    /// CHECK: not initialized if the Whirlpool has no reward schedules, loaded by load_whirlpool_reward_schedule
+    pub whirlpool_rewards_two: UncheckedAccount<'info>,
     // remaining accounts
     // - accounts for transfer hook program of token_mint_input
     // - accounts for transfer hook program of token_mint_intermediate
     // - accounts for transfer hook program of token_mint_output
     // - supplemental TickArray accounts for whirlpool_one
     // - supplemental TickArray accounts for whirlpool_two
+    // - TokenBadges of the mints with a transfer hook or permanent delegate
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -148,7 +177,33 @@ pub fn handler<'info>(
             AccountsType::TransferHookOutput,
             AccountsType::SupplementalTickArraysOne,
             AccountsType::SupplementalTickArraysTwo,
+            AccountsType::TokenBadges,
+        ],
+    )?;
+    verify_token_badge_allowlists(
+        &whirlpool_one.whirlpools_config,
+        &[
+            &ctx.accounts.token_mint_input,
+            &ctx.accounts.token_mint_intermediate,
+        ],
+        remaining_accounts.token_badges,
+    )?;
+    verify_token_badge_allowlists(
+        &whirlpool_two.whirlpools_config,
+        &[
+            &ctx.accounts.token_mint_intermediate,
+            &ctx.accounts.token_mint_output,
         ],
+        remaining_accounts.token_badges,
+    )?;
+
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension_one,
+        &ctx.accounts.whirlpool_pause_one,
+    )?;
+    verify_whirlpool_not_paused(
+        &ctx.accounts.whirlpools_config_extension_two,
+        &ctx.accounts.whirlpool_pause_two,
     )?;
 
     let builder_one = SparseSwapTickSequenceBuilder::try_from(
@@ -175,6 +230,18 @@ pub fn handler<'info>(
     )?;
     let mut swap_tick_sequence_two = builder_two.build()?;
 
+    let oracle_accessor_one =
+        OracleAccessor::new(whirlpool_one, ctx.accounts.oracle_one.to_account_info())?;
+    let oracle_accessor_two =
+        OracleAccessor::new(whirlpool_two, ctx.accounts.oracle_two.to_account_info())?;
+
+    let reward_schedule_one = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards_one)?;
+    let reward_schedule_two = load_whirlpool_reward_schedule(&ctx.accounts.whirlpool_rewards_two)?;
+    let reward_extension_one =
+        load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards_one)?;
+    let reward_extension_two =
+        load_whirlpool_reward_extension(&ctx.accounts.whirlpool_rewards_two)?;
+
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
     // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
     let (swap_update_one, swap_update_two) = if amount_specified_is_input {
@@ -199,6 +266,9 @@ pub fn handler<'info>(
             amount_specified_is_input, // true
             a_to_b_one,
             timestamp,
+            &oracle_accessor_one.get_adaptive_fee_info()?,
+            &reward_schedule_one,
+            &reward_extension_one,
         )?;
 
         // Swap two input is the output of swap one
@@ -227,6 +297,9 @@ pub fn handler<'info>(
             amount_specified_is_input, // true
             a_to_b_two,
             timestamp,
+            &oracle_accessor_two.get_adaptive_fee_info()?,
+            &reward_schedule_two,
+            &reward_extension_two,
         )?;
         (swap_calc_one, swap_calc_two)
     } else {
@@ -251,6 +324,9 @@ pub fn handler<'info>(
             amount_specified_is_input, // false
             a_to_b_two,
             timestamp,
+            &oracle_accessor_two.get_adaptive_fee_info()?,
+            &reward_schedule_two,
+            &reward_extension_two,
         )?;
 
         // The output of swap 1 is input of swap_calc_two
@@ -286,6 +362,9 @@ pub fn handler<'info>(
             amount_specified_is_input, // false
             a_to_b_one,
             timestamp,
+            &oracle_accessor_one.get_adaptive_fee_info()?,
+            &reward_schedule_one,
+            &reward_extension_one,
         )?;
         (swap_calc_one, swap_calc_two)
     };
@@ -339,6 +418,51 @@ pub fn handler<'info>(
         }
     }
 
+    let pre_sqrt_price_one = whirlpool_one.sqrt_price;
+    let pre_tick_index_one = whirlpool_one.tick_current_index;
+    let pre_sqrt_price_two = whirlpool_two.sqrt_price;
+    let pre_tick_index_two = whirlpool_two.tick_current_index;
+
+    let (input_amount, intermediate_amount) = if a_to_b_one {
+        (swap_update_one.amount_a, swap_update_one.amount_b)
+    } else {
+        (swap_update_one.amount_b, swap_update_one.amount_a)
+    };
+    let output_amount = if a_to_b_two {
+        swap_update_two.amount_b
+    } else {
+        swap_update_two.amount_a
+    };
+
+    // The intermediate token is transferred once from vault to vault,
+    // so its transfer fee is reported on both hops.
+    let input_transfer_fee =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_input, input_amount)?
+            .transfer_fee;
+    let intermediate_transfer_fee = calculate_transfer_fee_excluded_amount(
+        &ctx.accounts.token_mint_intermediate,
+        intermediate_amount,
+    )?
+    .transfer_fee;
+    let output_transfer_fee =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_output, output_amount)?
+            .transfer_fee;
+
+    let swap_results = vec![
+        SwapResult::new(
+            &swap_update_one,
+            a_to_b_one,
+            input_transfer_fee,
+            intermediate_transfer_fee,
+        ),
+        SwapResult::new(
+            &swap_update_two,
+            a_to_b_two,
+            intermediate_transfer_fee,
+            output_transfer_fee,
+        ),
+    ];
+
     /*
     update_and_swap_whirlpool_v2(
         whirlpool_one,
@@ -386,6 +510,10 @@ pub fn handler<'info>(
         swap_update_two,
         whirlpool_one,
         whirlpool_two,
+        &ctx.accounts.whirlpool_rewards_one,
+        &ctx.accounts.whirlpool_rewards_two,
+        &oracle_accessor_one,
+        &oracle_accessor_two,
         a_to_b_one,
         a_to_b_two,
         &ctx.accounts.token_mint_input,
@@ -407,5 +535,33 @@ pub fn handler<'info>(
         &ctx.accounts.memo_program,
         timestamp,
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
-    )
+    )?;
+
+    emit_cpi!(Traded {
+        whirlpool: ctx.accounts.whirlpool_one.key(),
+        a_to_b: a_to_b_one,
+        pre_sqrt_price: pre_sqrt_price_one,
+        post_sqrt_price: ctx.accounts.whirlpool_one.sqrt_price,
+        pre_tick_index: pre_tick_index_one,
+        post_tick_index: ctx.accounts.whirlpool_one.tick_current_index,
+        input_amount,
+        output_amount: intermediate_amount,
+        input_transfer_fee,
+        output_transfer_fee: intermediate_transfer_fee,
+    });
+
+    emit_cpi!(Traded {
+        whirlpool: ctx.accounts.whirlpool_two.key(),
+        a_to_b: a_to_b_two,
+        pre_sqrt_price: pre_sqrt_price_two,
+        post_sqrt_price: ctx.accounts.whirlpool_two.sqrt_price,
+        pre_tick_index: pre_tick_index_two,
+        post_tick_index: ctx.accounts.whirlpool_two.tick_current_index,
+        input_amount: intermediate_amount,
+        output_amount,
+        input_transfer_fee: intermediate_transfer_fee,
+        output_transfer_fee,
+    });
+
+    set_return_data(&swap_results)
 }
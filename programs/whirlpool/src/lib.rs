@@ -6,12 +6,14 @@ declare_id!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
 pub mod constants;
 #[doc(hidden)]
 pub mod errors;
+pub mod events;
 #[doc(hidden)]
 pub mod instructions;
 #[doc(hidden)]
 pub mod manager;
 #[doc(hidden)]
 pub mod math;
+pub mod return_data;
 #[doc(hidden)]
 pub mod security;
 pub mod state;
@@ -20,7 +22,10 @@ pub mod tests;
 #[doc(hidden)]
 pub mod util;
 
-use crate::state::{OpenPositionBumps, OpenPositionWithMetadataBumps, WhirlpoolBumps};
+use crate::state::{
+    AdaptiveFeeConstants, LockType, OpenPositionBumps, OpenPositionWithMetadataBumps,
+    WhirlpoolBumps,
+};
 use crate::util::RemainingAccountsInfo;
 use instructions::*;
 
@@ -73,6 +78,7 @@ pub mod whirlpool {
     }
 
     /// Initializes a tick_array account to represent a tick-range in a Whirlpool.
+    /// The funder is recorded as the payer of the rent in the TickArrayRentPayers PDA of the tick array.
     ///
     /// ### Parameters
     /// - `start_tick_index` - The starting tick index for this tick-array.
@@ -109,6 +115,8 @@ pub mod whirlpool {
     }
 
     /// Initialize reward for a Whirlpool. A pool can only support up to a set number of rewards.
+    /// The WhirlpoolRewards PDA of the Whirlpool, which holds the reward schedules tracking the
+    /// amounts owed to the positions, is created along with the first reward.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -137,7 +145,11 @@ pub mod whirlpool {
     ///
     /// #### Special Errors
     /// - `RewardVaultAmountInsufficient` - The amount of rewards in the reward vault cannot emit
-    ///                                     more than a day of desired emissions.
+    ///                                     more than a day of desired emissions, or an increased
+    ///                                     rate cannot emit a day of desired emissions from the
+    ///                                     rewards not owed to the positions yet.
+    /// - `RewardScheduleNotInitialized` - If the rate is increased and the Whirlpool has no reward
+    ///                                    schedules to track the rewards owed to the positions.
     /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
     /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
     ///                          index in this pool, or exceeds NUM_REWARDS, or
@@ -195,6 +207,7 @@ pub mod whirlpool {
     }
 
     /// Add liquidity to a position in the Whirlpool. This call also updates the position's accrued fees and rewards.
+    /// A price observation is recorded in the Oracle of the Whirlpool, if initialized, with the liquidity before the change.
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
@@ -204,12 +217,18 @@ pub mod whirlpool {
     /// - `token_max_a` - The maximum amount of tokenA the user is willing to deposit.
     /// - `token_max_b` - The maximum amount of tokenB the user is willing to deposit.
     ///
+    /// ### Return Data
+    /// - `ModifyLiquidityResult` - Borsh-encoded in the return data: the liquidity delta and the token amounts.
+    ///
     /// #### Special Errors
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
-    pub fn increase_liquidity(
-        ctx: Context<ModifyLiquidity>,
+    /// - `TickArrayRentNotFunded` - A DynamicTickArray grows while the position authority is not writable
+    ///                              or the System program is not in the remaining accounts.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    pub fn increase_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
         liquidity_amount: u128,
         token_max_a: u64,
         token_max_b: u64,
@@ -218,6 +237,9 @@ pub mod whirlpool {
     }
 
     /// Withdraw liquidity from a position in the Whirlpool. This call also updates the position's accrued fees and rewards.
+    /// The rent paid for a tick uninitialized in a DynamicTickArray is refunded to its recorded payer, which must be
+    /// writable and be the position authority or passed in the remaining accounts.
+    /// A price observation is recorded in the Oracle of the Whirlpool, if initialized, with the liquidity before the change.
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
@@ -227,12 +249,18 @@ pub mod whirlpool {
     /// - `token_min_a` - The minimum amount of tokenA the user is willing to withdraw.
     /// - `token_min_b` - The minimum amount of tokenB the user is willing to withdraw.
     ///
+    /// ### Return Data
+    /// - `ModifyLiquidityResult` - Borsh-encoded in the return data: the liquidity delta and the token amounts.
+    ///
     /// #### Special Errors
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
-    pub fn decrease_liquidity(
-        ctx: Context<ModifyLiquidity>,
+    /// - `PositionLocked` - The position is locked by lock_position.
+    /// - `TickArrayRentPayerMissing` - The recorded payer of an uninitialized DynamicTickArray tick is not passed
+    ///                                 as a writable account.
+    pub fn decrease_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
         liquidity_amount: u128,
         token_min_a: u64,
         token_min_b: u64,
@@ -258,6 +286,8 @@ pub mod whirlpool {
     }
 
     /// Collect rewards accrued for this position.
+    /// The collected amount is recorded against the reward of the Whirlpool, if the Whirlpool has
+    /// reward schedules.
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
@@ -285,6 +315,9 @@ pub mod whirlpool {
     /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
     /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
     ///
+    /// ### Return Data
+    /// - `SwapResult` - Borsh-encoded in the return data: the amounts in and out, the fees and the price after the swap.
+    ///
     /// #### Special Errors
     /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
@@ -294,6 +327,7 @@ pub mod whirlpool {
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
     pub fn swap(
         ctx: Context<Swap>,
         amount: u64,
@@ -475,6 +509,9 @@ pub mod whirlpool {
     /// - `sqrt_price_limit_one` - The maximum/minimum price the swap will swap to in the first hop.
     /// - `sqrt_price_limit_two` - The maximum/minimum price the swap will swap to in the second hop.
     ///
+    /// ### Return Data
+    /// - `Vec<SwapResult>` - Borsh-encoded in the return data: one SwapResult per hop.
+    ///
     /// #### Special Errors
     /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
@@ -486,6 +523,7 @@ pub mod whirlpool {
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if whirlpool one & two are the same pool.
+    /// - `WhirlpoolPaused` - Either whirlpool is paused by the config extension authority.
     #[allow(clippy::too_many_arguments)]
     pub fn two_hop_swap(
         ctx: Context<TwoHopSwap>,
@@ -535,6 +573,31 @@ pub mod whirlpool {
         instructions::delete_position_bundle::handler(ctx)
     }
 
+    /// Initializes a PositionBundle account that bundles several positions.
+    /// A unique token will be minted to represent the position bundle in the users wallet.
+    /// The Mint and TokenAccount are owned by Token-2022 program, and the metadata is
+    /// stored in the Mint account with TokenMetadata extension.
+    pub fn initialize_position_bundle_with_token_extensions(
+        ctx: Context<InitializePositionBundleWithTokenExtensions>,
+    ) -> Result<()> {
+        instructions::initialize_position_bundle_with_token_extensions::handler(ctx)
+    }
+
+    /// Delete a PositionBundle account initialized with Token-2022 program.
+    /// Burns the position bundle token in the owner's wallet and closes
+    /// the TokenAccount and the Mint account.
+    ///
+    /// ### Authority
+    /// - `position_bundle_owner` - The owner that owns the position bundle token.
+    ///
+    /// ### Special Errors
+    /// - `PositionBundleNotDeletable` - The provided position bundle has open positions.
+    pub fn delete_position_bundle_with_token_extensions(
+        ctx: Context<DeletePositionBundleWithTokenExtensions>,
+    ) -> Result<()> {
+        instructions::delete_position_bundle_with_token_extensions::handler(ctx)
+    }
+
     /// Open a bundled position in a Whirlpool. No new tokens are issued
     /// because the owner of the position bundle becomes the owner of the position.
     /// The position will start off with 0 liquidity.
@@ -618,6 +681,7 @@ pub mod whirlpool {
     ///
     /// #### Special Errors
     /// - `ClosePositionNotEmpty` - The provided position account is not empty.
+    /// - `PositionLocked` - The position is locked by lock_position.
     pub fn close_position_with_token_extensions(
         ctx: Context<ClosePositionWithTokenExtensions>,
     ) -> Result<()> {
@@ -653,6 +717,8 @@ pub mod whirlpool {
     }
 
     /// Collect rewards accrued for this position.
+    /// The collected amount is recorded against the reward of the Whirlpool, if the Whirlpool has
+    /// reward schedules.
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
@@ -665,6 +731,9 @@ pub mod whirlpool {
     }
 
     /// Withdraw liquidity from a position in the Whirlpool. This call also updates the position's accrued fees and rewards.
+    /// A price observation is recorded in the Oracle of the Whirlpool, if initialized, with the liquidity before the change.
+    /// The rent paid for a tick uninitialized in a DynamicTickArray is refunded to its recorded payer, which must be
+    /// writable and be the position authority or passed in the remaining accounts.
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
@@ -674,10 +743,16 @@ pub mod whirlpool {
     /// - `token_min_a` - The minimum amount of tokenA the user is willing to withdraw.
     /// - `token_min_b` - The minimum amount of tokenB the user is willing to withdraw.
     ///
+    /// ### Return Data
+    /// - `ModifyLiquidityResult` - Borsh-encoded in the return data: the liquidity delta and the token amounts.
+    ///
     /// #### Special Errors
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
+    /// - `PositionLocked` - The position is locked by lock_position.
+    /// - `TickArrayRentPayerMissing` - The recorded payer of an uninitialized DynamicTickArray tick is not passed
+    ///                                 as a writable account.
     pub fn decrease_liquidity_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
@@ -690,11 +765,57 @@ pub mod whirlpool {
             liquidity_amount,
             token_min_a,
             token_min_b,
+            None, // no price range
+            None,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Withdraw liquidity from a position in the Whirlpool, as decrease_liquidity_v2, only if the
+    /// sqrt price of the Whirlpool is within the given range. The range protects the withdrawal from
+    /// price manipulation within the position range, which token_min_a and token_min_b alone do not bound.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity the user desires to withdraw.
+    /// - `token_min_a` - The minimum amount of tokenA the user is willing to withdraw.
+    /// - `token_min_b` - The minimum amount of tokenB the user is willing to withdraw.
+    /// - `sqrt_price_min` - The minimum sqrt price of the Whirlpool the user accepts, if any.
+    /// - `sqrt_price_max` - The maximum sqrt price of the Whirlpool the user accepts, if any.
+    ///
+    /// ### Return Data
+    /// - `ModifyLiquidityResult` - Borsh-encoded in the return data: the liquidity delta and the token amounts.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
+    /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
+    /// - `PositionLocked` - The position is locked by lock_position.
+    /// - `SqrtPriceOutOfRange` - The sqrt price of the Whirlpool is out of the range specified by the user.
+    pub fn decrease_liquidity_v2_with_price_range<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+        sqrt_price_min: Option<u128>,
+        sqrt_price_max: Option<u128>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::decrease_liquidity_with_price_range::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+            sqrt_price_min,
+            sqrt_price_max,
             remaining_accounts_info,
         )
     }
 
     /// Add liquidity to a position in the Whirlpool. This call also updates the position's accrued fees and rewards.
+    /// A price observation is recorded in the Oracle of the Whirlpool, if initialized, with the liquidity before the change.
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
@@ -704,10 +825,16 @@ pub mod whirlpool {
     /// - `token_max_a` - The maximum amount of tokenA the user is willing to deposit.
     /// - `token_max_b` - The maximum amount of tokenB the user is willing to deposit.
     ///
+    /// ### Return Data
+    /// - `ModifyLiquidityResult` - Borsh-encoded in the return data: the liquidity delta and the token amounts.
+    ///
     /// #### Special Errors
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    /// - `TickArrayRentNotFunded` - A DynamicTickArray grows while the position authority is not writable
+    ///                              or the System program is not in the remaining accounts.
     pub fn increase_liquidity_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
@@ -720,6 +847,53 @@ pub mod whirlpool {
             liquidity_amount,
             token_max_a,
             token_max_b,
+            None, // no price range
+            None,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Add liquidity to a position in the Whirlpool, as increase_liquidity_v2, only if the
+    /// sqrt price of the Whirlpool is within the given range. The range protects the deposit from
+    /// price manipulation within the position range, which token_max_a and token_max_b alone do not bound.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity the user is willing to deposit.
+    /// - `token_max_a` - The maximum amount of tokenA the user is willing to deposit.
+    /// - `token_max_b` - The maximum amount of tokenB the user is willing to deposit.
+    /// - `sqrt_price_min` - The minimum sqrt price of the Whirlpool the user accepts, if any.
+    /// - `sqrt_price_max` - The maximum sqrt price of the Whirlpool the user accepts, if any.
+    ///
+    /// ### Return Data
+    /// - `ModifyLiquidityResult` - Borsh-encoded in the return data: the liquidity delta and the token amounts.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
+    /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    /// - `SqrtPriceOutOfRange` - The sqrt price of the Whirlpool is out of the range specified by the user.
+    /// - `TickArrayRentNotFunded` - A DynamicTickArray grows while the position authority is not writable
+    ///                              or the System program is not in the remaining accounts.
+    pub fn increase_liquidity_v2_with_price_range<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+        sqrt_price_min: Option<u128>,
+        sqrt_price_max: Option<u128>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::increase_liquidity_with_price_range::handler(
+            ctx,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+            sqrt_price_min,
+            sqrt_price_max,
             remaining_accounts_info,
         )
     }
@@ -745,6 +919,8 @@ pub mod whirlpool {
     }
 
     /// Initialize reward for a Whirlpool. A pool can only support up to a set number of rewards.
+    /// The WhirlpoolRewards PDA of the Whirlpool, which holds the reward schedules tracking the
+    /// amounts owed to the positions, is created along with the first reward.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -773,7 +949,11 @@ pub mod whirlpool {
     ///
     /// #### Special Errors
     /// - `RewardVaultAmountInsufficient` - The amount of rewards in the reward vault cannot emit
-    ///                                     more than a day of desired emissions.
+    ///                                     more than a day of desired emissions, or an increased
+    ///                                     rate cannot emit a day of desired emissions from the
+    ///                                     rewards not owed to the positions yet.
+    /// - `RewardScheduleNotInitialized` - If the rate is increased and the Whirlpool has no reward
+    ///                                    schedules to track the rewards owed to the positions.
     /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
     /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
     ///                          index in this pool, or exceeds NUM_REWARDS, or
@@ -786,6 +966,69 @@ pub mod whirlpool {
         instructions::v2::set_reward_emissions::handler(ctx, reward_index, emissions_per_second_x64)
     }
 
+    /// Initializes the reward schedules of a Whirlpool, which are stored in the WhirlpoolRewards
+    /// PDA of the Whirlpool along with the ledgers of the amounts owed to the positions.
+    /// The WhirlpoolRewards PDA is created if the Whirlpool does not have one yet.
+    ///
+    /// The ledgers are seeded with the amounts held by the reward vaults, all of which are
+    /// considered owed to the positions. The budgets of the schedules must be deposited afterwards.
+    ///
+    /// ### Authority
+    /// - "reward_emissions_super_authority" - Set authority that can initialize the reward schedules.
+    ///
+    /// ### Remaining Accounts
+    /// - The reward vaults of the initialized rewards, in the order of the reward indexes.
+    ///
+    /// #### Special Errors
+    /// - `RewardScheduleAlreadyInitialized` - If the Whirlpool already has reward schedules.
+    /// - `RemainingAccountsInsufficient` - If the vault of an initialized reward is missing.
+    pub fn initialize_reward_schedule<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeRewardSchedule<'info>>,
+    ) -> Result<()> {
+        instructions::v2::initialize_reward_schedule::handler(ctx)
+    }
+
+    /// Set an emission schedule for a reward in a Whirlpool. The budget is emitted evenly between
+    /// the start and end timestamps, and the reward stops accruing once the schedule ends or the
+    /// budget is exhausted. Setting constant emissions with set_reward_emissions or
+    /// set_reward_emissions_v2 removes the schedule of the reward.
+    ///
+    /// The remaining budget is spread over the remaining seconds of the schedule, so the budget
+    /// which is not emitted while the Whirlpool has no liquidity is emitted later on.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
+    ///                        reward-index in this Whirlpool
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index <= NUM_REWARDS) that we'd like to modify.
+    /// - `start_timestamp` - The timestamp the emissions start at. Past timestamps start the
+    ///                       emissions immediately.
+    /// - `end_timestamp` - The timestamp the emissions end at.
+    /// - `emissions_budget` - The total amount of rewards emitted over the schedule.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardSchedule` - The schedule does not end after it starts.
+    /// - `RewardScheduleNotInitialized` - If the Whirlpool has no reward schedules.
+    /// - `RewardVaultAmountInsufficient` - The amount of rewards in the reward vault which are not
+    ///                                     owed to the positions is less than the emissions budget.
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    pub fn set_reward_emissions_schedule(
+        ctx: Context<SetRewardEmissionsSchedule>,
+        reward_index: u8,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        emissions_budget: u64,
+    ) -> Result<()> {
+        instructions::v2::set_reward_emissions_schedule::handler(
+            ctx,
+            reward_index,
+            start_timestamp,
+            end_timestamp,
+            emissions_budget,
+        )
+    }
+
     /// Perform a swap in this Whirlpool
     ///
     /// ### Authority
@@ -798,6 +1041,9 @@ pub mod whirlpool {
     /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
     /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
     ///
+    /// ### Return Data
+    /// - `SwapResult` - Borsh-encoded in the return data: the amounts in and out, the fees and the price after the swap.
+    ///
     /// #### Special Errors
     /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
@@ -807,8 +1053,10 @@ pub mod whirlpool {
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
-    pub fn swap_v2<'info>(
-        ctx: Context<'_, '_, '_, 'info, SwapV2<'info>>,
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_v2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapV2<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         sqrt_price_limit: u128,
@@ -823,6 +1071,64 @@ pub mod whirlpool {
             sqrt_price_limit,
             amount_specified_is_input,
             a_to_b,
+            0, // no referral fee
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a swap in this Whirlpool, as swap_v2, and charge a referral fee for the integrator.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `referral_fee_rate` - The referral fee charged for the integrator in basis points, zero for no referral fee.
+    ///                         The fee is sent to the referral fee account passed in the remaining accounts (ReferralFee
+    ///                         slice), on top of
+    ///                         the input amount if the account holds the input token, or out of the output amount if
+    ///                         it holds the output token. other_amount_threshold applies to the amounts including the fee.
+    ///
+    /// ### Return Data
+    /// - `SwapResult` - Borsh-encoded in the return data: the amounts in and out, the fees and the price after the swap.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over Whirlppool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
+    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
+    /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
+    /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    /// - `ReferralFeeRateMaxExceeded` - referral_fee_rate exceeds the cap set in the WhirlpoolsConfigExtension,
+    ///                                  or the WhirlpoolsConfig has no WhirlpoolsConfigExtension.
+    /// - `InvalidReferralFeeAccount` - The referral fee account is missing, holds neither token of the swap,
+    ///                                 or holds the output token of an exact output swap.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_v2_with_referral_fee<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        referral_fee_rate: u16,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::swap_with_referral_fee::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            referral_fee_rate,
             remaining_accounts_info,
         )
     }
@@ -841,6 +1147,9 @@ pub mod whirlpool {
     /// - `sqrt_price_limit_one` - The maximum/minimum price the swap will swap to in the first hop.
     /// - `sqrt_price_limit_two` - The maximum/minimum price the swap will swap to in the second hop.
     ///
+    /// ### Return Data
+    /// - `Vec<SwapResult>` - Borsh-encoded in the return data: one SwapResult per hop.
+    ///
     /// #### Special Errors
     /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
@@ -852,6 +1161,7 @@ pub mod whirlpool {
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if whirlpool one & two are the same pool.
+    /// - `WhirlpoolPaused` - Either whirlpool is paused by the config extension authority.
     #[allow(clippy::too_many_arguments)]
     pub fn two_hop_swap_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, TwoHopSwapV2<'info>>,
@@ -896,4 +1206,809 @@ pub mod whirlpool {
     pub fn delete_token_badge(ctx: Context<DeleteTokenBadge>) -> Result<()> {
         instructions::v2::delete_token_badge::handler(ctx)
     }
+
+    /// Set the transfer hook program and permanent delegate allowed for a token mint with a TokenBadge.
+    /// A default Pubkey leaves the corresponding extension unrestricted.
+    ///
+    /// The allowlist is checked by the v2 instructions transferring tokens of the mint. The TokenBadges
+    /// slice of the remaining accounts must hold the TokenBadge of every mint with a transfer hook or
+    /// a permanent delegate, and the instruction is rejected if it is missing or if the transfer hook
+    /// program or the permanent delegate of a mint is not allowed by its TokenBadge.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `transfer_hook_program_id` - The transfer hook program allowed for the token mint.
+    /// - `permanent_delegate` - The permanent delegate allowed for the token mint.
+    pub fn set_token_badge_allowlist(
+        ctx: Context<SetTokenBadgeAllowlist>,
+        transfer_hook_program_id: Pubkey,
+        permanent_delegate: Pubkey,
+    ) -> Result<()> {
+        instructions::v2::set_token_badge_allowlist::handler(
+            ctx,
+            transfer_hook_program_id,
+            permanent_delegate,
+        )
+    }
+
+    /// Pause or unpause all of the Whirlpools in a WhirlpoolsConfig.
+    /// While paused, the instructions which move the price or add liquidity are rejected: the swaps
+    /// (v1 and v2, two_hop, multi_hop and flash_swap), increase_liquidity (v1 and v2), compound_fees
+    /// and open_limit_order. Withdrawing liquidity and collecting fees and rewards stay open.
+    ///
+    /// These instructions take the WhirlpoolsConfigExtension PDA and the WhirlpoolPause PDA of each
+    /// Whirlpool as required accounts, which are not initialized if the Whirlpool was never paused.
+    ///
+    /// ### Authority
+    /// - "config_extension_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `paused` - Whether the Whirlpools of the WhirlpoolsConfig are paused.
+    pub fn set_config_paused(ctx: Context<SetConfigPaused>, paused: bool) -> Result<()> {
+        instructions::v2::set_config_paused::handler(ctx, paused)
+    }
+
+    /// Pause or unpause a single Whirlpool, with the same effect as set_config_paused.
+    /// The flag is stored in the WhirlpoolPause PDA of the Whirlpool, which the funder pays for
+    /// when the Whirlpool is paused for the first time.
+    ///
+    /// ### Authority
+    /// - "config_extension_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `paused` - Whether the Whirlpool is paused.
+    pub fn set_whirlpool_paused(ctx: Context<SetWhirlpoolPaused>, paused: bool) -> Result<()> {
+        instructions::v2::set_whirlpool_paused::handler(ctx, paused)
+    }
+
+    /// Set the cap of the referral fee rate integrators can charge on swap_v2_with_referral_fee in a WhirlpoolsConfig.
+    ///
+    /// ### Authority
+    /// - "config_extension_authority" - Set authority in the WhirlpoolsConfigExtension
+    ///
+    /// ### Parameters
+    /// - `max_referral_fee_rate` - The cap of the referral fee rate, stored as basis points.
+    ///
+    /// #### Special Errors
+    /// - `ReferralFeeRateMaxExceeded` - If the provided max_referral_fee_rate exceeds MAX_REFERRAL_FEE_RATE.
+    pub fn set_max_referral_fee_rate(
+        ctx: Context<SetMaxReferralFeeRate>,
+        max_referral_fee_rate: u16,
+    ) -> Result<()> {
+        instructions::v2::set_max_referral_fee_rate::handler(ctx, max_referral_fee_rate)
+    }
+
+    /// Initializes an adaptive_fee_tier account usable by Whirlpools in a WhirlpoolConfig space.
+    /// Pools initialized with an adaptive fee tier charge a variable fee on top of the base fee
+    /// rate, depending on how far the price has recently moved.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the WhirlpoolConfig
+    ///
+    /// ### Parameters
+    /// - `fee_tier_index` - The index of this fee tier. Used as a seed of the Whirlpool address
+    ///                      and must not be equal to `tick_spacing`.
+    /// - `tick_spacing` - The tick-spacing of the pools using this fee-tier.
+    /// - `default_base_fee_rate` - The base fee rate that a pool will use if the pool uses this
+    ///                             fee tier during initialization.
+    /// - `adaptive_fee_constants` - The parameters of the variable fee.
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeTierIndex` - If the provided fee_tier_index is equal to tick_spacing.
+    /// - `FeeRateMaxExceeded` - If the provided default_base_fee_rate exceeds MAX_FEE_RATE.
+    /// - `InvalidAdaptiveFeeConstants` - If the provided adaptive_fee_constants are invalid.
+    pub fn initialize_adaptive_fee_tier(
+        ctx: Context<InitializeAdaptiveFeeTier>,
+        fee_tier_index: u16,
+        tick_spacing: u16,
+        default_base_fee_rate: u16,
+        adaptive_fee_constants: AdaptiveFeeConstants,
+    ) -> Result<()> {
+        instructions::initialize_adaptive_fee_tier::handler(
+            ctx,
+            fee_tier_index,
+            tick_spacing,
+            default_base_fee_rate,
+            adaptive_fee_constants,
+        )
+    }
+
+    /// Initializes a Whirlpool account and its Oracle account with an adaptive fee tier.
+    /// Fee rate is set to the default base fee rate of the supplied adaptive_fee_tier.
+    ///
+    /// ### Parameters
+    /// - `initial_sqrt_price` - The desired initial sqrt-price for this pool
+    ///
+    /// #### Special Errors
+    /// `InvalidTokenMintOrder` - The order of mints have to be ordered by
+    /// `SqrtPriceOutOfBounds` - provided initial_sqrt_price is not between 2^-64 to 2^64
+    ///
+    pub fn initialize_pool_with_adaptive_fee(
+        ctx: Context<InitializePoolWithAdaptiveFee>,
+        initial_sqrt_price: u128,
+    ) -> Result<()> {
+        instructions::initialize_pool_with_adaptive_fee::handler(ctx, initial_sqrt_price)
+    }
+
+    /// Initializes the Oracle account of a Whirlpool to record price observations.
+    /// Pools initialized with an adaptive fee tier already have an Oracle account.
+    ///
+    /// Observations of the tick and seconds-per-liquidity cumulative values are recorded on
+    /// every swap, so that time-weighted average prices can be computed from the Oracle.
+    /// The Oracle starts with a capacity of a single observation.
+    pub fn initialize_oracle(ctx: Context<InitializeOracle>) -> Result<()> {
+        instructions::initialize_oracle::handler(ctx)
+    }
+
+    /// Increases the number of observations the Oracle account of a Whirlpool can store.
+    /// The funder pays the rent for the additional space.
+    ///
+    /// ### Parameters
+    /// - `observation_capacity` - The new number of observations the Oracle can store.
+    ///
+    /// #### Special Errors
+    /// - `InvalidObservationCapacity` - If the provided capacity is not larger than the current one.
+    pub fn increase_oracle_capacity(
+        ctx: Context<IncreaseOracleCapacity>,
+        observation_capacity: u16,
+    ) -> Result<()> {
+        instructions::increase_oracle_capacity::handler(ctx, observation_capacity)
+    }
+
+    /// Opens a limit order at a single tick of a Whirlpool.
+    /// The order is filled at the price of the tick when a swap reaches the tick,
+    /// and the filled amount is never converted back when the price reverses.
+    /// Limit orders are only supported on fixed TickArrays of pools whose mints are owned by the Token program.
+    ///
+    /// ### Parameters
+    /// - `tick_index` - The tick index the order rests at.
+    /// - `a_to_b` - If true, the order sells token A for token B. Otherwise it sells token B for token A.
+    /// - `amount` - The amount of the input token to sell.
+    ///
+    /// #### Special Errors
+    /// - `InvalidLimitOrderTickIndex` - If an order selling token A is not above the current tick,
+    ///                                  or an order selling token B is above the current tick.
+    /// - `ZeroLimitOrderAmount` - If the provided amount is zero.
+    /// - `LimitOrderTickArrayMismatch` - If the tick is not in the provided tick array.
+    /// - `LimitOrderNotSupported` - If the tick array is a DynamicTickArray or a mint of the Whirlpool is
+    ///                              owned by the Token-2022 program.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    pub fn open_limit_order(
+        ctx: Context<OpenLimitOrder>,
+        tick_index: i32,
+        a_to_b: bool,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::open_limit_order::handler(ctx, tick_index, a_to_b, amount)
+    }
+
+    /// Collects the output token of the filled portion of a limit order.
+    ///
+    /// #### Special Errors
+    /// - `LimitOrderTickArrayMismatch` - If the tick of the order is not in the provided tick array.
+    pub fn collect_limit_order(ctx: Context<CollectLimitOrder>) -> Result<()> {
+        instructions::collect_limit_order::handler(ctx)
+    }
+
+    /// Cancels a limit order and closes its account.
+    /// The unfilled amount is returned and the output of the filled amount is collected.
+    ///
+    /// #### Special Errors
+    /// - `LimitOrderTickArrayMismatch` - If the tick of the order is not in the provided tick array.
+    pub fn cancel_limit_order(ctx: Context<CancelLimitOrder>) -> Result<()> {
+        instructions::cancel_limit_order::handler(ctx)
+    }
+
+    /// Closes an empty TickArray or DynamicTickArray account and refunds the rent.
+    ///
+    /// The TickArrayRentPayers of a TickArray is closed with it. The rent of the LimitOrderBook and
+    /// TickArrayRewardExtension sections is refunded to their recorded payers, which must be passed
+    /// as remaining accounts. The remaining lamports are sent to the receiver.
+    ///
+    /// ### Authority
+    /// - "authority" - The payer who initialized the tick array, or the collect_protocol_fees_authority
+    ///                 for TickArrays initialized before the payer was recorded.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickArrayPayer` - If the signer is not the authority of the tick array.
+    /// - `CloseTickArrayNotEmpty` - If any tick in the tick array is initialized or any limit order is open.
+    /// - `RemainingAccountsInsufficient` - If the payer of a section is not in the remaining accounts.
+    pub fn close_tick_array(ctx: Context<CloseTickArray>) -> Result<()> {
+        instructions::close_tick_array::handler(ctx)
+    }
+
+    /// Initializes a dynamic_tick_array account to represent a tick-range in a Whirlpool.
+    /// Unlike tick_array, a dynamic_tick_array only allocates space for its initialized ticks.
+    /// It can be used wherever a tick_array is accepted and shares the same address.
+    ///
+    /// The account grows when a tick is initialized by increasing liquidity, and shrinks when
+    /// a tick is uninitialized. The rent for the additional space must be transferred to the
+    /// account before increasing liquidity.
+    ///
+    /// ### Parameters
+    /// - `start_tick_index` - The starting tick index for this tick-array.
+    ///                        Has to be a multiple of TickArray size & the tick spacing of this pool.
+    ///
+    /// #### Special Errors
+    /// - `InvalidStartTick` - if the provided start tick is out of bounds or is not a multiple of
+    ///                        TICK_ARRAY_SIZE * tick spacing.
+    pub fn initialize_dynamic_tick_array(
+        ctx: Context<InitializeDynamicTickArray>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        instructions::initialize_dynamic_tick_array::handler(ctx, start_tick_index)
+    }
+
+    /// Add the largest liquidity which fits in the given token amounts to a position in the Whirlpool.
+    /// The liquidity is computed on-chain at the current price, so the call does not fail when the price
+    /// moves between quoting and landing. This call also updates the position's accrued fees and rewards.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `token_max_a` - The maximum amount of tokenA the user is willing to deposit (transfer fee included).
+    /// - `token_max_b` - The maximum amount of tokenB the user is willing to deposit (transfer fee included).
+    /// - `liquidity_min` - The minimum amount of Liquidity the user is willing to accept.
+    /// - `sqrt_price_min` - The minimum sqrt price of the Whirlpool the user accepts, if any.
+    /// - `sqrt_price_max` - The maximum sqrt price of the Whirlpool the user accepts, if any.
+    ///
+    /// ### Return Data
+    /// - `ModifyLiquidityResult` - Borsh-encoded in the return data: the liquidity delta and the token amounts.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - The token amounts are too small to add any liquidity.
+    /// - `LiquidityTooHigh` - Computed liquidity exceeds u128::max.
+    /// - `LiquidityMinSubceeded` - The computed liquidity is less than liquidity_min.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    /// - `SqrtPriceOutOfRange` - The sqrt price of the Whirlpool is out of the range specified by the user.
+    pub fn increase_liquidity_by_token_amounts_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+        token_max_a: u64,
+        token_max_b: u64,
+        liquidity_min: u128,
+        sqrt_price_min: Option<u128>,
+        sqrt_price_max: Option<u128>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::increase_liquidity_by_token_amounts::handler(
+            ctx,
+            token_max_a,
+            token_max_b,
+            liquidity_min,
+            sqrt_price_min,
+            sqrt_price_max,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a multi-hop swap through a route of 2 to 4 Whirlpools.
+    /// The route is provided in the remaining accounts: one SwapHop slice per hop and
+    /// one IntermediateToken slice per intermediate token, both in the order of the route.
+    /// A SwapHop slice holds the whirlpool, its input and output vaults, its oracle, its
+    /// WhirlpoolRewards, WhirlpoolsConfigExtension and WhirlpoolPause PDAs and then its
+    /// tick arrays.
+    /// The direction of each hop is derived from the input token of the hop.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    ///                              It is applied once to the whole route.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    ///
+    /// ### Return Data
+    /// - `Vec<SwapResult>` - Borsh-encoded in the return data: one SwapResult per hop.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `InvalidMultiHopSwapRoute` - The number of hops or intermediate tokens is out of bounds, or a hop lacks accounts.
+    /// - `InvalidIntermediaryMint` - A whirlpool of the route does not trade the input and output token of its hop.
+    /// - `DuplicateMultiHopPool` - A whirlpool appears more than once in the route.
+    /// - `IntermediateTokenAmountMismatch` - The output of a hop is not fully consumed by the next hop.
+    /// - `WhirlpoolPaused` - A whirlpool of the route is paused by the config extension authority.
+    pub fn multi_hop_swap_v2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MultiHopSwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::multi_hop_swap::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Collect the fees and all initialized rewards accrued for this position in one instruction.
+    /// This call also updates the position's accrued fees and rewards before collecting them,
+    /// unless the position has no liquidity.
+    /// The Reward slices cover the initialized rewards, followed by the initialized extension rewards
+    /// if the position has a reward extension.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// #### Special Errors
+    /// - `RewardAccountsMismatch` - The number of Reward slices in remaining_accounts_info is not
+    ///                              the number of initialized rewards and extension rewards.
+    pub fn collect_all_v2<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CollectAllV2<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::collect_all::handler(ctx, remaining_accounts_info)
+    }
+
+    /// Add the fees owed to a position back to the position as liquidity in the same range.
+    /// The largest liquidity which fits in the fees owed at the current price is added, and the
+    /// remainder stays owed to the position. This call also updates the position's accrued fees and rewards.
+    /// A price observation is recorded in the Oracle of the Whirlpool, if initialized, with the liquidity before the change.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - The fees owed are too small to add any liquidity.
+    /// - `WhirlpoolPaused` - The whirlpool is paused by the config extension authority.
+    /// - `TickArrayRentNotFunded` - A DynamicTickArray grows while the position authority is not writable
+    ///                              or the System program is not in the remaining accounts.
+    pub fn compound_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompoundFees<'info>>,
+    ) -> Result<()> {
+        instructions::compound_fees::handler(ctx)
+    }
+
+    /// Move a Position with Mint and TokenAccount owned by Token program into a PositionBundle.
+    /// The bundled position keeps the liquidity, fee and reward checkpoints and amounts owed of the
+    /// position, and the position token is burned.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to the position.
+    /// - `position_bundle_authority` - authority that owns the token corresponding to the PositionBundle.
+    ///
+    /// ### Parameters
+    /// - `bundle_index` - The bundle index that the position is moved into.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
+    /// - `BundledPositionAlreadyOpened` - If the provided bundle index is already in use.
+    pub fn migrate_position_into_bundle(
+        ctx: Context<MigratePositionIntoBundle>,
+        bundle_index: u16,
+    ) -> Result<()> {
+        instructions::migrate_position_into_bundle::handler(ctx, bundle_index)
+    }
+
+    /// Move a Position with Mint and TokenAccount owned by Token-2022 into a PositionBundle.
+    /// The bundled position keeps the liquidity, fee and reward checkpoints and amounts owed of the
+    /// position, and the position token is burned and its Mint account is closed.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to the position.
+    /// - `position_bundle_authority` - authority that owns the token corresponding to the PositionBundle.
+    ///
+    /// ### Parameters
+    /// - `bundle_index` - The bundle index that the position is moved into.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
+    /// - `BundledPositionAlreadyOpened` - If the provided bundle index is already in use.
+    /// - `PositionLocked` - The position is locked by lock_position.
+    pub fn migrate_position_with_token_extensions_into_bundle(
+        ctx: Context<MigratePositionWithTokenExtensionsIntoBundle>,
+        bundle_index: u16,
+    ) -> Result<()> {
+        instructions::migrate_position_with_token_extensions_into_bundle::handler(ctx, bundle_index)
+    }
+
+    /// Move a bundled position out of its PositionBundle into a new Position with Mint and
+    /// TokenAccount owned by Token-2022. The new position keeps the liquidity, fee and reward
+    /// checkpoints and amounts owed of the bundled position.
+    ///
+    /// ### Authority
+    /// - `position_bundle_authority` - authority that owns the token corresponding to the PositionBundle.
+    ///
+    /// ### Parameters
+    /// - `bundle_index` - The bundle index of the bundled position.
+    /// - `with_token_metadata` - If true, the token metadata extension will be initialized.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
+    /// - `BundledPositionAlreadyClosed` - If the provided bundle index is not in use.
+    pub fn migrate_bundled_position_out_of_bundle(
+        ctx: Context<MigrateBundledPositionOutOfBundle>,
+        bundle_index: u16,
+        with_token_metadata: bool,
+    ) -> Result<()> {
+        instructions::migrate_bundled_position_out_of_bundle::handler(
+            ctx,
+            bundle_index,
+            with_token_metadata,
+        )
+    }
+
+    /// Initializes a reward extension for a Whirlpool, which adds NUM_EXTENSION_REWARDS rewards
+    /// on top of the NUM_REWARDS rewards of the Whirlpool. The extension is appended to the
+    /// WhirlpoolRewards PDA of the Whirlpool.
+    ///
+    /// Extension rewards accrue with the same math as the rewards of the Whirlpool. Their growths
+    /// outside of each tick are stored in a TickArrayRewardExtension appended to the TickArray.
+    /// The ticks of TickArrays without one (including DynamicTickArrays) are not tracked:
+    /// swaps cross them as usual, but only positions whose TickArrays both carry a
+    /// TickArrayRewardExtension can accrue the extension rewards.
+    ///
+    /// ### Authority
+    /// - "reward_emissions_super_authority" - Set authority that can initialize the reward extension.
+    ///                                        It becomes the authority of all extension rewards.
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionAlreadyInitialized` - If the Whirlpool already has a reward extension.
+    /// - `RewardScheduleNotInitialized` - If the Whirlpool has no reward schedules, which precede
+    ///                                    the extension in the WhirlpoolRewards PDA.
+    pub fn initialize_reward_extension(ctx: Context<InitializeRewardExtension>) -> Result<()> {
+        instructions::initialize_reward_extension::handler(ctx)
+    }
+
+    /// Set the authority of the extension reward at the provided `reward_index`.
+    /// Only the current authority of this extension reward has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - Set authority that can control emission for this extension reward.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The extension reward index (0 <= index < NUM_EXTENSION_REWARDS).
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionNotInitialized` - If the Whirlpool has no reward extension.
+    /// - `InvalidRewardIndex` - If the provided reward index exceeds NUM_EXTENSION_REWARDS.
+    pub fn set_extension_reward_authority(
+        ctx: Context<SetExtensionRewardAuthority>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::set_extension_reward_authority::handler(ctx, reward_index)
+    }
+
+    /// Appends a LimitOrderBook to a TickArray. Anyone can invoke this instruction and the funder
+    /// pays the rent, which is recorded in the TickArrayRentPayers of the TickArray.
+    ///
+    /// open_limit_order allocates the LimitOrderBook on the first order. This instruction allocates
+    /// it without an order, as required before initialize_tick_array_reward_extension.
+    ///
+    /// #### Special Errors
+    /// - `LimitOrderBookAlreadyInitialized` - If the TickArray already has a LimitOrderBook.
+    pub fn initialize_limit_order_book(ctx: Context<InitializeLimitOrderBook>) -> Result<()> {
+        instructions::initialize_limit_order_book::handler(ctx)
+    }
+
+    /// Appends a TickArrayRewardExtension to a TickArray to store the growths outside of its ticks
+    /// for the extension rewards. Anyone can invoke this instruction and the funder pays the rent.
+    ///
+    /// The extension is stored after the LimitOrderBook of the TickArray. As an account can only
+    /// grow by 10KiB in a single instruction, the LimitOrderBook must be allocated beforehand by
+    /// initialize_limit_order_book or open_limit_order.
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionAlreadyInitialized` - If the TickArray already has a TickArrayRewardExtension.
+    /// - `LimitOrderBookNotInitialized` - If the TickArray has no LimitOrderBook.
+    pub fn initialize_tick_array_reward_extension(
+        ctx: Context<InitializeTickArrayRewardExtension>,
+    ) -> Result<()> {
+        instructions::initialize_tick_array_reward_extension::handler(ctx)
+    }
+
+    /// Appends a PositionRewardExtension to a Position, so that it accrues the extension rewards
+    /// of the Whirlpool from now on. The funder pays the rent.
+    ///
+    /// A position with a reward extension cannot be migrated into or out of a PositionBundle,
+    /// and cannot be closed until all of its extension rewards are collected.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionNotInitialized` - If the Whirlpool has no reward extension.
+    /// - `RewardExtensionAlreadyInitialized` - If the Position already has a PositionRewardExtension.
+    /// - `RewardExtensionNotAllocated` - If a TickArray of the position has no TickArrayRewardExtension.
+    pub fn initialize_position_reward_extension(
+        ctx: Context<InitializePositionRewardExtension>,
+    ) -> Result<()> {
+        instructions::initialize_position_reward_extension::handler(ctx)
+    }
+
+    /// Initialize an extension reward for a Whirlpool with a reward extension.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority of the extension reward at the provided index.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The extension reward index (0 <= index < NUM_EXTENSION_REWARDS).
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionNotInitialized` - If the Whirlpool has no reward extension.
+    /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
+    ///                          extension reward, or exceeds NUM_EXTENSION_REWARDS.
+    pub fn initialize_extension_reward(
+        ctx: Context<InitializeExtensionReward>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::v2::initialize_extension_reward::handler(ctx, reward_index)
+    }
+
+    /// Set the extension reward emissions for a Whirlpool with a reward extension.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority of the extension reward at the provided index.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The extension reward index (0 <= index < NUM_EXTENSION_REWARDS).
+    /// - `emissions_per_second_x64` - The amount of rewards emitted in this pool.
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionNotInitialized` - If the Whirlpool has no reward extension.
+    /// - `RewardVaultAmountInsufficient` - The amount of rewards in the reward vault cannot emit
+    ///                                     more than a day of desired emissions.
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    /// - `InvalidRewardIndex` - If the provided reward index exceeds NUM_EXTENSION_REWARDS.
+    pub fn set_extension_reward_emissions(
+        ctx: Context<SetExtensionRewardEmissions>,
+        reward_index: u8,
+        emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        instructions::v2::set_extension_reward_emissions::handler(
+            ctx,
+            reward_index,
+            emissions_per_second_x64,
+        )
+    }
+
+    /// Set an emission schedule for an extension reward, as set_reward_emissions_schedule does for
+    /// the rewards of the Whirlpool. Setting constant emissions with set_extension_reward_emissions
+    /// removes the schedule of the extension reward.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority of the extension reward at the provided index.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The extension reward index (0 <= index < NUM_EXTENSION_REWARDS).
+    /// - `start_timestamp` - The timestamp the emissions start at. Past timestamps start the
+    ///                       emissions immediately.
+    /// - `end_timestamp` - The timestamp the emissions end at.
+    /// - `emissions_budget` - The total amount of rewards emitted over the schedule.
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionNotInitialized` - If the Whirlpool has no reward extension.
+    /// - `InvalidRewardSchedule` - The schedule does not end after it starts.
+    /// - `RewardVaultAmountInsufficient` - The amount of rewards in the reward vault which are not
+    ///                                     owed to the positions is less than the emissions budget.
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    /// - `InvalidRewardIndex` - If the provided reward index exceeds NUM_EXTENSION_REWARDS.
+    pub fn set_extension_reward_emissions_schedule(
+        ctx: Context<SetExtensionRewardEmissionsSchedule>,
+        reward_index: u8,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        emissions_budget: u64,
+    ) -> Result<()> {
+        instructions::v2::set_extension_reward_emissions_schedule::handler(
+            ctx,
+            reward_index,
+            start_timestamp,
+            end_timestamp,
+            emissions_budget,
+        )
+    }
+
+    /// Collect extension rewards accrued for this position.
+    /// The extension rewards of a position are accrued by update_fees_and_rewards and by every
+    /// liquidity change of the position. The collected amount is recorded against the extension
+    /// reward in the WhirlpoolRewards PDA of the Whirlpool.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The extension reward index (0 <= index < NUM_EXTENSION_REWARDS).
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionNotInitialized` - If the Whirlpool has no reward extension.
+    /// - `RewardExtensionNotAllocated` - If the Position has no PositionRewardExtension.
+    /// - `InvalidRewardIndex` - If the extension reward at the provided index is not initialized.
+    pub fn collect_extension_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectExtensionReward<'info>>,
+        reward_index: u8,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::collect_extension_reward::handler(
+            ctx,
+            reward_index,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a flash swap in this Whirlpool: the output tokens are sent before the input tokens
+    /// are received, within the same instruction.
+    /// After sending the output tokens to the destination account, the callback program is invoked
+    /// with `callback_data` followed by the input and output amounts (transfer fee included, as
+    /// little-endian u64) and with the FlashSwapCallback accounts of the remaining accounts.
+    /// The callback must transfer the input amount to the input vault before returning.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `callback_data` - The instruction data passed to the callback program, before the amounts.
+    ///
+    /// ### Return Data
+    /// - `SwapResult` - Borsh-encoded in the return data: the amounts in and out, the fees and the price after the swap.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over Whirlppool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `WhirlpoolPaused` - The Whirlpool is paused by the config extension authority.
+    /// - `InvalidFlashSwapCallbackProgram` - The callback program is the Whirlpool program.
+    /// - `FlashSwapNotRepaid` - The input vault did not receive the input amount by the end of the callback.
+    #[allow(clippy::too_many_arguments)]
+    pub fn flash_swap<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FlashSwap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        callback_data: Vec<u8>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::flash_swap::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            callback_data,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Lock a position opened by open_position_with_token_extensions, so that its liquidity
+    /// cannot be withdrawn and the position cannot be closed or transferred until the lock expires.
+    /// The position token account is frozen while locked. Fees and rewards can still be collected,
+    /// and liquidity can still be added.
+    ///
+    /// ### Authority
+    /// - `position_authority` - owner of the token corresponding to this desired position.
+    ///   A delegate of the token cannot lock the position.
+    /// - `funder` - pays the rent of the LockConfig, and is refunded on unlock.
+    ///
+    /// ### Parameters
+    /// - `lock_type` - Permanent, or until the provided unlock timestamp.
+    ///
+    /// #### Special Errors
+    /// - `PositionNotLockable` - The position has no liquidity, or its mint is not frozen by the position.
+    /// - `InvalidUnlockTimestamp` - The unlock timestamp is not in the future.
+    pub fn lock_position(ctx: Context<LockPosition>, lock_type: LockType) -> Result<()> {
+        instructions::lock_position::handler(ctx, lock_type)
+    }
+
+    /// Unlock a position whose lock has expired, thawing the position token account and
+    /// closing its LockConfig account.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    /// - `funder` - the funder recorded in the LockConfig (the position owner for older locks),
+    ///   refunded with its rent.
+    ///
+    /// #### Special Errors
+    /// - `PositionLocked` - The lock is permanent or has not expired yet.
+    pub fn unlock_position(ctx: Context<UnlockPosition>) -> Result<()> {
+        instructions::unlock_position::handler(ctx)
+    }
+
+    /// Change the tick range of an empty position in place, keeping the position mint.
+    /// The fee and reward checkpoints of the position are reset.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `new_tick_lower_index` - The new tick specifying the lower end of the position range.
+    /// - `new_tick_upper_index` - The new tick specifying the upper end of the position range.
+    ///
+    /// #### Special Errors
+    /// - `ClosePositionNotEmpty` - The position has liquidity, fees or rewards owed, including extension rewards.
+    /// - `SameTickRangeNotAllowed` - The new tick range is the same as the current tick range.
+    /// - `InvalidTickIndex` - If a provided tick is out of bounds, not usable with the tick spacing,
+    ///                        or if the lower tick is not below the upper tick.
+    /// - `FullRangeOnlyPool` - The Whirlpool only accepts full range positions.
+    pub fn reset_position_range(
+        ctx: Context<ResetPositionRange>,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+    ) -> Result<()> {
+        instructions::reset_position_range::handler(ctx, new_tick_lower_index, new_tick_upper_index)
+    }
+
+    /// Compute a swap of the Whirlpool without transferring tokens or updating any account.
+    /// The swap loop is the same as in swap_v2, so that clients can get exact quotes by simulating
+    /// this instruction. The TickArray accounts can be passed as read-only, since the swap loop runs
+    /// on copies of their data. The copies are allocated on the heap, so passing supplemental
+    /// TickArrays may require a larger heap frame (ComputeBudget RequestHeapFrame).
+    ///
+    /// Paused Whirlpools can be simulated, so that quotes remain available while swap_v2 is rejected.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `referral_fee_rate` - The referral fee rate of swap_v2_with_referral_fee in basis points, zero for no referral fee.
+    ///                         The referral fee account is passed in the remaining accounts as for
+    ///                         swap_v2_with_referral_fee.
+    ///
+    /// ### Return Data
+    /// - `SwapResult` - Borsh-encoded in the return data: the amounts in and out, the fees and the price after the swap,
+    ///                  as returned by swap_v2 (or swap_v2_with_referral_fee) for the same swap.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over Whirlppool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
+    /// - `ReferralFeeRateMaxExceeded` - referral_fee_rate exceeds the cap set in the WhirlpoolsConfigExtension,
+    ///                                  or the WhirlpoolsConfig has no WhirlpoolsConfigExtension.
+    /// - `InvalidReferralFeeAccount` - The referral fee account is missing, holds neither token of the swap,
+    ///                                 or holds the output token of an exact output swap.
+    #[allow(clippy::too_many_arguments)]
+    pub fn simulate_swap<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SimulateSwap<'info>>,
+        amount: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        referral_fee_rate: u16,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::simulate_swap::handler(
+            ctx,
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            referral_fee_rate,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Compute the solvency of a reward of the Whirlpool as of the current timestamp, without
+    /// updating any account.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index < NUM_REWARDS), or the extension reward index
+    ///                    offset by NUM_REWARDS.
+    ///
+    /// ### Return Data
+    /// - `RewardSolvency` - Borsh-encoded in the return data: the amount in the reward vault and
+    ///                      the amount owed to the positions.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - If the reward at the provided index is not initialized.
+    /// - `RewardScheduleNotInitialized` - If the Whirlpool has no reward schedules.
+    /// - `RewardExtensionNotInitialized` - If an extension reward is requested and the Whirlpool
+    ///                                     has no reward extension.
+    pub fn get_reward_solvency(ctx: Context<GetRewardSolvency>, reward_index: u8) -> Result<()> {
+        instructions::v2::get_reward_solvency::handler(ctx, reward_index)
+    }
 }
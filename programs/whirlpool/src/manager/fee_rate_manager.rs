@@ -0,0 +1,399 @@
+use crate::math::{sqrt_price_from_tick_index, MAX_FEE_RATE};
+use crate::state::*;
+
+// Provides the fee rate applied to each step of a swap.
+//
+// Static pools always use the Whirlpool fee rate. Pools initialized with an AdaptiveFeeTier
+// add a variable fee on top of it (the Whirlpool fee rate acts as the base fee rate).
+// The variable fee grows with the number of tick groups the price has moved away from the
+// reference tick group, and the reference decays over time (filter / decay periods).
+#[derive(Debug)]
+pub enum FeeRateManager {
+    Static {
+        static_fee_rate: u16,
+    },
+    Adaptive {
+        a_to_b: bool,
+        base_fee_rate: u16,
+        tick_group_index: i32,
+        adaptive_fee_constants: AdaptiveFeeConstants,
+        adaptive_fee_variables: AdaptiveFeeVariables,
+    },
+}
+
+impl FeeRateManager {
+    pub fn new(
+        a_to_b: bool,
+        current_tick_index: i32,
+        current_sqrt_price: u128,
+        timestamp: u64,
+        static_fee_rate: u16,
+        adaptive_fee_info: &Option<AdaptiveFeeInfo>,
+    ) -> Self {
+        let adaptive_fee_info = match adaptive_fee_info {
+            None => return Self::Static { static_fee_rate },
+            Some(adaptive_fee_info) => adaptive_fee_info,
+        };
+
+        let adaptive_fee_constants = adaptive_fee_info.constants;
+        let mut adaptive_fee_variables = adaptive_fee_info.variables;
+
+        let tick_group_index = get_tick_group_index(
+            current_tick_index,
+            current_sqrt_price,
+            adaptive_fee_constants.tick_group_size,
+            a_to_b,
+        );
+
+        update_reference(
+            &mut adaptive_fee_variables,
+            &adaptive_fee_constants,
+            tick_group_index,
+            timestamp,
+        );
+
+        let mut fee_rate_manager = Self::Adaptive {
+            a_to_b,
+            base_fee_rate: static_fee_rate,
+            tick_group_index,
+            adaptive_fee_constants,
+            adaptive_fee_variables,
+        };
+        fee_rate_manager.update_volatility_accumulator(current_tick_index, current_sqrt_price);
+        fee_rate_manager
+    }
+
+    /// Move to the tick group of the given price and update the volatility accumulator.
+    pub fn update_volatility_accumulator(&mut self, curr_tick_index: i32, curr_sqrt_price: u128) {
+        if let Self::Adaptive {
+            a_to_b,
+            tick_group_index,
+            adaptive_fee_constants,
+            adaptive_fee_variables,
+            ..
+        } = self
+        {
+            *tick_group_index = get_tick_group_index(
+                curr_tick_index,
+                curr_sqrt_price,
+                adaptive_fee_constants.tick_group_size,
+                *a_to_b,
+            );
+
+            let tick_group_index_delta = (*tick_group_index as i64
+                - adaptive_fee_variables.tick_group_index_reference as i64)
+                .unsigned_abs();
+            let volatility_accumulator = (adaptive_fee_variables.volatility_reference as u64)
+                .saturating_add(
+                    tick_group_index_delta
+                        .saturating_mul(VOLATILITY_ACCUMULATOR_SCALE_FACTOR as u64),
+                )
+                .min(adaptive_fee_constants.max_volatility_accumulator as u64);
+
+            adaptive_fee_variables.volatility_accumulator = volatility_accumulator as u32;
+        }
+    }
+
+    pub fn get_total_fee_rate(&self) -> u16 {
+        match self {
+            Self::Static { static_fee_rate } => *static_fee_rate,
+            Self::Adaptive {
+                base_fee_rate,
+                adaptive_fee_constants,
+                adaptive_fee_variables,
+                ..
+            } => {
+                let adaptive_fee_rate = compute_adaptive_fee_rate(
+                    adaptive_fee_constants,
+                    adaptive_fee_variables.volatility_accumulator,
+                );
+                (*base_fee_rate as u128 + adaptive_fee_rate).min(MAX_FEE_RATE as u128) as u16
+            }
+        }
+    }
+
+    /// Limit the sqrt price target of a swap step to the boundary of the current tick group,
+    /// so that a single fee rate applies to the whole step.
+    pub fn get_bounded_sqrt_price_target(&self, sqrt_price_target: u128) -> u128 {
+        match self {
+            Self::Static { .. } => sqrt_price_target,
+            Self::Adaptive {
+                a_to_b,
+                tick_group_index,
+                adaptive_fee_constants,
+                adaptive_fee_variables,
+                ..
+            } => {
+                // Once the accumulator is saturated and the price keeps moving away from the
+                // reference, the fee rate cannot change anymore and no bound is needed.
+                let moving_away_from_reference = if *a_to_b {
+                    *tick_group_index <= adaptive_fee_variables.tick_group_index_reference
+                } else {
+                    *tick_group_index >= adaptive_fee_variables.tick_group_index_reference
+                };
+                if moving_away_from_reference
+                    && adaptive_fee_variables.volatility_accumulator
+                        >= adaptive_fee_constants.max_volatility_accumulator
+                {
+                    return sqrt_price_target;
+                }
+
+                let tick_group_size = adaptive_fee_constants.tick_group_size as i64;
+                if *a_to_b {
+                    let boundary_tick_index = (*tick_group_index as i64 * tick_group_size)
+                        .max(MIN_TICK_INDEX as i64)
+                        as i32;
+                    sqrt_price_target.max(sqrt_price_from_tick_index(boundary_tick_index))
+                } else {
+                    let boundary_tick_index = ((*tick_group_index as i64 + 1) * tick_group_size)
+                        .min(MAX_TICK_INDEX as i64)
+                        as i32;
+                    sqrt_price_target.min(sqrt_price_from_tick_index(boundary_tick_index))
+                }
+            }
+        }
+    }
+
+    pub fn get_next_adaptive_fee_info(&self) -> Option<AdaptiveFeeInfo> {
+        match self {
+            Self::Static { .. } => None,
+            Self::Adaptive {
+                adaptive_fee_constants,
+                adaptive_fee_variables,
+                ..
+            } => Some(AdaptiveFeeInfo {
+                constants: *adaptive_fee_constants,
+                variables: *adaptive_fee_variables,
+            }),
+        }
+    }
+}
+
+// Returns the tick group the next step of the swap will trade in.
+// If the price sits exactly on a tick group boundary, the group on the swap direction side is used.
+fn get_tick_group_index(
+    curr_tick_index: i32,
+    curr_sqrt_price: u128,
+    tick_group_size: u16,
+    a_to_b: bool,
+) -> i32 {
+    let tick_group_size = tick_group_size as i32;
+    let tick_group_index = curr_tick_index.div_euclid(tick_group_size);
+
+    if a_to_b {
+        let lower_tick_index = (tick_group_index * tick_group_size).max(MIN_TICK_INDEX);
+        if curr_sqrt_price <= sqrt_price_from_tick_index(lower_tick_index) {
+            return tick_group_index - 1;
+        }
+    } else {
+        let upper_tick_index = (tick_group_index + 1) * tick_group_size;
+        if upper_tick_index <= MAX_TICK_INDEX
+            && curr_sqrt_price >= sqrt_price_from_tick_index(upper_tick_index)
+        {
+            return tick_group_index + 1;
+        }
+    }
+
+    tick_group_index
+}
+
+fn update_reference(
+    adaptive_fee_variables: &mut AdaptiveFeeVariables,
+    adaptive_fee_constants: &AdaptiveFeeConstants,
+    tick_group_index: i32,
+    timestamp: u64,
+) {
+    let elapsed = timestamp.saturating_sub(adaptive_fee_variables.last_reference_update_timestamp);
+
+    // High frequency trades keep the same reference
+    if elapsed < adaptive_fee_constants.filter_period as u64 {
+        return;
+    }
+
+    adaptive_fee_variables.tick_group_index_reference = tick_group_index;
+    adaptive_fee_variables.volatility_reference =
+        if elapsed < adaptive_fee_constants.decay_period as u64 {
+            (adaptive_fee_variables.volatility_accumulator as u64
+                * adaptive_fee_constants.reduction_factor as u64
+                / REDUCTION_FACTOR_DENOMINATOR as u64) as u32
+        } else {
+            0
+        };
+    adaptive_fee_variables.last_reference_update_timestamp = timestamp;
+}
+
+// adaptive_fee_rate = control_factor * (volatility_accumulator * tick_group_size)^2
+// (rounded up, in hundredths of a basis point)
+fn compute_adaptive_fee_rate(
+    adaptive_fee_constants: &AdaptiveFeeConstants,
+    volatility_accumulator: u32,
+) -> u128 {
+    let crossed = volatility_accumulator as u128 * adaptive_fee_constants.tick_group_size as u128;
+    let numerator = adaptive_fee_constants.adaptive_fee_control_factor as u128 * crossed * crossed;
+    let denominator = ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR as u128
+        * VOLATILITY_ACCUMULATOR_SCALE_FACTOR as u128
+        * VOLATILITY_ACCUMULATOR_SCALE_FACTOR as u128;
+    numerator.div_ceil(denominator)
+}
+
+#[cfg(test)]
+mod fee_rate_manager_tests {
+    use super::*;
+
+    fn adaptive_fee_info(variables: AdaptiveFeeVariables) -> Option<AdaptiveFeeInfo> {
+        Some(AdaptiveFeeInfo {
+            constants: AdaptiveFeeConstants {
+                filter_period: 30,
+                decay_period: 600,
+                reduction_factor: 5_000,
+                adaptive_fee_control_factor: 4_000,
+                max_volatility_accumulator: 350_000,
+                tick_group_size: 64,
+            },
+            variables,
+        })
+    }
+
+    #[test]
+    fn test_static() {
+        let mut manager =
+            FeeRateManager::new(true, 100, sqrt_price_from_tick_index(100), 0, 3000, &None);
+        manager.update_volatility_accumulator(-10_000, sqrt_price_from_tick_index(-10_000));
+        assert_eq!(manager.get_total_fee_rate(), 3000);
+        assert_eq!(manager.get_bounded_sqrt_price_target(1 << 64), 1 << 64);
+        assert!(manager.get_next_adaptive_fee_info().is_none());
+    }
+
+    #[test]
+    fn test_reference_reset_after_decay_period() {
+        let manager = FeeRateManager::new(
+            false,
+            640,
+            sqrt_price_from_tick_index(640),
+            1_000,
+            3000,
+            &adaptive_fee_info(AdaptiveFeeVariables {
+                last_reference_update_timestamp: 100,
+                volatility_reference: 20_000,
+                tick_group_index_reference: -3,
+                volatility_accumulator: 50_000,
+            }),
+        );
+        let variables = manager.get_next_adaptive_fee_info().unwrap().variables;
+        assert_eq!(variables.last_reference_update_timestamp, 1_000);
+        assert_eq!(variables.volatility_reference, 0);
+        assert_eq!(variables.tick_group_index_reference, 10);
+        assert_eq!(manager.get_total_fee_rate(), 3000);
+    }
+
+    #[test]
+    fn test_reference_reduced_within_decay_period() {
+        let manager = FeeRateManager::new(
+            false,
+            640,
+            sqrt_price_from_tick_index(640),
+            200,
+            3000,
+            &adaptive_fee_info(AdaptiveFeeVariables {
+                last_reference_update_timestamp: 100,
+                volatility_reference: 20_000,
+                tick_group_index_reference: -3,
+                volatility_accumulator: 50_000,
+            }),
+        );
+        let variables = manager.get_next_adaptive_fee_info().unwrap().variables;
+        assert_eq!(variables.last_reference_update_timestamp, 200);
+        assert_eq!(variables.volatility_reference, 25_000);
+        assert_eq!(variables.tick_group_index_reference, 10);
+    }
+
+    #[test]
+    fn test_reference_kept_within_filter_period() {
+        let variables = AdaptiveFeeVariables {
+            last_reference_update_timestamp: 100,
+            volatility_reference: 20_000,
+            tick_group_index_reference: -3,
+            volatility_accumulator: 50_000,
+        };
+        let manager = FeeRateManager::new(
+            false,
+            640,
+            sqrt_price_from_tick_index(640),
+            110,
+            3000,
+            &adaptive_fee_info(variables),
+        );
+        // only the accumulator moves: 20_000 + (10 - (-3)) * 10_000
+        assert_eq!(
+            manager.get_next_adaptive_fee_info().unwrap().variables,
+            AdaptiveFeeVariables {
+                volatility_accumulator: 150_000,
+                ..variables
+            }
+        );
+    }
+
+    #[test]
+    fn test_volatility_accumulator_and_fee_rate() {
+        let mut manager = FeeRateManager::new(
+            false,
+            0,
+            sqrt_price_from_tick_index(0),
+            1_000,
+            3000,
+            &adaptive_fee_info(AdaptiveFeeVariables::default()),
+        );
+
+        manager.update_volatility_accumulator(0, sqrt_price_from_tick_index(0));
+        assert_eq!(manager.get_total_fee_rate(), 3000);
+
+        // one tick group: 4_000 * (10_000 * 64)^2 / (100_000 * 10_000^2) = 163.84
+        manager.update_volatility_accumulator(64, sqrt_price_from_tick_index(64));
+        let variables = manager.get_next_adaptive_fee_info().unwrap().variables;
+        assert_eq!(variables.volatility_accumulator, 10_000);
+        assert_eq!(manager.get_total_fee_rate(), 3000 + 164);
+
+        // saturated at max_volatility_accumulator (35 tick groups)
+        manager.update_volatility_accumulator(64 * 100, sqrt_price_from_tick_index(64 * 100));
+        let variables = manager.get_next_adaptive_fee_info().unwrap().variables;
+        assert_eq!(variables.volatility_accumulator, 350_000);
+        assert_eq!(manager.get_total_fee_rate(), MAX_FEE_RATE);
+    }
+
+    #[test]
+    fn test_bounded_sqrt_price_target() {
+        let mut manager = FeeRateManager::new(
+            true,
+            100,
+            sqrt_price_from_tick_index(100),
+            1_000,
+            3000,
+            &adaptive_fee_info(AdaptiveFeeVariables::default()),
+        );
+        manager.update_volatility_accumulator(100, sqrt_price_from_tick_index(100));
+        assert_eq!(
+            manager.get_bounded_sqrt_price_target(sqrt_price_from_tick_index(-1000)),
+            sqrt_price_from_tick_index(64)
+        );
+
+        // exactly on the boundary, moving left: next group
+        manager.update_volatility_accumulator(64, sqrt_price_from_tick_index(64));
+        assert_eq!(
+            manager.get_bounded_sqrt_price_target(sqrt_price_from_tick_index(-1000)),
+            sqrt_price_from_tick_index(0)
+        );
+
+        // target within the group is not modified
+        assert_eq!(
+            manager.get_bounded_sqrt_price_target(sqrt_price_from_tick_index(10)),
+            sqrt_price_from_tick_index(10)
+        );
+
+        // saturated and moving away from the reference: unbounded
+        manager.update_volatility_accumulator(-6400, sqrt_price_from_tick_index(-6400));
+        assert_eq!(
+            manager.get_bounded_sqrt_price_target(sqrt_price_from_tick_index(-100_000)),
+            sqrt_price_from_tick_index(-100_000)
+        );
+    }
+}
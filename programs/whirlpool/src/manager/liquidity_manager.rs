@@ -1,5 +1,6 @@
 use super::{
     position_manager::next_position_modify_liquidity_update,
+    reward_extension_manager::{calculate_reward_extension_update, RewardExtensionUpdate},
     tick_manager::{
         next_fee_growths_inside, next_reward_growths_inside, next_tick_modify_liquidity_update,
     },
@@ -9,8 +10,15 @@ use crate::{
     errors::ErrorCode,
     math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
     state::*,
+    util::{
+        get_tick_from_tick_array, get_tick_reward_growths_outside, load_position_reward_extension,
+        load_whirlpool_reward_extension, load_whirlpool_reward_schedule,
+        store_position_reward_extension, store_whirlpool_reward_extension,
+        store_whirlpool_reward_schedule, update_tick_in_tick_array,
+        update_tick_reward_growths_outside, TickArrayRentFunder,
+    },
 };
-use anchor_lang::prelude::{AccountLoader, *};
+use anchor_lang::prelude::*;
 
 #[derive(Debug)]
 pub struct ModifyLiquidityUpdate {
@@ -18,74 +26,206 @@ pub struct ModifyLiquidityUpdate {
     pub tick_lower_update: TickUpdate,
     pub tick_upper_update: TickUpdate,
     pub reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS],
+    // None if the Whirlpool has no reward schedules
+    pub reward_schedule: Option<WhirlpoolRewardSchedule>,
     pub position_update: PositionUpdate,
+    // None if the Whirlpool has no reward extension
+    pub reward_extension_update: Option<RewardExtensionUpdate>,
 }
 
 // Calculates state after modifying liquidity by the liquidity_delta for the given positon.
 // Fee and reward growths will also be calculated by this function.
 // To trigger only calculation of fee and reward growths, use calculate_fee_and_reward_growths.
 pub fn calculate_modify_liquidity<'info>(
-    whirlpool: &Whirlpool,
-    position: &Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    whirlpool: &Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
+    position: &Account<'info, Position>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
     liquidity_delta: i128,
     timestamp: u64,
 ) -> Result<ModifyLiquidityUpdate> {
-    let tick_array_lower = tick_array_lower.load()?;
-    let tick_lower =
-        tick_array_lower.get_tick(position.tick_lower_index, whirlpool.tick_spacing)?;
+    let tick_lower = get_tick_from_tick_array(
+        tick_array_lower,
+        &whirlpool.key(),
+        position.tick_lower_index,
+        whirlpool.tick_spacing,
+    )?;
 
-    let tick_array_upper = tick_array_upper.load()?;
-    let tick_upper =
-        tick_array_upper.get_tick(position.tick_upper_index, whirlpool.tick_spacing)?;
+    let tick_upper = get_tick_from_tick_array(
+        tick_array_upper,
+        &whirlpool.key(),
+        position.tick_upper_index,
+        whirlpool.tick_spacing,
+    )?;
 
-    _calculate_modify_liquidity(
+    let reward_schedule = load_whirlpool_reward_schedule(whirlpool_rewards)?;
+
+    let mut update = _calculate_modify_liquidity(
         whirlpool,
+        &reward_schedule,
         position,
-        tick_lower,
-        tick_upper,
+        &tick_lower,
+        &tick_upper,
         position.tick_lower_index,
         position.tick_upper_index,
         liquidity_delta,
         timestamp,
+    )?;
+
+    update.reward_extension_update = _calculate_reward_extension_update(
+        whirlpool,
+        whirlpool_rewards,
+        position,
+        tick_array_lower,
+        &tick_lower,
+        tick_array_upper,
+        &tick_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    Ok(update)
+}
+
+// Calculates the reward growths of the reward extension for the given position
+// without modifying its liquidity. Returns None if the Whirlpool has no reward extension.
+pub fn calculate_reward_extension_growths<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
+    position: &Account<'info, Position>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
+    timestamp: u64,
+) -> Result<Option<RewardExtensionUpdate>> {
+    let tick_lower = get_tick_from_tick_array(
+        tick_array_lower,
+        &whirlpool.key(),
+        position.tick_lower_index,
+        whirlpool.tick_spacing,
+    )?;
+
+    let tick_upper = get_tick_from_tick_array(
+        tick_array_upper,
+        &whirlpool.key(),
+        position.tick_upper_index,
+        whirlpool.tick_spacing,
+    )?;
+
+    _calculate_reward_extension_update(
+        whirlpool,
+        whirlpool_rewards,
+        position,
+        tick_array_lower,
+        &tick_lower,
+        tick_array_upper,
+        &tick_upper,
+        0,
+        timestamp,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
+fn _calculate_reward_extension_update<'info>(
+    whirlpool: &Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
+    position: &Account<'info, Position>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_lower: &Tick,
+    tick_array_upper: &AccountInfo<'info>,
+    tick_upper: &Tick,
+    liquidity_delta: i128,
+    timestamp: u64,
+) -> Result<Option<RewardExtensionUpdate>> {
+    let whirlpool_reward_extension = match load_whirlpool_reward_extension(whirlpool_rewards)? {
+        Some(whirlpool_reward_extension) => whirlpool_reward_extension,
+        None => return Ok(None),
+    };
+
+    let tick_lower_reward_growths_outside = get_tick_reward_growths_outside(
+        tick_array_lower,
+        &whirlpool.key(),
+        position.tick_lower_index,
+        whirlpool.tick_spacing,
+    )?;
+
+    let tick_upper_reward_growths_outside = get_tick_reward_growths_outside(
+        tick_array_upper,
+        &whirlpool.key(),
+        position.tick_upper_index,
+        whirlpool.tick_spacing,
+    )?;
+
+    let position_reward_extension = load_position_reward_extension(&position.to_account_info())?;
+
+    calculate_reward_extension_update(
+        whirlpool,
+        &whirlpool_reward_extension,
+        position,
+        &position_reward_extension,
+        tick_lower,
+        &tick_lower_reward_growths_outside,
+        tick_upper,
+        &tick_upper_reward_growths_outside,
+        liquidity_delta,
+        timestamp,
+    )
+    .map(Some)
+}
+
 pub fn calculate_fee_and_reward_growths<'info>(
-    whirlpool: &Whirlpool,
+    whirlpool: &Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
     position: &Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
     timestamp: u64,
-) -> Result<(PositionUpdate, [WhirlpoolRewardInfo; NUM_REWARDS])> {
-    let tick_array_lower = tick_array_lower.load()?;
-    let tick_lower =
-        tick_array_lower.get_tick(position.tick_lower_index, whirlpool.tick_spacing)?;
+) -> Result<(
+    PositionUpdate,
+    [WhirlpoolRewardInfo; NUM_REWARDS],
+    Option<WhirlpoolRewardSchedule>,
+)> {
+    let tick_lower = get_tick_from_tick_array(
+        tick_array_lower,
+        &whirlpool.key(),
+        position.tick_lower_index,
+        whirlpool.tick_spacing,
+    )?;
+
+    let tick_upper = get_tick_from_tick_array(
+        tick_array_upper,
+        &whirlpool.key(),
+        position.tick_upper_index,
+        whirlpool.tick_spacing,
+    )?;
 
-    let tick_array_upper = tick_array_upper.load()?;
-    let tick_upper =
-        tick_array_upper.get_tick(position.tick_upper_index, whirlpool.tick_spacing)?;
+    let reward_schedule = load_whirlpool_reward_schedule(whirlpool_rewards)?;
 
     // Pass in a liquidity_delta value of 0 to trigger only calculations for fee and reward growths.
     // Calculating fees and rewards for positions with zero liquidity will result in an error.
     let update = _calculate_modify_liquidity(
         whirlpool,
+        &reward_schedule,
         position,
-        tick_lower,
-        tick_upper,
+        &tick_lower,
+        &tick_upper,
         position.tick_lower_index,
         position.tick_upper_index,
         0,
         timestamp,
     )?;
-    Ok((update.position_update, update.reward_infos))
+    Ok((
+        update.position_update,
+        update.reward_infos,
+        update.reward_schedule,
+    ))
 }
 
 // Calculates the state changes after modifying liquidity of a whirlpool position.
 #[allow(clippy::too_many_arguments)]
 fn _calculate_modify_liquidity(
     whirlpool: &Whirlpool,
+    reward_schedule: &Option<WhirlpoolRewardSchedule>,
     position: &Position,
     tick_lower: &Tick,
     tick_upper: &Tick,
@@ -99,7 +239,8 @@ fn _calculate_modify_liquidity(
         return Err(ErrorCode::LiquidityZero.into());
     }
 
-    let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+    let (next_reward_infos, next_reward_schedule) =
+        next_whirlpool_reward_infos(whirlpool, reward_schedule, timestamp)?;
 
     let next_global_liquidity = next_whirlpool_liquidity(
         whirlpool,
@@ -160,9 +301,11 @@ fn _calculate_modify_liquidity(
     Ok(ModifyLiquidityUpdate {
         whirlpool_liquidity: next_global_liquidity,
         reward_infos: next_reward_infos,
+        reward_schedule: next_reward_schedule,
         position_update,
         tick_lower_update,
         tick_upper_update,
+        reward_extension_update: None,
     })
 }
 
@@ -200,34 +343,94 @@ pub fn calculate_liquidity_token_deltas(
     Ok((delta_a, delta_b))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn sync_modify_liquidity_values<'info>(
-    whirlpool: &mut Whirlpool,
-    position: &mut Position,
-    tick_array_lower: &AccountLoader<'info, TickArray>,
-    tick_array_upper: &AccountLoader<'info, TickArray>,
+    whirlpool: &mut Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
+    position: &mut Account<'info, Position>,
+    tick_array_lower: &AccountInfo<'info>,
+    tick_array_upper: &AccountInfo<'info>,
     modify_liquidity_update: ModifyLiquidityUpdate,
     reward_last_updated_timestamp: u64,
+    rent_funder: &TickArrayRentFunder<'_, 'info>,
 ) -> Result<()> {
     position.update(&modify_liquidity_update.position_update);
 
-    tick_array_lower.load_mut()?.update_tick(
+    update_tick_in_tick_array(
+        tick_array_lower,
+        &whirlpool.key(),
         position.tick_lower_index,
         whirlpool.tick_spacing,
         &modify_liquidity_update.tick_lower_update,
+        rent_funder,
     )?;
 
-    tick_array_upper.load_mut()?.update_tick(
+    update_tick_in_tick_array(
+        tick_array_upper,
+        &whirlpool.key(),
         position.tick_upper_index,
         whirlpool.tick_spacing,
         &modify_liquidity_update.tick_upper_update,
+        rent_funder,
     )?;
 
+    if let Some(reward_extension_update) = &modify_liquidity_update.reward_extension_update {
+        if let Some(reward_growths_outside) =
+            &reward_extension_update.tick_lower_reward_growths_outside
+        {
+            update_tick_reward_growths_outside(
+                tick_array_lower,
+                &whirlpool.key(),
+                position.tick_lower_index,
+                whirlpool.tick_spacing,
+                reward_growths_outside,
+            )?;
+        }
+
+        if let Some(reward_growths_outside) =
+            &reward_extension_update.tick_upper_reward_growths_outside
+        {
+            update_tick_reward_growths_outside(
+                tick_array_upper,
+                &whirlpool.key(),
+                position.tick_upper_index,
+                whirlpool.tick_spacing,
+                reward_growths_outside,
+            )?;
+        }
+
+        sync_reward_extension_values(whirlpool_rewards, position, reward_extension_update)?;
+    }
+
     whirlpool.update_rewards_and_liquidity(
         modify_liquidity_update.reward_infos,
         modify_liquidity_update.whirlpool_liquidity,
         reward_last_updated_timestamp,
     );
 
+    if let Some(reward_schedule) = &modify_liquidity_update.reward_schedule {
+        store_whirlpool_reward_schedule(whirlpool_rewards, reward_schedule)?;
+    }
+
+    Ok(())
+}
+
+// Stores the reward growths of the reward extension for the Whirlpool and the position.
+// The growths outside of the ticks are only modified along with the liquidity.
+pub fn sync_reward_extension_values<'info>(
+    whirlpool_rewards: &AccountInfo<'info>,
+    position: &Account<'info, Position>,
+    reward_extension_update: &RewardExtensionUpdate,
+) -> Result<()> {
+    store_whirlpool_reward_extension(
+        whirlpool_rewards,
+        &reward_extension_update.whirlpool_reward_extension,
+    )?;
+
+    if let Some(position_reward_extension) = &reward_extension_update.position_reward_extension {
+        store_position_reward_extension(&position.to_account_info(), position_reward_extension)?;
+    }
+
     Ok(())
 }
 
@@ -263,6 +466,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -291,6 +495,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -319,6 +524,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -347,6 +553,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -388,6 +595,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -441,6 +649,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -518,6 +727,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Create position which initializes the lower tick
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -583,6 +793,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -636,6 +847,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -698,6 +910,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -753,6 +966,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -825,6 +1039,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Create position which initializes the lower tick
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -893,6 +1108,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -950,6 +1166,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1013,6 +1230,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1069,6 +1287,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1137,6 +1356,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1235,6 +1455,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // t = 300 to 400, recalculate position fees/rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1303,6 +1524,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1360,6 +1582,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1428,6 +1651,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1495,6 +1719,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1531,6 +1756,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1596,6 +1822,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1660,6 +1887,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1703,6 +1931,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1762,6 +1991,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1798,6 +2028,7 @@ mod calculate_modify_liquidity_unit_tests {
                 });
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -1853,6 +2084,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -1902,6 +2134,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -1955,6 +2188,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2005,6 +2239,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2054,6 +2289,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2107,6 +2343,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2157,6 +2394,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2206,6 +2444,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2259,6 +2498,7 @@ mod calculate_modify_liquidity_unit_tests {
             });
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2328,6 +2568,7 @@ mod calculate_modify_liquidity_unit_tests {
             // Add 100 liquidity
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2356,6 +2597,7 @@ mod calculate_modify_liquidity_unit_tests {
             test.increment_whirlpool_fee_growths(to_x64(10), to_x64(20));
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2385,6 +2627,7 @@ mod calculate_modify_liquidity_unit_tests {
             test.increment_whirlpool_fee_growths(to_x64(10), to_x64(20));
             let update = _calculate_modify_liquidity(
                 &test.whirlpool,
+                &None,
                 &test.position,
                 &test.tick_lower,
                 &test.tick_upper,
@@ -2455,6 +2698,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2519,6 +2763,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2585,6 +2830,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2643,6 +2889,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2709,6 +2956,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2778,6 +3026,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2845,6 +3094,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2899,6 +3149,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -2964,6 +3215,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3015,6 +3267,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3082,6 +3335,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3145,6 +3399,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3215,6 +3470,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3278,6 +3534,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3350,6 +3607,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3407,6 +3665,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3477,6 +3736,7 @@ mod calculate_modify_liquidity_unit_tests {
 
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
@@ -3545,6 +3805,7 @@ mod calculate_modify_liquidity_unit_tests {
                 // Calculate fees and rewards
                 let update = _calculate_modify_liquidity(
                     &test.whirlpool,
+                    &None,
                     &test.position,
                     &test.tick_lower,
                     &test.tick_upper,
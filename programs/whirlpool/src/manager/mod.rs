@@ -1,5 +1,7 @@
+pub mod fee_rate_manager;
 pub mod liquidity_manager;
 pub mod position_manager;
+pub mod reward_extension_manager;
 pub mod swap_manager;
 pub mod tick_manager;
 pub mod whirlpool_manager;
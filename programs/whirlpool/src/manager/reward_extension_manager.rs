@@ -0,0 +1,383 @@
+use super::{
+    position_manager::next_position_reward_infos,
+    tick_manager::{
+        calculate_reward_growths_inside, next_tick_reward_extension_modify_liquidity_update,
+    },
+    whirlpool_manager::next_whirlpool_reward_extension,
+};
+use crate::{errors::ErrorCode, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Debug, PartialEq)]
+pub struct RewardExtensionUpdate {
+    pub whirlpool_reward_extension: WhirlpoolRewardExtension,
+    // None if the TickArray of the tick has no TickArrayRewardExtension
+    pub tick_lower_reward_growths_outside: Option<[u128; NUM_EXTENSION_REWARDS]>,
+    pub tick_upper_reward_growths_outside: Option<[u128; NUM_EXTENSION_REWARDS]>,
+    // None if the position has not allocated a PositionRewardExtension
+    pub position_reward_extension: Option<PositionRewardExtension>,
+}
+
+// Calculates the state of the reward extension after modifying liquidity by the liquidity_delta
+// for the given position. Pass in a liquidity_delta value of 0 to only update the reward growths.
+//
+// The growths outside of the ticks are None if their TickArray has no TickArrayRewardExtension.
+// Such ticks are not tracked, which is fine as long as the position does not accrue the extension rewards.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_reward_extension_update(
+    whirlpool: &Whirlpool,
+    whirlpool_reward_extension: &WhirlpoolRewardExtension,
+    position: &Position,
+    position_reward_extension: &Option<PositionRewardExtension>,
+    tick_lower: &Tick,
+    tick_lower_reward_growths_outside: &Option<[u128; NUM_EXTENSION_REWARDS]>,
+    tick_upper: &Tick,
+    tick_upper_reward_growths_outside: &Option<[u128; NUM_EXTENSION_REWARDS]>,
+    liquidity_delta: i128,
+    timestamp: u64,
+) -> Result<RewardExtensionUpdate> {
+    let next_reward_extension =
+        next_whirlpool_reward_extension(whirlpool, whirlpool_reward_extension, timestamp)?;
+
+    let next_tick_lower_reward_growths_outside = tick_lower_reward_growths_outside
+        .map(|reward_growths_outside| {
+            next_tick_reward_extension_modify_liquidity_update(
+                tick_lower,
+                position.tick_lower_index,
+                whirlpool.tick_current_index,
+                &next_reward_extension.reward_infos,
+                &reward_growths_outside,
+                liquidity_delta,
+            )
+        })
+        .transpose()?;
+
+    let next_tick_upper_reward_growths_outside = tick_upper_reward_growths_outside
+        .map(|reward_growths_outside| {
+            next_tick_reward_extension_modify_liquidity_update(
+                tick_upper,
+                position.tick_upper_index,
+                whirlpool.tick_current_index,
+                &next_reward_extension.reward_infos,
+                &reward_growths_outside,
+                liquidity_delta,
+            )
+        })
+        .transpose()?;
+
+    let next_position_reward_extension = match (
+        position_reward_extension,
+        tick_lower_reward_growths_outside,
+        tick_upper_reward_growths_outside,
+    ) {
+        (None, _, _) => None,
+        (
+            Some(position_reward_extension),
+            Some(tick_lower_reward_growths_outside),
+            Some(tick_upper_reward_growths_outside),
+        ) => {
+            let reward_growths_inside = calculate_reward_growths_inside(
+                whirlpool.tick_current_index,
+                tick_lower.initialized,
+                tick_lower_reward_growths_outside,
+                position.tick_lower_index,
+                tick_upper.initialized,
+                tick_upper_reward_growths_outside,
+                position.tick_upper_index,
+                &next_reward_extension.reward_infos,
+            );
+
+            Some(PositionRewardExtension {
+                reward_infos: next_position_reward_infos(
+                    position.liquidity,
+                    &position_reward_extension.reward_infos,
+                    &reward_growths_inside,
+                ),
+            })
+        }
+        // The checkpoints of a position accruing the extension rewards cannot be kept up to date
+        // if a TickArray of the position no longer tracks the growths outside of its tick.
+        _ => return Err(ErrorCode::RewardExtensionNotAllocated.into()),
+    };
+
+    Ok(RewardExtensionUpdate {
+        whirlpool_reward_extension: next_reward_extension,
+        tick_lower_reward_growths_outside: next_tick_lower_reward_growths_outside,
+        tick_upper_reward_growths_outside: next_tick_upper_reward_growths_outside,
+        position_reward_extension: next_position_reward_extension,
+    })
+}
+
+// Calculates the state of the reward extension when the given position starts to track the extension rewards.
+// The checkpoints of the position are set to the current reward growths inside, so nothing is owed for the past.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_position_reward_extension_init(
+    whirlpool: &Whirlpool,
+    whirlpool_reward_extension: &WhirlpoolRewardExtension,
+    position: &Position,
+    tick_lower: &Tick,
+    tick_lower_reward_growths_outside: &[u128; NUM_EXTENSION_REWARDS],
+    tick_upper: &Tick,
+    tick_upper_reward_growths_outside: &[u128; NUM_EXTENSION_REWARDS],
+    timestamp: u64,
+) -> Result<(WhirlpoolRewardExtension, PositionRewardExtension)> {
+    let next_reward_extension =
+        next_whirlpool_reward_extension(whirlpool, whirlpool_reward_extension, timestamp)?;
+
+    let reward_growths_inside = calculate_reward_growths_inside(
+        whirlpool.tick_current_index,
+        tick_lower.initialized,
+        tick_lower_reward_growths_outside,
+        position.tick_lower_index,
+        tick_upper.initialized,
+        tick_upper_reward_growths_outside,
+        position.tick_upper_index,
+        &next_reward_extension.reward_infos,
+    );
+
+    let mut position_reward_extension = PositionRewardExtension::default();
+    for (reward_info, growth_inside) in position_reward_extension
+        .reward_infos
+        .iter_mut()
+        .zip(reward_growths_inside)
+    {
+        reward_info.growth_inside_checkpoint = growth_inside;
+    }
+
+    Ok((next_reward_extension, position_reward_extension))
+}
+
+#[cfg(test)]
+mod reward_extension_manager_tests {
+    use super::*;
+    use crate::math::Q64_RESOLUTION;
+    use crate::state::{
+        position_builder::PositionBuilder, tick_builder::TickBuilder,
+        whirlpool_builder::WhirlpoolBuilder,
+    };
+
+    fn reward_extension(growth_global_x64: u128, timestamp: u64) -> WhirlpoolRewardExtension {
+        let mut reward_extension = WhirlpoolRewardExtension::new(Pubkey::default(), timestamp);
+        reward_extension.reward_infos[0] = WhirlpoolRewardInfo {
+            mint: Pubkey::new_unique(),
+            emissions_per_second_x64: 1 << Q64_RESOLUTION,
+            growth_global_x64,
+            ..Default::default()
+        };
+        reward_extension
+    }
+
+    #[test]
+    fn test_position_in_range_accrues_extension_rewards() {
+        let whirlpool = WhirlpoolBuilder::new()
+            .liquidity(100)
+            .tick_current_index(0)
+            .build();
+        let position = PositionBuilder::new(-10, 10).liquidity(100).build();
+        let tick = TickBuilder::default()
+            .initialized(true)
+            .liquidity_gross(100)
+            .build();
+
+        // 200 seconds at 1 token per second for liquidity 100 => growth of 2 per unit of liquidity
+        let update = calculate_reward_extension_update(
+            &whirlpool,
+            &reward_extension(0, 1000),
+            &position,
+            &Some(PositionRewardExtension::default()),
+            &tick,
+            &Some([0; NUM_EXTENSION_REWARDS]),
+            &tick,
+            &Some([0; NUM_EXTENSION_REWARDS]),
+            0,
+            1200,
+        )
+        .unwrap();
+
+        assert_eq!(
+            update.whirlpool_reward_extension.reward_infos[0].growth_global_x64,
+            2 << Q64_RESOLUTION
+        );
+        assert_eq!(
+            update
+                .whirlpool_reward_extension
+                .reward_last_updated_timestamp,
+            1200
+        );
+        let position_reward_extension = update.position_reward_extension.unwrap();
+        assert_eq!(position_reward_extension.reward_infos[0].amount_owed, 200);
+        assert_eq!(
+            position_reward_extension.reward_infos[0].growth_inside_checkpoint,
+            2 << Q64_RESOLUTION
+        );
+        assert_eq!(
+            update.tick_lower_reward_growths_outside,
+            Some([0; NUM_EXTENSION_REWARDS])
+        );
+    }
+
+    #[test]
+    fn test_position_out_of_range_accrues_nothing() {
+        let whirlpool = WhirlpoolBuilder::new()
+            .liquidity(100)
+            .tick_current_index(20)
+            .build();
+        let position = PositionBuilder::new(-10, 10).liquidity(100).build();
+        let tick = TickBuilder::default()
+            .initialized(true)
+            .liquidity_gross(100)
+            .build();
+        let mut growths_outside = [0; NUM_EXTENSION_REWARDS];
+        growths_outside[0] = 1 << Q64_RESOLUTION;
+        let mut position_reward_extension = PositionRewardExtension::default();
+        position_reward_extension.reward_infos[0].growth_inside_checkpoint = 1 << Q64_RESOLUTION;
+
+        let update = calculate_reward_extension_update(
+            &whirlpool,
+            &reward_extension(1 << Q64_RESOLUTION, 1000),
+            &position,
+            &Some(position_reward_extension),
+            &tick,
+            &Some([0; NUM_EXTENSION_REWARDS]),
+            &tick,
+            &Some(growths_outside),
+            0,
+            1200,
+        )
+        .unwrap();
+
+        let position_reward_extension = update.position_reward_extension.unwrap();
+        assert_eq!(position_reward_extension.reward_infos[0].amount_owed, 0);
+    }
+
+    #[test]
+    fn test_position_reward_extension_init_owes_nothing() {
+        let whirlpool = WhirlpoolBuilder::new()
+            .liquidity(100)
+            .tick_current_index(0)
+            .build();
+        let position = PositionBuilder::new(-10, 10).liquidity(100).build();
+        let tick = TickBuilder::default()
+            .initialized(true)
+            .liquidity_gross(100)
+            .build();
+        let mut growths_outside = [0; NUM_EXTENSION_REWARDS];
+        growths_outside[0] = 1 << Q64_RESOLUTION;
+
+        let (whirlpool_reward_extension, position_reward_extension) =
+            calculate_position_reward_extension_init(
+                &whirlpool,
+                &reward_extension(3 << Q64_RESOLUTION, 1000),
+                &position,
+                &tick,
+                &growths_outside,
+                &tick,
+                &[0; NUM_EXTENSION_REWARDS],
+                1200,
+            )
+            .unwrap();
+
+        // 3 + 2 (accrued) - 1 (below) - 0 (above)
+        assert_eq!(
+            whirlpool_reward_extension.reward_infos[0].growth_global_x64,
+            5 << Q64_RESOLUTION
+        );
+        assert_eq!(
+            position_reward_extension.reward_infos[0].growth_inside_checkpoint,
+            4 << Q64_RESOLUTION
+        );
+        assert!(position_reward_extension.is_empty());
+    }
+
+    #[test]
+    fn test_position_without_extension_updates_ticks_only() {
+        let whirlpool = WhirlpoolBuilder::new()
+            .liquidity(100)
+            .tick_current_index(0)
+            .build();
+        let position = PositionBuilder::new(-10, 10).build();
+
+        let update = calculate_reward_extension_update(
+            &whirlpool,
+            &reward_extension(1 << Q64_RESOLUTION, 1000),
+            &position,
+            &None,
+            &Tick::default(),
+            &Some([0; NUM_EXTENSION_REWARDS]),
+            &Tick::default(),
+            &Some([0; NUM_EXTENSION_REWARDS]),
+            100,
+            1000,
+        )
+        .unwrap();
+
+        assert!(update.position_reward_extension.is_none());
+        // By convention, all prior growth happened below the newly initialized ticks
+        assert_eq!(
+            update.tick_lower_reward_growths_outside.unwrap()[0],
+            1 << Q64_RESOLUTION
+        );
+        assert_eq!(update.tick_upper_reward_growths_outside.unwrap()[0], 0);
+    }
+
+    #[test]
+    fn test_ticks_without_reward_extension_are_not_tracked() {
+        let whirlpool = WhirlpoolBuilder::new()
+            .liquidity(100)
+            .tick_current_index(0)
+            .build();
+        let position = PositionBuilder::new(-10, 10).build();
+
+        let update = calculate_reward_extension_update(
+            &whirlpool,
+            &reward_extension(1 << Q64_RESOLUTION, 1000),
+            &position,
+            &None,
+            &Tick::default(),
+            &Some([0; NUM_EXTENSION_REWARDS]),
+            &Tick::default(),
+            &None,
+            100,
+            1200,
+        )
+        .unwrap();
+
+        // The extension rewards still accrue globally
+        assert_eq!(
+            update.whirlpool_reward_extension.reward_infos[0].growth_global_x64,
+            3 << Q64_RESOLUTION
+        );
+        assert_eq!(
+            update.tick_lower_reward_growths_outside.unwrap()[0],
+            3 << Q64_RESOLUTION
+        );
+        assert!(update.tick_upper_reward_growths_outside.is_none());
+        assert!(update.position_reward_extension.is_none());
+    }
+
+    #[test]
+    fn test_position_accruing_extension_rewards_requires_tracked_ticks() {
+        let whirlpool = WhirlpoolBuilder::new()
+            .liquidity(100)
+            .tick_current_index(0)
+            .build();
+        let position = PositionBuilder::new(-10, 10).build();
+
+        let result = calculate_reward_extension_update(
+            &whirlpool,
+            &reward_extension(1 << Q64_RESOLUTION, 1000),
+            &position,
+            &Some(PositionRewardExtension::default()),
+            &Tick::default(),
+            &None,
+            &Tick::default(),
+            &Some([0; NUM_EXTENSION_REWARDS]),
+            100,
+            1200,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::RewardExtensionNotAllocated.into()
+        );
+    }
+}
@@ -3,7 +3,9 @@ use solana_program::msg;
 use crate::{
     errors::ErrorCode,
     manager::{
-        tick_manager::next_tick_cross_update, whirlpool_manager::next_whirlpool_reward_infos,
+        fee_rate_manager::FeeRateManager,
+        tick_manager::next_tick_cross_update,
+        whirlpool_manager::{next_whirlpool_reward_extension, next_whirlpool_reward_infos},
     },
     math::*,
     state::*,
@@ -21,9 +23,17 @@ pub struct PostSwapUpdate {
     pub next_sqrt_price: u128,
     pub next_fee_growth_global: u128,
     pub next_reward_infos: [WhirlpoolRewardInfo; NUM_REWARDS],
+    // None if the Whirlpool has no reward schedules
+    pub next_reward_schedule: Option<WhirlpoolRewardSchedule>,
+    // None if the Whirlpool has no reward extension
+    pub next_reward_extension: Option<WhirlpoolRewardExtension>,
     pub next_protocol_fee: u64,
+    pub next_adaptive_fee_info: Option<AdaptiveFeeInfo>,
+    // Swap fee paid in the input token (LP fee and protocol fee)
+    pub fee_amount: u64,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn swap(
     whirlpool: &Whirlpool,
     swap_tick_sequence: &mut SwapTickSequence,
@@ -32,6 +42,9 @@ pub fn swap(
     amount_specified_is_input: bool,
     a_to_b: bool,
     timestamp: u64,
+    adaptive_fee_info: &Option<AdaptiveFeeInfo>,
+    reward_schedule: &Option<WhirlpoolRewardSchedule>,
+    reward_extension: &Option<WhirlpoolRewardExtension>,
 ) -> Result<PostSwapUpdate> {
     let adjusted_sqrt_price_limit = if sqrt_price_limit == NO_EXPLICIT_SQRT_PRICE_LIMIT {
         if a_to_b {
@@ -58,9 +71,15 @@ pub fn swap(
     }
 
     let tick_spacing = whirlpool.tick_spacing;
-    let fee_rate = whirlpool.fee_rate;
     let protocol_fee_rate = whirlpool.protocol_fee_rate;
-    let next_reward_infos = next_whirlpool_reward_infos(whirlpool, timestamp)?;
+    let (next_reward_infos, next_reward_schedule) =
+        next_whirlpool_reward_infos(whirlpool, reward_schedule, timestamp)?;
+    let next_reward_extension = reward_extension
+        .as_ref()
+        .map(|reward_extension| {
+            next_whirlpool_reward_extension(whirlpool, reward_extension, timestamp)
+        })
+        .transpose()?;
 
     let mut amount_remaining: u64 = amount;
     let mut amount_calculated: u64 = 0;
@@ -68,6 +87,7 @@ pub fn swap(
     let mut curr_tick_index = whirlpool.tick_current_index;
     let mut curr_liquidity = whirlpool.liquidity;
     let mut curr_protocol_fee: u64 = 0;
+    let mut curr_fee_amount: u64 = 0;
     let mut curr_array_index: usize = 0;
     let mut curr_fee_growth_global_input = if a_to_b {
         whirlpool.fee_growth_global_a
@@ -75,6 +95,15 @@ pub fn swap(
         whirlpool.fee_growth_global_b
     };
 
+    let mut fee_rate_manager = FeeRateManager::new(
+        a_to_b,
+        whirlpool.tick_current_index,
+        whirlpool.sqrt_price,
+        timestamp,
+        whirlpool.fee_rate,
+        adaptive_fee_info,
+    );
+
     while amount_remaining > 0 && adjusted_sqrt_price_limit != curr_sqrt_price {
         let (next_array_index, next_tick_index) = swap_tick_sequence
             .get_next_initialized_tick_index(
@@ -87,12 +116,14 @@ pub fn swap(
         let (next_tick_sqrt_price, sqrt_price_target) =
             get_next_sqrt_prices(next_tick_index, adjusted_sqrt_price_limit, a_to_b);
 
+        fee_rate_manager.update_volatility_accumulator(curr_tick_index, curr_sqrt_price);
+
         let swap_computation = compute_swap(
             amount_remaining,
-            fee_rate,
+            fee_rate_manager.get_total_fee_rate(),
             curr_liquidity,
             curr_sqrt_price,
-            sqrt_price_target,
+            fee_rate_manager.get_bounded_sqrt_price_target(sqrt_price_target),
             amount_specified_is_input,
             a_to_b,
         )?;
@@ -121,6 +152,10 @@ pub fn swap(
                 .ok_or(ErrorCode::AmountCalcOverflow)?;
         }
 
+        curr_fee_amount = curr_fee_amount
+            .checked_add(swap_computation.fee_amount)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+
         let (next_protocol_fee, next_fee_growth_global_input) = calculate_fees(
             swap_computation.fee_amount,
             protocol_fee_rate,
@@ -132,6 +167,75 @@ pub fn swap(
         curr_fee_growth_global_input = next_fee_growth_global_input;
 
         if swap_computation.next_price == next_tick_sqrt_price {
+            // Limit orders resting at the tick are filled at the price of the tick before crossing it
+            if let Some(limit_orders) = swap_tick_sequence.get_fillable_limit_orders_mut(
+                next_array_index,
+                next_tick_index,
+                tick_spacing,
+                a_to_b,
+            )? {
+                let fill_computation = compute_limit_order_fill(
+                    amount_remaining,
+                    fee_rate_manager.get_total_fee_rate(),
+                    limit_orders.amount_remaining,
+                    next_tick_sqrt_price,
+                    amount_specified_is_input,
+                    a_to_b,
+                )?;
+                limit_orders.fill(fill_computation.amount_in, fill_computation.amount_out)?;
+                let limit_orders_pending = limit_orders.amount_remaining > 0;
+
+                if amount_specified_is_input {
+                    amount_remaining = amount_remaining
+                        .checked_sub(fill_computation.amount_in)
+                        .ok_or(ErrorCode::AmountRemainingOverflow)?;
+                    amount_remaining = amount_remaining
+                        .checked_sub(fill_computation.fee_amount)
+                        .ok_or(ErrorCode::AmountRemainingOverflow)?;
+
+                    amount_calculated = amount_calculated
+                        .checked_add(fill_computation.amount_out)
+                        .ok_or(ErrorCode::AmountCalcOverflow)?;
+                } else {
+                    amount_remaining = amount_remaining
+                        .checked_sub(fill_computation.amount_out)
+                        .ok_or(ErrorCode::AmountRemainingOverflow)?;
+
+                    amount_calculated = amount_calculated
+                        .checked_add(fill_computation.amount_in)
+                        .ok_or(ErrorCode::AmountCalcOverflow)?;
+                    amount_calculated = amount_calculated
+                        .checked_add(fill_computation.fee_amount)
+                        .ok_or(ErrorCode::AmountCalcOverflow)?;
+                }
+
+                curr_fee_amount = curr_fee_amount
+                    .checked_add(fill_computation.fee_amount)
+                    .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+                let (next_protocol_fee, next_fee_growth_global_input) = calculate_fees(
+                    fill_computation.fee_amount,
+                    protocol_fee_rate,
+                    curr_liquidity,
+                    curr_protocol_fee,
+                    curr_fee_growth_global_input,
+                );
+                curr_protocol_fee = next_protocol_fee;
+                curr_fee_growth_global_input = next_fee_growth_global_input;
+
+                // The tick is not crossed while orders are left unfilled,
+                // so that the next swap in this direction fills them first.
+                if limit_orders_pending {
+                    curr_tick_index = if a_to_b {
+                        next_tick_index
+                    } else {
+                        next_tick_index - 1
+                    };
+                    curr_sqrt_price = next_tick_sqrt_price;
+                    break;
+                }
+            }
+
             let (next_tick, next_tick_initialized) = swap_tick_sequence
                 .get_tick(next_array_index, next_tick_index, tick_spacing)
                 .map_or_else(|_| (None, false), |tick| (Some(tick), tick.initialized));
@@ -159,6 +263,15 @@ pub fn swap(
                     tick_spacing,
                     &update,
                 )?;
+
+                if let Some(next_reward_extension) = &next_reward_extension {
+                    swap_tick_sequence.cross_reward_extension(
+                        next_array_index,
+                        next_tick_index,
+                        tick_spacing,
+                        &next_reward_extension.reward_infos,
+                    )?;
+                }
             }
 
             let tick_offset = swap_tick_sequence.get_tick_offset(
@@ -223,7 +336,11 @@ pub fn swap(
         next_sqrt_price: curr_sqrt_price,
         next_fee_growth_global: curr_fee_growth_global_input,
         next_reward_infos,
+        next_reward_schedule,
+        next_reward_extension,
         next_protocol_fee: curr_protocol_fee,
+        next_adaptive_fee_info: fee_rate_manager.get_next_adaptive_fee_info(),
+        fee_amount: curr_fee_amount,
     })
 }
 
@@ -2825,3 +2942,464 @@ mod swap_error_tests {
         swap_test_info.run(&mut tick_sequence, 100);
     }
 }
+
+#[cfg(test)]
+mod swap_adaptive_fee_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+
+    const TICK_GROUP_SIZE: u16 = 64;
+
+    fn adaptive_fee_info() -> Option<AdaptiveFeeInfo> {
+        Some(AdaptiveFeeInfo {
+            constants: AdaptiveFeeConstants {
+                filter_period: 30,
+                decay_period: 600,
+                reduction_factor: 5_000,
+                adaptive_fee_control_factor: 4_000,
+                max_volatility_accumulator: 350_000,
+                tick_group_size: TICK_GROUP_SIZE,
+            },
+            variables: AdaptiveFeeVariables::default(),
+        })
+    }
+
+    fn run_swap(
+        trade_amount: u64,
+        a_to_b: bool,
+        adaptive_fee_info: Option<AdaptiveFeeInfo>,
+    ) -> PostSwapUpdate {
+        let swap_test_info = SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 1000,
+            start_tick_index: 0,
+            trade_amount,
+            sqrt_price_limit: if a_to_b {
+                sqrt_price_from_tick_index(-20_000)
+            } else {
+                sqrt_price_from_tick_index(30_000)
+            },
+            amount_specified_is_input: true,
+            a_to_b,
+            array_2_ticks: Some(&vec![]),
+            array_3_ticks: Some(&vec![]),
+            fee_rate: 3000,
+            adaptive_fee_info,
+            ..Default::default()
+        });
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        swap_test_info.run(&mut tick_sequence, 100)
+    }
+
+    #[test]
+    /// Without adaptive fee info, no adaptive fee state is returned.
+    fn static_fee_pool_has_no_adaptive_fee_info() {
+        let post_swap = run_swap(1_000_000, true, None);
+        assert!(post_swap.next_adaptive_fee_info.is_none());
+    }
+
+    #[test]
+    /// A trade that stays within the current tick group only pays the base fee.
+    fn swap_within_tick_group_pays_base_fee() {
+        let static_swap = run_swap(1_000_000, true, None);
+        let adaptive_swap = run_swap(1_000_000, true, adaptive_fee_info());
+
+        // 1000 is in the tick group [960, 1024)
+        assert!(adaptive_swap.next_tick_index >= 960);
+        assert_eq!(adaptive_swap.amount_a, static_swap.amount_a);
+        assert_eq!(adaptive_swap.amount_b, static_swap.amount_b);
+        assert_eq!(
+            adaptive_swap.next_fee_growth_global,
+            static_swap.next_fee_growth_global
+        );
+
+        let variables = adaptive_swap.next_adaptive_fee_info.unwrap().variables;
+        assert_eq!(variables.tick_group_index_reference, 15);
+        assert_eq!(variables.volatility_accumulator, 0);
+        assert_eq!(variables.last_reference_update_timestamp, 100);
+    }
+
+    #[test]
+    /// The fee paid is accumulated over the swap steps, variable fee included.
+    fn swap_returns_fee_amount() {
+        // fee_rate 3000 (0.3%) of the input amount
+        let static_swap = run_swap(1_000_000, true, None);
+        assert_eq!(static_swap.fee_amount, 3000);
+
+        let static_swap = run_swap(20_000_000_000, true, None);
+        let adaptive_swap = run_swap(20_000_000_000, true, adaptive_fee_info());
+        assert_eq!(static_swap.fee_amount, 60_000_000);
+        assert!(adaptive_swap.fee_amount > static_swap.fee_amount);
+    }
+
+    #[test]
+    /// A trade crossing several tick groups pays a variable fee on top of the base fee.
+    fn swap_across_tick_groups_pays_variable_fee_a_to_b() {
+        let static_swap = run_swap(20_000_000_000, true, None);
+        let adaptive_swap = run_swap(20_000_000_000, true, adaptive_fee_info());
+
+        assert_eq!(adaptive_swap.amount_a, static_swap.amount_a);
+        assert!(adaptive_swap.amount_b < static_swap.amount_b);
+        assert!(adaptive_swap.next_fee_growth_global > static_swap.next_fee_growth_global);
+        assert!(adaptive_swap.next_sqrt_price > static_swap.next_sqrt_price);
+
+        let variables = adaptive_swap.next_adaptive_fee_info.unwrap().variables;
+        let tick_groups_crossed = 15
+            - adaptive_swap
+                .next_tick_index
+                .div_euclid(TICK_GROUP_SIZE as i32);
+        assert!(tick_groups_crossed > 1);
+        assert_eq!(
+            variables.volatility_accumulator,
+            (tick_groups_crossed as u32 * VOLATILITY_ACCUMULATOR_SCALE_FACTOR).min(350_000)
+        );
+    }
+
+    #[test]
+    /// A trade crossing several tick groups pays a variable fee on top of the base fee.
+    fn swap_across_tick_groups_pays_variable_fee_b_to_a() {
+        let static_swap = run_swap(20_000_000_000, false, None);
+        let adaptive_swap = run_swap(20_000_000_000, false, adaptive_fee_info());
+
+        assert_eq!(adaptive_swap.amount_b, static_swap.amount_b);
+        assert!(adaptive_swap.amount_a < static_swap.amount_a);
+        assert!(adaptive_swap.next_sqrt_price < static_swap.next_sqrt_price);
+
+        let variables = adaptive_swap.next_adaptive_fee_info.unwrap().variables;
+        let tick_groups_crossed = adaptive_swap
+            .next_tick_index
+            .div_euclid(TICK_GROUP_SIZE as i32)
+            - 15;
+        assert!(tick_groups_crossed > 1);
+        assert_eq!(
+            variables.volatility_accumulator,
+            (tick_groups_crossed as u32 * VOLATILITY_ACCUMULATOR_SCALE_FACTOR).min(350_000)
+        );
+    }
+}
+
+#[cfg(test)]
+mod swap_limit_order_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+    use crate::util::ProxiedTickArray;
+    use std::cell::RefCell;
+
+    const FEE_RATE: u16 = 3000;
+
+    fn fixture(
+        liquidity: u128,
+        trade_amount: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> SwapTestFixture {
+        SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity,
+            curr_tick_index: 1000,
+            start_tick_index: 0,
+            trade_amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            array_2_ticks: Some(&vec![]),
+            array_3_ticks: Some(&vec![]),
+            fee_rate: FEE_RATE,
+            ..Default::default()
+        })
+    }
+
+    // The first tick array of the fixture covers [0, 11264), so every order is placed there.
+    fn build_sequence<'a>(
+        swap_test_info: &'a SwapTestFixture,
+        limit_order_book: &'a RefCell<LimitOrderBook>,
+    ) -> SwapTickSequence<'a> {
+        SwapTickSequence::new_with_proxy(
+            ProxiedTickArray::new_initialized_with_limit_order_book(
+                swap_test_info.tick_arrays[0].borrow_mut(),
+                limit_order_book.borrow_mut(),
+                None,
+            ),
+            Some(ProxiedTickArray::new_initialized(
+                swap_test_info.tick_arrays[1].borrow_mut(),
+            )),
+            Some(ProxiedTickArray::new_initialized(
+                swap_test_info.tick_arrays[2].borrow_mut(),
+            )),
+        )
+    }
+
+    fn book_with_orders(tick_index: i32, a_to_b: bool, amount: u64) -> RefCell<LimitOrderBook> {
+        let mut book = LimitOrderBook::default();
+        let offset = (tick_index / TS_128 as i32) as usize;
+        book.ticks[offset]
+            .level_mut(a_to_b)
+            .deposit(amount)
+            .unwrap();
+        RefCell::new(book)
+    }
+
+    fn level(book: &RefCell<LimitOrderBook>, tick_index: i32, a_to_b: bool) -> LimitOrderLevel {
+        let offset = (tick_index / TS_128 as i32) as usize;
+        *book.borrow().ticks[offset].level(a_to_b)
+    }
+
+    fn fee(amount_in: u64) -> u64 {
+        checked_mul_div_round_up(
+            amount_in as u128,
+            FEE_RATE as u128,
+            FEE_RATE_MUL_VALUE - FEE_RATE as u128,
+        )
+        .unwrap() as u64
+    }
+
+    #[test]
+    /// A swap crossing a tick with orders fills all of them on top of the liquidity.
+    fn swap_fills_orders_selling_a() {
+        let order_amount = 1_000_000;
+        let sqrt_price_limit = sqrt_price_from_tick_index(5000);
+
+        let static_info = fixture(
+            1_000_000_000_000,
+            u64::MAX / 4,
+            sqrt_price_limit,
+            true,
+            false,
+        );
+        let mut static_sequence = SwapTickSequence::new(
+            static_info.tick_arrays[0].borrow_mut(),
+            Some(static_info.tick_arrays[1].borrow_mut()),
+            Some(static_info.tick_arrays[2].borrow_mut()),
+        );
+        let static_swap = static_info.run(&mut static_sequence, 100);
+
+        let swap_test_info = fixture(
+            1_000_000_000_000,
+            u64::MAX / 4,
+            sqrt_price_limit,
+            true,
+            false,
+        );
+        let book = book_with_orders(1280, true, order_amount);
+        let mut tick_sequence = build_sequence(&swap_test_info, &book);
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        drop(tick_sequence);
+
+        let amount_in =
+            get_limit_order_input(order_amount, sqrt_price_from_tick_index(1280), false).unwrap();
+        assert_eq!(post_swap.next_sqrt_price, static_swap.next_sqrt_price);
+        assert_eq!(post_swap.next_tick_index, static_swap.next_tick_index);
+        assert_eq!(post_swap.amount_a, static_swap.amount_a + order_amount);
+        // the step through the liquidity is split at the tick, so its input may be rounded up once more
+        let expected_amount_b = static_swap.amount_b + amount_in + fee(amount_in);
+        assert!(post_swap.amount_b >= expected_amount_b);
+        assert!(post_swap.amount_b <= expected_amount_b + 1);
+        assert!(post_swap.next_fee_growth_global > static_swap.next_fee_growth_global);
+
+        let level = level(&book, 1280, true);
+        assert_eq!({ level.amount_remaining }, 0);
+        assert_eq!({ level.amount_filled }, amount_in);
+    }
+
+    #[test]
+    /// Orders selling token B are filled by a_to_b swaps.
+    fn swap_fills_orders_selling_b() {
+        let order_amount = 1_000_000;
+        let swap_test_info = fixture(0, 10_000_000, sqrt_price_from_tick_index(0), true, true);
+        let book = book_with_orders(768, false, order_amount);
+        let mut tick_sequence = build_sequence(&swap_test_info, &book);
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        drop(tick_sequence);
+
+        let amount_in =
+            get_limit_order_input(order_amount, sqrt_price_from_tick_index(768), true).unwrap();
+        assert_eq!(post_swap.amount_b, order_amount);
+        assert_eq!(post_swap.amount_a, amount_in + fee(amount_in));
+        assert_eq!(post_swap.next_sqrt_price, sqrt_price_from_tick_index(0));
+        assert_eq!({ level(&book, 768, false).amount_remaining }, 0);
+    }
+
+    #[test]
+    /// Orders in the same direction as the swap are not filled.
+    fn swap_skips_orders_in_same_direction() {
+        let swap_test_info = fixture(0, 10_000_000, sqrt_price_from_tick_index(5000), true, false);
+        let book = book_with_orders(1280, false, 1_000_000);
+        let mut tick_sequence = build_sequence(&swap_test_info, &book);
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        drop(tick_sequence);
+
+        assert_eq!(post_swap.amount_a, 0);
+        assert_eq!(post_swap.amount_b, 0);
+        assert_eq!(post_swap.next_sqrt_price, sqrt_price_from_tick_index(5000));
+        assert_eq!({ level(&book, 1280, false).amount_remaining }, 1_000_000);
+    }
+
+    #[test]
+    /// A partially filled tick is not crossed, and the next swap fills the rest first.
+    fn partial_fill_stops_at_tick() {
+        let order_amount = 1_000_000;
+        let sqrt_price_limit = sqrt_price_from_tick_index(5000);
+        let swap_test_info = fixture(0, 400_000, sqrt_price_limit, false, false);
+        let book = book_with_orders(1280, true, order_amount);
+
+        let mut tick_sequence = build_sequence(&swap_test_info, &book);
+        let post_swap = swap_test_info.run(&mut tick_sequence, 100);
+        drop(tick_sequence);
+
+        assert_eq!(post_swap.amount_a, 400_000);
+        assert_eq!(post_swap.next_sqrt_price, sqrt_price_from_tick_index(1280));
+        assert_eq!(post_swap.next_tick_index, 1279);
+        assert_eq!({ level(&book, 1280, true).amount_remaining }, 600_000);
+
+        // the price does not move until the remaining orders are filled
+        let mut whirlpool = swap_test_info.whirlpool.clone();
+        whirlpool.sqrt_price = post_swap.next_sqrt_price;
+        whirlpool.tick_current_index = post_swap.next_tick_index;
+        let mut tick_sequence = build_sequence(&swap_test_info, &book);
+        let post_swap = swap(
+            &whirlpool,
+            &mut tick_sequence,
+            700_000,
+            sqrt_price_limit,
+            false,
+            false,
+            100,
+            &None,
+            &None,
+            &None,
+        )
+        .unwrap();
+        drop(tick_sequence);
+
+        // no liquidity beyond the tick, so only the orders are filled
+        assert_eq!(post_swap.amount_a, 600_000);
+        assert_eq!(post_swap.next_sqrt_price, sqrt_price_limit);
+        assert_eq!({ level(&book, 1280, true).amount_remaining }, 0);
+    }
+}
+
+#[cfg(test)]
+mod swap_reward_extension_tests {
+    use super::*;
+    use crate::util::test_utils::swap_test_fixture::*;
+    use crate::util::ProxiedTickArray;
+    use std::cell::RefCell;
+
+    fn fixture(array_1_ticks: &Vec<TestTickInfo>) -> SwapTestFixture {
+        SwapTestFixture::new(SwapTestFixtureInfo {
+            tick_spacing: TS_128,
+            liquidity: 1_000_000_000_000,
+            curr_tick_index: 1000,
+            start_tick_index: 0,
+            trade_amount: u64::MAX / 4,
+            sqrt_price_limit: sqrt_price_from_tick_index(5000),
+            amount_specified_is_input: true,
+            a_to_b: false,
+            array_1_ticks,
+            array_2_ticks: Some(&vec![]),
+            array_3_ticks: Some(&vec![]),
+            fee_rate: 3000,
+            ..Default::default()
+        })
+    }
+
+    fn reward_extension() -> WhirlpoolRewardExtension {
+        let mut reward_extension = WhirlpoolRewardExtension::new(Pubkey::default(), 100);
+        reward_extension.reward_infos[0].mint = Pubkey::new_unique();
+        reward_extension.reward_infos[0].growth_global_x64 = 500;
+        reward_extension
+    }
+
+    fn run_swap(
+        swap_test_info: &SwapTestFixture,
+        tick_sequence: &mut SwapTickSequence,
+        reward_extension: &Option<WhirlpoolRewardExtension>,
+    ) -> Result<PostSwapUpdate> {
+        swap(
+            &swap_test_info.whirlpool,
+            tick_sequence,
+            swap_test_info.trade_amount,
+            swap_test_info.sqrt_price_limit,
+            swap_test_info.amount_specified_is_input,
+            swap_test_info.a_to_b,
+            100,
+            &None,
+            &None,
+            reward_extension,
+        )
+    }
+
+    #[test]
+    /// Crossing an initialized tick flips its growths outside for the extension rewards.
+    fn swap_crossing_tick_updates_reward_extension() {
+        let ticks = vec![TestTickInfo {
+            index: 1280,
+            ..Default::default()
+        }];
+        let swap_test_info = fixture(&ticks);
+        let limit_order_book = RefCell::new(LimitOrderBook::default());
+        let mut tick_array_reward_extension = TickArrayRewardExtension::default();
+        tick_array_reward_extension.reward_growths_outside[10][0] = 200;
+        let tick_array_reward_extension = RefCell::new(tick_array_reward_extension);
+
+        let mut tick_sequence = SwapTickSequence::new_with_proxy(
+            ProxiedTickArray::new_initialized_with_limit_order_book(
+                swap_test_info.tick_arrays[0].borrow_mut(),
+                limit_order_book.borrow_mut(),
+                Some(tick_array_reward_extension.borrow_mut()),
+            ),
+            Some(ProxiedTickArray::new_initialized(
+                swap_test_info.tick_arrays[1].borrow_mut(),
+            )),
+            Some(ProxiedTickArray::new_initialized(
+                swap_test_info.tick_arrays[2].borrow_mut(),
+            )),
+        );
+        let reward_extension = Some(reward_extension());
+        let post_swap = run_swap(&swap_test_info, &mut tick_sequence, &reward_extension).unwrap();
+        drop(tick_sequence);
+
+        // no time has passed, so the extension rewards are unchanged
+        assert_eq!(post_swap.next_reward_extension, reward_extension);
+        let reward_growths_outside =
+            { tick_array_reward_extension.borrow().reward_growths_outside };
+        assert_eq!(reward_growths_outside[10][0], 300);
+        assert_eq!(reward_growths_outside[10][1], 0);
+        assert_eq!(reward_growths_outside[11][0], 0);
+    }
+
+    #[test]
+    /// Ticks of a TickArray without a reward extension are crossed without tracking the extension rewards.
+    fn swap_crossing_tick_without_reward_extension() {
+        let ticks = vec![TestTickInfo {
+            index: 1280,
+            ..Default::default()
+        }];
+        let swap_test_info = fixture(&ticks);
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+
+        let reward_extension = Some(reward_extension());
+        let post_swap = run_swap(&swap_test_info, &mut tick_sequence, &reward_extension).unwrap();
+        assert_eq!(post_swap.next_reward_extension, reward_extension);
+        assert_eq!(post_swap.next_tick_index, 5000);
+        drop(tick_sequence);
+
+        let mut tick_sequence = SwapTickSequence::new(
+            swap_test_info.tick_arrays[0].borrow_mut(),
+            Some(swap_test_info.tick_arrays[1].borrow_mut()),
+            Some(swap_test_info.tick_arrays[2].borrow_mut()),
+        );
+        let post_swap = run_swap(&swap_test_info, &mut tick_sequence, &None).unwrap();
+        assert!(post_swap.next_reward_extension.is_none());
+    }
+}
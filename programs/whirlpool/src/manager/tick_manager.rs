@@ -1,7 +1,7 @@
 use crate::{
     errors::ErrorCode,
     math::add_liquidity_delta,
-    state::{Tick, TickUpdate, WhirlpoolRewardInfo, NUM_REWARDS},
+    state::{Tick, TickUpdate, WhirlpoolRewardInfo, NUM_EXTENSION_REWARDS, NUM_REWARDS},
 };
 
 pub fn next_tick_cross_update(
@@ -15,16 +15,28 @@ pub fn next_tick_cross_update(
     update.fee_growth_outside_a = fee_growth_global_a.wrapping_sub(tick.fee_growth_outside_a);
     update.fee_growth_outside_b = fee_growth_global_b.wrapping_sub(tick.fee_growth_outside_b);
 
+    update.reward_growths_outside =
+        next_reward_growths_outside_on_cross(reward_infos, &{ tick.reward_growths_outside });
+    Ok(update)
+}
+
+// Calculates the reward growths outside of a tick after the tick is crossed.
+// The growths of an uninitialized reward are left unchanged.
+pub fn next_reward_growths_outside_on_cross<const N: usize>(
+    reward_infos: &[WhirlpoolRewardInfo; N],
+    reward_growths_outside: &[u128; N],
+) -> [u128; N] {
+    let mut next_reward_growths_outside = *reward_growths_outside;
     for (i, reward_info) in reward_infos.iter().enumerate() {
         if !reward_info.initialized() {
             continue;
         }
 
-        update.reward_growths_outside[i] = reward_info
+        next_reward_growths_outside[i] = reward_info
             .growth_global_x64
-            .wrapping_sub(tick.reward_growths_outside[i]);
+            .wrapping_sub(reward_growths_outside[i]);
     }
-    Ok(update)
+    next_reward_growths_outside
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -90,6 +102,40 @@ pub fn next_tick_modify_liquidity_update(
     })
 }
 
+// Calculates the growths outside of a tick for the extension rewards after modifying liquidity,
+// following the same conventions as next_tick_modify_liquidity_update.
+pub fn next_tick_reward_extension_modify_liquidity_update(
+    tick: &Tick,
+    tick_index: i32,
+    tick_current_index: i32,
+    reward_infos: &[WhirlpoolRewardInfo; NUM_EXTENSION_REWARDS],
+    reward_growths_outside: &[u128; NUM_EXTENSION_REWARDS],
+    liquidity_delta: i128,
+) -> Result<[u128; NUM_EXTENSION_REWARDS], ErrorCode> {
+    // noop if there is no change in liquidity
+    if liquidity_delta == 0 {
+        return Ok(*reward_growths_outside);
+    }
+
+    let liquidity_gross = add_liquidity_delta(tick.liquidity_gross, liquidity_delta)?;
+
+    // Reset if remaining liquidity is being removed
+    if liquidity_gross == 0 {
+        return Ok([0; NUM_EXTENSION_REWARDS]);
+    }
+
+    if tick.liquidity_gross == 0 {
+        // By convention, assume all prior growth happened below the tick
+        if tick_current_index >= tick_index {
+            Ok(WhirlpoolRewardInfo::to_reward_growths(reward_infos))
+        } else {
+            Ok([0; NUM_EXTENSION_REWARDS])
+        }
+    } else {
+        Ok(*reward_growths_outside)
+    }
+}
+
 // Calculates the fee growths inside of tick_lower and tick_upper based on their
 // index relative to tick_current_index.
 pub fn next_fee_growths_inside(
@@ -151,33 +197,58 @@ pub fn next_reward_growths_inside(
     tick_upper_index: i32,
     reward_infos: &[WhirlpoolRewardInfo; NUM_REWARDS],
 ) -> [u128; NUM_REWARDS] {
-    let mut reward_growths_inside = [0; NUM_REWARDS];
+    calculate_reward_growths_inside(
+        tick_current_index,
+        tick_lower.initialized,
+        &{ tick_lower.reward_growths_outside },
+        tick_lower_index,
+        tick_upper.initialized,
+        &{ tick_upper.reward_growths_outside },
+        tick_upper_index,
+        reward_infos,
+    )
+}
 
-    for i in 0..NUM_REWARDS {
+// Calculates the reward growths inside of tick_lower and tick_upper from their reward growths outside.
+// Shared by the rewards of the Whirlpool and the rewards of the reward extension.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_reward_growths_inside<const N: usize>(
+    tick_current_index: i32,
+    tick_lower_initialized: bool,
+    tick_lower_reward_growths_outside: &[u128; N],
+    tick_lower_index: i32,
+    tick_upper_initialized: bool,
+    tick_upper_reward_growths_outside: &[u128; N],
+    tick_upper_index: i32,
+    reward_infos: &[WhirlpoolRewardInfo; N],
+) -> [u128; N] {
+    let mut reward_growths_inside = [0; N];
+
+    for i in 0..N {
         if !reward_infos[i].initialized() {
             continue;
         }
 
         // By convention, assume all prior growth happened below the tick
-        let reward_growths_below = if !tick_lower.initialized {
+        let reward_growths_below = if !tick_lower_initialized {
             reward_infos[i].growth_global_x64
         } else if tick_current_index < tick_lower_index {
             reward_infos[i]
                 .growth_global_x64
-                .wrapping_sub(tick_lower.reward_growths_outside[i])
+                .wrapping_sub(tick_lower_reward_growths_outside[i])
         } else {
-            tick_lower.reward_growths_outside[i]
+            tick_lower_reward_growths_outside[i]
         };
 
         // By convention, assume all prior growth happened below the tick, not above
-        let reward_growths_above = if !tick_upper.initialized {
+        let reward_growths_above = if !tick_upper_initialized {
             0
         } else if tick_current_index < tick_upper_index {
-            tick_upper.reward_growths_outside[i]
+            tick_upper_reward_growths_outside[i]
         } else {
             reward_infos[i]
                 .growth_global_x64
-                .wrapping_sub(tick_upper.reward_growths_outside[i])
+                .wrapping_sub(tick_upper_reward_growths_outside[i])
         };
 
         reward_growths_inside[i] = reward_infos[i]
@@ -196,11 +267,15 @@ mod tick_manager_tests {
     use crate::{
         errors::ErrorCode,
         manager::tick_manager::{
-            next_fee_growths_inside, next_tick_cross_update, next_tick_modify_liquidity_update,
+            next_fee_growths_inside, next_reward_growths_outside_on_cross, next_tick_cross_update,
+            next_tick_modify_liquidity_update, next_tick_reward_extension_modify_liquidity_update,
             TickUpdate,
         },
         math::Q64_RESOLUTION,
-        state::{tick_builder::TickBuilder, Tick, WhirlpoolRewardInfo, NUM_REWARDS},
+        state::{
+            tick_builder::TickBuilder, Tick, WhirlpoolRewardInfo, NUM_EXTENSION_REWARDS,
+            NUM_REWARDS,
+        },
     };
 
     use super::next_reward_growths_inside;
@@ -861,4 +936,83 @@ mod tick_manager_tests {
             }
         }
     }
+
+    #[test]
+    fn test_next_reward_growths_outside_on_cross_skips_uninitialized_rewards() {
+        let reward_infos = [
+            create_test_whirlpool_reward_info(1, 1000, true),
+            create_test_whirlpool_reward_info(1, 1000, false),
+        ];
+
+        let reward_growths_outside =
+            next_reward_growths_outside_on_cross(&reward_infos, &[300, 400]);
+        assert_eq!(reward_growths_outside, [700, 400]);
+    }
+
+    #[test]
+    fn test_next_tick_reward_extension_modify_liquidity_update() {
+        let mut reward_infos = [WhirlpoolRewardInfo::default(); NUM_EXTENSION_REWARDS];
+        reward_infos[0] = create_test_whirlpool_reward_info(1, 1000, true);
+        reward_infos[1] = create_test_whirlpool_reward_info(1, 500, true);
+        let growths_global = WhirlpoolRewardInfo::to_reward_growths(&reward_infos);
+        let growths_outside = [300, 200, 0, 0, 0];
+        let initialized_tick = TickBuilder::default().liquidity_gross(100).build();
+
+        struct Test<'a> {
+            name: &'a str,
+            tick: Tick,
+            tick_current_index: i32,
+            liquidity_delta: i128,
+            expected: [u128; NUM_EXTENSION_REWARDS],
+        }
+
+        for test in [
+            Test {
+                name: "initialize tick below current tick",
+                tick: Tick::default(),
+                tick_current_index: 10,
+                liquidity_delta: 100,
+                expected: growths_global,
+            },
+            Test {
+                name: "initialize tick above current tick",
+                tick: Tick::default(),
+                tick_current_index: -10,
+                liquidity_delta: 100,
+                expected: [0; NUM_EXTENSION_REWARDS],
+            },
+            Test {
+                name: "add liquidity to initialized tick",
+                tick: initialized_tick,
+                tick_current_index: 10,
+                liquidity_delta: 100,
+                expected: growths_outside,
+            },
+            Test {
+                name: "remove all liquidity from tick",
+                tick: initialized_tick,
+                tick_current_index: 10,
+                liquidity_delta: -100,
+                expected: [0; NUM_EXTENSION_REWARDS],
+            },
+            Test {
+                name: "zero liquidity delta",
+                tick: initialized_tick,
+                tick_current_index: -10,
+                liquidity_delta: 0,
+                expected: growths_outside,
+            },
+        ] {
+            let result = next_tick_reward_extension_modify_liquidity_update(
+                &test.tick,
+                0,
+                test.tick_current_index,
+                &reward_infos,
+                &growths_outside,
+                test.liquidity_delta,
+            )
+            .unwrap();
+            assert_eq!(result, test.expected, "{}", test.name);
+        }
+    }
 }
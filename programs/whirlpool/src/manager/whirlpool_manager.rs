@@ -4,37 +4,109 @@ use crate::state::*;
 
 // Calculates the next global reward growth variables based on the given timestamp.
 // The provided timestamp must be greater than or equal to the last updated timestamp.
+// Scheduled rewards only accrue within their schedule and up to their budget. The updated
+// schedules and ledgers of the emitted amounts are returned along with the reward infos.
 pub fn next_whirlpool_reward_infos(
     whirlpool: &Whirlpool,
+    reward_schedule: &Option<WhirlpoolRewardSchedule>,
     next_timestamp: u64,
-) -> Result<[WhirlpoolRewardInfo; NUM_REWARDS], ErrorCode> {
-    let curr_timestamp = whirlpool.reward_last_updated_timestamp;
+) -> Result<
+    (
+        [WhirlpoolRewardInfo; NUM_REWARDS],
+        Option<WhirlpoolRewardSchedule>,
+    ),
+    ErrorCode,
+> {
+    let mut next_reward_schedule = *reward_schedule;
+    let next_reward_infos = next_reward_infos(
+        &whirlpool.reward_infos,
+        next_reward_schedule
+            .as_mut()
+            .map(|reward_schedule| (&mut reward_schedule.schedules, &mut reward_schedule.ledgers)),
+        whirlpool.liquidity,
+        whirlpool.reward_last_updated_timestamp,
+        next_timestamp,
+    )?;
+
+    Ok((next_reward_infos, next_reward_schedule))
+}
+
+// Calculates the next global reward growth variables of the reward extension based on the given
+// timestamp. The extension keeps its own last updated timestamp, schedules and ledgers.
+pub fn next_whirlpool_reward_extension(
+    whirlpool: &Whirlpool,
+    reward_extension: &WhirlpoolRewardExtension,
+    next_timestamp: u64,
+) -> Result<WhirlpoolRewardExtension, ErrorCode> {
+    let mut next_reward_extension = *reward_extension;
+    let reward_infos = next_reward_infos(
+        &reward_extension.reward_infos,
+        Some((
+            &mut next_reward_extension.schedules,
+            &mut next_reward_extension.ledgers,
+        )),
+        whirlpool.liquidity,
+        reward_extension.reward_last_updated_timestamp,
+        next_timestamp,
+    )?;
+
+    next_reward_extension.update_rewards(reward_infos, next_timestamp);
+    Ok(next_reward_extension)
+}
+
+fn next_reward_infos<const N: usize>(
+    reward_infos: &[WhirlpoolRewardInfo; N],
+    mut reward_schedules: Option<(&mut [RewardSchedule; N], &mut [RewardLedger; N])>,
+    liquidity: u128,
+    curr_timestamp: u64,
+    next_timestamp: u64,
+) -> Result<[WhirlpoolRewardInfo; N], ErrorCode> {
     if next_timestamp < curr_timestamp {
         return Err(ErrorCode::InvalidTimestamp);
     }
 
     // No-op if no liquidity or no change in timestamp
-    if whirlpool.liquidity == 0 || next_timestamp == curr_timestamp {
-        return Ok(whirlpool.reward_infos);
+    if liquidity == 0 || next_timestamp == curr_timestamp {
+        return Ok(*reward_infos);
     }
 
     // Calculate new global reward growth
-    let mut next_reward_infos = whirlpool.reward_infos;
+    let mut next_reward_infos = *reward_infos;
     let time_delta = u128::from(next_timestamp - curr_timestamp);
-    for reward_info in next_reward_infos.iter_mut() {
+    for (i, reward_info) in next_reward_infos.iter_mut().enumerate() {
         if !reward_info.initialized() {
             continue;
         }
 
+        let (reward_schedule, reward_ledger) = match reward_schedules.as_mut() {
+            Some((schedules, ledgers)) => (
+                Some(&mut schedules[i]).filter(|reward_schedule| reward_schedule.is_scheduled()),
+                Some(&mut ledgers[i]),
+            ),
+            None => (None, None),
+        };
+
         // Calculate the new reward growth delta.
         // If the calculation overflows, set the delta value to zero.
         // This will halt reward distributions for this reward.
-        let reward_growth_delta = checked_mul_div(
-            time_delta,
-            reward_info.emissions_per_second_x64,
-            whirlpool.liquidity,
-        )
-        .unwrap_or(0);
+        let reward_growth_delta = match reward_schedule {
+            Some(reward_schedule) => {
+                // Emissions are capped by the remaining budget, so no overflow can occur
+                let emissions_x64 =
+                    reward_schedule.next_emissions_x64(curr_timestamp, next_timestamp);
+                reward_schedule.amount_emitted_x64 += emissions_x64;
+                emissions_x64 / liquidity
+            }
+            None => checked_mul_div(time_delta, reward_info.emissions_per_second_x64, liquidity)
+                .unwrap_or(0),
+        };
+
+        // Record the amount owed to the positions.
+        if let Some(reward_ledger) = reward_ledger {
+            reward_ledger.amount_emitted_x64 = reward_ledger
+                .amount_emitted_x64
+                .saturating_add(reward_growth_delta.saturating_mul(liquidity));
+        }
 
         // Add the reward growth delta to the global reward growth.
         let curr_growth_global = reward_info.growth_global_x64;
@@ -66,12 +138,17 @@ mod whirlpool_manager_tests {
 
     use anchor_lang::prelude::Pubkey;
 
-    use crate::manager::whirlpool_manager::next_whirlpool_reward_infos;
+    use crate::errors::ErrorCode;
+    use crate::manager::whirlpool_manager::{
+        next_whirlpool_reward_extension, next_whirlpool_reward_infos,
+    };
     use crate::math::Q64_RESOLUTION;
     use crate::state::whirlpool::WhirlpoolRewardInfo;
     use crate::state::whirlpool::NUM_REWARDS;
     use crate::state::whirlpool_builder::WhirlpoolBuilder;
-    use crate::state::Whirlpool;
+    use crate::state::{
+        RewardSchedule, Whirlpool, WhirlpoolRewardExtension, WhirlpoolRewardSchedule,
+    };
 
     // Initializes a whirlpool for testing with all the rewards initialized
     fn init_test_whirlpool(liquidity: u128, reward_last_updated_timestamp: u64) -> Whirlpool {
@@ -105,9 +182,9 @@ mod whirlpool_manager_tests {
     fn test_next_whirlpool_reward_infos_zero_liquidity_no_op() {
         let whirlpool = init_test_whirlpool(0, 1577854800);
 
-        let result = next_whirlpool_reward_infos(&whirlpool, 1577855800);
+        let result = next_whirlpool_reward_infos(&whirlpool, &None, 1577855800);
         assert_eq!(
-            WhirlpoolRewardInfo::to_reward_growths(&result.unwrap()),
+            WhirlpoolRewardInfo::to_reward_growths(&result.unwrap().0),
             [
                 100 << Q64_RESOLUTION,
                 200 << Q64_RESOLUTION,
@@ -120,9 +197,9 @@ mod whirlpool_manager_tests {
     fn test_next_whirlpool_reward_infos_same_timestamp_no_op() {
         let whirlpool = init_test_whirlpool(100, 1577854800);
 
-        let result = next_whirlpool_reward_infos(&whirlpool, 1577854800);
+        let result = next_whirlpool_reward_infos(&whirlpool, &None, 1577854800);
         assert_eq!(
-            WhirlpoolRewardInfo::to_reward_growths(&result.unwrap()),
+            WhirlpoolRewardInfo::to_reward_growths(&result.unwrap().0),
             [
                 100 << Q64_RESOLUTION,
                 200 << Q64_RESOLUTION,
@@ -140,7 +217,7 @@ mod whirlpool_manager_tests {
             .build();
 
         // New timestamp is earlier than the last updated timestamp
-        next_whirlpool_reward_infos(whirlpool, 1577768400).unwrap(); // Dec 31 2019 EST
+        next_whirlpool_reward_infos(whirlpool, &None, 1577768400).unwrap(); // Dec 31 2019 EST
     }
 
     #[test]
@@ -151,7 +228,9 @@ mod whirlpool_manager_tests {
             .build();
 
         let new_timestamp = 1577854800 + 300;
-        let result = next_whirlpool_reward_infos(whirlpool, new_timestamp).unwrap();
+        let result = next_whirlpool_reward_infos(whirlpool, &None, new_timestamp)
+            .unwrap()
+            .0;
         assert_eq!(WhirlpoolRewardInfo::to_reward_growths(&result), [0, 0, 0]);
     }
 
@@ -171,7 +250,9 @@ mod whirlpool_manager_tests {
             .build();
 
         let new_timestamp = 1577854800 + 300;
-        let result = next_whirlpool_reward_infos(whirlpool, new_timestamp).unwrap();
+        let result = next_whirlpool_reward_infos(whirlpool, &None, new_timestamp)
+            .unwrap()
+            .0;
         assert_eq!(result[0].growth_global_x64, 3 << Q64_RESOLUTION);
         for i in 1..NUM_REWARDS {
             assert_eq!(whirlpool.reward_infos[i].growth_global_x64, 0);
@@ -195,7 +276,9 @@ mod whirlpool_manager_tests {
             .build();
 
         let new_timestamp = i64::MAX as u64;
-        let result = next_whirlpool_reward_infos(whirlpool, new_timestamp).unwrap();
+        let result = next_whirlpool_reward_infos(whirlpool, &None, new_timestamp)
+            .unwrap()
+            .0;
         assert_eq!(result[0].growth_global_x64, 100);
     }
 
@@ -204,7 +287,9 @@ mod whirlpool_manager_tests {
         let whirlpool = init_test_whirlpool(100, 1577854800);
 
         let new_timestamp = 1577854800 + 300;
-        let result = next_whirlpool_reward_infos(&whirlpool, new_timestamp).unwrap();
+        let result = next_whirlpool_reward_infos(&whirlpool, &None, new_timestamp)
+            .unwrap()
+            .0;
         assert_eq!(result[0].growth_global_x64, 130 << Q64_RESOLUTION);
         assert_eq!(
             result[1].growth_global_x64,
@@ -215,4 +300,148 @@ mod whirlpool_manager_tests {
             0b1001011011 << (Q64_RESOLUTION - 1) // 301.5
         );
     }
+
+    #[test]
+    fn test_next_whirlpool_reward_extension() {
+        let whirlpool = init_test_whirlpool(100, 1577854800);
+        let mut reward_extension = WhirlpoolRewardExtension::new(Pubkey::new_unique(), 1577854500);
+        reward_extension.reward_infos[0] = WhirlpoolRewardInfo {
+            mint: Pubkey::new_unique(),
+            emissions_per_second_x64: 10 << Q64_RESOLUTION,
+            growth_global_x64: 100 << Q64_RESOLUTION,
+            ..Default::default()
+        };
+
+        // The extension accrues from its own last updated timestamp
+        let new_timestamp = 1577854800 + 300;
+        let result =
+            next_whirlpool_reward_extension(&whirlpool, &reward_extension, new_timestamp).unwrap();
+        assert_eq!(result.reward_last_updated_timestamp, new_timestamp);
+        assert_eq!(
+            result.reward_infos[0].growth_global_x64,
+            160 << Q64_RESOLUTION
+        );
+        assert_eq!(result.reward_infos[1], reward_extension.reward_infos[1]);
+
+        let result = next_whirlpool_reward_extension(&whirlpool, &reward_extension, 1577854499);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidTimestamp);
+    }
+
+    #[test]
+    fn test_next_whirlpool_reward_infos_scheduled_reward_within_schedule() {
+        let whirlpool = init_test_whirlpool(100, 1577854800);
+        let mut reward_schedule = WhirlpoolRewardSchedule::default();
+        reward_schedule.schedules[0] =
+            RewardSchedule::new(1577854800 + 100, 1577854800 + 200, 1000);
+
+        // Only 100 of the 300 seconds are within the schedule
+        let new_timestamp = 1577854800 + 300;
+        let (result, next_reward_schedule) =
+            next_whirlpool_reward_infos(&whirlpool, &Some(reward_schedule), new_timestamp).unwrap();
+        assert_eq!(result[0].growth_global_x64, 110 << Q64_RESOLUTION);
+        assert_eq!(
+            result[1].growth_global_x64,
+            0b110011001 << (Q64_RESOLUTION - 1) // 204.5
+        );
+        let next_reward_schedule = next_reward_schedule.unwrap();
+        assert_eq!(
+            next_reward_schedule.schedules[0].amount_emitted_x64,
+            1000 << Q64_RESOLUTION
+        );
+        assert_eq!(next_reward_schedule.schedules[1], RewardSchedule::default());
+
+        // The ledgers record the amounts owed to the positions of all rewards
+        assert_eq!(next_reward_schedule.ledgers[0].amount_outstanding(), 1000);
+        assert_eq!(next_reward_schedule.ledgers[1].amount_outstanding(), 450);
+        assert_eq!(next_reward_schedule.ledgers[2].amount_outstanding(), 150);
+    }
+
+    #[test]
+    fn test_next_whirlpool_reward_infos_scheduled_reward_stops_at_budget() {
+        let whirlpool = init_test_whirlpool(100, 1577854800);
+        let mut reward_schedule = WhirlpoolRewardSchedule::default();
+        reward_schedule.schedules[0] = RewardSchedule::new(1577854800, 1577854800 + 1000, 1500);
+        reward_schedule.schedules[0].amount_emitted_x64 = 1000 << Q64_RESOLUTION;
+
+        // The remaining 500 tokens are emitted over the remaining 1000 seconds
+        let new_timestamp = 1577854800 + 300;
+        let (result, next_reward_schedule) =
+            next_whirlpool_reward_infos(&whirlpool, &Some(reward_schedule), new_timestamp).unwrap();
+        assert_eq!(
+            result[0].growth_global_x64,
+            0b11001011 << (Q64_RESOLUTION - 1) // 101.5
+        );
+        assert_eq!(
+            next_reward_schedule.unwrap().schedules[0].remaining_emissions_x64(),
+            350 << Q64_RESOLUTION
+        );
+
+        // The whole budget is emitted by the end of the schedule
+        let whirlpool = init_test_whirlpool(100, new_timestamp);
+        let (result, next_reward_schedule) =
+            next_whirlpool_reward_infos(&whirlpool, &next_reward_schedule, new_timestamp + 2000)
+                .unwrap();
+        assert_eq!(
+            result[0].growth_global_x64,
+            0b11001111 << (Q64_RESOLUTION - 1) // 103.5
+        );
+        assert_eq!(
+            next_reward_schedule.unwrap().schedules[0].remaining_emissions_x64(),
+            0
+        );
+
+        // Nothing accrues once the budget is exhausted
+        let whirlpool = init_test_whirlpool(100, new_timestamp + 2000);
+        let (result, _) =
+            next_whirlpool_reward_infos(&whirlpool, &next_reward_schedule, new_timestamp + 2300)
+                .unwrap();
+        assert_eq!(result[0].growth_global_x64, 100 << Q64_RESOLUTION);
+    }
+
+    #[test]
+    fn test_next_whirlpool_reward_infos_scheduled_reward_without_liquidity() {
+        let mut reward_schedule = WhirlpoolRewardSchedule::default();
+        reward_schedule.schedules[0] = RewardSchedule::new(1577854800, 1577854800 + 1000, 1000);
+
+        // Nothing is emitted without liquidity
+        let whirlpool = init_test_whirlpool(0, 1577854800);
+        let (result, next_reward_schedule) =
+            next_whirlpool_reward_infos(&whirlpool, &Some(reward_schedule), 1577854800 + 500)
+                .unwrap();
+        assert_eq!(result[0].growth_global_x64, 100 << Q64_RESOLUTION);
+        assert_eq!(next_reward_schedule, Some(reward_schedule));
+
+        // The whole budget is emitted over the second half of the schedule
+        let whirlpool = init_test_whirlpool(100, 1577854800 + 500);
+        let (result, next_reward_schedule) =
+            next_whirlpool_reward_infos(&whirlpool, &next_reward_schedule, 1577854800 + 1000)
+                .unwrap();
+        assert_eq!(result[0].growth_global_x64, 110 << Q64_RESOLUTION);
+        assert_eq!(
+            next_reward_schedule.unwrap().ledgers[0].amount_outstanding(),
+            1000
+        );
+    }
+
+    #[test]
+    fn test_next_whirlpool_reward_extension_scheduled_reward() {
+        let whirlpool = init_test_whirlpool(100, 1577854800);
+        let mut reward_extension = WhirlpoolRewardExtension::new(Pubkey::new_unique(), 1577854800);
+        reward_extension.reward_infos[0] = WhirlpoolRewardInfo {
+            mint: Pubkey::new_unique(),
+            emissions_per_second_x64: 10 << Q64_RESOLUTION,
+            ..Default::default()
+        };
+        reward_extension.schedules[0] = RewardSchedule::new(1577854800, 1577854800 + 100, 200);
+
+        let result =
+            next_whirlpool_reward_extension(&whirlpool, &reward_extension, 1577854800 + 300)
+                .unwrap();
+        assert_eq!(
+            result.reward_infos[0].growth_global_x64,
+            2 << Q64_RESOLUTION
+        );
+        assert_eq!(result.schedules[0].remaining_emissions_x64(), 0);
+        assert_eq!(result.ledgers[0].amount_outstanding(), 200);
+    }
 }
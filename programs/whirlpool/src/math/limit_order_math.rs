@@ -0,0 +1,206 @@
+use std::convert::TryInto;
+
+use crate::errors::ErrorCode;
+use crate::math::*;
+
+#[derive(PartialEq, Debug, Default)]
+pub struct LimitOrderFillComputation {
+    // amount paid to the limit orders (fee excluded)
+    pub amount_in: u64,
+    // amount taken from the limit orders
+    pub amount_out: u64,
+    pub fee_amount: u64,
+}
+
+/// Computes how much of the resting limit orders at a tick are filled by the remaining swap amount.
+/// Limit orders are filled at the exact price of the tick, so no price movement is involved.
+///
+/// # Parameters
+/// - `amount_remaining` - The remaining amount of the swap (input or output, fee included)
+/// - `fee_rate` - The fee rate applied to the input of the swap
+/// - `order_amount` - The unfilled amount of the limit orders at the tick (output token of the swap)
+/// - `sqrt_price` - The sqrt price of the tick
+/// - `amount_specified_is_input` - If the swap is exact input
+/// - `a_to_b` - The direction of the swap
+pub fn compute_limit_order_fill(
+    amount_remaining: u64,
+    fee_rate: u16,
+    order_amount: u64,
+    sqrt_price: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Result<LimitOrderFillComputation, ErrorCode> {
+    if amount_remaining == 0 || order_amount == 0 {
+        return Ok(LimitOrderFillComputation::default());
+    }
+
+    let amount_out = if amount_specified_is_input {
+        let amount_in_after_fee: u64 = checked_mul_div(
+            amount_remaining as u128,
+            FEE_RATE_MUL_VALUE - fee_rate as u128,
+            FEE_RATE_MUL_VALUE,
+        )?
+        .try_into()?;
+        let max_amount_out = get_limit_order_output(amount_in_after_fee, sqrt_price, a_to_b)?;
+        max_amount_out.min(order_amount as u128) as u64
+    } else {
+        amount_remaining.min(order_amount)
+    };
+
+    if amount_out == 0 {
+        return Ok(LimitOrderFillComputation::default());
+    }
+
+    let amount_in = get_limit_order_input(amount_out, sqrt_price, a_to_b)?;
+
+    let is_orders_exhausted = amount_out == order_amount;
+    let fee_amount = if amount_specified_is_input && !is_orders_exhausted {
+        amount_remaining - amount_in
+    } else {
+        checked_mul_div_round_up(
+            amount_in as u128,
+            fee_rate as u128,
+            FEE_RATE_MUL_VALUE - fee_rate as u128,
+        )?
+        .try_into()?
+    };
+
+    Ok(LimitOrderFillComputation {
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+/// Converts an input amount into the output amount at the given sqrt price, rounding down.
+/// The result is not capped to the u64 range.
+pub fn get_limit_order_output(
+    amount_in: u64,
+    sqrt_price: u128,
+    a_to_b: bool,
+) -> Result<u128, ErrorCode> {
+    let price_x128 = U256::from(sqrt_price) * U256::from(sqrt_price);
+    let amount_out = if a_to_b {
+        (U256::from(amount_in) * price_x128) >> 128
+    } else {
+        (U256::from(amount_in) << 128) / price_x128
+    };
+    amount_out.try_into_u128()
+}
+
+/// Converts an output amount into the input amount required at the given sqrt price, rounding up.
+pub fn get_limit_order_input(
+    amount_out: u64,
+    sqrt_price: u128,
+    a_to_b: bool,
+) -> Result<u64, ErrorCode> {
+    let price_x128 = U256::from(sqrt_price) * U256::from(sqrt_price);
+    let (numerator, denominator) = if a_to_b {
+        (U256::from(amount_out) << 128, price_x128)
+    } else {
+        (U256::from(amount_out) * price_x128, U256::one() << 128)
+    };
+    let quotient = numerator / denominator;
+    let amount_in = if numerator % denominator > U256::zero() {
+        quotient + 1
+    } else {
+        quotient
+    };
+    amount_in.try_into_u64()
+}
+
+#[cfg(test)]
+mod limit_order_math_tests {
+    use super::*;
+
+    // price = 4.0
+    const SQRT_PRICE_2: u128 = 2u128 << 64;
+
+    #[test]
+    fn test_get_limit_order_output() {
+        assert_eq!(
+            get_limit_order_output(100, SQRT_PRICE_2, true).unwrap(),
+            400
+        );
+        assert_eq!(
+            get_limit_order_output(400, SQRT_PRICE_2, false).unwrap(),
+            100
+        );
+        // rounded down
+        assert_eq!(
+            get_limit_order_output(403, SQRT_PRICE_2, false).unwrap(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_get_limit_order_input() {
+        assert_eq!(get_limit_order_input(400, SQRT_PRICE_2, true).unwrap(), 100);
+        assert_eq!(
+            get_limit_order_input(100, SQRT_PRICE_2, false).unwrap(),
+            400
+        );
+        // rounded up
+        assert_eq!(get_limit_order_input(401, SQRT_PRICE_2, true).unwrap(), 101);
+    }
+
+    #[test]
+    fn test_get_limit_order_input_overflow() {
+        assert_eq!(
+            get_limit_order_input(u64::MAX, SQRT_PRICE_2, false),
+            Err(ErrorCode::NumberCastError)
+        );
+    }
+
+    #[test]
+    fn test_fill_exact_in_partial() {
+        // 1000 B in at 1% fee, 990 B after fee buys 247 A
+        let fill =
+            compute_limit_order_fill(1000, 10_000, 1_000, SQRT_PRICE_2, true, false).unwrap();
+        assert_eq!(
+            fill,
+            LimitOrderFillComputation {
+                amount_in: 988,
+                amount_out: 247,
+                fee_amount: 12,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fill_exact_in_exhausts_orders() {
+        let fill = compute_limit_order_fill(1000, 10_000, 100, SQRT_PRICE_2, true, false).unwrap();
+        assert_eq!(
+            fill,
+            LimitOrderFillComputation {
+                amount_in: 400,
+                amount_out: 100,
+                fee_amount: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fill_exact_out() {
+        let fill = compute_limit_order_fill(80, 0, 100, SQRT_PRICE_2, false, true).unwrap();
+        assert_eq!(
+            fill,
+            LimitOrderFillComputation {
+                amount_in: 20,
+                amount_out: 80,
+                fee_amount: 0,
+            }
+        );
+
+        let fill = compute_limit_order_fill(800, 0, 100, SQRT_PRICE_2, false, true).unwrap();
+        assert_eq!(fill.amount_out, 100);
+        assert_eq!(fill.amount_in, 25);
+    }
+
+    #[test]
+    fn test_fill_dust() {
+        // 3 B cannot buy any A at price 4
+        let fill = compute_limit_order_fill(3, 0, 100, SQRT_PRICE_2, true, false).unwrap();
+        assert_eq!(fill, LimitOrderFillComputation::default());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::errors::ErrorCode;
+use crate::math::{increasing_price_order, sqrt_price_from_tick_index, U256};
 
 // Adds a signed liquidity delta to a given integer liquidity amount.
 // Errors on overflow or underflow.
@@ -35,6 +36,95 @@ pub fn convert_to_liquidity_delta(
     })
 }
 
+// Computes the liquidity provided by amount_a over the given price range, rounded down.
+// This is the inverse of get_amount_delta_a:
+// liquidity = amount_a * sqrt_price_lower * sqrt_price_upper / ((sqrt_price_upper - sqrt_price_lower) << 64)
+pub fn get_liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_a: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    let numerator = U256::from(amount_a)
+        .checked_mul(U256::from(sqrt_price_lower))
+        .and_then(|n| n.checked_mul(U256::from(sqrt_price_upper)))
+        .ok_or(ErrorCode::MultiplicationOverflow)?;
+    let denominator = U256::from(sqrt_price_diff) << 64;
+
+    (numerator / denominator).try_into_u128()
+}
+
+// Computes the liquidity provided by amount_b over the given price range, rounded down.
+// This is the inverse of get_amount_delta_b:
+// liquidity = (amount_b << 64) / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_b: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    ((U256::from(amount_b) << 64) / U256::from(sqrt_price_diff)).try_into_u128()
+}
+
+// Computes the largest liquidity which can be added to the position range
+// without requiring more than amount_a and amount_b at the current price.
+// The current price is located in the same way as calculate_liquidity_token_deltas.
+pub fn get_liquidity_from_token_amounts(
+    tick_current_index: i32,
+    sqrt_price: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128, ErrorCode> {
+    let lower_price = sqrt_price_from_tick_index(tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(tick_upper_index);
+
+    if tick_current_index < tick_lower_index {
+        // current tick below position, only token A is required
+        get_liquidity_from_amount_a(lower_price, upper_price, amount_a)
+    } else if tick_current_index < tick_upper_index {
+        // current tick inside position, both tokens are required unless the price is on a boundary
+        let liquidity_a = if sqrt_price < upper_price {
+            Some(get_liquidity_from_amount_a(
+                sqrt_price,
+                upper_price,
+                amount_a,
+            )?)
+        } else {
+            None
+        };
+        let liquidity_b = if sqrt_price > lower_price {
+            Some(get_liquidity_from_amount_b(
+                lower_price,
+                sqrt_price,
+                amount_b,
+            )?)
+        } else {
+            None
+        };
+        match (liquidity_a, liquidity_b) {
+            (Some(a), Some(b)) => Ok(a.min(b)),
+            (Some(a), None) => Ok(a),
+            (None, Some(b)) => Ok(b),
+            (None, None) => Err(ErrorCode::LiquidityZero),
+        }
+    } else {
+        // current tick above position, only token B is required
+        get_liquidity_from_amount_b(lower_price, upper_price, amount_b)
+    }
+}
+
 #[cfg(test)]
 mod liquidity_math_tests {
     use super::add_liquidity_delta;
@@ -58,4 +148,91 @@ mod liquidity_math_tests {
         let result = add_liquidity_delta(u128::MIN, -1);
         assert_eq!(result.unwrap_err(), ErrorCode::LiquidityUnderflow);
     }
+
+    mod get_liquidity_from_token_amounts_tests {
+        use crate::math::{
+            get_amount_delta_a, get_amount_delta_b, get_liquidity_from_token_amounts,
+            sqrt_price_from_tick_index,
+        };
+
+        fn assert_max_liquidity(
+            tick_current_index: i32,
+            sqrt_price: u128,
+            tick_lower_index: i32,
+            tick_upper_index: i32,
+            amount_a: u64,
+            amount_b: u64,
+        ) -> u128 {
+            let lower_price = sqrt_price_from_tick_index(tick_lower_index);
+            let upper_price = sqrt_price_from_tick_index(tick_upper_index);
+            let deltas = |liquidity: u128| -> (u64, u64) {
+                if tick_current_index < tick_lower_index {
+                    (
+                        get_amount_delta_a(lower_price, upper_price, liquidity, true).unwrap(),
+                        0,
+                    )
+                } else if tick_current_index < tick_upper_index {
+                    (
+                        get_amount_delta_a(sqrt_price, upper_price, liquidity, true).unwrap(),
+                        get_amount_delta_b(lower_price, sqrt_price, liquidity, true).unwrap(),
+                    )
+                } else {
+                    (
+                        0,
+                        get_amount_delta_b(lower_price, upper_price, liquidity, true).unwrap(),
+                    )
+                }
+            };
+
+            let liquidity = get_liquidity_from_token_amounts(
+                tick_current_index,
+                sqrt_price,
+                tick_lower_index,
+                tick_upper_index,
+                amount_a,
+                amount_b,
+            )
+            .unwrap();
+
+            // the computed liquidity fits in the amounts
+            let (delta_a, delta_b) = deltas(liquidity);
+            assert!(delta_a <= amount_a);
+            assert!(delta_b <= amount_b);
+
+            // and is the largest liquidity which fits in the amounts
+            let (delta_a, delta_b) = deltas(liquidity + 1);
+            assert!(delta_a > amount_a || delta_b > amount_b);
+
+            liquidity
+        }
+
+        #[test]
+        fn test_below_range() {
+            let sqrt_price = sqrt_price_from_tick_index(-100);
+            assert_max_liquidity(-100, sqrt_price, 0, 128, 1_000_000, 0);
+        }
+
+        #[test]
+        fn test_above_range() {
+            let sqrt_price = sqrt_price_from_tick_index(200);
+            assert_max_liquidity(200, sqrt_price, 0, 128, 0, 1_000_000);
+        }
+
+        #[test]
+        fn test_in_range() {
+            let sqrt_price = sqrt_price_from_tick_index(64);
+            // token A is the limiting token
+            assert_max_liquidity(64, sqrt_price, 0, 128, 1_000, 1_000_000);
+            // token B is the limiting token
+            assert_max_liquidity(64, sqrt_price, 0, 128, 1_000_000, 1_000);
+        }
+
+        #[test]
+        fn test_in_range_on_lower_boundary() {
+            // price is exactly on the lower tick, so token B is not required
+            let sqrt_price = sqrt_price_from_tick_index(0);
+            let liquidity = assert_max_liquidity(0, sqrt_price, 0, 128, 1_000_000, 0);
+            assert!(liquidity > 0);
+        }
+    }
 }
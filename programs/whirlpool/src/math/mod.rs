@@ -1,5 +1,6 @@
 pub mod bit_math;
 pub mod bn;
+pub mod limit_order_math;
 pub mod liquidity_math;
 pub mod swap_math;
 pub mod tick_math;
@@ -8,6 +9,7 @@ pub mod u256_math;
 
 pub use bit_math::*;
 pub use bn::*;
+pub use limit_order_math::*;
 pub use liquidity_math::*;
 pub use swap_math::*;
 pub use tick_math::*;
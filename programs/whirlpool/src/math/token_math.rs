@@ -24,6 +24,15 @@ pub const MAX_PROTOCOL_FEE_RATE: u16 = 2_500;
 // We want PROTOCOL_FEE_RATE_MUL_VALUE = 1/PROTOCOL_FEE_UNIT, so 1e4
 pub const PROTOCOL_FEE_RATE_MUL_VALUE: u128 = 10_000;
 
+// Referral fee rate is represented as a basis point.
+// Referral fee amount = swap_amount * referral_fee_rate / 10_000.
+// Max referral fee rate supported is 10%, a WhirlpoolsConfig can set a lower cap.
+pub const MAX_REFERRAL_FEE_RATE: u16 = 1_000;
+
+// Assuming that REFERRAL_FEE_RATE is represented as a basis point
+// We want REFERRAL_FEE_RATE_MUL_VALUE = 1/REFERRAL_FEE_UNIT, so 1e4
+pub const REFERRAL_FEE_RATE_MUL_VALUE: u128 = 10_000;
+
 #[derive(Debug)]
 pub enum AmountDeltaU64 {
     Valid(u64),
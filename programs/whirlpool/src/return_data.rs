@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+
+use crate::manager::swap_manager::PostSwapUpdate;
+use crate::state::RewardLedger;
+
+// Results are published with `set_return_data` at the end of the swap and liquidity instructions,
+// so that programs calling them through CPI don't have to reload their token accounts.
+// They are read with `get_return_data` right after the CPI and decoded with Borsh.
+//
+// As in the events, amounts are the amounts moved in or out of the pool vaults (transfer fee included).
+// The `_after_transfer_fee` fields hold the amounts left once the Token-2022 TransferFee extension
+// has withheld its portion, and are equal to the amounts for mints owned by the Token program.
+
+/// Result of a swap through a single Whirlpool.
+///
+/// Two-hop and multi-hop swaps return one result per hop (`Vec<SwapResult>`),
+/// in which the output of a hop is the input of the next hop.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapResult {
+    pub amount_in: u64,
+    // Amount received by the input vault
+    pub amount_in_after_transfer_fee: u64,
+    pub amount_out: u64,
+    // Amount received by the destination token account (referral fee excluded)
+    pub amount_out_after_transfer_fee: u64,
+    // Swap fee paid in the input token (LP fee and protocol fee)
+    pub fee_amount: u64,
+    // Referral fee charged on top of amount_in, or deducted from amount_out (swap_v2_with_referral_fee only)
+    pub referral_fee_amount: u64,
+    pub post_sqrt_price: u128,
+    pub post_tick_index: i32,
+}
+
+impl SwapResult {
+    pub fn new(
+        swap_update: &PostSwapUpdate,
+        a_to_b: bool,
+        input_transfer_fee: u64,
+        output_transfer_fee: u64,
+    ) -> Self {
+        let (amount_in, amount_out) = if a_to_b {
+            (swap_update.amount_a, swap_update.amount_b)
+        } else {
+            (swap_update.amount_b, swap_update.amount_a)
+        };
+        Self {
+            amount_in,
+            amount_in_after_transfer_fee: amount_in.saturating_sub(input_transfer_fee),
+            amount_out,
+            amount_out_after_transfer_fee: amount_out.saturating_sub(output_transfer_fee),
+            fee_amount: swap_update.fee_amount,
+            referral_fee_amount: 0,
+            post_sqrt_price: swap_update.next_sqrt_price,
+            post_tick_index: swap_update.next_tick_index,
+        }
+    }
+}
+
+/// Result of an increase or decrease of the liquidity of a position.
+///
+/// On increase, the amounts are sent by the token owner accounts and the vaults receive the amounts
+/// after transfer fee. On decrease, the vaults send the amounts and the token owner accounts receive
+/// the amounts after transfer fee.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ModifyLiquidityResult {
+    pub liquidity_delta: u128,
+    pub token_a_amount: u64,
+    pub token_a_amount_after_transfer_fee: u64,
+    pub token_b_amount: u64,
+    pub token_b_amount_after_transfer_fee: u64,
+}
+
+impl ModifyLiquidityResult {
+    pub fn new(
+        liquidity_delta: u128,
+        token_a_amount: u64,
+        token_a_transfer_fee: u64,
+        token_b_amount: u64,
+        token_b_transfer_fee: u64,
+    ) -> Self {
+        Self {
+            liquidity_delta,
+            token_a_amount,
+            token_a_amount_after_transfer_fee: token_a_amount.saturating_sub(token_a_transfer_fee),
+            token_b_amount,
+            token_b_amount_after_transfer_fee: token_b_amount.saturating_sub(token_b_transfer_fee),
+        }
+    }
+}
+
+/// Solvency of a reward of a Whirlpool, as of the current timestamp.
+///
+/// The reward vault is solvent if it holds all of the rewards emitted to the positions
+/// and not collected yet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardSolvency {
+    pub vault_amount: u64,
+    pub amount_outstanding: u64,
+    // Amount in the vault which is not owed to the positions
+    pub amount_available: u64,
+    pub is_solvent: bool,
+}
+
+impl RewardSolvency {
+    pub fn new(ledger: &RewardLedger, vault_amount: u64) -> Self {
+        Self {
+            vault_amount,
+            amount_outstanding: ledger.amount_outstanding(),
+            amount_available: ledger.amount_available(vault_amount),
+            is_solvent: ledger.is_solvent(vault_amount),
+        }
+    }
+}
+
+/// Publishes the Borsh-encoded result of the instruction.
+///
+/// The return data is cleared by every CPI, including `emit_cpi!`,
+/// so this must be the last call of the handler, after the events are emitted.
+pub fn set_return_data<T: AnchorSerialize>(result: &T) -> Result<()> {
+    solana_program::program::set_return_data(&result.try_to_vec()?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod return_data_tests {
+    use super::*;
+    use crate::state::{WhirlpoolRewardInfo, NUM_REWARDS};
+
+    fn post_swap_update(amount_a: u64, amount_b: u64, fee_amount: u64) -> PostSwapUpdate {
+        PostSwapUpdate {
+            amount_a,
+            amount_b,
+            next_liquidity: 1_000_000,
+            next_tick_index: -100,
+            next_sqrt_price: 1 << 63,
+            next_fee_growth_global: 0,
+            next_reward_infos: [WhirlpoolRewardInfo::default(); NUM_REWARDS],
+            next_reward_schedule: None,
+            next_reward_extension: None,
+            next_protocol_fee: 0,
+            next_adaptive_fee_info: None,
+            fee_amount,
+        }
+    }
+
+    #[test]
+    fn test_swap_result_a_to_b() {
+        let swap_result = SwapResult::new(&post_swap_update(1000, 900, 3), true, 10, 9);
+        assert_eq!(
+            swap_result,
+            SwapResult {
+                amount_in: 1000,
+                amount_in_after_transfer_fee: 990,
+                amount_out: 900,
+                amount_out_after_transfer_fee: 891,
+                fee_amount: 3,
+                referral_fee_amount: 0,
+                post_sqrt_price: 1 << 63,
+                post_tick_index: -100,
+            }
+        );
+    }
+
+    #[test]
+    fn test_swap_result_b_to_a() {
+        let swap_result = SwapResult::new(&post_swap_update(900, 1000, 3), false, 0, 0);
+        assert_eq!(swap_result.amount_in, 1000);
+        assert_eq!(swap_result.amount_in_after_transfer_fee, 1000);
+        assert_eq!(swap_result.amount_out, 900);
+        assert_eq!(swap_result.amount_out_after_transfer_fee, 900);
+    }
+
+    #[test]
+    fn test_swap_result_serialization() {
+        let swap_result = SwapResult::new(&post_swap_update(1000, 900, 3), true, 10, 9);
+        let data = swap_result.try_to_vec().unwrap();
+        // 6 u64 + u128 + i32
+        assert_eq!(data.len(), 6 * 8 + 16 + 4);
+        assert_eq!(SwapResult::try_from_slice(&data).unwrap(), swap_result);
+
+        let swap_results = vec![swap_result; 2];
+        let data = swap_results.try_to_vec().unwrap();
+        assert_eq!(
+            Vec::<SwapResult>::try_from_slice(&data).unwrap(),
+            swap_results
+        );
+    }
+
+    #[test]
+    fn test_reward_solvency() {
+        let ledger = RewardLedger {
+            amount_emitted_x64: (150 << 64) + 1,
+            amount_collected: 50,
+        };
+
+        let solvency = RewardSolvency::new(&ledger, 120);
+        assert_eq!(
+            solvency,
+            RewardSolvency {
+                vault_amount: 120,
+                amount_outstanding: 101,
+                amount_available: 19,
+                is_solvent: true,
+            }
+        );
+        assert!(!RewardSolvency::new(&ledger, 100).is_solvent);
+
+        let data = solvency.try_to_vec().unwrap();
+        assert_eq!(data.len(), 3 * 8 + 1);
+        assert_eq!(RewardSolvency::try_from_slice(&data).unwrap(), solvency);
+    }
+
+    #[test]
+    fn test_modify_liquidity_result() {
+        let result = ModifyLiquidityResult::new(5000, 100, 1, 200, 0);
+        assert_eq!(result.token_a_amount_after_transfer_fee, 99);
+        assert_eq!(result.token_b_amount_after_transfer_fee, 200);
+
+        let data = result.try_to_vec().unwrap();
+        assert_eq!(data.len(), 16 + 4 * 8);
+        assert_eq!(
+            ModifyLiquidityResult::try_from_slice(&data).unwrap(),
+            result
+        );
+    }
+}
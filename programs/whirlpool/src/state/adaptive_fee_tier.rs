@@ -0,0 +1,134 @@
+use crate::state::{AdaptiveFeeConstants, WhirlpoolsConfig};
+use crate::{errors::ErrorCode, math::MAX_FEE_RATE};
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct AdaptiveFeeTier {
+    pub whirlpools_config: Pubkey,
+    // Shares the PDA seed space with FeeTier::tick_spacing, so it must not equal tick_spacing
+    pub fee_tier_index: u16,
+    pub tick_spacing: u16,
+    pub default_base_fee_rate: u16,
+    pub adaptive_fee_constants: AdaptiveFeeConstants,
+}
+
+impl AdaptiveFeeTier {
+    pub const LEN: usize = 8 + 32 + 2 + 2 + 2 + AdaptiveFeeConstants::LEN;
+
+    pub fn initialize(
+        &mut self,
+        whirlpools_config: &Account<WhirlpoolsConfig>,
+        fee_tier_index: u16,
+        tick_spacing: u16,
+        default_base_fee_rate: u16,
+        adaptive_fee_constants: AdaptiveFeeConstants,
+    ) -> Result<()> {
+        if fee_tier_index == tick_spacing {
+            return Err(ErrorCode::InvalidFeeTierIndex.into());
+        }
+
+        if tick_spacing == 0 {
+            return Err(ErrorCode::InvalidTickSpacing.into());
+        }
+
+        self.whirlpools_config = whirlpools_config.key();
+        self.fee_tier_index = fee_tier_index;
+        self.tick_spacing = tick_spacing;
+        self.update_default_base_fee_rate(default_base_fee_rate)?;
+        self.update_adaptive_fee_constants(adaptive_fee_constants)?;
+        Ok(())
+    }
+
+    pub fn update_default_base_fee_rate(&mut self, default_base_fee_rate: u16) -> Result<()> {
+        if default_base_fee_rate > MAX_FEE_RATE {
+            return Err(ErrorCode::FeeRateMaxExceeded.into());
+        }
+        self.default_base_fee_rate = default_base_fee_rate;
+
+        Ok(())
+    }
+
+    pub fn update_adaptive_fee_constants(
+        &mut self,
+        adaptive_fee_constants: AdaptiveFeeConstants,
+    ) -> Result<()> {
+        if !adaptive_fee_constants.validate(self.tick_spacing) {
+            return Err(ErrorCode::InvalidAdaptiveFeeConstants.into());
+        }
+        self.adaptive_fee_constants = adaptive_fee_constants;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_adaptive_fee_tier_data_layout() {
+        let whirlpools_config = Pubkey::new_unique();
+        let fee_tier_index = 0x1234u16;
+        let tick_spacing = 0x00ffu16;
+        let default_base_fee_rate = 0x22u16;
+        let filter_period = 0x1122u16;
+        let decay_period = 0x3344u16;
+        let reduction_factor = 0x5566u16;
+        let adaptive_fee_control_factor = 0x778899aau32;
+        let max_volatility_accumulator = 0xbbccddeeu32;
+        let tick_group_size = 0xff00u16;
+
+        let mut data = [0u8; AdaptiveFeeTier::LEN];
+        let mut offset = 0;
+        data[offset..offset + 8].copy_from_slice(&AdaptiveFeeTier::discriminator());
+        offset += 8;
+        data[offset..offset + 32].copy_from_slice(&whirlpools_config.to_bytes());
+        offset += 32;
+        data[offset..offset + 2].copy_from_slice(&fee_tier_index.to_le_bytes());
+        offset += 2;
+        data[offset..offset + 2].copy_from_slice(&tick_spacing.to_le_bytes());
+        offset += 2;
+        data[offset..offset + 2].copy_from_slice(&default_base_fee_rate.to_le_bytes());
+        offset += 2;
+        data[offset..offset + 2].copy_from_slice(&filter_period.to_le_bytes());
+        offset += 2;
+        data[offset..offset + 2].copy_from_slice(&decay_period.to_le_bytes());
+        offset += 2;
+        data[offset..offset + 2].copy_from_slice(&reduction_factor.to_le_bytes());
+        offset += 2;
+        data[offset..offset + 4].copy_from_slice(&adaptive_fee_control_factor.to_le_bytes());
+        offset += 4;
+        data[offset..offset + 4].copy_from_slice(&max_volatility_accumulator.to_le_bytes());
+        offset += 4;
+        data[offset..offset + 2].copy_from_slice(&tick_group_size.to_le_bytes());
+        offset += 2;
+        assert_eq!(offset, AdaptiveFeeTier::LEN);
+
+        // deserialize
+        let deserialized = AdaptiveFeeTier::try_deserialize(&mut data.as_ref()).unwrap();
+
+        assert_eq!(whirlpools_config, deserialized.whirlpools_config);
+        assert_eq!(fee_tier_index, deserialized.fee_tier_index);
+        assert_eq!(tick_spacing, deserialized.tick_spacing);
+        assert_eq!(default_base_fee_rate, deserialized.default_base_fee_rate);
+        assert_eq!(
+            AdaptiveFeeConstants {
+                filter_period,
+                decay_period,
+                reduction_factor,
+                adaptive_fee_control_factor,
+                max_volatility_accumulator,
+                tick_group_size,
+            },
+            deserialized.adaptive_fee_constants
+        );
+
+        // serialize
+        let mut serialized = Vec::new();
+        deserialized.try_serialize(&mut serialized).unwrap();
+
+        assert_eq!(serialized.as_slice(), data.as_ref());
+    }
+}
@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
 
+use crate::{errors::ErrorCode, math::MAX_REFERRAL_FEE_RATE};
+
 #[account]
 pub struct WhirlpoolsConfigExtension {
     pub whirlpools_config: Pubkey,          // 32
     pub config_extension_authority: Pubkey, // 32
     pub token_badge_authority: Pubkey,      // 32
-                                            // 512 RESERVE
+
+    // Pauses all of the Whirlpools in the WhirlpoolsConfig
+    // (each Whirlpool can also be paused individually, see WhirlpoolPause)
+    pub paused: bool, // 1
+
+    // Cap of the referral fee rate integrators can charge on swaps, stored as basis points
+    pub max_referral_fee_rate: u16, // 2
+                                    // 509 RESERVE
 }
 
 impl WhirlpoolsConfigExtension {
@@ -29,6 +38,19 @@ impl WhirlpoolsConfigExtension {
     pub fn update_token_badge_authority(&mut self, token_badge_authority: Pubkey) {
         self.token_badge_authority = token_badge_authority;
     }
+
+    pub fn update_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn update_max_referral_fee_rate(&mut self, max_referral_fee_rate: u16) -> Result<()> {
+        if max_referral_fee_rate > MAX_REFERRAL_FEE_RATE {
+            return Err(ErrorCode::ReferralFeeRateMaxExceeded.into());
+        }
+        self.max_referral_fee_rate = max_referral_fee_rate;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -42,6 +64,8 @@ mod whirlpools_config_extension_initialize_tests {
             whirlpools_config: Pubkey::default(),
             config_extension_authority: Pubkey::default(),
             token_badge_authority: Pubkey::default(),
+            paused: false,
+            max_referral_fee_rate: 0,
         };
 
         let whirlpools_config =
@@ -72,6 +96,8 @@ mod whirlpools_config_extension_update_tests {
             whirlpools_config: Pubkey::default(),
             config_extension_authority: Pubkey::default(),
             token_badge_authority: Pubkey::default(),
+            paused: false,
+            max_referral_fee_rate: 0,
         };
 
         let config_extension_authority =
@@ -92,6 +118,8 @@ mod whirlpools_config_extension_update_tests {
             whirlpools_config: Pubkey::default(),
             config_extension_authority: Pubkey::default(),
             token_badge_authority: Pubkey::default(),
+            paused: false,
+            max_referral_fee_rate: 0,
         };
 
         let token_badge_authority =
@@ -108,6 +136,57 @@ mod whirlpools_config_extension_update_tests {
             config_extension.config_extension_authority
         );
     }
+
+    #[test]
+    fn test_update_max_referral_fee_rate() {
+        let mut config_extension = WhirlpoolsConfigExtension {
+            whirlpools_config: Pubkey::default(),
+            config_extension_authority: Pubkey::default(),
+            token_badge_authority: Pubkey::default(),
+            paused: false,
+            max_referral_fee_rate: 0,
+        };
+
+        config_extension
+            .update_max_referral_fee_rate(MAX_REFERRAL_FEE_RATE)
+            .unwrap();
+        assert_eq!(
+            MAX_REFERRAL_FEE_RATE,
+            config_extension.max_referral_fee_rate
+        );
+
+        let result = config_extension.update_max_referral_fee_rate(MAX_REFERRAL_FEE_RATE + 1);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::ReferralFeeRateMaxExceeded.into()
+        );
+        assert_eq!(
+            MAX_REFERRAL_FEE_RATE,
+            config_extension.max_referral_fee_rate
+        );
+    }
+}
+
+#[cfg(test)]
+mod whirlpools_config_extension_pause_tests {
+    use super::*;
+
+    #[test]
+    fn test_update_paused() {
+        let mut config_extension = WhirlpoolsConfigExtension {
+            whirlpools_config: Pubkey::default(),
+            config_extension_authority: Pubkey::default(),
+            token_badge_authority: Pubkey::default(),
+            paused: false,
+            max_referral_fee_rate: 0,
+        };
+
+        config_extension.update_paused(true);
+        assert!(config_extension.paused);
+
+        config_extension.update_paused(false);
+        assert!(!config_extension.paused);
+    }
 }
 
 #[cfg(test)]
@@ -121,7 +200,9 @@ mod data_layout_tests {
         let config_extension_whirlpools_config = Pubkey::new_unique();
         let config_extension_config_extension_authority = Pubkey::new_unique();
         let config_extension_token_badge_authority = Pubkey::new_unique();
-        let config_extension_reserved = [0u8; 512];
+        let config_extension_paused = true;
+        let config_extension_max_referral_fee_rate = 0x1122u16;
+        let config_extension_reserved = [0u8; 509];
 
         let mut config_extension_data = [0u8; WhirlpoolsConfigExtension::LEN];
         let mut offset = 0;
@@ -137,6 +218,11 @@ mod data_layout_tests {
         config_extension_data[offset..offset + 32]
             .copy_from_slice(&config_extension_token_badge_authority.to_bytes());
         offset += 32;
+        config_extension_data[offset] = config_extension_paused as u8;
+        offset += 1;
+        config_extension_data[offset..offset + 2]
+            .copy_from_slice(&config_extension_max_referral_fee_rate.to_le_bytes());
+        offset += 2;
         config_extension_data[offset..offset + config_extension_reserved.len()]
             .copy_from_slice(&config_extension_reserved);
         offset += config_extension_reserved.len();
@@ -159,6 +245,11 @@ mod data_layout_tests {
             config_extension_token_badge_authority,
             deserialized.token_badge_authority
         );
+        assert_eq!(config_extension_paused, deserialized.paused);
+        assert_eq!(
+            config_extension_max_referral_fee_rate,
+            deserialized.max_referral_fee_rate
+        );
 
         // serialize
         let mut serialized = Vec::new();
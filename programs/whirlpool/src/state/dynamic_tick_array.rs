@@ -0,0 +1,465 @@
+use std::cell::RefMut;
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+use super::{Tick, TickArrayType, TickUpdate, Whirlpool, TICK_ARRAY_SIZE, TICK_ARRAY_SIZE_USIZE};
+
+// DynamicTickArray covers the same range of ticks as TickArray, but only stores its initialized ticks.
+// The account data is laid out as [discriminator][DynamicTickArray][DynamicTick; initialized tick count],
+// where the initialized ticks are ordered by their offset in the array.
+// The account grows and shrinks by DynamicTick::LEN as ticks are initialized and uninitialized.
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct DynamicTickArray {
+    pub start_tick_index: i32, // 4
+    pub whirlpool: Pubkey,     // 32
+    // The bit at position i is set if the tick at offset i is initialized
+    pub tick_bitmap: u128, // 16
+    // Payer of the rent, refunded when the DynamicTickArray is closed
+    pub funder: Pubkey, // 32
+}
+
+// An initialized tick of a DynamicTickArray, with the payer of the rent for its space
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct DynamicTick {
+    pub tick: Tick,         // 113
+    pub rent_payer: Pubkey, // 32
+    pub rent: u64,          // 8
+}
+
+// Pod is implemented in the same way as for Tick to map the ticks onto the account data.
+unsafe impl bytemuck::Pod for DynamicTick {}
+unsafe impl bytemuck::Zeroable for DynamicTick {}
+
+impl DynamicTick {
+    pub const LEN: usize = Tick::LEN + 32 + 8;
+}
+
+impl DynamicTickArray {
+    pub const MIN_LEN: usize = 8 + 4 + 32 + 16 + 32;
+    pub const MAX_LEN: usize = DynamicTickArray::MIN_LEN + DynamicTick::LEN * TICK_ARRAY_SIZE_USIZE;
+
+    /// Initialize the DynamicTickArray object
+    ///
+    /// # Parameters
+    /// - `whirlpool` - the Whirlpool this DynamicTickArray belongs to
+    /// - `start_tick_index` - the first tick index covered by this DynamicTickArray
+    /// - `funder` - the payer of the rent
+    ///
+    /// # Errors
+    /// - `InvalidStartTick`: - The provided start-tick-index is not an initializable tick index in this Whirlpool w/ this tick-spacing.
+    pub fn initialize(
+        &mut self,
+        whirlpool: &Account<Whirlpool>,
+        start_tick_index: i32,
+        funder: Pubkey,
+    ) -> Result<()> {
+        if !Tick::check_is_valid_start_tick(start_tick_index, whirlpool.tick_spacing) {
+            return Err(ErrorCode::InvalidStartTick.into());
+        }
+
+        self.whirlpool = whirlpool.key();
+        self.start_tick_index = start_tick_index;
+        self.tick_bitmap = 0;
+        self.funder = funder;
+        Ok(())
+    }
+
+    /// Returns the account data length required to store the given number of initialized ticks.
+    pub fn required_len(initialized_tick_count: usize) -> usize {
+        DynamicTickArray::MIN_LEN + DynamicTick::LEN * initialized_tick_count
+    }
+
+    pub fn initialized_tick_count(&self) -> usize {
+        self.tick_bitmap.count_ones() as usize
+    }
+
+    pub fn is_tick_initialized(&self, offset: usize) -> bool {
+        (self.tick_bitmap >> offset) & 1 == 1
+    }
+
+    /// Returns the position of the tick at the given offset among the stored (initialized) ticks.
+    pub fn tick_position(&self, offset: usize) -> usize {
+        (self.tick_bitmap & ((1u128 << offset) - 1)).count_ones() as usize
+    }
+
+    /// Inserts a zeroed tick at the given offset into the account data, records the payer of the
+    /// rent for its space and marks it as initialized.
+    /// The account data must already have been extended by DynamicTick::LEN bytes.
+    pub fn insert_tick(data: &mut [u8], offset: usize, rent_payer: Pubkey, rent: u64) {
+        let header: &mut DynamicTickArray =
+            bytemuck::from_bytes_mut(&mut data[8..DynamicTickArray::MIN_LEN]);
+        let start = DynamicTickArray::MIN_LEN + header.tick_position(offset) * DynamicTick::LEN;
+        header.tick_bitmap |= 1u128 << offset;
+
+        let end = data.len() - DynamicTick::LEN;
+        data.copy_within(start..end, start + DynamicTick::LEN);
+        let tick: &mut DynamicTick =
+            bytemuck::from_bytes_mut(&mut data[start..start + DynamicTick::LEN]);
+        *tick = DynamicTick {
+            rent_payer,
+            rent,
+            ..Default::default()
+        };
+    }
+
+    /// Removes the tick at the given offset from the account data, marks it as uninitialized and
+    /// returns the removed tick.
+    /// The account data must be shrunk by DynamicTick::LEN bytes afterwards.
+    pub fn remove_tick(data: &mut [u8], offset: usize) -> DynamicTick {
+        let header: &mut DynamicTickArray =
+            bytemuck::from_bytes_mut(&mut data[8..DynamicTickArray::MIN_LEN]);
+        let start = DynamicTickArray::MIN_LEN + header.tick_position(offset) * DynamicTick::LEN;
+        header.tick_bitmap &= !(1u128 << offset);
+
+        let removed: DynamicTick = *bytemuck::from_bytes(&data[start..start + DynamicTick::LEN]);
+        let end = data.len();
+        data.copy_within(start + DynamicTick::LEN..end, start);
+        data[end - DynamicTick::LEN..end].fill(0);
+        removed
+    }
+}
+
+/// Mutable view over the account data of a DynamicTickArray.
+/// Uninitialized ticks are substituted by Tick::default() in the same way as ZeroedTickArray.
+pub struct DynamicTickArrayMut<'a> {
+    header: RefMut<'a, DynamicTickArray>,
+    ticks: RefMut<'a, [DynamicTick]>,
+    zeroed_tick: Tick,
+}
+
+impl<'a> DynamicTickArrayMut<'a> {
+    /// Splits the account data (discriminator included) into the header and the initialized ticks.
+    ///
+    /// # Errors
+    /// - `AccountDidNotDeserialize` - If the data length does not match the number of initialized ticks
+    pub fn load(data: RefMut<'a, &mut [u8]>) -> Result<Self> {
+        if data.len() < DynamicTickArray::MIN_LEN {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+        let header: &DynamicTickArray = bytemuck::from_bytes(&data[8..DynamicTickArray::MIN_LEN]);
+        if data.len() != DynamicTickArray::required_len(header.initialized_tick_count()) {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+
+        let (header, ticks) = RefMut::map_split(data, |data| {
+            let (header, ticks) = data.deref_mut().split_at_mut(DynamicTickArray::MIN_LEN);
+            (
+                bytemuck::from_bytes_mut(&mut header[8..]),
+                bytemuck::cast_slice_mut(ticks),
+            )
+        });
+
+        Ok(DynamicTickArrayMut {
+            header,
+            ticks,
+            zeroed_tick: Tick::default(),
+        })
+    }
+
+    pub fn whirlpool(&self) -> Pubkey {
+        self.header.whirlpool
+    }
+
+    pub fn funder(&self) -> Pubkey {
+        self.header.funder
+    }
+
+    /// Returns true if no tick in this array is initialized.
+    pub fn is_empty(&self) -> bool {
+        self.header.tick_bitmap == 0
+    }
+
+    fn checked_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        if !self.check_in_array_bounds(tick_index, tick_spacing)
+            || !Tick::check_is_usable_tick(tick_index, tick_spacing)
+        {
+            return Err(ErrorCode::TickNotFound.into());
+        }
+        let offset = self.tick_offset(tick_index, tick_spacing)?;
+        if offset < 0 {
+            return Err(ErrorCode::TickNotFound.into());
+        }
+        Ok(offset as usize)
+    }
+}
+
+impl TickArrayType for DynamicTickArrayMut<'_> {
+    fn start_tick_index(&self) -> i32 {
+        self.header.start_tick_index
+    }
+
+    fn get_next_init_tick_index(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>> {
+        if !self.in_search_range(tick_index, tick_spacing, !a_to_b) {
+            return Err(ErrorCode::InvalidTickArraySequence.into());
+        }
+
+        let mut curr_offset = self.tick_offset(tick_index, tick_spacing)? as i32;
+
+        // For a_to_b searches, the search moves to the left. The next possible init-tick can be the 1st tick in the current offset
+        // For b_to_a searches, the search moves to the right. The next possible init-tick cannot be within the current offset
+        if !a_to_b {
+            curr_offset += 1;
+        }
+
+        while (0..TICK_ARRAY_SIZE).contains(&curr_offset) {
+            if self.header.is_tick_initialized(curr_offset as usize) {
+                return Ok(Some(
+                    (curr_offset * tick_spacing as i32) + self.start_tick_index(),
+                ));
+            }
+
+            curr_offset = if a_to_b {
+                curr_offset - 1
+            } else {
+                curr_offset + 1
+            };
+        }
+
+        Ok(None)
+    }
+
+    fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
+        let offset = self.checked_offset(tick_index, tick_spacing)?;
+        if !self.header.is_tick_initialized(offset) {
+            return Ok(&self.zeroed_tick);
+        }
+        Ok(&self.ticks[self.header.tick_position(offset)].tick)
+    }
+
+    /// Updates an initialized Tick in place.
+    /// Initializing or uninitializing a tick changes the account size, so it must be done through
+    /// DynamicTickArray::insert_tick and DynamicTickArray::remove_tick instead.
+    ///
+    /// # Errors
+    /// - `TickNotFound`: - The provided tick-index is not an initializable tick index in this Whirlpool w/ this tick-spacing.
+    /// - `DynamicTickArrayResizeRequired`: - The update changes the initialized state of the tick.
+    fn update_tick(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        update: &TickUpdate,
+    ) -> Result<()> {
+        let offset = self.checked_offset(tick_index, tick_spacing)?;
+        match (self.header.is_tick_initialized(offset), update.initialized) {
+            (true, true) => {
+                let position = self.header.tick_position(offset);
+                self.ticks[position].tick.update(update);
+                Ok(())
+            }
+            // uninitialized ticks are not stored
+            (false, false) => Ok(()),
+            _ => Err(ErrorCode::DynamicTickArrayResizeRequired.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod dynamic_tick_array_tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    const TICK_SPACING: u16 = 8;
+
+    fn build_data(start_tick_index: i32) -> Vec<u8> {
+        let mut data = vec![0u8; DynamicTickArray::MIN_LEN];
+        let header: &mut DynamicTickArray =
+            bytemuck::from_bytes_mut(&mut data[8..DynamicTickArray::MIN_LEN]);
+        header.start_tick_index = start_tick_index;
+        data
+    }
+
+    fn insert(data: &mut Vec<u8>, offset: usize) {
+        data.resize(data.len() + DynamicTick::LEN, 0);
+        DynamicTickArray::insert_tick(data, offset, Pubkey::default(), 0);
+    }
+
+    fn remove(data: &mut Vec<u8>, offset: usize) -> DynamicTick {
+        let removed = DynamicTickArray::remove_tick(data, offset);
+        data.truncate(data.len() - DynamicTick::LEN);
+        removed
+    }
+
+    fn update(liquidity_gross: u128) -> TickUpdate {
+        TickUpdate {
+            initialized: true,
+            liquidity_gross,
+            liquidity_net: liquidity_gross as i128,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_insert_and_remove_tick() {
+        let mut data = build_data(0);
+        insert(&mut data, 10);
+        insert(&mut data, 2);
+        insert(&mut data, 87);
+        assert_eq!(data.len(), DynamicTickArray::required_len(3));
+
+        {
+            let data_ref = RefCell::new(&mut data[..]);
+            let mut array = DynamicTickArrayMut::load(data_ref.borrow_mut()).unwrap();
+            array.update_tick(2 * 8, TICK_SPACING, &update(2)).unwrap();
+            array
+                .update_tick(10 * 8, TICK_SPACING, &update(10))
+                .unwrap();
+            array
+                .update_tick(87 * 8, TICK_SPACING, &update(87))
+                .unwrap();
+        }
+
+        remove(&mut data, 10);
+        assert_eq!(data.len(), DynamicTickArray::required_len(2));
+
+        let data_ref = RefCell::new(&mut data[..]);
+        let array = DynamicTickArrayMut::load(data_ref.borrow_mut()).unwrap();
+        assert_eq!(
+            { array.get_tick(2 * 8, TICK_SPACING).unwrap().liquidity_gross },
+            2
+        );
+        assert_eq!(
+            *array.get_tick(10 * 8, TICK_SPACING).unwrap(),
+            Tick::default()
+        );
+        assert_eq!(
+            {
+                array
+                    .get_tick(87 * 8, TICK_SPACING)
+                    .unwrap()
+                    .liquidity_gross
+            },
+            87
+        );
+    }
+
+    #[test]
+    fn test_remove_tick_returns_rent_payer() {
+        let mut data = build_data(0);
+        let (payer_a, payer_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        for (offset, payer, rent) in [(40, payer_a, 1_000), (3, payer_b, 2_000)] {
+            data.resize(data.len() + DynamicTick::LEN, 0);
+            DynamicTickArray::insert_tick(&mut data, offset, payer, rent);
+        }
+
+        // the tick inserted at offset 3 is stored before the tick at offset 40
+        let removed = remove(&mut data, 40);
+        assert_eq!(removed.rent_payer, payer_a);
+        assert_eq!({ removed.rent }, 1_000);
+
+        let removed = remove(&mut data, 3);
+        assert_eq!(removed.rent_payer, payer_b);
+        assert_eq!({ removed.rent }, 2_000);
+        assert_eq!(data.len(), DynamicTickArray::MIN_LEN);
+    }
+
+    #[test]
+    fn test_get_next_init_tick_index() {
+        let mut data = build_data(-704);
+        insert(&mut data, 20);
+        insert(&mut data, 60);
+
+        let data_ref = RefCell::new(&mut data[..]);
+        let array = DynamicTickArrayMut::load(data_ref.borrow_mut()).unwrap();
+        assert_eq!(
+            array
+                .get_next_init_tick_index(-704 + 40 * 8, TICK_SPACING, true)
+                .unwrap(),
+            Some(-704 + 20 * 8)
+        );
+        assert_eq!(
+            array
+                .get_next_init_tick_index(-704 + 40 * 8, TICK_SPACING, false)
+                .unwrap(),
+            Some(-704 + 60 * 8)
+        );
+        assert_eq!(
+            array
+                .get_next_init_tick_index(-704 + 60 * 8, TICK_SPACING, false)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_update_tick_requires_resize() {
+        let mut data = build_data(0);
+        insert(&mut data, 1);
+
+        let data_ref = RefCell::new(&mut data[..]);
+        let mut array = DynamicTickArrayMut::load(data_ref.borrow_mut()).unwrap();
+
+        // initializing a tick which is not stored
+        let result = array.update_tick(0, TICK_SPACING, &update(1));
+        assert!(result.is_err());
+
+        // uninitializing a stored tick
+        let result = array.update_tick(8, TICK_SPACING, &TickUpdate::default());
+        assert!(result.is_err());
+
+        // uninitialized ticks stay uninitialized
+        array
+            .update_tick(16, TICK_SPACING, &TickUpdate::default())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_load_length_mismatch() {
+        let mut data = build_data(0);
+        insert(&mut data, 1);
+        data.truncate(data.len() - 1);
+
+        let data_ref = RefCell::new(&mut data[..]);
+        assert!(DynamicTickArrayMut::load(data_ref.borrow_mut()).is_err());
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn test_dynamic_tick_array_data_layout() {
+        let start_tick_index = 0x70e0d0c0i32;
+        let whirlpool = Pubkey::new_unique();
+        let tick_bitmap = 0x11002233445566778899aabbccddeeffu128;
+        let funder = Pubkey::new_unique();
+
+        let mut data = [0u8; DynamicTickArray::MIN_LEN];
+        let mut offset = 0;
+        data[offset..offset + 8].copy_from_slice(&DynamicTickArray::discriminator());
+        offset += 8;
+        data[offset..offset + 4].copy_from_slice(&start_tick_index.to_le_bytes());
+        offset += 4;
+        data[offset..offset + 32].copy_from_slice(&whirlpool.to_bytes());
+        offset += 32;
+        data[offset..offset + 16].copy_from_slice(&tick_bitmap.to_le_bytes());
+        offset += 16;
+        data[offset..offset + 32].copy_from_slice(&funder.to_bytes());
+        offset += 32;
+        assert_eq!(offset, DynamicTickArray::MIN_LEN);
+        assert_eq!(
+            8 + core::mem::size_of::<DynamicTickArray>(),
+            DynamicTickArray::MIN_LEN
+        );
+        assert_eq!(core::mem::size_of::<DynamicTick>(), DynamicTick::LEN);
+
+        let header: &DynamicTickArray = bytemuck::from_bytes(&data[8..]);
+        assert_eq!({ header.start_tick_index }, start_tick_index);
+        assert_eq!(header.whirlpool, whirlpool);
+        assert_eq!({ header.tick_bitmap }, tick_bitmap);
+        assert_eq!(header.funder, funder);
+    }
+}
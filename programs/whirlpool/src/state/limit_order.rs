@@ -0,0 +1,516 @@
+use crate::errors::ErrorCode;
+use crate::math::{
+    checked_mul_div, get_limit_order_output, sqrt_price_from_tick_index, Q64_RESOLUTION,
+};
+use anchor_lang::prelude::*;
+
+use super::{TickArray, TICK_ARRAY_SIZE, TICK_ARRAY_SIZE_USIZE};
+
+pub const FILL_FACTOR_ONE_X64: u128 = 1u128 << Q64_RESOLUTION;
+
+// When the fill factor becomes smaller than this value, it is scaled up by FILL_FACTOR_SCALE_SHIFT bits
+// to keep its precision. Orders placed two or more scales ago are treated as fully filled.
+pub const FILL_FACTOR_SCALE_SHIFT: u32 = 32;
+pub const MIN_FILL_FACTOR_X64: u128 = 1u128 << FILL_FACTOR_SCALE_SHIFT;
+
+/// Aggregated state of the limit orders in one direction at a tick (a price level).
+///
+/// Every order at a level is filled pro rata. The fill factor tracks the fraction of the unfilled amount
+/// that is left after each fill, so the unfilled amount of an order can be derived from the fill factor
+/// recorded when the order was placed.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct LimitOrderLevel {
+    // Total 44 bytes
+    pub amount_remaining: u64, // 8
+    pub amount_filled: u64,    // 8
+
+    // Q64.64
+    pub fill_factor_x64: u128, // 16
+    pub epoch: u32,            // 4
+    pub scale: u32,            // 4
+    pub order_count: u32,      // 4
+}
+
+impl LimitOrderLevel {
+    pub const LEN: usize = 44;
+
+    /// Adds an order to this level.
+    ///
+    /// If this level has no unfilled amount, a new epoch is started so that the orders placed before
+    /// are not affected by the new order.
+    ///
+    /// # Returns
+    /// - `(u32, u32, u128)`: The epoch, scale and fill factor to be checkpointed by the order
+    pub fn deposit(&mut self, amount: u64) -> Result<(u32, u32, u128)> {
+        if self.amount_remaining == 0 || self.fill_factor_x64 == 0 {
+            self.epoch = self.epoch.wrapping_add(1);
+            self.scale = 0;
+            self.fill_factor_x64 = FILL_FACTOR_ONE_X64;
+        }
+
+        self.amount_remaining = self
+            .amount_remaining
+            .checked_add(amount)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        self.order_count = self
+            .order_count
+            .checked_add(1)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+        Ok((self.epoch, self.scale, self.fill_factor_x64))
+    }
+
+    /// Removes a cancelled order and its unfilled amount from this level.
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        self.amount_remaining = self
+            .amount_remaining
+            .checked_sub(amount)
+            .ok_or(ErrorCode::AmountRemainingOverflow)?;
+        self.order_count = self
+            .order_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::AmountRemainingOverflow)?;
+        Ok(())
+    }
+
+    /// Applies a fill computed by the swap.
+    ///
+    /// # Parameters
+    /// - `amount_in` - The amount paid to the orders (fee excluded)
+    /// - `amount_out` - The unfilled amount taken from the orders
+    pub fn fill(&mut self, amount_in: u64, amount_out: u64) -> Result<()> {
+        let amount_remaining = self.amount_remaining;
+        let next_amount_remaining = amount_remaining
+            .checked_sub(amount_out)
+            .ok_or(ErrorCode::AmountRemainingOverflow)?;
+
+        let mut fill_factor_x64 = checked_mul_div(
+            self.fill_factor_x64,
+            next_amount_remaining as u128,
+            amount_remaining as u128,
+        )?;
+        if fill_factor_x64 > 0 && fill_factor_x64 < MIN_FILL_FACTOR_X64 {
+            fill_factor_x64 <<= FILL_FACTOR_SCALE_SHIFT;
+            self.scale = self.scale.wrapping_add(1);
+        }
+
+        self.fill_factor_x64 = fill_factor_x64;
+        self.amount_remaining = next_amount_remaining;
+        self.amount_filled = self
+            .amount_filled
+            .checked_add(amount_in)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        Ok(())
+    }
+
+    /// Takes the filled amount owed to an order, capped by the filled amount held by this level.
+    pub fn collect(&mut self, amount: u64) -> u64 {
+        let amount = amount.min(self.amount_filled);
+        self.amount_filled -= amount;
+        amount
+    }
+}
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct TickLimitOrders {
+    // Total 88 bytes
+    pub a_to_b: LimitOrderLevel, // 44 (orders selling token A)
+    pub b_to_a: LimitOrderLevel, // 44 (orders selling token B)
+}
+
+impl TickLimitOrders {
+    pub const LEN: usize = 88;
+
+    pub fn level(&self, a_to_b: bool) -> &LimitOrderLevel {
+        if a_to_b {
+            &self.a_to_b
+        } else {
+            &self.b_to_a
+        }
+    }
+
+    pub fn level_mut(&mut self, a_to_b: bool) -> &mut LimitOrderLevel {
+        if a_to_b {
+            &mut self.a_to_b
+        } else {
+            &mut self.b_to_a
+        }
+    }
+}
+
+/// Limit orders of the ticks in a TickArray.
+///
+/// The book is not an account by itself. It is appended to the data of the TickArray account
+/// when the first limit order of the TickArray is opened, so existing TickArray accounts keep their layout.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+pub struct LimitOrderBook {
+    pub ticks: [TickLimitOrders; TICK_ARRAY_SIZE_USIZE],
+}
+
+// LimitOrderBook is not an account, so Pod is implemented here to map it onto the TickArray account data.
+// It only consists of packed integers, so any bit pattern is valid.
+unsafe impl bytemuck::Pod for LimitOrderBook {}
+unsafe impl bytemuck::Zeroable for LimitOrderBook {}
+
+impl Default for LimitOrderBook {
+    #[inline]
+    fn default() -> LimitOrderBook {
+        LimitOrderBook {
+            ticks: [TickLimitOrders::default(); TICK_ARRAY_SIZE_USIZE],
+        }
+    }
+}
+
+impl LimitOrderBook {
+    pub const LEN: usize = TickLimitOrders::LEN * TICK_ARRAY_SIZE_USIZE;
+    // Offset of the book in the TickArray account data
+    pub const OFFSET: usize = TickArray::LEN;
+
+    pub fn is_allocated(tick_array_data_len: usize) -> bool {
+        tick_array_data_len >= Self::OFFSET + Self::LEN
+    }
+
+    /// Returns true if no order is open in this book.
+    pub fn is_empty(&self) -> bool {
+        self.ticks.iter().all(|tick| {
+            let (a_to_b, b_to_a) = (tick.a_to_b, tick.b_to_a);
+            a_to_b.order_count == 0 && b_to_a.order_count == 0
+        })
+    }
+
+    /// Search for the next tick with unfilled orders that can be filled by a swap in the given direction.
+    /// The search follows the same rules as TickArrayType::get_next_init_tick_index.
+    ///
+    /// # Parameters
+    /// - `offset` - The offset of the tick index to start searching from
+    /// - `a_to_b` - The direction of the swap. Orders in the opposite direction are searched.
+    ///
+    /// # Returns
+    /// - `Some(isize)`: The offset of the next tick with fillable orders
+    /// - `None`: No fillable orders were found in this book
+    pub fn get_next_fillable_offset(&self, offset: isize, a_to_b: bool) -> Option<isize> {
+        let mut curr_offset = if a_to_b { offset } else { offset + 1 };
+
+        while (0..TICK_ARRAY_SIZE as isize).contains(&curr_offset) {
+            let level = *self.ticks[curr_offset as usize].level(!a_to_b);
+            if level.amount_remaining > 0 {
+                return Some(curr_offset);
+            }
+
+            curr_offset = if a_to_b {
+                curr_offset - 1
+            } else {
+                curr_offset + 1
+            };
+        }
+
+        None
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct LimitOrder {
+    pub whirlpool: Pubkey, // 32
+    pub owner: Pubkey,     // 32
+    pub tick_index: i32,   // 4
+    pub a_to_b: bool,      // 1
+    pub amount: u64,       // 8
+
+    // Filled amount (in the input token) for which the output has been collected
+    pub amount_filled_collected: u64, // 8
+
+    pub epoch: u32, // 4
+    pub scale: u32, // 4
+    // Q64.64
+    pub fill_factor_checkpoint_x64: u128, // 16
+}
+
+impl LimitOrder {
+    pub const LEN: usize = 8 + 109;
+
+    pub fn initialize(
+        &mut self,
+        whirlpool: Pubkey,
+        owner: Pubkey,
+        tick_index: i32,
+        a_to_b: bool,
+        amount: u64,
+        level: &mut LimitOrderLevel,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Err(ErrorCode::ZeroLimitOrderAmount.into());
+        }
+
+        let (epoch, scale, fill_factor_x64) = level.deposit(amount)?;
+
+        self.whirlpool = whirlpool;
+        self.owner = owner;
+        self.tick_index = tick_index;
+        self.a_to_b = a_to_b;
+        self.amount = amount;
+        self.amount_filled_collected = 0;
+        self.epoch = epoch;
+        self.scale = scale;
+        self.fill_factor_checkpoint_x64 = fill_factor_x64;
+        Ok(())
+    }
+
+    /// Returns the unfilled amount (in the input token) of this order.
+    pub fn get_amount_remaining(&self, level: &LimitOrderLevel) -> Result<u64> {
+        if self.epoch != level.epoch {
+            return Ok(0);
+        }
+
+        let remaining = checked_mul_div(
+            self.amount as u128,
+            level.fill_factor_x64,
+            self.fill_factor_checkpoint_x64,
+        )?;
+        let remaining = match level.scale.wrapping_sub(self.scale) {
+            0 => remaining,
+            1 => remaining >> FILL_FACTOR_SCALE_SHIFT,
+            _ => 0,
+        };
+
+        Ok(remaining.min(self.amount as u128) as u64)
+    }
+
+    /// Settles the output of the amount filled since the last collection.
+    ///
+    /// # Parameters
+    /// - `level` - The level of the tick and direction of this order
+    ///
+    /// # Returns
+    /// - `u64`: The output amount to be transferred to the owner
+    pub fn collect_filled(&mut self, level: &mut LimitOrderLevel) -> Result<u64> {
+        let amount_filled = self.amount - self.get_amount_remaining(level)?;
+        let amount_to_collect = amount_filled.saturating_sub(self.amount_filled_collected);
+
+        let sqrt_price = sqrt_price_from_tick_index(self.tick_index);
+        let amount_out_owed = get_limit_order_output(amount_to_collect, sqrt_price, self.a_to_b)?
+            .min(u64::MAX as u128) as u64;
+        let amount_out = level.collect(amount_out_owed);
+
+        // When the level cannot pay the whole output, only the filled amount matching the paid
+        // output is settled, so that the rest can be collected later.
+        if amount_out == amount_out_owed {
+            self.amount_filled_collected = amount_filled;
+        } else {
+            let amount_settled = checked_mul_div(
+                amount_to_collect as u128,
+                amount_out as u128,
+                amount_out_owed as u128,
+            )?;
+            self.amount_filled_collected += amount_settled as u64;
+        }
+        Ok(amount_out)
+    }
+}
+
+#[cfg(test)]
+mod limit_order_level_tests {
+    use super::*;
+
+    fn open(level: &mut LimitOrderLevel, amount: u64) -> LimitOrder {
+        let mut order = LimitOrder::default();
+        order
+            .initialize(Pubkey::default(), Pubkey::default(), 0, true, amount, level)
+            .unwrap();
+        order
+    }
+
+    #[test]
+    fn test_zero_amount() {
+        let mut level = LimitOrderLevel::default();
+        let mut order = LimitOrder::default();
+        let result = order.initialize(Pubkey::default(), Pubkey::default(), 0, true, 0, &mut level);
+        assert_eq!(result.unwrap_err(), ErrorCode::ZeroLimitOrderAmount.into());
+    }
+
+    #[test]
+    fn test_partial_fill_is_shared_pro_rata() {
+        let mut level = LimitOrderLevel::default();
+        let order_1 = open(&mut level, 100);
+        let order_2 = open(&mut level, 300);
+        assert_eq!({ level.amount_remaining }, 400);
+
+        level.fill(100, 100).unwrap();
+        assert_eq!({ level.amount_remaining }, 300);
+        assert_eq!(order_1.get_amount_remaining(&level).unwrap(), 75);
+        assert_eq!(order_2.get_amount_remaining(&level).unwrap(), 225);
+    }
+
+    #[test]
+    fn test_order_after_partial_fill() {
+        let mut level = LimitOrderLevel::default();
+        let order_1 = open(&mut level, 100);
+        level.fill(50, 50).unwrap();
+
+        let order_2 = open(&mut level, 100);
+        assert_eq!({ level.amount_remaining }, 150);
+        level.fill(75, 75).unwrap();
+
+        assert_eq!(order_1.get_amount_remaining(&level).unwrap(), 25);
+        assert_eq!(order_2.get_amount_remaining(&level).unwrap(), 50);
+    }
+
+    #[test]
+    fn test_full_fill_starts_new_epoch() {
+        let mut level = LimitOrderLevel::default();
+        let order_1 = open(&mut level, 100);
+        level.fill(400, 100).unwrap();
+        assert_eq!(order_1.get_amount_remaining(&level).unwrap(), 0);
+
+        let order_2 = open(&mut level, 100);
+        assert_eq!(order_2.epoch, order_1.epoch + 1);
+        assert_eq!(order_1.get_amount_remaining(&level).unwrap(), 0);
+        assert_eq!(order_2.get_amount_remaining(&level).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_fill_factor_scaling() {
+        let mut level = LimitOrderLevel::default();
+        let order = open(&mut level, u64::MAX);
+
+        // leave 1 / 2^40 of the orders unfilled
+        let amount_out = u64::MAX - (u64::MAX >> 40);
+        level.fill(0, amount_out).unwrap();
+        assert_eq!({ level.scale }, 1);
+        assert!(level.fill_factor_x64 >= MIN_FILL_FACTOR_X64);
+        let remaining = order.get_amount_remaining(&level).unwrap();
+        assert!(remaining <= u64::MAX >> 40);
+        assert!(remaining >= (u64::MAX >> 40) - 1);
+    }
+
+    #[test]
+    fn test_collect_filled() {
+        // tick index 0, price = 1.0
+        let mut level = LimitOrderLevel::default();
+        let mut order = open(&mut level, 100);
+
+        level.fill(50, 50).unwrap();
+        assert_eq!(order.collect_filled(&mut level).unwrap(), 50);
+        assert_eq!(order.amount_filled_collected, 50);
+        // nothing more to collect
+        assert_eq!(order.collect_filled(&mut level).unwrap(), 0);
+
+        level.fill(50, 50).unwrap();
+        assert_eq!(order.collect_filled(&mut level).unwrap(), 50);
+        assert_eq!({ level.amount_filled }, 0);
+    }
+
+    #[test]
+    fn test_collect_filled_capped_by_level() {
+        // tick index 0, price = 1.0
+        let mut level = LimitOrderLevel::default();
+        let mut order = open(&mut level, 100);
+
+        level.fill(100, 100).unwrap();
+        // the level holds less than the output owed to the order
+        level.amount_filled = 40;
+        assert_eq!(order.collect_filled(&mut level).unwrap(), 40);
+        assert_eq!(order.amount_filled_collected, 40);
+
+        // the rest is collected once the level holds it
+        level.amount_filled = 60;
+        assert_eq!(order.collect_filled(&mut level).unwrap(), 60);
+        assert_eq!(order.amount_filled_collected, 100);
+        assert_eq!(order.collect_filled(&mut level).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_withdraw() {
+        let mut level = LimitOrderLevel::default();
+        let order_1 = open(&mut level, 100);
+        let order_2 = open(&mut level, 100);
+        assert_eq!({ level.order_count }, 2);
+        level.fill(100, 100).unwrap();
+
+        level
+            .withdraw(order_1.get_amount_remaining(&level).unwrap())
+            .unwrap();
+        assert_eq!({ level.amount_remaining }, 50);
+        assert_eq!({ level.order_count }, 1);
+        assert_eq!(order_2.get_amount_remaining(&level).unwrap(), 50);
+    }
+
+    #[test]
+    fn test_book_is_empty() {
+        let mut book = LimitOrderBook::default();
+        assert!(book.is_empty());
+
+        let order = open(book.ticks[87].level_mut(false), 100);
+        assert!(!book.is_empty());
+
+        // fully filled orders keep the book non-empty until they are cancelled
+        let level = book.ticks[87].level_mut(false);
+        level.fill(100, 100).unwrap();
+        assert!(!book.is_empty());
+
+        let level = book.ticks[87].level_mut(false);
+        level
+            .withdraw(order.get_amount_remaining(level).unwrap())
+            .unwrap();
+        assert!(book.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_order_book_size() {
+        assert_eq!(std::mem::size_of::<LimitOrderLevel>(), LimitOrderLevel::LEN);
+        assert_eq!(std::mem::size_of::<TickLimitOrders>(), TickLimitOrders::LEN);
+        assert_eq!(std::mem::size_of::<LimitOrderBook>(), LimitOrderBook::LEN);
+        assert_eq!(LimitOrderBook::OFFSET, 8 + std::mem::size_of::<TickArray>());
+    }
+
+    #[test]
+    fn test_limit_order_data_layout() {
+        let mut order = LimitOrder {
+            whirlpool: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            tick_index: 0x11223344,
+            a_to_b: true,
+            amount: 0x0102030405060708,
+            amount_filled_collected: 0x1112131415161718,
+            epoch: 0x21222324,
+            scale: 0x31323334,
+            fill_factor_checkpoint_x64: 0x4142434445464748494a4b4c4d4e4f50,
+        };
+
+        let mut serialized = Vec::new();
+        order.try_serialize(&mut serialized).unwrap();
+        assert_eq!(serialized.len(), LimitOrder::LEN);
+
+        let deserialized = LimitOrder::try_deserialize(&mut serialized.as_slice()).unwrap();
+        assert_eq!(deserialized.whirlpool, order.whirlpool);
+        assert_eq!(deserialized.owner, order.owner);
+        assert_eq!(deserialized.tick_index, order.tick_index);
+        assert_eq!(deserialized.a_to_b, order.a_to_b);
+        assert_eq!(deserialized.amount, order.amount);
+        assert_eq!(
+            deserialized.amount_filled_collected,
+            order.amount_filled_collected
+        );
+        assert_eq!(deserialized.epoch, order.epoch);
+        assert_eq!(deserialized.scale, order.scale);
+        assert_eq!(
+            deserialized.fill_factor_checkpoint_x64,
+            order.fill_factor_checkpoint_x64
+        );
+
+        order.a_to_b = false;
+        let mut serialized = Vec::new();
+        order.try_serialize(&mut serialized).unwrap();
+        assert_eq!(serialized[8 + 32 + 32 + 4], 0);
+    }
+}
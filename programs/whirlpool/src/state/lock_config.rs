@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LockType {
+    #[default]
+    Permanent,
+    UntilTimestamp {
+        unlock_timestamp: u64,
+    },
+}
+
+#[account]
+#[derive(Default)]
+pub struct LockConfig {
+    pub position: Pubkey,       // 32
+    pub position_owner: Pubkey, // 32
+    pub whirlpool: Pubkey,      // 32
+    pub locked_timestamp: u64,  // 8
+    pub lock_type: LockType,    // 1 + 8
+    pub funder: Pubkey,         // 32
+                                // 96 RESERVE
+}
+
+impl LockConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 9 + 128;
+
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        position_owner: Pubkey,
+        whirlpool: Pubkey,
+        locked_timestamp: u64,
+        lock_type: LockType,
+        funder: Pubkey,
+    ) -> Result<()> {
+        if let LockType::UntilTimestamp { unlock_timestamp } = lock_type {
+            if unlock_timestamp <= locked_timestamp {
+                return Err(ErrorCode::InvalidUnlockTimestamp.into());
+            }
+        }
+
+        self.position = position;
+        self.position_owner = position_owner;
+        self.whirlpool = whirlpool;
+        self.locked_timestamp = locked_timestamp;
+        self.lock_type = lock_type;
+        self.funder = funder;
+        Ok(())
+    }
+
+    /// Returns the account refunded with the rent when the LockConfig is closed.
+    /// LockConfigs initialized before the funder was recorded refund the position owner.
+    pub fn rent_receiver(&self) -> Pubkey {
+        if self.funder == Pubkey::default() {
+            self.position_owner
+        } else {
+            self.funder
+        }
+    }
+
+    /// Returns true if the position cannot be unlocked at the given timestamp.
+    pub fn is_locked(&self, timestamp: u64) -> bool {
+        match self.lock_type {
+            LockType::Permanent => true,
+            LockType::UntilTimestamp { unlock_timestamp } => timestamp < unlock_timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod lock_config_tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_config_size() {
+        let lock_config = LockConfig {
+            lock_type: LockType::UntilTimestamp {
+                unlock_timestamp: u64::MAX,
+            },
+            ..LockConfig::default()
+        };
+        let mut serialized = Vec::new();
+        lock_config.try_serialize(&mut serialized).unwrap();
+        assert!(serialized.len() <= LockConfig::LEN);
+    }
+
+    #[test]
+    fn test_is_locked() {
+        let mut lock_config = LockConfig::default();
+        lock_config
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                100,
+                LockType::Permanent,
+                Pubkey::new_unique(),
+            )
+            .unwrap();
+        assert!(lock_config.is_locked(100));
+        assert!(lock_config.is_locked(u64::MAX));
+
+        lock_config
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                100,
+                LockType::UntilTimestamp {
+                    unlock_timestamp: 200,
+                },
+                Pubkey::new_unique(),
+            )
+            .unwrap();
+        assert!(lock_config.is_locked(199));
+        assert!(!lock_config.is_locked(200));
+    }
+
+    #[test]
+    fn test_initialize_invalid_unlock_timestamp() {
+        let mut lock_config = LockConfig::default();
+        let result = lock_config.initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            100,
+            LockType::UntilTimestamp {
+                unlock_timestamp: 100,
+            },
+            Pubkey::new_unique(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rent_receiver() {
+        let (position_owner, funder) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut lock_config = LockConfig::default();
+        lock_config
+            .initialize(
+                Pubkey::new_unique(),
+                position_owner,
+                Pubkey::new_unique(),
+                100,
+                LockType::Permanent,
+                funder,
+            )
+            .unwrap();
+        assert_eq!(lock_config.rent_receiver(), funder);
+
+        // LockConfigs without a recorded funder deserialize with the default key
+        lock_config.funder = Pubkey::default();
+        assert_eq!(lock_config.rent_receiver(), position_owner);
+    }
+
+    #[test]
+    fn test_deserialize_without_funder() {
+        let lock_config = LockConfig {
+            position_owner: Pubkey::new_unique(),
+            funder: Pubkey::new_unique(),
+            ..LockConfig::default()
+        };
+        let mut data = Vec::new();
+        lock_config.try_serialize(&mut data).unwrap();
+        // the funder was previously part of the zeroed reserve
+        data.truncate(data.len() - 32);
+        data.resize(LockConfig::LEN, 0);
+
+        let deserialized = LockConfig::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(deserialized.funder, Pubkey::default());
+        assert_eq!(deserialized.rent_receiver(), lock_config.position_owner);
+    }
+}
@@ -1,17 +1,37 @@
+pub mod adaptive_fee_tier;
 pub mod config;
 pub mod config_extension;
+pub mod dynamic_tick_array;
 pub mod fee_tier;
+pub mod limit_order;
+pub mod lock_config;
+pub mod oracle;
 pub mod position;
 pub mod position_bundle;
+pub mod reward_extension;
+pub mod reward_schedule;
 pub mod tick;
+pub mod tick_array_rent_payers;
 pub mod token_badge;
 pub mod whirlpool;
+pub mod whirlpool_pause;
+pub mod whirlpool_rewards;
 
 pub use self::whirlpool::*;
+pub use adaptive_fee_tier::*;
 pub use config::*;
 pub use config_extension::*;
+pub use dynamic_tick_array::*;
 pub use fee_tier::*;
+pub use limit_order::*;
+pub use lock_config::*;
+pub use oracle::*;
 pub use position::*;
 pub use position_bundle::*;
+pub use reward_extension::*;
+pub use reward_schedule::*;
 pub use tick::*;
+pub use tick_array_rent_payers::*;
 pub use token_badge::*;
+pub use whirlpool_pause::*;
+pub use whirlpool_rewards::*;
@@ -0,0 +1,1080 @@
+use crate::{
+    errors::ErrorCode,
+    math::{checked_mul_div, sqrt_price_from_tick_index},
+};
+use anchor_lang::prelude::*;
+
+use super::Whirlpool;
+
+// Volatility accumulator is stored scaled so that crossing one tick group adds this amount
+pub const VOLATILITY_ACCUMULATOR_SCALE_FACTOR: u32 = 10_000;
+
+// reduction_factor is stored as basis points (10_000 = 100%)
+pub const REDUCTION_FACTOR_DENOMINATOR: u16 = 10_000;
+
+// adaptive_fee_control_factor is stored as hundredths of a basis point (100_000 = 100%)
+pub const ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR: u32 = 100_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct AdaptiveFeeConstants {
+    // Period (seconds) during which the volatility reference is kept as is
+    pub filter_period: u16,
+    // Period (seconds) after which the volatility reference is reset to zero
+    pub decay_period: u16,
+    // Portion of the volatility accumulator carried over to the next reference (basis points)
+    pub reduction_factor: u16,
+    // Scale of the variable fee against the squared volatility
+    pub adaptive_fee_control_factor: u32,
+    // Upper bound of the volatility accumulator
+    pub max_volatility_accumulator: u32,
+    // Number of ticks that make up a single tick group
+    pub tick_group_size: u16,
+}
+
+impl AdaptiveFeeConstants {
+    pub const LEN: usize = 2 + 2 + 2 + 4 + 4 + 2;
+
+    pub fn validate(&self, tick_spacing: u16) -> bool {
+        // filter_period must be shorter than decay_period
+        if self.decay_period == 0 || self.filter_period >= self.decay_period {
+            return false;
+        }
+
+        if self.reduction_factor >= REDUCTION_FACTOR_DENOMINATOR {
+            return false;
+        }
+
+        if self.adaptive_fee_control_factor >= ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR {
+            return false;
+        }
+
+        // tick groups must be aligned with the initializable ticks
+        if self.tick_group_size == 0
+            || self.tick_group_size > tick_spacing
+            || tick_spacing / self.tick_group_size * self.tick_group_size != tick_spacing
+        {
+            return false;
+        }
+
+        // keeps the variable fee calculation within u128
+        (self.max_volatility_accumulator as u64) * (self.tick_group_size as u64) <= u32::MAX as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct AdaptiveFeeVariables {
+    pub last_reference_update_timestamp: u64,
+    pub volatility_reference: u32,
+    pub tick_group_index_reference: i32,
+    pub volatility_accumulator: u32,
+}
+
+impl AdaptiveFeeVariables {
+    pub const LEN: usize = 8 + 4 + 4 + 4;
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct AdaptiveFeeInfo {
+    pub constants: AdaptiveFeeConstants,
+    pub variables: AdaptiveFeeVariables,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Observation {
+    pub timestamp: u64,
+    // Sum of tick_current_index * elapsed seconds
+    pub tick_cumulative: i64,
+    // Sum of elapsed seconds / liquidity (Q64.64)
+    pub seconds_per_liquidity_cumulative_x64: u128,
+}
+
+impl Observation {
+    pub const LEN: usize = 8 + 8 + 16;
+
+    /// Returns the observation at `timestamp`, given the tick and liquidity that were active
+    /// since this observation.
+    pub fn transform(&self, timestamp: u64, tick_current_index: i32, liquidity: u128) -> Self {
+        let elapsed = timestamp.saturating_sub(self.timestamp);
+        Self {
+            timestamp,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add((tick_current_index as i64).wrapping_mul(elapsed as i64)),
+            seconds_per_liquidity_cumulative_x64: self
+                .seconds_per_liquidity_cumulative_x64
+                .wrapping_add(((elapsed as u128) << 64) / liquidity.max(1)),
+        }
+    }
+}
+
+#[account]
+pub struct Oracle {
+    pub whirlpool: Pubkey,                            // 32
+    pub adaptive_fee_constants: AdaptiveFeeConstants, // 16
+    pub adaptive_fee_variables: AdaptiveFeeVariables, // 20
+    pub observation_index: u16,                       // 2
+    pub observation_cardinality: u16,                 // 2
+                                                      // 124 RESERVE
+                                                      // observation_capacity * Observation::LEN
+}
+
+impl Oracle {
+    pub const LEN: usize = 8 + 32 + AdaptiveFeeConstants::LEN + AdaptiveFeeVariables::LEN + 128;
+
+    /// Observations are stored in a ring buffer right after the fixed part of the account.
+    pub fn space(observation_capacity: u16) -> usize {
+        Self::LEN + observation_capacity as usize * Observation::LEN
+    }
+
+    pub fn observation_capacity(data_len: usize) -> u16 {
+        (data_len.saturating_sub(Self::LEN) / Observation::LEN) as u16
+    }
+
+    pub fn initialize(
+        &mut self,
+        whirlpool: Pubkey,
+        adaptive_fee_constants: AdaptiveFeeConstants,
+    ) -> Result<()> {
+        self.whirlpool = whirlpool;
+        self.adaptive_fee_constants = adaptive_fee_constants;
+        self.adaptive_fee_variables = AdaptiveFeeVariables::default();
+        self.observation_index = 0;
+        self.observation_cardinality = 0;
+        Ok(())
+    }
+
+    /// Record an observation using the tick and liquidity that were active since the last one.
+    /// Only one observation is recorded per second.
+    ///
+    /// # Parameters
+    /// - `data` - The whole data of the Oracle account
+    /// - `timestamp` - The current timestamp
+    /// - `tick_current_index` - The tick index of the Whirlpool before it is updated
+    /// - `liquidity` - The liquidity of the Whirlpool before it is updated
+    pub fn write_observation(
+        &mut self,
+        data: &mut [u8],
+        timestamp: u64,
+        tick_current_index: i32,
+        liquidity: u128,
+    ) {
+        let observation_capacity = Self::observation_capacity(data.len());
+        if observation_capacity == 0 {
+            return;
+        }
+
+        if self.observation_cardinality == 0 {
+            set_observation(
+                data,
+                0,
+                &Observation {
+                    timestamp,
+                    ..Default::default()
+                },
+            );
+            self.observation_index = 0;
+            self.observation_cardinality = 1;
+            return;
+        }
+
+        let last = get_observation(data, self.observation_index);
+        if timestamp <= last.timestamp {
+            return;
+        }
+
+        // Use the slots added by increase_oracle_capacity once the ring buffer reaches its end
+        if self.observation_index + 1 == self.observation_cardinality
+            && self.observation_cardinality < observation_capacity
+        {
+            self.observation_cardinality += 1;
+        }
+
+        self.observation_index = (self.observation_index + 1) % self.observation_cardinality;
+        set_observation(
+            data,
+            self.observation_index,
+            &last.transform(timestamp, tick_current_index, liquidity),
+        );
+    }
+
+    /// Returns the cumulative values at `target_timestamp`, interpolating between recorded
+    /// observations or extrapolating from the latest one with the current Whirlpool state.
+    ///
+    /// # Parameters
+    /// - `data` - The whole data of the Oracle account
+    /// - `target_timestamp` - The timestamp to observe, not later than the current timestamp
+    /// - `tick_current_index` - The current tick index of the Whirlpool
+    /// - `liquidity` - The current liquidity of the Whirlpool
+    ///
+    /// # Errors
+    /// - `ObservationUnavailable` - If no observation was recorded at or before target_timestamp
+    pub fn observe(
+        &self,
+        data: &[u8],
+        target_timestamp: u64,
+        tick_current_index: i32,
+        liquidity: u128,
+    ) -> Result<Observation> {
+        if self.observation_cardinality == 0 {
+            return Err(ErrorCode::ObservationUnavailable.into());
+        }
+
+        let latest = get_observation(data, self.observation_index);
+        if target_timestamp >= latest.timestamp {
+            return Ok(latest.transform(target_timestamp, tick_current_index, liquidity));
+        }
+
+        // Chronological position -> slot in the ring buffer (position 0 is the oldest)
+        let oldest_index = (self.observation_index + 1) % self.observation_cardinality;
+        let observation_at = |position: u16| {
+            get_observation(
+                data,
+                (oldest_index + position) % self.observation_cardinality,
+            )
+        };
+
+        if target_timestamp < observation_at(0).timestamp {
+            return Err(ErrorCode::ObservationUnavailable.into());
+        }
+
+        // observation_at(lo).timestamp <= target_timestamp < observation_at(hi).timestamp
+        let mut lo = 0;
+        let mut hi = self.observation_cardinality - 1;
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if observation_at(mid).timestamp <= target_timestamp {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let before = observation_at(lo);
+        let after = observation_at(hi);
+        if before.timestamp == target_timestamp {
+            return Ok(before);
+        }
+
+        // The tick and liquidity are constant between two consecutive observations
+        let observation_time_delta = after.timestamp - before.timestamp;
+        let target_time_delta = target_timestamp - before.timestamp;
+        let tick_cumulative_delta = after.tick_cumulative.wrapping_sub(before.tick_cumulative)
+            / observation_time_delta as i64;
+        let seconds_per_liquidity_cumulative_delta = checked_mul_div(
+            after
+                .seconds_per_liquidity_cumulative_x64
+                .wrapping_sub(before.seconds_per_liquidity_cumulative_x64),
+            target_time_delta as u128,
+            observation_time_delta as u128,
+        )?;
+
+        Ok(Observation {
+            timestamp: target_timestamp,
+            tick_cumulative: before
+                .tick_cumulative
+                .wrapping_add(tick_cumulative_delta.wrapping_mul(target_time_delta as i64)),
+            seconds_per_liquidity_cumulative_x64: before
+                .seconds_per_liquidity_cumulative_x64
+                .wrapping_add(seconds_per_liquidity_cumulative_delta),
+        })
+    }
+
+    /// Returns the time-weighted average tick index of the Whirlpool over the `window_seconds`
+    /// seconds ending at `window_end_timestamp` (rounded towards negative infinity).
+    ///
+    /// The caller is responsible for passing the Whirlpool this Oracle belongs to.
+    ///
+    /// # Parameters
+    /// - `current_timestamp` - The current timestamp
+    /// - `window_end_timestamp` - The end of the window, not later than current_timestamp
+    /// - `window_seconds` - The length of the window
+    ///
+    /// # Errors
+    /// - `InvalidTwapWindow` - If window_seconds is zero or exceeds window_end_timestamp,
+    ///   or if window_end_timestamp is later than current_timestamp
+    /// - `ObservationUnavailable` - If the oldest observation is newer than the window start
+    pub fn get_twap_tick_index(
+        &self,
+        data: &[u8],
+        whirlpool: &Whirlpool,
+        current_timestamp: u64,
+        window_end_timestamp: u64,
+        window_seconds: u32,
+    ) -> Result<i32> {
+        if window_seconds == 0 || window_end_timestamp > current_timestamp {
+            return Err(ErrorCode::InvalidTwapWindow.into());
+        }
+        let start_timestamp = window_end_timestamp
+            .checked_sub(window_seconds as u64)
+            .ok_or(ErrorCode::InvalidTwapWindow)?;
+
+        let start = self.observe(
+            data,
+            start_timestamp,
+            whirlpool.tick_current_index,
+            whirlpool.liquidity,
+        )?;
+        let end = self.observe(
+            data,
+            window_end_timestamp,
+            whirlpool.tick_current_index,
+            whirlpool.liquidity,
+        )?;
+
+        let tick_cumulative_delta = end.tick_cumulative.wrapping_sub(start.tick_cumulative);
+        Ok(tick_cumulative_delta.div_euclid(window_seconds as i64) as i32)
+    }
+
+    /// Returns the sqrt-price (Q64.64) at the time-weighted average tick index of the Whirlpool
+    /// over the `window_seconds` seconds ending at `window_end_timestamp`.
+    pub fn get_twap_sqrt_price(
+        &self,
+        data: &[u8],
+        whirlpool: &Whirlpool,
+        current_timestamp: u64,
+        window_end_timestamp: u64,
+        window_seconds: u32,
+    ) -> Result<u128> {
+        let twap_tick_index = self.get_twap_tick_index(
+            data,
+            whirlpool,
+            current_timestamp,
+            window_end_timestamp,
+            window_seconds,
+        )?;
+        Ok(sqrt_price_from_tick_index(twap_tick_index))
+    }
+}
+
+fn observation_offset(index: u16) -> usize {
+    Oracle::LEN + index as usize * Observation::LEN
+}
+
+fn get_observation(data: &[u8], index: u16) -> Observation {
+    let offset = observation_offset(index);
+    Observation::deserialize(&mut &data[offset..offset + Observation::LEN]).unwrap()
+}
+
+fn set_observation(data: &mut [u8], index: u16, observation: &Observation) {
+    let offset = observation_offset(index);
+    let mut dst = &mut data[offset..offset + Observation::LEN];
+    observation.serialize(&mut dst).unwrap();
+}
+
+/// Reads and writes the Oracle account passed to a swap instruction.
+///
+/// The Oracle account is initialized for Whirlpools with an AdaptiveFeeTier (it holds the
+/// adaptive fee state) and for Whirlpools whose price oracle has been enabled with
+/// initialize_oracle. Otherwise it is an uninitialized PDA and nothing is read or recorded.
+pub struct OracleAccessor<'info> {
+    oracle_account_info: AccountInfo<'info>,
+    oracle_account_initialized: bool,
+    adaptive_fee_enabled: bool,
+}
+
+impl<'info> OracleAccessor<'info> {
+    /// The initialized Oracle is updated on every swap, so it must be writable.
+    pub fn new(whirlpool: &Whirlpool, oracle_account_info: AccountInfo<'info>) -> Result<Self> {
+        let accessor = Self::new_read_only(whirlpool, oracle_account_info)?;
+        if accessor.oracle_account_initialized && !accessor.oracle_account_info.is_writable {
+            return Err(ErrorCode::OracleNotWritable.into());
+        }
+        Ok(accessor)
+    }
+
+    /// For instructions which only read the adaptive fee state (simulate_swap).
+    /// `update_on_swap` fails on a read-only Oracle.
+    pub fn new_read_only(
+        whirlpool: &Whirlpool,
+        oracle_account_info: AccountInfo<'info>,
+    ) -> Result<Self> {
+        let oracle_account_initialized = *oracle_account_info.owner == crate::id();
+        let adaptive_fee_enabled = whirlpool.is_initialized_with_adaptive_fee_tier();
+        if adaptive_fee_enabled && !oracle_account_initialized {
+            return Err(ErrorCode::OracleNotInitialized.into());
+        }
+
+        Ok(Self {
+            oracle_account_info,
+            oracle_account_initialized,
+            adaptive_fee_enabled,
+        })
+    }
+
+    pub fn is_adaptive_fee_enabled(&self) -> bool {
+        self.adaptive_fee_enabled
+    }
+
+    pub fn get_adaptive_fee_info(&self) -> Result<Option<AdaptiveFeeInfo>> {
+        if !self.adaptive_fee_enabled {
+            return Ok(None);
+        }
+
+        let data = self.oracle_account_info.try_borrow_data()?;
+        let oracle = Oracle::try_deserialize(&mut data.as_ref())?;
+        Ok(Some(AdaptiveFeeInfo {
+            constants: oracle.adaptive_fee_constants,
+            variables: oracle.adaptive_fee_variables,
+        }))
+    }
+
+    /// Store the adaptive fee state after a swap and record a price observation.
+    /// Must be called before the Whirlpool is updated with the result of the swap.
+    pub fn update_on_swap(
+        &self,
+        whirlpool: &Whirlpool,
+        next_adaptive_fee_info: &Option<AdaptiveFeeInfo>,
+        timestamp: u64,
+    ) -> Result<()> {
+        if !self.oracle_account_initialized {
+            return Ok(());
+        }
+        if !self.oracle_account_info.is_writable {
+            return Err(ErrorCode::OracleNotWritable.into());
+        }
+
+        let mut data = self.oracle_account_info.try_borrow_mut_data()?;
+        let mut oracle = Oracle::try_deserialize(&mut data.as_ref())?;
+
+        if self.adaptive_fee_enabled {
+            let adaptive_fee_info =
+                next_adaptive_fee_info.ok_or(ErrorCode::OracleNotInitialized)?;
+            oracle.adaptive_fee_variables = adaptive_fee_info.variables;
+        }
+
+        oracle.write_observation(
+            &mut data,
+            timestamp,
+            whirlpool.tick_current_index,
+            whirlpool.liquidity,
+        );
+
+        let mut dst: &mut [u8] = &mut data;
+        oracle.try_serialize(&mut dst)
+    }
+}
+
+/// Record a price observation before the liquidity of the Whirlpool is modified,
+/// so that the elapsed time is accumulated with the liquidity that was active.
+///
+/// Nothing is recorded if the Oracle of the Whirlpool is not initialized.
+///
+/// # Errors
+/// - `OracleNotWritable` - If the account is not writable
+/// - `InvalidOracle` - If the Oracle belongs to another Whirlpool
+pub fn update_oracle_on_liquidity_change(
+    whirlpool_key: &Pubkey,
+    whirlpool: &Whirlpool,
+    oracle_account_info: &AccountInfo,
+    timestamp: u64,
+) -> Result<()> {
+    if *oracle_account_info.owner != crate::id() {
+        return Ok(());
+    }
+    if !oracle_account_info.is_writable {
+        return Err(ErrorCode::OracleNotWritable.into());
+    }
+
+    let mut data = oracle_account_info.try_borrow_mut_data()?;
+    let mut oracle = Oracle::try_deserialize(&mut data.as_ref())?;
+    if oracle.whirlpool != *whirlpool_key {
+        return Err(ErrorCode::InvalidOracle.into());
+    }
+
+    oracle.write_observation(
+        &mut data,
+        timestamp,
+        whirlpool.tick_current_index,
+        whirlpool.liquidity,
+    );
+
+    let mut dst: &mut [u8] = &mut data;
+    oracle.try_serialize(&mut dst)
+}
+
+#[cfg(test)]
+mod adaptive_fee_constants_tests {
+    use super::*;
+
+    fn valid_constants() -> AdaptiveFeeConstants {
+        AdaptiveFeeConstants {
+            filter_period: 30,
+            decay_period: 600,
+            reduction_factor: 5_000,
+            adaptive_fee_control_factor: 4_000,
+            max_volatility_accumulator: 350_000,
+            tick_group_size: 64,
+        }
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        assert!(valid_constants().validate(64));
+        assert!(valid_constants().validate(128));
+    }
+
+    #[test]
+    fn test_validate_periods() {
+        let mut constants = valid_constants();
+        constants.filter_period = constants.decay_period;
+        assert!(!constants.validate(64));
+
+        constants.filter_period = 0;
+        constants.decay_period = 0;
+        assert!(!constants.validate(64));
+    }
+
+    #[test]
+    fn test_validate_factors() {
+        let mut constants = valid_constants();
+        constants.reduction_factor = REDUCTION_FACTOR_DENOMINATOR;
+        assert!(!constants.validate(64));
+
+        let mut constants = valid_constants();
+        constants.adaptive_fee_control_factor = ADAPTIVE_FEE_CONTROL_FACTOR_DENOMINATOR;
+        assert!(!constants.validate(64));
+    }
+
+    #[test]
+    fn test_validate_tick_group_size() {
+        let mut constants = valid_constants();
+        constants.tick_group_size = 0;
+        assert!(!constants.validate(64));
+
+        constants.tick_group_size = 128;
+        assert!(!constants.validate(64));
+
+        constants.tick_group_size = 48;
+        assert!(!constants.validate(64));
+    }
+
+    #[test]
+    fn test_validate_max_volatility_accumulator() {
+        let mut constants = valid_constants();
+        constants.max_volatility_accumulator = u32::MAX / 64 + 1;
+        assert!(!constants.validate(64));
+    }
+}
+
+#[cfg(test)]
+mod observation_tests {
+    use super::*;
+    use crate::state::whirlpool_builder::WhirlpoolBuilder;
+
+    fn new_oracle(observation_capacity: u16) -> (Oracle, Vec<u8>) {
+        let mut oracle = Oracle {
+            whirlpool: Pubkey::default(),
+            adaptive_fee_constants: AdaptiveFeeConstants::default(),
+            adaptive_fee_variables: AdaptiveFeeVariables::default(),
+            observation_index: 0,
+            observation_cardinality: 0,
+        };
+        oracle
+            .initialize(Pubkey::new_unique(), AdaptiveFeeConstants::default())
+            .unwrap();
+        (oracle, vec![0u8; Oracle::space(observation_capacity)])
+    }
+
+    fn timestamps(oracle: &Oracle, data: &[u8]) -> Vec<u64> {
+        (0..oracle.observation_cardinality)
+            .map(|i| get_observation(data, i).timestamp)
+            .collect()
+    }
+
+    #[test]
+    fn test_observation_capacity() {
+        assert_eq!(Oracle::observation_capacity(Oracle::LEN), 0);
+        assert_eq!(Oracle::observation_capacity(Oracle::space(1)), 1);
+        assert_eq!(Oracle::observation_capacity(Oracle::space(300)), 300);
+    }
+
+    #[test]
+    fn test_transform() {
+        let observation = Observation {
+            timestamp: 100,
+            tick_cumulative: 1_000,
+            seconds_per_liquidity_cumulative_x64: 1 << 64,
+        };
+        let next = observation.transform(110, -20, 5);
+        assert_eq!(next.timestamp, 110);
+        assert_eq!(next.tick_cumulative, 1_000 - 200);
+        assert_eq!(
+            next.seconds_per_liquidity_cumulative_x64,
+            (1 << 64) + (2 << 64)
+        );
+
+        // zero liquidity is treated as one
+        let next = observation.transform(110, 0, 0);
+        assert_eq!(
+            next.seconds_per_liquidity_cumulative_x64,
+            (1 << 64) + (10 << 64)
+        );
+    }
+
+    #[test]
+    fn test_write_observation_first() {
+        let (mut oracle, mut data) = new_oracle(1);
+        oracle.write_observation(&mut data, 100, 50, 1_000);
+        assert_eq!(oracle.observation_index, 0);
+        assert_eq!(oracle.observation_cardinality, 1);
+        assert_eq!(
+            get_observation(&data, 0),
+            Observation {
+                timestamp: 100,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_write_observation_same_timestamp_is_ignored() {
+        let (mut oracle, mut data) = new_oracle(3);
+        oracle.write_observation(&mut data, 100, 50, 1_000);
+        oracle.write_observation(&mut data, 110, 50, 1_000);
+        oracle.write_observation(&mut data, 110, 70, 1_000);
+        assert_eq!(oracle.observation_index, 1);
+        assert_eq!(oracle.observation_cardinality, 2);
+        assert_eq!(get_observation(&data, 1).tick_cumulative, 500);
+    }
+
+    #[test]
+    fn test_write_observation_wraps_around() {
+        let (mut oracle, mut data) = new_oracle(3);
+        for timestamp in [100, 110, 120, 130, 140] {
+            oracle.write_observation(&mut data, timestamp, 1, 1);
+        }
+        assert_eq!(oracle.observation_cardinality, 3);
+        assert_eq!(oracle.observation_index, 1);
+        assert_eq!(timestamps(&oracle, &data), vec![130, 140, 120]);
+    }
+
+    #[test]
+    fn test_write_observation_after_capacity_increase() {
+        let (mut oracle, mut data) = new_oracle(2);
+        for timestamp in [100, 110, 120] {
+            oracle.write_observation(&mut data, timestamp, 1, 1);
+        }
+        assert_eq!(timestamps(&oracle, &data), vec![120, 110]);
+        assert_eq!(oracle.observation_index, 0);
+
+        data.resize(Oracle::space(4), 0);
+
+        // the new slots are used once the ring buffer reaches its current end
+        oracle.write_observation(&mut data, 130, 1, 1);
+        assert_eq!(oracle.observation_cardinality, 2);
+        assert_eq!(timestamps(&oracle, &data), vec![120, 130]);
+
+        oracle.write_observation(&mut data, 140, 1, 1);
+        oracle.write_observation(&mut data, 150, 1, 1);
+        assert_eq!(oracle.observation_cardinality, 4);
+        assert_eq!(timestamps(&oracle, &data), vec![120, 130, 140, 150]);
+
+        oracle.write_observation(&mut data, 160, 1, 1);
+        assert_eq!(oracle.observation_cardinality, 4);
+        assert_eq!(timestamps(&oracle, &data), vec![160, 130, 140, 150]);
+    }
+
+    #[test]
+    fn test_observe() {
+        let (mut oracle, mut data) = new_oracle(3);
+        // tick 10 during [100, 110), tick -20 during [110, 130), tick 40 after 130
+        oracle.write_observation(&mut data, 100, 0, 1);
+        oracle.write_observation(&mut data, 110, 10, 1);
+        oracle.write_observation(&mut data, 130, -20, 1);
+
+        let tick_cumulative_at = |timestamp: u64| {
+            oracle
+                .observe(&data, timestamp, 40, 1)
+                .unwrap()
+                .tick_cumulative
+        };
+
+        assert_eq!(tick_cumulative_at(100), 0);
+        assert_eq!(tick_cumulative_at(105), 50);
+        assert_eq!(tick_cumulative_at(110), 100);
+        assert_eq!(tick_cumulative_at(120), -100);
+        assert_eq!(tick_cumulative_at(130), -300);
+        assert_eq!(tick_cumulative_at(140), 100);
+
+        let observation = oracle.observe(&data, 105, 40, 1).unwrap();
+        assert_eq!(observation.seconds_per_liquidity_cumulative_x64, 5 << 64);
+
+        assert!(oracle.observe(&data, 99, 40, 1).is_err());
+    }
+
+    #[test]
+    fn test_observe_after_wrap_around() {
+        let (mut oracle, mut data) = new_oracle(3);
+        for (timestamp, tick) in [(100, 0), (110, 1), (120, 2), (130, 3)] {
+            oracle.write_observation(&mut data, timestamp, tick, 1);
+        }
+
+        // the observation at 100 has been overwritten
+        assert!(oracle.observe(&data, 105, 0, 1).is_err());
+        assert_eq!(
+            oracle.observe(&data, 110, 0, 1).unwrap().tick_cumulative,
+            10
+        );
+        assert_eq!(
+            oracle.observe(&data, 125, 0, 1).unwrap().tick_cumulative,
+            45
+        );
+    }
+
+    #[test]
+    fn test_observe_empty() {
+        let (oracle, data) = new_oracle(1);
+        assert!(oracle.observe(&data, 100, 0, 1).is_err());
+    }
+
+    #[test]
+    fn test_get_twap_tick_index() {
+        let (mut oracle, mut data) = new_oracle(4);
+        oracle.write_observation(&mut data, 1_000, 0, 1);
+        oracle.write_observation(&mut data, 1_100, 100, 1);
+        oracle.write_observation(&mut data, 1_200, -300, 1);
+
+        // tick -100 since 1_200
+        let whirlpool = WhirlpoolBuilder::new()
+            .tick_current_index(-100)
+            .liquidity(1)
+            .build();
+
+        // [1_150, 1_250): 50 * -300 + 50 * -100
+        assert_eq!(
+            oracle
+                .get_twap_tick_index(&data, &whirlpool, 1_250, 1_250, 100)
+                .unwrap(),
+            -200
+        );
+        // [1_050, 1_250): 50 * 100 + 100 * -300 + 50 * -100
+        assert_eq!(
+            oracle
+                .get_twap_tick_index(&data, &whirlpool, 1_250, 1_250, 200)
+                .unwrap(),
+            -150
+        );
+        // rounded towards negative infinity: (10 * -300 + 1 * -100) / 11
+        assert_eq!(
+            oracle
+                .get_twap_tick_index(&data, &whirlpool, 1_201, 1_201, 11)
+                .unwrap(),
+            -282
+        );
+        assert_eq!(
+            oracle
+                .get_twap_sqrt_price(&data, &whirlpool, 1_250, 1_250, 100)
+                .unwrap(),
+            sqrt_price_from_tick_index(-200)
+        );
+
+        // [1_050, 1_150): 50 * 100 + 50 * -300, ending before the latest observation
+        assert_eq!(
+            oracle
+                .get_twap_tick_index(&data, &whirlpool, 1_250, 1_150, 100)
+                .unwrap(),
+            -100
+        );
+        // [1_180, 1_220): 20 * -300 + 20 * -100, across the latest observation
+        assert_eq!(
+            oracle
+                .get_twap_tick_index(&data, &whirlpool, 1_250, 1_220, 40)
+                .unwrap(),
+            -200
+        );
+
+        assert!(oracle
+            .get_twap_tick_index(&data, &whirlpool, 1_250, 1_251, 100)
+            .is_err());
+        assert!(oracle
+            .get_twap_tick_index(&data, &whirlpool, 1_250, 1_250, 0)
+            .is_err());
+        assert!(oracle
+            .get_twap_tick_index(&data, &whirlpool, 1_250, 1_250, 251)
+            .is_err());
+        assert!(oracle
+            .get_twap_tick_index(&data, &whirlpool, 100, 100, 101)
+            .is_err());
+    }
+}
+
+#[cfg(test)]
+mod oracle_accessor_tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+
+    fn whirlpool(fee_tier_index: u16) -> Whirlpool {
+        Whirlpool {
+            tick_spacing: 64,
+            tick_spacing_seed: fee_tier_index.to_le_bytes(),
+            ..Default::default()
+        }
+    }
+
+    fn oracle_data(oracle_whirlpool: Pubkey) -> Vec<u8> {
+        let mut oracle = Oracle {
+            whirlpool: Pubkey::default(),
+            adaptive_fee_constants: AdaptiveFeeConstants::default(),
+            adaptive_fee_variables: AdaptiveFeeVariables::default(),
+            observation_index: 0,
+            observation_cardinality: 0,
+        };
+        oracle
+            .initialize(oracle_whirlpool, AdaptiveFeeConstants::default())
+            .unwrap();
+
+        let mut data = vec![0u8; Oracle::space(4)];
+        oracle.write_observation(&mut data, 1_000, 0, 1);
+        let mut dst: &mut [u8] = &mut data;
+        oracle.try_serialize(&mut dst).unwrap();
+        data
+    }
+
+    fn account_info(owner: &Pubkey, is_writable: bool, data: Vec<u8>) -> AccountInfo<'static> {
+        let key = Box::leak(Box::new(Pubkey::new_unique()));
+        let owner = Box::leak(Box::new(*owner));
+        let lamports = Box::leak(Box::new(0u64));
+        let data = Box::leak(data.into_boxed_slice());
+        AccountInfo::new(key, false, is_writable, lamports, data, owner, false, 0)
+    }
+
+    fn test_accessor(
+        whirlpool: &Whirlpool,
+        owner: &Pubkey,
+        is_writable: bool,
+    ) -> Result<OracleAccessor<'static>> {
+        let account_info = account_info(owner, is_writable, vec![0u8; Oracle::space(1)]);
+        OracleAccessor::new(whirlpool, account_info)
+    }
+
+    fn load_oracle(account_info: &AccountInfo) -> Oracle {
+        Oracle::try_deserialize(&mut account_info.try_borrow_data().unwrap().as_ref()).unwrap()
+    }
+
+    fn assert_error(result: Result<()>, error_code: ErrorCode) {
+        assert_eq!(
+            result.err().unwrap(),
+            anchor_lang::error::Error::from(error_code)
+        );
+    }
+
+    #[test]
+    fn test_adaptive_fee_pool_requires_writable_oracle() {
+        let whirlpool = whirlpool(1024);
+
+        let accessor = test_accessor(&whirlpool, &crate::ID, true).unwrap();
+        assert!(accessor.is_adaptive_fee_enabled());
+
+        let result = test_accessor(&whirlpool, &crate::ID, false);
+        assert_eq!(
+            result.err().unwrap(),
+            anchor_lang::error::Error::from(ErrorCode::OracleNotWritable)
+        );
+    }
+
+    #[test]
+    fn test_adaptive_fee_pool_requires_initialized_oracle() {
+        let whirlpool = whirlpool(1024);
+        let result = test_accessor(&whirlpool, &Pubkey::default(), true);
+        assert_eq!(
+            result.err().unwrap(),
+            anchor_lang::error::Error::from(ErrorCode::OracleNotInitialized)
+        );
+    }
+
+    #[test]
+    fn test_uninitialized_oracle_can_be_read_only() {
+        let whirlpool = whirlpool(64);
+
+        let accessor = test_accessor(&whirlpool, &Pubkey::default(), false).unwrap();
+        assert!(!accessor.is_adaptive_fee_enabled());
+        assert_eq!(accessor.get_adaptive_fee_info().unwrap(), None);
+        accessor.update_on_swap(&whirlpool, &None, 1_100).unwrap();
+    }
+
+    #[test]
+    fn test_initialized_oracle_requires_writable() {
+        let whirlpool = whirlpool(64);
+        let data = oracle_data(Pubkey::new_unique());
+
+        let oracle_account_info = account_info(&crate::ID, false, data.clone());
+        let result = OracleAccessor::new(&whirlpool, oracle_account_info);
+        assert_eq!(
+            result.err().unwrap(),
+            anchor_lang::error::Error::from(ErrorCode::OracleNotWritable)
+        );
+
+        let oracle_account_info = account_info(&crate::ID, true, data);
+        let accessor = OracleAccessor::new(&whirlpool, oracle_account_info.clone()).unwrap();
+        accessor.update_on_swap(&whirlpool, &None, 1_100).unwrap();
+        assert_eq!(load_oracle(&oracle_account_info).observation_cardinality, 2);
+    }
+
+    #[test]
+    fn test_read_only_accessor() {
+        let whirlpool = whirlpool(1024);
+        let oracle_account_info =
+            account_info(&crate::ID, false, oracle_data(Pubkey::new_unique()));
+
+        let accessor = OracleAccessor::new_read_only(&whirlpool, oracle_account_info).unwrap();
+        assert!(accessor.get_adaptive_fee_info().unwrap().is_some());
+        assert_error(
+            accessor.update_on_swap(
+                &whirlpool,
+                &accessor.get_adaptive_fee_info().unwrap(),
+                1_100,
+            ),
+            ErrorCode::OracleNotWritable,
+        );
+    }
+
+    #[test]
+    fn test_update_oracle_on_liquidity_change() {
+        let whirlpool_key = Pubkey::new_unique();
+        let whirlpool = Whirlpool {
+            tick_current_index: 10,
+            liquidity: 4,
+            ..whirlpool(64)
+        };
+
+        let oracle_account_info = account_info(&crate::ID, true, oracle_data(whirlpool_key));
+        update_oracle_on_liquidity_change(&whirlpool_key, &whirlpool, &oracle_account_info, 1_100)
+            .unwrap();
+
+        let oracle = load_oracle(&oracle_account_info);
+        assert_eq!(oracle.observation_index, 1);
+        assert_eq!(oracle.observation_cardinality, 2);
+        let data = oracle_account_info.try_borrow_data().unwrap();
+        let observation = get_observation(&data, 1);
+        assert_eq!(observation.timestamp, 1_100);
+        assert_eq!(observation.tick_cumulative, 10 * 100);
+        assert_eq!(
+            observation.seconds_per_liquidity_cumulative_x64,
+            (100u128 << 64) / 4
+        );
+    }
+
+    #[test]
+    fn test_update_oracle_on_liquidity_change_invalid_oracle() {
+        let whirlpool_key = Pubkey::new_unique();
+        let whirlpool = whirlpool(64);
+
+        let other_oracle = account_info(&crate::ID, true, oracle_data(Pubkey::new_unique()));
+        assert_error(
+            update_oracle_on_liquidity_change(&whirlpool_key, &whirlpool, &other_oracle, 1_100),
+            ErrorCode::InvalidOracle,
+        );
+
+        let read_only_oracle = account_info(&crate::ID, false, oracle_data(whirlpool_key));
+        assert_error(
+            update_oracle_on_liquidity_change(&whirlpool_key, &whirlpool, &read_only_oracle, 1_100),
+            ErrorCode::OracleNotWritable,
+        );
+
+        // nothing is recorded without an initialized Oracle
+        let uninitialized_oracle = account_info(&Pubkey::default(), true, vec![]);
+        update_oracle_on_liquidity_change(&whirlpool_key, &whirlpool, &uninitialized_oracle, 1_100)
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod data_layout_tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_oracle_data_layout() {
+        let oracle_whirlpool = Pubkey::new_unique();
+        let filter_period = 0x1122u16;
+        let decay_period = 0x3344u16;
+        let reduction_factor = 0x5566u16;
+        let adaptive_fee_control_factor = 0x778899aau32;
+        let max_volatility_accumulator = 0xbbccddeeu32;
+        let tick_group_size = 0xff00u16;
+        let last_reference_update_timestamp = 0x0102030405060708u64;
+        let volatility_reference = 0x11223344u32;
+        let tick_group_index_reference = -0x01020304i32;
+        let volatility_accumulator = 0x55667788u32;
+        let observation_index = 0x99aau16;
+        let observation_cardinality = 0xbbccu16;
+        let oracle_reserved = [0u8; 124];
+
+        let mut oracle_data = [0u8; Oracle::LEN];
+        let mut offset = 0;
+        oracle_data[offset..offset + 8].copy_from_slice(&Oracle::discriminator());
+        offset += 8;
+        oracle_data[offset..offset + 32].copy_from_slice(&oracle_whirlpool.to_bytes());
+        offset += 32;
+        oracle_data[offset..offset + 2].copy_from_slice(&filter_period.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 2].copy_from_slice(&decay_period.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 2].copy_from_slice(&reduction_factor.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 4].copy_from_slice(&adaptive_fee_control_factor.to_le_bytes());
+        offset += 4;
+        oracle_data[offset..offset + 4].copy_from_slice(&max_volatility_accumulator.to_le_bytes());
+        offset += 4;
+        oracle_data[offset..offset + 2].copy_from_slice(&tick_group_size.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 8]
+            .copy_from_slice(&last_reference_update_timestamp.to_le_bytes());
+        offset += 8;
+        oracle_data[offset..offset + 4].copy_from_slice(&volatility_reference.to_le_bytes());
+        offset += 4;
+        oracle_data[offset..offset + 4].copy_from_slice(&tick_group_index_reference.to_le_bytes());
+        offset += 4;
+        oracle_data[offset..offset + 4].copy_from_slice(&volatility_accumulator.to_le_bytes());
+        offset += 4;
+        oracle_data[offset..offset + 2].copy_from_slice(&observation_index.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + 2].copy_from_slice(&observation_cardinality.to_le_bytes());
+        offset += 2;
+        oracle_data[offset..offset + oracle_reserved.len()].copy_from_slice(&oracle_reserved);
+        offset += oracle_reserved.len();
+        assert_eq!(offset, Oracle::LEN);
+
+        // deserialize
+        let deserialized = Oracle::try_deserialize(&mut oracle_data.as_ref()).unwrap();
+
+        assert_eq!(oracle_whirlpool, deserialized.whirlpool);
+        let constants = deserialized.adaptive_fee_constants;
+        assert_eq!(filter_period, constants.filter_period);
+        assert_eq!(decay_period, constants.decay_period);
+        assert_eq!(reduction_factor, constants.reduction_factor);
+        assert_eq!(
+            adaptive_fee_control_factor,
+            constants.adaptive_fee_control_factor
+        );
+        assert_eq!(
+            max_volatility_accumulator,
+            constants.max_volatility_accumulator
+        );
+        assert_eq!(tick_group_size, constants.tick_group_size);
+        let variables = deserialized.adaptive_fee_variables;
+        assert_eq!(
+            last_reference_update_timestamp,
+            variables.last_reference_update_timestamp
+        );
+        assert_eq!(volatility_reference, variables.volatility_reference);
+        assert_eq!(
+            tick_group_index_reference,
+            variables.tick_group_index_reference
+        );
+        assert_eq!(volatility_accumulator, variables.volatility_accumulator);
+        assert_eq!(observation_index, deserialized.observation_index);
+        assert_eq!(
+            observation_cardinality,
+            deserialized.observation_cardinality
+        );
+
+        // serialize
+        let mut serialized = Vec::new();
+        deserialized.try_serialize(&mut serialized).unwrap();
+        serialized.extend_from_slice(&oracle_reserved);
+
+        assert_eq!(serialized.as_slice(), oracle_data.as_ref());
+    }
+}
@@ -62,30 +62,84 @@ impl Position {
         tick_lower_index: i32,
         tick_upper_index: i32,
     ) -> Result<()> {
-        if !Tick::check_is_usable_tick(tick_lower_index, whirlpool.tick_spacing)
-            || !Tick::check_is_usable_tick(tick_upper_index, whirlpool.tick_spacing)
+        Self::check_tick_range(whirlpool.tick_spacing, tick_lower_index, tick_upper_index)?;
+
+        self.whirlpool = whirlpool.key();
+        self.position_mint = position_mint;
+
+        self.tick_lower_index = tick_lower_index;
+        self.tick_upper_index = tick_upper_index;
+        Ok(())
+    }
+
+    /// Changes the tick range of an empty position, keeping its Whirlpool and position mint.
+    /// The fee and reward checkpoints are reset, they are set again when liquidity is added.
+    pub fn reset_position_range(
+        &mut self,
+        tick_spacing: u16,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+    ) -> Result<()> {
+        if !Position::is_position_empty(self) {
+            return Err(ErrorCode::ClosePositionNotEmpty.into());
+        }
+
+        if self.tick_lower_index == new_tick_lower_index
+            && self.tick_upper_index == new_tick_upper_index
+        {
+            return Err(ErrorCode::SameTickRangeNotAllowed.into());
+        }
+
+        Self::check_tick_range(tick_spacing, new_tick_lower_index, new_tick_upper_index)?;
+
+        self.tick_lower_index = new_tick_lower_index;
+        self.tick_upper_index = new_tick_upper_index;
+        self.fee_growth_checkpoint_a = 0;
+        self.fee_growth_checkpoint_b = 0;
+        for reward_info in self.reward_infos.iter_mut() {
+            reward_info.growth_inside_checkpoint = 0;
+        }
+        Ok(())
+    }
+
+    fn check_tick_range(
+        tick_spacing: u16,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        if !Tick::check_is_usable_tick(tick_lower_index, tick_spacing)
+            || !Tick::check_is_usable_tick(tick_upper_index, tick_spacing)
             || tick_lower_index >= tick_upper_index
         {
             return Err(ErrorCode::InvalidTickIndex.into());
         }
 
         // On tick spacing >= 2^15, should only be able to open full range positions
-        if whirlpool.tick_spacing >= FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD {
+        if tick_spacing >= FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD {
             let (full_range_lower_index, full_range_upper_index) =
-                Tick::full_range_indexes(whirlpool.tick_spacing);
+                Tick::full_range_indexes(tick_spacing);
             if tick_lower_index != full_range_lower_index
                 || tick_upper_index != full_range_upper_index
             {
                 return Err(ErrorCode::FullRangeOnlyPool.into());
             }
         }
+        Ok(())
+    }
 
-        self.whirlpool = whirlpool.key();
+    /// Takes over the state of a position which is being moved to a new position token,
+    /// keeping its liquidity and its fee and reward checkpoints.
+    pub fn migrate_position(&mut self, source: &Position, position_mint: Pubkey) {
+        self.whirlpool = source.whirlpool;
         self.position_mint = position_mint;
-
-        self.tick_lower_index = tick_lower_index;
-        self.tick_upper_index = tick_upper_index;
-        Ok(())
+        self.liquidity = source.liquidity;
+        self.tick_lower_index = source.tick_lower_index;
+        self.tick_upper_index = source.tick_upper_index;
+        self.fee_growth_checkpoint_a = source.fee_growth_checkpoint_a;
+        self.fee_owed_a = source.fee_owed_a;
+        self.fee_growth_checkpoint_b = source.fee_growth_checkpoint_b;
+        self.fee_owed_b = source.fee_owed_b;
+        self.reward_infos = source.reward_infos;
     }
 
     pub fn reset_fees_owed(&mut self) {
@@ -198,6 +252,120 @@ mod is_position_empty_tests {
     }
 }
 
+#[cfg(test)]
+mod migrate_position_tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_position() {
+        let source = Position {
+            whirlpool: Pubkey::new_unique(),
+            position_mint: Pubkey::new_unique(),
+            liquidity: 1_000,
+            tick_lower_index: -128,
+            tick_upper_index: 256,
+            fee_growth_checkpoint_a: 11,
+            fee_owed_a: 12,
+            fee_growth_checkpoint_b: 13,
+            fee_owed_b: 14,
+            reward_infos: [PositionRewardInfo {
+                growth_inside_checkpoint: 15,
+                amount_owed: 16,
+            }; NUM_REWARDS],
+        };
+        let position_mint = Pubkey::new_unique();
+
+        let mut position = Position::default();
+        position.migrate_position(&source, position_mint);
+
+        assert_eq!(position.position_mint, position_mint);
+        assert_eq!(position.whirlpool, source.whirlpool);
+        assert_eq!(position.liquidity, source.liquidity);
+        assert_eq!(position.tick_lower_index, source.tick_lower_index);
+        assert_eq!(position.tick_upper_index, source.tick_upper_index);
+        assert_eq!(
+            position.fee_growth_checkpoint_a,
+            source.fee_growth_checkpoint_a
+        );
+        assert_eq!(position.fee_owed_a, source.fee_owed_a);
+        assert_eq!(
+            position.fee_growth_checkpoint_b,
+            source.fee_growth_checkpoint_b
+        );
+        assert_eq!(position.fee_owed_b, source.fee_owed_b);
+        assert_eq!(position.reward_infos, source.reward_infos);
+    }
+}
+
+#[cfg(test)]
+mod reset_position_range_tests {
+    use super::*;
+
+    fn build_empty_position() -> Position {
+        Position {
+            tick_lower_index: -128,
+            tick_upper_index: 128,
+            fee_growth_checkpoint_a: 11,
+            fee_growth_checkpoint_b: 13,
+            reward_infos: [PositionRewardInfo {
+                growth_inside_checkpoint: 15,
+                amount_owed: 0,
+            }; NUM_REWARDS],
+            ..Position::default()
+        }
+    }
+
+    #[test]
+    fn test_reset_position_range() {
+        let mut position = build_empty_position();
+        position.reset_position_range(64, -256, 64).unwrap();
+
+        assert_eq!(position.tick_lower_index, -256);
+        assert_eq!(position.tick_upper_index, 64);
+        assert_eq!(position.fee_growth_checkpoint_a, 0);
+        assert_eq!(position.fee_growth_checkpoint_b, 0);
+        assert!(position
+            .reward_infos
+            .iter()
+            .all(|reward_info| reward_info.growth_inside_checkpoint == 0));
+    }
+
+    #[test]
+    fn test_reset_position_range_not_empty() {
+        let mut position = build_empty_position();
+        position.fee_owed_b = 1;
+        assert!(position.reset_position_range(64, -256, 64).is_err());
+    }
+
+    #[test]
+    fn test_reset_position_range_same_range() {
+        let mut position = build_empty_position();
+        assert!(position.reset_position_range(64, -128, 128).is_err());
+    }
+
+    #[test]
+    fn test_reset_position_range_invalid_ticks() {
+        let mut position = build_empty_position();
+        assert!(position.reset_position_range(64, -100, 64).is_err());
+        assert!(position.reset_position_range(64, 64, -256).is_err());
+    }
+
+    #[test]
+    fn test_reset_position_range_full_range_only() {
+        let tick_spacing = FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD;
+        let (full_range_lower_index, full_range_upper_index) =
+            Tick::full_range_indexes(tick_spacing);
+
+        let mut position = build_empty_position();
+        assert!(position
+            .reset_position_range(tick_spacing, 0, full_range_upper_index)
+            .is_err());
+        position
+            .reset_position_range(tick_spacing, full_range_lower_index, full_range_upper_index)
+            .unwrap();
+    }
+}
+
 #[cfg(test)]
 pub mod position_builder {
     use anchor_lang::prelude::Pubkey;
@@ -0,0 +1,260 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+use super::{
+    LimitOrderBook, Position, PositionRewardInfo, RewardLedger, RewardSchedule,
+    WhirlpoolRewardInfo, WhirlpoolRewardSchedule, TICK_ARRAY_SIZE_USIZE,
+};
+
+// Number of rewards supported by the reward extension, in addition to NUM_REWARDS
+pub const NUM_EXTENSION_REWARDS: usize = 5;
+
+/// Additional rewards of a Whirlpool, appended to the WhirlpoolRewards account data.
+///
+/// Extension rewards accrue with the same math as `Whirlpool.reward_infos`. Their growths outside
+/// of each tick are stored in the `TickArrayRewardExtension` appended to the TickArray, and the
+/// checkpoints of each position in the `PositionRewardExtension` appended to the Position.
+/// Their emission schedules and ledgers are stored here, as `WhirlpoolRewardSchedule` does for
+/// `Whirlpool.reward_infos`.
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct WhirlpoolRewardExtension {
+    pub reward_last_updated_timestamp: u64, // 8
+    pub reward_infos: [WhirlpoolRewardInfo; NUM_EXTENSION_REWARDS], // 640
+    pub schedules: [RewardSchedule; NUM_EXTENSION_REWARDS], // 200
+    pub ledgers: [RewardLedger; NUM_EXTENSION_REWARDS], // 120
+    // Reserved for future use
+    pub reserved: [u8; 32], // 32
+}
+
+impl WhirlpoolRewardExtension {
+    pub const LEN: usize = 8
+        + 128 * NUM_EXTENSION_REWARDS
+        + 40 * NUM_EXTENSION_REWARDS
+        + 24 * NUM_EXTENSION_REWARDS
+        + 32;
+    // Offset of the extension in the WhirlpoolRewards account data, after the reward schedules
+    pub const OFFSET: usize = WhirlpoolRewardSchedule::OFFSET + WhirlpoolRewardSchedule::LEN;
+
+    pub fn is_allocated(whirlpool_rewards_data_len: usize) -> bool {
+        whirlpool_rewards_data_len >= Self::OFFSET + Self::LEN
+    }
+
+    pub fn new(authority: Pubkey, timestamp: u64) -> Self {
+        Self {
+            reward_last_updated_timestamp: timestamp,
+            reward_infos: [WhirlpoolRewardInfo::new(authority); NUM_EXTENSION_REWARDS],
+            ..Default::default()
+        }
+    }
+
+    /// Update all reward values for the extension.
+    pub fn update_rewards(
+        &mut self,
+        reward_infos: [WhirlpoolRewardInfo; NUM_EXTENSION_REWARDS],
+        reward_last_updated_timestamp: u64,
+    ) {
+        self.reward_last_updated_timestamp = reward_last_updated_timestamp;
+        self.reward_infos = reward_infos;
+    }
+
+    /// Records the amount of reward tokens collected from the vault of the extension reward at the
+    /// given index.
+    pub fn record_collected(&mut self, index: usize, amount: u64) {
+        self.ledgers[index].record_collected(amount);
+    }
+
+    pub fn update_reward_authority(&mut self, index: usize, authority: Pubkey) -> Result<()> {
+        if index >= NUM_EXTENSION_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.reward_infos[index].authority = authority;
+
+        Ok(())
+    }
+
+    pub fn update_emissions(
+        &mut self,
+        index: usize,
+        reward_infos: [WhirlpoolRewardInfo; NUM_EXTENSION_REWARDS],
+        timestamp: u64,
+        emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        if index >= NUM_EXTENSION_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.update_rewards(reward_infos, timestamp);
+        self.reward_infos[index].emissions_per_second_x64 = emissions_per_second_x64;
+
+        Ok(())
+    }
+
+    pub fn initialize_reward(&mut self, index: usize, mint: Pubkey, vault: Pubkey) -> Result<()> {
+        if index >= NUM_EXTENSION_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+
+        let lowest_index = match self.reward_infos.iter().position(|r| !r.initialized()) {
+            Some(lowest_index) => lowest_index,
+            None => return Err(ErrorCode::InvalidRewardIndex.into()),
+        };
+
+        if lowest_index != index {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+
+        self.reward_infos[index].mint = mint;
+        self.reward_infos[index].vault = vault;
+
+        Ok(())
+    }
+
+    /// Returns the extension reward at the given index if it is initialized.
+    pub fn get_initialized_reward(&self, index: usize) -> Result<&WhirlpoolRewardInfo> {
+        match self.reward_infos.get(index) {
+            Some(reward_info) if reward_info.initialized() => Ok(reward_info),
+            _ => Err(ErrorCode::InvalidRewardIndex.into()),
+        }
+    }
+}
+
+/// Growths outside of each tick for the extension rewards, appended to the TickArray account data
+/// after the LimitOrderBook.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+pub struct TickArrayRewardExtension {
+    pub reward_growths_outside: [[u128; NUM_EXTENSION_REWARDS]; TICK_ARRAY_SIZE_USIZE],
+}
+
+// TickArrayRewardExtension is not an account, so Pod is implemented here to map it onto the TickArray account data.
+// It only consists of packed integers, so any bit pattern is valid.
+unsafe impl bytemuck::Pod for TickArrayRewardExtension {}
+unsafe impl bytemuck::Zeroable for TickArrayRewardExtension {}
+
+impl Default for TickArrayRewardExtension {
+    #[inline]
+    fn default() -> TickArrayRewardExtension {
+        TickArrayRewardExtension {
+            reward_growths_outside: [[0; NUM_EXTENSION_REWARDS]; TICK_ARRAY_SIZE_USIZE],
+        }
+    }
+}
+
+impl TickArrayRewardExtension {
+    pub const LEN: usize = 16 * NUM_EXTENSION_REWARDS * TICK_ARRAY_SIZE_USIZE;
+    // Offset of the extension in the TickArray account data
+    pub const OFFSET: usize = LimitOrderBook::OFFSET + LimitOrderBook::LEN;
+
+    pub fn is_allocated(tick_array_data_len: usize) -> bool {
+        tick_array_data_len >= Self::OFFSET + Self::LEN
+    }
+}
+
+/// Checkpoints and amounts owed of a position for the extension rewards,
+/// appended to the Position account data.
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct PositionRewardExtension {
+    pub reward_infos: [PositionRewardInfo; NUM_EXTENSION_REWARDS], // 120
+}
+
+impl PositionRewardExtension {
+    pub const LEN: usize = 24 * NUM_EXTENSION_REWARDS;
+    // Offset of the extension in the Position account data
+    pub const OFFSET: usize = Position::LEN;
+
+    pub fn is_allocated(position_data_len: usize) -> bool {
+        position_data_len >= Self::OFFSET + Self::LEN
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reward_infos
+            .iter()
+            .all(|reward_info| reward_info.amount_owed == 0)
+    }
+
+    pub fn update_reward_owed(&mut self, index: usize, amount_owed: u64) {
+        self.reward_infos[index].amount_owed = amount_owed;
+    }
+
+    pub fn reset_checkpoints(&mut self) {
+        for reward_info in self.reward_infos.iter_mut() {
+            reward_info.growth_inside_checkpoint = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod reward_extension_tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_reward_in_order() {
+        let authority = Pubkey::new_unique();
+        let mut extension = WhirlpoolRewardExtension::new(authority, 100);
+        assert_eq!(extension.reward_last_updated_timestamp, 100);
+        assert!(extension
+            .reward_infos
+            .iter()
+            .all(|r| r.authority == authority && !r.initialized()));
+
+        let result = extension.initialize_reward(1, Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(result.is_err());
+
+        for index in 0..NUM_EXTENSION_REWARDS {
+            extension
+                .initialize_reward(index, Pubkey::new_unique(), Pubkey::new_unique())
+                .unwrap();
+            assert!(extension.get_initialized_reward(index).is_ok());
+        }
+
+        let result = extension.initialize_reward(
+            NUM_EXTENSION_REWARDS,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_get_initialized_reward() {
+        let mut extension = WhirlpoolRewardExtension::default();
+        assert!(extension.get_initialized_reward(0).is_err());
+        assert!(extension
+            .get_initialized_reward(NUM_EXTENSION_REWARDS)
+            .is_err());
+
+        let mint = Pubkey::new_unique();
+        extension
+            .initialize_reward(0, mint, Pubkey::new_unique())
+            .unwrap();
+        assert_eq!(extension.get_initialized_reward(0).unwrap().mint, mint);
+    }
+
+    #[test]
+    fn test_position_reward_extension_is_empty() {
+        let mut extension = PositionRewardExtension::default();
+        assert!(extension.is_empty());
+
+        extension.update_reward_owed(NUM_EXTENSION_REWARDS - 1, 1);
+        assert!(!extension.is_empty());
+    }
+
+    #[test]
+    fn test_reward_extension_size() {
+        let mut serialized = Vec::new();
+        WhirlpoolRewardExtension::default()
+            .serialize(&mut serialized)
+            .unwrap();
+        assert_eq!(serialized.len(), WhirlpoolRewardExtension::LEN);
+
+        let mut serialized = Vec::new();
+        PositionRewardExtension::default()
+            .serialize(&mut serialized)
+            .unwrap();
+        assert_eq!(serialized.len(), PositionRewardExtension::LEN);
+
+        assert_eq!(
+            std::mem::size_of::<TickArrayRewardExtension>(),
+            TickArrayRewardExtension::LEN
+        );
+    }
+}
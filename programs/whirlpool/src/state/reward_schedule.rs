@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+
+use crate::math::{div_round_up_if_u256, mul_u256, U256Muldiv, Q64_RESOLUTION};
+
+use super::{WhirlpoolRewards, NUM_REWARDS};
+
+/// Emission schedule of a reward of the Whirlpool.
+///
+/// A scheduled reward only accrues between `start_timestamp` and `end_timestamp`,
+/// and stops accruing once `emissions_budget` tokens have been emitted.
+///
+/// The remaining budget is spread over the remaining seconds of the schedule, so the budget
+/// which is not emitted while the Whirlpool has no liquidity is emitted later on.
+/// The budget left when the schedule ends without liquidity is not owed to the positions,
+/// so it can be used by the next schedule of the reward.
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct RewardSchedule {
+    pub start_timestamp: u64, // 8
+    pub end_timestamp: u64,   // 8
+
+    // Total amount of reward tokens to emit over the schedule
+    pub emissions_budget: u64, // 8
+
+    // Q64.64
+    pub amount_emitted_x64: u128, // 16
+}
+
+impl RewardSchedule {
+    pub fn new(start_timestamp: u64, end_timestamp: u64, emissions_budget: u64) -> Self {
+        Self {
+            start_timestamp,
+            end_timestamp,
+            emissions_budget,
+            amount_emitted_x64: 0,
+        }
+    }
+
+    /// Returns true if the reward follows this schedule.
+    /// A zeroed schedule means that the reward emits at a constant rate.
+    pub fn is_scheduled(&self) -> bool {
+        self.end_timestamp != 0
+    }
+
+    /// Returns the number of seconds between the given timestamps that fall within the schedule.
+    pub fn active_seconds(&self, curr_timestamp: u64, next_timestamp: u64) -> u64 {
+        next_timestamp
+            .min(self.end_timestamp)
+            .saturating_sub(curr_timestamp.max(self.start_timestamp))
+    }
+
+    /// Returns the amount of reward tokens (Q64.64) that can still be emitted.
+    pub fn remaining_emissions_x64(&self) -> u128 {
+        (u128::from(self.emissions_budget) << Q64_RESOLUTION)
+            .saturating_sub(self.amount_emitted_x64)
+    }
+
+    /// Returns the amount of reward tokens (Q64.64) emitted between the given timestamps,
+    /// the remaining budget being emitted evenly over the remaining seconds of the schedule.
+    pub fn next_emissions_x64(&self, curr_timestamp: u64, next_timestamp: u64) -> u128 {
+        let remaining_seconds = self.active_seconds(curr_timestamp, self.end_timestamp);
+        let active_seconds = self.active_seconds(curr_timestamp, next_timestamp);
+        if remaining_seconds == 0 || active_seconds == 0 {
+            return 0;
+        }
+
+        // active_seconds <= remaining_seconds, so the result never exceeds the remaining budget
+        div_round_up_if_u256(
+            mul_u256(self.remaining_emissions_x64(), u128::from(active_seconds)),
+            U256Muldiv::new(0, u128::from(remaining_seconds)),
+            false,
+        )
+        .unwrap_or(0)
+    }
+}
+
+/// Amounts of a reward of the Whirlpool emitted to and collected by the positions.
+///
+/// The difference is the amount still owed to the positions, which must remain in the reward vault.
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct RewardLedger {
+    // Q64.64
+    pub amount_emitted_x64: u128, // 16
+    pub amount_collected: u64,    // 8
+}
+
+impl RewardLedger {
+    /// Creates the ledger of a reward whose vault already holds the given amount.
+    /// The amounts emitted before the ledger are unknown, so all of it is considered owed.
+    pub fn new(vault_amount: u64) -> Self {
+        Self {
+            amount_emitted_x64: u128::from(vault_amount) << Q64_RESOLUTION,
+            amount_collected: 0,
+        }
+    }
+
+    /// Returns the amount of reward tokens emitted but not collected yet, rounded up.
+    pub fn amount_outstanding(&self) -> u64 {
+        let amount_emitted = self
+            .amount_emitted_x64
+            .div_ceil(1 << Q64_RESOLUTION)
+            .try_into()
+            .unwrap_or(u64::MAX);
+        amount_emitted.saturating_sub(self.amount_collected)
+    }
+
+    /// Returns the amount of reward tokens in the vault which are not owed to the positions.
+    pub fn amount_available(&self, vault_amount: u64) -> u64 {
+        vault_amount.saturating_sub(self.amount_outstanding())
+    }
+
+    /// Returns true if the vault holds all of the reward tokens owed to the positions.
+    pub fn is_solvent(&self, vault_amount: u64) -> bool {
+        vault_amount >= self.amount_outstanding()
+    }
+
+    /// Records the amount of reward tokens collected from the vault.
+    pub fn record_collected(&mut self, amount: u64) {
+        self.amount_collected = self.amount_collected.saturating_add(amount);
+    }
+}
+
+/// Emission schedules and ledgers of the rewards of a Whirlpool, appended to the
+/// WhirlpoolRewards account data.
+///
+/// The ledgers are seeded with the vault amounts when the schedules are appended, and track the
+/// emissions and collections from then on.
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct WhirlpoolRewardSchedule {
+    pub schedules: [RewardSchedule; NUM_REWARDS], // 120
+    pub ledgers: [RewardLedger; NUM_REWARDS],     // 72
+    // Reserved for future use, so that the WhirlpoolRewardExtension which follows never moves
+    pub reserved: [u8; 32], // 32
+}
+
+impl WhirlpoolRewardSchedule {
+    pub const LEN: usize = 40 * NUM_REWARDS + 24 * NUM_REWARDS + 32;
+    // Offset of the schedules in the WhirlpoolRewards account data
+    pub const OFFSET: usize = WhirlpoolRewards::LEN;
+
+    pub fn is_allocated(whirlpool_rewards_data_len: usize) -> bool {
+        whirlpool_rewards_data_len >= Self::OFFSET + Self::LEN
+    }
+
+    /// Records the amount of reward tokens collected from the vault of the reward at the given index.
+    pub fn record_collected(&mut self, index: usize, amount: u64) {
+        self.ledgers[index].record_collected(amount);
+    }
+}
+
+#[cfg(test)]
+mod reward_schedule_tests {
+    use super::*;
+    use crate::state::WhirlpoolRewardExtension;
+
+    #[test]
+    fn test_active_seconds() {
+        let schedule = RewardSchedule::new(100, 200, 0);
+        assert_eq!(schedule.active_seconds(0, 50), 0);
+        assert_eq!(schedule.active_seconds(50, 150), 50);
+        assert_eq!(schedule.active_seconds(120, 180), 60);
+        assert_eq!(schedule.active_seconds(150, 250), 50);
+        assert_eq!(schedule.active_seconds(50, 250), 100);
+        assert_eq!(schedule.active_seconds(250, 300), 0);
+    }
+
+    #[test]
+    fn test_next_emissions_capped_by_budget() {
+        let mut schedule = RewardSchedule::new(100, 200, 150);
+        assert!(schedule.is_scheduled());
+        assert!(!RewardSchedule::default().is_scheduled());
+
+        let emissions = schedule.next_emissions_x64(100, 200);
+        assert_eq!(emissions, 150 << Q64_RESOLUTION);
+        let emissions = schedule.next_emissions_x64(0, 120);
+        assert_eq!(emissions, 30 << Q64_RESOLUTION);
+
+        schedule.amount_emitted_x64 = 100 << Q64_RESOLUTION;
+        let emissions = schedule.next_emissions_x64(100, 300);
+        assert_eq!(emissions, 50 << Q64_RESOLUTION);
+
+        schedule.amount_emitted_x64 = 150 << Q64_RESOLUTION;
+        assert_eq!(schedule.next_emissions_x64(100, 200), 0);
+    }
+
+    #[test]
+    fn test_next_emissions_spread_over_remaining_seconds() {
+        let mut schedule = RewardSchedule::new(100, 200, u64::MAX);
+
+        // Half of the schedule has passed without emissions (no liquidity),
+        // the whole budget is emitted over the second half
+        let emissions = schedule.next_emissions_x64(150, 175);
+        assert_eq!(emissions, u128::from(u64::MAX) << (Q64_RESOLUTION - 1));
+        schedule.amount_emitted_x64 = emissions;
+        assert_eq!(
+            schedule.next_emissions_x64(175, 250),
+            schedule.remaining_emissions_x64()
+        );
+
+        // Nothing is emitted once the schedule has ended
+        assert_eq!(schedule.next_emissions_x64(200, 250), 0);
+    }
+
+    #[test]
+    fn test_reward_schedule_layout() {
+        // The offsets of the sections appended to the WhirlpoolRewards account data must never change
+        assert_eq!(WhirlpoolRewardSchedule::OFFSET, 104);
+        assert_eq!(WhirlpoolRewardSchedule::LEN, 224);
+        assert_eq!(WhirlpoolRewardExtension::OFFSET, 328);
+        assert_eq!(WhirlpoolRewardExtension::LEN, 1000);
+
+        let mut serialized = Vec::new();
+        WhirlpoolRewardExtension::default()
+            .serialize(&mut serialized)
+            .unwrap();
+        assert_eq!(serialized.len(), WhirlpoolRewardExtension::LEN);
+    }
+
+    #[test]
+    fn test_reward_schedule_size() {
+        let mut serialized = Vec::new();
+        WhirlpoolRewardSchedule::default()
+            .serialize(&mut serialized)
+            .unwrap();
+        assert_eq!(serialized.len(), WhirlpoolRewardSchedule::LEN);
+    }
+
+    #[test]
+    fn test_reward_ledger_new() {
+        let ledger = RewardLedger::new(1000);
+        assert_eq!(ledger.amount_outstanding(), 1000);
+        assert_eq!(ledger.amount_available(1000), 0);
+        assert_eq!(ledger.amount_available(1500), 500);
+    }
+
+    #[test]
+    fn test_reward_ledger_amount_outstanding() {
+        let mut ledger = RewardLedger::default();
+        assert_eq!(ledger.amount_outstanding(), 0);
+        assert!(ledger.is_solvent(0));
+
+        // Partial tokens are rounded up
+        ledger.amount_emitted_x64 = (100 << Q64_RESOLUTION) + 1;
+        assert_eq!(ledger.amount_outstanding(), 101);
+        assert_eq!(ledger.amount_available(150), 49);
+        assert!(!ledger.is_solvent(100));
+
+        ledger.amount_collected = 60;
+        assert_eq!(ledger.amount_outstanding(), 41);
+        assert_eq!(ledger.amount_available(40), 0);
+        assert!(ledger.is_solvent(41));
+    }
+
+    #[test]
+    fn test_record_collected() {
+        let mut reward_schedule = WhirlpoolRewardSchedule::default();
+        reward_schedule.record_collected(1, 10);
+        reward_schedule.record_collected(1, 5);
+        assert_eq!(reward_schedule.ledgers[0].amount_collected, 0);
+        assert_eq!(reward_schedule.ledgers[1].amount_collected, 15);
+    }
+}
@@ -31,6 +31,11 @@ pub struct Tick {
     pub reward_growths_outside: [u128; NUM_REWARDS], // 48 = 16 * 3
 }
 
+// Pod is implemented to map the initialized ticks onto the DynamicTickArray account data (as DynamicTick).
+// Ticks are only ever written by this program, so `initialized` always holds a valid bool.
+unsafe impl bytemuck::Pod for Tick {}
+unsafe impl bytemuck::Zeroable for Tick {}
+
 impl Tick {
     pub const LEN: usize = 113;
 
@@ -245,6 +250,10 @@ impl Default for TickArray {
 
 impl TickArray {
     pub const LEN: usize = 8 + 36 + (Tick::LEN * TICK_ARRAY_SIZE_USIZE);
+    /// Returns true if no tick in this array is initialized.
+    pub fn is_empty(&self) -> bool {
+        self.ticks.iter().all(|tick| !tick.initialized)
+    }
 
     /// Initialize the TickArray object
     ///
@@ -753,6 +762,20 @@ mod array_update_tests {
     }
 }
 
+#[cfg(test)]
+mod close_tick_array_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_empty() {
+        let mut array = TickArray::default();
+        assert!(array.is_empty());
+
+        array.ticks[87].initialized = true;
+        assert!(!array.is_empty());
+    }
+}
+
 #[cfg(test)]
 mod data_layout_tests {
     use super::*;
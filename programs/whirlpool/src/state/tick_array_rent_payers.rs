@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+/// Records who paid the rent of a TickArray account and of the sections appended to it,
+/// so that every portion can be refunded to its payer when the TickArray is closed.
+///
+/// The record is a PDA of the TickArray, so the size of the TickArray account is not changed.
+/// TickArrays initialized before the record was introduced get a record without a TickArray payer
+/// when a section is first appended to them.
+#[account]
+#[derive(Default, Debug, PartialEq)]
+pub struct TickArrayRentPayers {
+    pub tick_array: Pubkey,             // 32
+    pub tick_array_payer: Pubkey,       // 32
+    pub limit_order_book_payer: Pubkey, // 32
+    pub limit_order_book_rent: u64,     // 8
+    pub reward_extension_payer: Pubkey, // 32
+    pub reward_extension_rent: u64,     // 8
+                                        // 64 RESERVE
+}
+
+impl TickArrayRentPayers {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 32 + 8 + 64;
+
+    pub fn initialize(&mut self, tick_array: Pubkey, tick_array_payer: Pubkey) {
+        self.tick_array = tick_array;
+        self.tick_array_payer = tick_array_payer;
+    }
+
+    /// Returns the payers of the sections and the rent they paid.
+    pub fn section_refunds(&self) -> [(Pubkey, u64); 2] {
+        [
+            (self.limit_order_book_payer, self.limit_order_book_rent),
+            (self.reward_extension_payer, self.reward_extension_rent),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tick_array_rent_payers_tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize() {
+        let tick_array = Pubkey::new_unique();
+        let tick_array_payer = Pubkey::new_unique();
+
+        let mut rent_payers = TickArrayRentPayers::default();
+        rent_payers.initialize(tick_array, tick_array_payer);
+
+        assert_eq!(rent_payers.tick_array, tick_array);
+        assert_eq!(rent_payers.tick_array_payer, tick_array_payer);
+        assert_eq!(
+            rent_payers.section_refunds(),
+            [(Pubkey::default(), 0), (Pubkey::default(), 0)]
+        );
+    }
+
+    #[test]
+    fn test_len() {
+        let rent_payers = TickArrayRentPayers::default();
+        let mut data = vec![];
+        rent_payers.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len() + 64, TickArrayRentPayers::LEN);
+    }
+}
@@ -5,7 +5,12 @@ use anchor_lang::prelude::*;
 pub struct TokenBadge {
     pub whirlpools_config: Pubkey, // 32
     pub token_mint: Pubkey,        // 32
-                                   // 128 RESERVE
+
+    // Allowed transfer hook program and permanent delegate of the mint,
+    // Pubkey::default() if any is allowed
+    pub transfer_hook_program_id: Pubkey, // 32
+    pub permanent_delegate: Pubkey,       // 32
+                                          // 64 RESERVE
 }
 
 impl TokenBadge {
@@ -16,6 +21,36 @@ impl TokenBadge {
         self.token_mint = token_mint;
         Ok(())
     }
+
+    pub fn update_allowlist(
+        &mut self,
+        transfer_hook_program_id: Pubkey,
+        permanent_delegate: Pubkey,
+    ) {
+        self.transfer_hook_program_id = transfer_hook_program_id;
+        self.permanent_delegate = permanent_delegate;
+    }
+
+    /// Returns true if the transfer hook program of the mint is allowed by this badge.
+    pub fn is_transfer_hook_program_allowed(
+        &self,
+        transfer_hook_program_id: Option<Pubkey>,
+    ) -> bool {
+        is_allowed(self.transfer_hook_program_id, transfer_hook_program_id)
+    }
+
+    /// Returns true if the permanent delegate of the mint is allowed by this badge.
+    pub fn is_permanent_delegate_allowed(&self, permanent_delegate: Option<Pubkey>) -> bool {
+        is_allowed(self.permanent_delegate, permanent_delegate)
+    }
+}
+
+fn is_allowed(allowed: Pubkey, actual: Option<Pubkey>) -> bool {
+    match actual {
+        // nothing to restrict if the mint has no hook program or delegate
+        None => true,
+        Some(actual) => allowed == Pubkey::default() || allowed == actual,
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +81,44 @@ mod token_badge_initialize_tests {
 
         assert_eq!(whirlpools_config, token_badge.whirlpools_config);
         assert_eq!(token_mint, token_badge.token_mint);
+        assert_eq!(Pubkey::default(), token_badge.transfer_hook_program_id);
+        assert_eq!(Pubkey::default(), token_badge.permanent_delegate);
+    }
+}
+
+#[cfg(test)]
+mod token_badge_allowlist_tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_allowlist_allows_any() {
+        let token_badge = TokenBadge::default();
+        assert!(token_badge.is_transfer_hook_program_allowed(None));
+        assert!(token_badge.is_transfer_hook_program_allowed(Some(Pubkey::new_unique())));
+        assert!(token_badge.is_permanent_delegate_allowed(None));
+        assert!(token_badge.is_permanent_delegate_allowed(Some(Pubkey::new_unique())));
+    }
+
+    #[test]
+    fn test_update_allowlist() {
+        let transfer_hook_program_id = Pubkey::new_unique();
+        let permanent_delegate = Pubkey::new_unique();
+
+        let mut token_badge = TokenBadge::default();
+        token_badge.update_allowlist(transfer_hook_program_id, permanent_delegate);
+        assert_eq!(
+            transfer_hook_program_id,
+            token_badge.transfer_hook_program_id
+        );
+        assert_eq!(permanent_delegate, token_badge.permanent_delegate);
+
+        assert!(token_badge.is_transfer_hook_program_allowed(None));
+        assert!(token_badge.is_transfer_hook_program_allowed(Some(transfer_hook_program_id)));
+        assert!(!token_badge.is_transfer_hook_program_allowed(Some(Pubkey::new_unique())));
+
+        assert!(token_badge.is_permanent_delegate_allowed(None));
+        assert!(token_badge.is_permanent_delegate_allowed(Some(permanent_delegate)));
+        assert!(!token_badge.is_permanent_delegate_allowed(Some(Pubkey::new_unique())));
     }
 }
 
@@ -59,7 +132,9 @@ mod data_layout_tests {
     fn test_token_badge_data_layout() {
         let token_badge_whirlpools_config = Pubkey::new_unique();
         let token_badge_token_mint = Pubkey::new_unique();
-        let token_badge_reserved = [0u8; 128];
+        let token_badge_transfer_hook_program_id = Pubkey::new_unique();
+        let token_badge_permanent_delegate = Pubkey::new_unique();
+        let token_badge_reserved = [0u8; 64];
 
         // manually build the expected data layout
         let mut token_badge_data = [0u8; TokenBadge::LEN];
@@ -71,6 +146,12 @@ mod data_layout_tests {
         offset += 32;
         token_badge_data[offset..offset + 32].copy_from_slice(&token_badge_token_mint.to_bytes());
         offset += 32;
+        token_badge_data[offset..offset + 32]
+            .copy_from_slice(&token_badge_transfer_hook_program_id.to_bytes());
+        offset += 32;
+        token_badge_data[offset..offset + 32]
+            .copy_from_slice(&token_badge_permanent_delegate.to_bytes());
+        offset += 32;
         token_badge_data[offset..offset + token_badge_reserved.len()]
             .copy_from_slice(&token_badge_reserved);
         offset += token_badge_reserved.len();
@@ -84,6 +165,14 @@ mod data_layout_tests {
             deserialized.whirlpools_config
         );
         assert_eq!(token_badge_token_mint, deserialized.token_mint);
+        assert_eq!(
+            token_badge_transfer_hook_program_id,
+            deserialized.transfer_hook_program_id
+        );
+        assert_eq!(
+            token_badge_permanent_delegate,
+            deserialized.permanent_delegate
+        );
 
         // serialize
         let mut serialized = Vec::new();
@@ -107,6 +107,7 @@ impl Whirlpool {
         whirlpools_config: &Account<WhirlpoolsConfig>,
         bump: u8,
         tick_spacing: u16,
+        fee_tier_index: u16,
         sqrt_price: u128,
         default_fee_rate: u16,
         token_mint_a: Pubkey,
@@ -126,7 +127,7 @@ impl Whirlpool {
         self.whirlpool_bump = [bump];
 
         self.tick_spacing = tick_spacing;
-        self.tick_spacing_seed = self.tick_spacing.to_le_bytes();
+        self.tick_spacing_seed = fee_tier_index.to_le_bytes();
 
         self.update_fee_rate(default_fee_rate)?;
         self.update_protocol_fee_rate(whirlpools_config.default_protocol_fee_rate)?;
@@ -153,6 +154,18 @@ impl Whirlpool {
         Ok(())
     }
 
+    /// The fee tier index used as the last seed of the Whirlpool address.
+    /// Pools initialized with a FeeTier use their tick spacing as the index.
+    pub fn fee_tier_index(&self) -> u16 {
+        u16::from_le_bytes(self.tick_spacing_seed)
+    }
+
+    /// Pools initialized with an AdaptiveFeeTier have a fee tier index distinct from their
+    /// tick spacing and an Oracle account holding the adaptive fee state.
+    pub fn is_initialized_with_adaptive_fee_tier(&self) -> bool {
+        self.fee_tier_index() != self.tick_spacing
+    }
+
     /// Update all reward values for the Whirlpool.
     ///
     /// # Parameters
@@ -310,11 +323,9 @@ impl WhirlpoolRewardInfo {
     }
 
     /// Maps all reward data to only the reward growth accumulators
-    pub fn to_reward_growths(
-        reward_infos: &[WhirlpoolRewardInfo; NUM_REWARDS],
-    ) -> [u128; NUM_REWARDS] {
-        let mut reward_growths = [0u128; NUM_REWARDS];
-        for i in 0..NUM_REWARDS {
+    pub fn to_reward_growths<const N: usize>(reward_infos: &[WhirlpoolRewardInfo; N]) -> [u128; N] {
+        let mut reward_growths = [0u128; N];
+        for i in 0..N {
             reward_growths[i] = reward_infos[i].growth_global_x64;
         }
         reward_growths
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+/// Pause of a single Whirlpool, set by the config extension authority.
+///
+/// The pause is a PDA of the Whirlpool, so the size of the Whirlpool account is not changed.
+/// It is created when the Whirlpool is paused for the first time, and a Whirlpool without one
+/// is not paused.
+#[account]
+#[derive(Default, Debug, PartialEq)]
+pub struct WhirlpoolPause {
+    pub whirlpool: Pubkey, // 32
+    // Halts trading and adding liquidity
+    pub paused: bool, // 1
+                      // 63 RESERVE
+}
+
+impl WhirlpoolPause {
+    pub const LEN: usize = 8 + 32 + 1 + 63;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey) {
+        self.whirlpool = whirlpool;
+    }
+
+    pub fn update_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+}
+
+#[cfg(test)]
+mod whirlpool_pause_tests {
+    use super::*;
+
+    #[test]
+    fn test_len() {
+        let mut serialized = Vec::new();
+        WhirlpoolPause::default()
+            .try_serialize(&mut serialized)
+            .unwrap();
+        assert!(serialized.len() <= WhirlpoolPause::LEN);
+        assert_eq!(WhirlpoolPause::LEN - serialized.len(), 63);
+    }
+
+    #[test]
+    fn test_update_paused() {
+        let whirlpool = Pubkey::new_unique();
+        let mut whirlpool_pause = WhirlpoolPause::default();
+        whirlpool_pause.initialize(whirlpool);
+        assert_eq!(whirlpool_pause.whirlpool, whirlpool);
+        assert!(!whirlpool_pause.paused);
+
+        whirlpool_pause.update_paused(true);
+        assert!(whirlpool_pause.paused);
+        whirlpool_pause.update_paused(false);
+        assert!(!whirlpool_pause.paused);
+    }
+}
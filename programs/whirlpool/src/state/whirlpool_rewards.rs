@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Reward schedules and extension rewards of a single Whirlpool.
+///
+/// The WhirlpoolRewardSchedule and the WhirlpoolRewardExtension are appended to this PDA of the
+/// Whirlpool, so the size of the Whirlpool account is not changed. It is created with the
+/// WhirlpoolRewardSchedule, and a Whirlpool without one has neither schedules nor extension rewards.
+#[account]
+#[derive(Default, Debug, PartialEq)]
+pub struct WhirlpoolRewards {
+    pub whirlpool: Pubkey, // 32
+                           // 64 RESERVE
+}
+
+impl WhirlpoolRewards {
+    pub const LEN: usize = 8 + 32 + 64;
+
+    pub fn initialize(&mut self, whirlpool: Pubkey) {
+        self.whirlpool = whirlpool;
+    }
+}
+
+#[cfg(test)]
+mod whirlpool_rewards_tests {
+    use super::*;
+
+    #[test]
+    fn test_len() {
+        let mut serialized = Vec::new();
+        WhirlpoolRewards::default()
+            .try_serialize(&mut serialized)
+            .unwrap();
+        assert!(serialized.len() <= WhirlpoolRewards::LEN);
+        assert_eq!(WhirlpoolRewards::LEN - serialized.len(), 64);
+    }
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::cell::{Ref, RefMut};
+use std::ops::DerefMut;
+
+use crate::{
+    errors::ErrorCode,
+    state::{DynamicTickArray, LimitOrderBook, TickArray, TickArrayType},
+    util::{
+        allocate_tick_array_section, load_tick_array_mut, LoadedTickArrayMut, TickArraySection,
+    },
+};
+
+/// Limit orders are only supported on fixed TickArrays, in pools whose mints are owned by the
+/// Token program. The LimitOrderBook is appended at a fixed offset after the TickArray data,
+/// and the limit order instructions do not handle the Token-2022 extensions.
+///
+/// # Errors
+/// - `LimitOrderNotSupported` - If the TickArray is a DynamicTickArray or the vault is owned by
+///   the Token-2022 program
+pub fn verify_limit_order_supported(
+    tick_array: &AccountInfo<'_>,
+    token_vault: &AccountInfo<'_>,
+) -> Result<()> {
+    if *token_vault.owner != anchor_spl::token::ID {
+        return Err(ErrorCode::LimitOrderNotSupported.into());
+    }
+
+    let data = tick_array.try_borrow_data()?;
+    if data.len() >= 8 && data[..8] == DynamicTickArray::discriminator() {
+        return Err(ErrorCode::LimitOrderNotSupported.into());
+    }
+
+    Ok(())
+}
+
+/// Appends a zeroed LimitOrderBook to the TickArray account data if it is not allocated yet.
+/// The funder pays the rent for the additional space and is refunded when the TickArray is closed.
+pub fn allocate_limit_order_book<'info>(
+    tick_array: &AccountInfo<'info>,
+    rent_payers: &AccountInfo<'info>,
+    rent_payers_bump: u8,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    allocate_tick_array_section(
+        tick_array,
+        TickArraySection::LimitOrderBook,
+        rent_payers,
+        rent_payers_bump,
+        funder,
+        system_program,
+    )
+}
+
+/// Returns the offset of the limit orders of the given tick in the LimitOrderBook of the TickArray.
+///
+/// # Errors
+/// - `LimitOrderTickArrayMismatch` - If the tick is not in the TickArray or the TickArray has no LimitOrderBook
+/// - `LimitOrderNotSupported` - If the TickArray is a DynamicTickArray
+/// - `DifferentWhirlpoolTickArrayAccount` - If the TickArray is not for the whirlpool
+/// - `TickNotFound` - If the tick is not an initializable tick for the tick spacing
+pub fn get_limit_order_offset(
+    tick_array: &AccountInfo<'_>,
+    whirlpool: &Pubkey,
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<usize> {
+    if !LimitOrderBook::is_allocated(tick_array.data_len()) {
+        return Err(ErrorCode::LimitOrderTickArrayMismatch.into());
+    }
+
+    let tick_array = match load_tick_array_mut(tick_array, whirlpool)? {
+        LoadedTickArrayMut::Fixed(tick_array) => tick_array,
+        LoadedTickArrayMut::Dynamic(_) => return Err(ErrorCode::LimitOrderNotSupported.into()),
+    };
+    if !tick_array.check_in_array_bounds(tick_index, tick_spacing) {
+        return Err(ErrorCode::LimitOrderTickArrayMismatch.into());
+    }
+    tick_array.get_tick(tick_index, tick_spacing)?;
+
+    Ok(tick_array.tick_offset(tick_index, tick_spacing)? as usize)
+}
+
+pub fn load_limit_order_book_mut<'a>(
+    tick_array: &'a AccountInfo<'_>,
+) -> Result<RefMut<'a, LimitOrderBook>> {
+    if !LimitOrderBook::is_allocated(tick_array.data_len()) {
+        return Err(ErrorCode::LimitOrderTickArrayMismatch.into());
+    }
+
+    let data = tick_array.try_borrow_mut_data()?;
+    Ok(RefMut::map(data, |data| {
+        bytemuck::from_bytes_mut(
+            &mut data.deref_mut()
+                [LimitOrderBook::OFFSET..LimitOrderBook::OFFSET + LimitOrderBook::LEN],
+        )
+    }))
+}
+
+pub fn load_limit_order_book<'a>(
+    tick_array: &'a AccountInfo<'_>,
+) -> Result<Ref<'a, LimitOrderBook>> {
+    if !LimitOrderBook::is_allocated(tick_array.data_len()) {
+        return Err(ErrorCode::LimitOrderTickArrayMismatch.into());
+    }
+
+    let data = tick_array.try_borrow_data()?;
+    Ok(Ref::map(data, |data| {
+        bytemuck::from_bytes(
+            &data[LimitOrderBook::OFFSET..LimitOrderBook::OFFSET + LimitOrderBook::LEN],
+        )
+    }))
+}
+
+#[cfg(test)]
+mod limit_order_support_tests {
+    use super::*;
+
+    fn account_info<'a>(
+        key: &'a Pubkey,
+        owner: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+    }
+
+    #[test]
+    fn test_verify_limit_order_supported() {
+        let key = Pubkey::new_unique();
+        let (mut tick_array_lamports, mut vault_lamports) = (0, 0);
+
+        let mut fixed_data = vec![0u8; TickArray::LEN];
+        fixed_data[..8].copy_from_slice(&TickArray::discriminator());
+        let mut dynamic_data = vec![0u8; TickArray::LEN];
+        dynamic_data[..8].copy_from_slice(&DynamicTickArray::discriminator());
+        let mut vault_data = vec![];
+
+        let fixed_tick_array =
+            account_info(&key, &crate::ID, &mut tick_array_lamports, &mut fixed_data);
+        let vault = account_info(
+            &key,
+            &anchor_spl::token::ID,
+            &mut vault_lamports,
+            &mut vault_data,
+        );
+        assert!(verify_limit_order_supported(&fixed_tick_array, &vault).is_ok());
+
+        let token_2022_vault = AccountInfo {
+            owner: &anchor_spl::token_2022::ID,
+            ..vault.clone()
+        };
+        assert_eq!(
+            verify_limit_order_supported(&fixed_tick_array, &token_2022_vault).unwrap_err(),
+            ErrorCode::LimitOrderNotSupported.into()
+        );
+
+        let mut dynamic_lamports = 0;
+        let dynamic_tick_array =
+            account_info(&key, &crate::ID, &mut dynamic_lamports, &mut dynamic_data);
+        assert_eq!(
+            verify_limit_order_supported(&dynamic_tick_array, &vault).unwrap_err(),
+            ErrorCode::LimitOrderNotSupported.into()
+        );
+    }
+}
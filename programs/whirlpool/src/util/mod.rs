@@ -1,15 +1,23 @@
+pub mod limit_order;
+pub mod reward_extension;
+pub mod reward_schedule;
 pub mod shared;
 pub mod sparse_swap;
 pub mod swap_tick_sequence;
 pub mod swap_utils;
+pub mod tick_array;
 pub mod token;
 pub mod token_2022;
 pub mod v2;
 
+pub use limit_order::*;
+pub use reward_extension::*;
+pub use reward_schedule::*;
 pub use shared::*;
 pub use sparse_swap::*;
 pub use swap_tick_sequence::*;
 pub use swap_utils::*;
+pub use tick_array::*;
 pub use token::*;
 pub use token_2022::*;
 pub use v2::*;
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::{Discriminator, Owner};
+use std::cell::RefMut;
+use std::ops::DerefMut;
+
+use crate::{
+    errors::ErrorCode,
+    state::{
+        PositionRewardExtension, TickArray, TickArrayRewardExtension, TickArrayType,
+        WhirlpoolRewardExtension, NUM_EXTENSION_REWARDS,
+    },
+};
+
+/// Extends the account data up to the given length if it is shorter.
+/// The added space is zeroed and the funder pays the rent for it.
+pub fn allocate_reward_extension<'info>(
+    account_info: &AccountInfo<'info>,
+    space: usize,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if account_info.data_len() >= space {
+        return Ok(());
+    }
+
+    let required_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account_info.lamports());
+    if required_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: funder.to_account_info(),
+                    to: account_info.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+
+    account_info.realloc(space, true)?;
+
+    Ok(())
+}
+
+/// Loads the WhirlpoolRewardExtension from the WhirlpoolRewards PDA of a Whirlpool.
+/// None is returned if the PDA has not been created or carries no extension.
+///
+/// The address of the PDA must have been checked by the caller.
+pub fn load_whirlpool_reward_extension(
+    whirlpool_rewards: &AccountInfo<'_>,
+) -> Result<Option<WhirlpoolRewardExtension>> {
+    if !WhirlpoolRewardExtension::is_allocated(whirlpool_rewards.data_len()) {
+        return Ok(None);
+    }
+
+    let data = whirlpool_rewards.try_borrow_data()?;
+    let reward_extension = WhirlpoolRewardExtension::deserialize(
+        &mut &data[WhirlpoolRewardExtension::OFFSET
+            ..WhirlpoolRewardExtension::OFFSET + WhirlpoolRewardExtension::LEN],
+    )?;
+    Ok(Some(reward_extension))
+}
+
+pub fn store_whirlpool_reward_extension(
+    whirlpool_rewards: &AccountInfo<'_>,
+    reward_extension: &WhirlpoolRewardExtension,
+) -> Result<()> {
+    if !WhirlpoolRewardExtension::is_allocated(whirlpool_rewards.data_len()) {
+        return Err(ErrorCode::RewardExtensionNotInitialized.into());
+    }
+
+    let mut data = whirlpool_rewards.try_borrow_mut_data()?;
+    reward_extension.serialize(
+        &mut &mut data[WhirlpoolRewardExtension::OFFSET
+            ..WhirlpoolRewardExtension::OFFSET + WhirlpoolRewardExtension::LEN],
+    )?;
+    Ok(())
+}
+
+/// Records the amount of reward tokens collected from the vault of the extension reward at the
+/// given index.
+pub fn record_extension_reward_collected(
+    whirlpool_rewards: &AccountInfo<'_>,
+    index: usize,
+    amount: u64,
+) -> Result<()> {
+    let mut reward_extension = load_whirlpool_reward_extension(whirlpool_rewards)?
+        .ok_or(ErrorCode::RewardExtensionNotInitialized)?;
+    reward_extension.record_collected(index, amount);
+    store_whirlpool_reward_extension(whirlpool_rewards, &reward_extension)
+}
+
+pub fn load_position_reward_extension(
+    position: &AccountInfo<'_>,
+) -> Result<Option<PositionRewardExtension>> {
+    if !PositionRewardExtension::is_allocated(position.data_len()) {
+        return Ok(None);
+    }
+
+    let data = position.try_borrow_data()?;
+    let reward_extension = PositionRewardExtension::deserialize(
+        &mut &data[PositionRewardExtension::OFFSET
+            ..PositionRewardExtension::OFFSET + PositionRewardExtension::LEN],
+    )?;
+    Ok(Some(reward_extension))
+}
+
+pub fn store_position_reward_extension(
+    position: &AccountInfo<'_>,
+    reward_extension: &PositionRewardExtension,
+) -> Result<()> {
+    if !PositionRewardExtension::is_allocated(position.data_len()) {
+        return Err(ErrorCode::RewardExtensionNotAllocated.into());
+    }
+
+    let mut data = position.try_borrow_mut_data()?;
+    reward_extension.serialize(
+        &mut &mut data[PositionRewardExtension::OFFSET
+            ..PositionRewardExtension::OFFSET + PositionRewardExtension::LEN],
+    )?;
+    Ok(())
+}
+
+/// Returns true if the position has no extension rewards left to collect.
+pub fn is_position_reward_extension_empty(position: &AccountInfo<'_>) -> Result<bool> {
+    Ok(load_position_reward_extension(position)?.is_none_or(|ext| ext.is_empty()))
+}
+
+/// Rejects accounts carrying a reward extension in instructions that cannot keep it up to date.
+pub fn verify_no_reward_extension(account_info: &AccountInfo<'_>, len: usize) -> Result<()> {
+    if account_info.data_len() > len {
+        return Err(ErrorCode::RewardExtensionNotSupported.into());
+    }
+    Ok(())
+}
+
+/// Load a TickArray account of the given whirlpool and the TickArrayRewardExtension appended to it.
+/// DynamicTickArray accounts never carry a TickArrayRewardExtension.
+///
+/// # Errors
+/// - `AccountOwnedByWrongProgram` - If the account is not owned by this program
+/// - `RewardExtensionNotAllocated` - If the account is not a TickArray with a TickArrayRewardExtension
+/// - `DifferentWhirlpoolTickArrayAccount` - If the tick array is not for the whirlpool
+pub fn load_tick_array_reward_extension_mut<'a>(
+    tick_array: &'a AccountInfo<'_>,
+    whirlpool: &Pubkey,
+) -> Result<(RefMut<'a, TickArray>, RefMut<'a, TickArrayRewardExtension>)> {
+    if tick_array.owner != &TickArray::owner() {
+        return Err(
+            Error::from(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram)
+                .with_pubkeys((*tick_array.owner, TickArray::owner())),
+        );
+    }
+
+    let data = tick_array.try_borrow_mut_data()?;
+    if !TickArrayRewardExtension::is_allocated(data.len())
+        || data[..8] != TickArray::discriminator()
+    {
+        return Err(ErrorCode::RewardExtensionNotAllocated.into());
+    }
+
+    let (tick_array, reward_extension) = RefMut::map_split(data, |data| {
+        let (tick_array_data, reward_extension_data) = data
+            .deref_mut()
+            .split_at_mut(TickArrayRewardExtension::OFFSET);
+        (
+            bytemuck::from_bytes_mut::<TickArray>(
+                &mut tick_array_data[8..std::mem::size_of::<TickArray>() + 8],
+            ),
+            bytemuck::from_bytes_mut(&mut reward_extension_data[..TickArrayRewardExtension::LEN]),
+        )
+    });
+
+    // has_one constraint equivalent check
+    if tick_array.whirlpool != *whirlpool {
+        return Err(ErrorCode::DifferentWhirlpoolTickArrayAccount.into());
+    }
+
+    Ok((tick_array, reward_extension))
+}
+
+/// Returns true if the account is a TickArray carrying a TickArrayRewardExtension.
+pub fn has_tick_array_reward_extension(tick_array: &AccountInfo<'_>) -> Result<bool> {
+    if tick_array.owner != &TickArray::owner() {
+        return Ok(false);
+    }
+    let data = tick_array.try_borrow_data()?;
+    Ok(TickArrayRewardExtension::is_allocated(data.len())
+        && data[..8] == TickArray::discriminator())
+}
+
+/// Returns the growths outside of the given tick for the extension rewards.
+///
+/// The growths outside of the ticks are only tracked by TickArrays carrying a TickArrayRewardExtension,
+/// so None is returned for the other TickArrays (including DynamicTickArrays).
+pub fn get_tick_reward_growths_outside(
+    tick_array: &AccountInfo<'_>,
+    whirlpool: &Pubkey,
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<Option<[u128; NUM_EXTENSION_REWARDS]>> {
+    if !has_tick_array_reward_extension(tick_array)? {
+        return Ok(None);
+    }
+    let (tick_array, reward_extension) =
+        load_tick_array_reward_extension_mut(tick_array, whirlpool)?;
+    let offset = tick_reward_extension_offset(&tick_array, tick_index, tick_spacing)?;
+    Ok(Some(reward_extension.reward_growths_outside[offset]))
+}
+
+pub fn update_tick_reward_growths_outside(
+    tick_array: &AccountInfo<'_>,
+    whirlpool: &Pubkey,
+    tick_index: i32,
+    tick_spacing: u16,
+    reward_growths_outside: &[u128; NUM_EXTENSION_REWARDS],
+) -> Result<()> {
+    let (tick_array, mut reward_extension) =
+        load_tick_array_reward_extension_mut(tick_array, whirlpool)?;
+    let offset = tick_reward_extension_offset(&tick_array, tick_index, tick_spacing)?;
+    reward_extension.reward_growths_outside[offset] = *reward_growths_outside;
+    Ok(())
+}
+
+fn tick_reward_extension_offset(
+    tick_array: &TickArray,
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<usize> {
+    // Verifies that the tick is an initializable tick of the array
+    tick_array.get_tick(tick_index, tick_spacing)?;
+    Ok(tick_array.tick_offset(tick_index, tick_spacing)? as usize)
+}
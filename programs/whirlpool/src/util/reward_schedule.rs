@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+use crate::{
+    errors::ErrorCode,
+    state::{WhirlpoolRewardSchedule, WhirlpoolRewards},
+};
+
+/// Creates the WhirlpoolRewards PDA of the Whirlpool with room for a WhirlpoolRewardSchedule.
+/// The funder pays the rent for it.
+pub fn create_whirlpool_rewards<'info>(
+    whirlpool_rewards: &AccountInfo<'info>,
+    whirlpool: Pubkey,
+    whirlpool_rewards_bump: u8,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if *whirlpool_rewards.owner == crate::ID {
+        return Err(ErrorCode::RewardScheduleAlreadyInitialized.into());
+    }
+
+    let space = WhirlpoolRewardSchedule::OFFSET + WhirlpoolRewardSchedule::LEN;
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: funder.to_account_info(),
+                to: whirlpool_rewards.clone(),
+            },
+            &[&[
+                b"whirlpool_rewards",
+                whirlpool.as_ref(),
+                &[whirlpool_rewards_bump],
+            ]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )?;
+
+    let mut header = WhirlpoolRewards::default();
+    header.initialize(whirlpool);
+    let mut data = whirlpool_rewards.try_borrow_mut_data()?;
+    let mut dst: &mut [u8] = &mut data;
+    header.try_serialize(&mut dst)
+}
+
+/// Loads the WhirlpoolRewardSchedule from the WhirlpoolRewards PDA of a Whirlpool.
+/// None is returned if the PDA has not been created, as the Whirlpool then has no schedules.
+///
+/// The address of the PDA must have been checked by the caller.
+pub fn load_whirlpool_reward_schedule(
+    whirlpool_rewards: &AccountInfo<'_>,
+) -> Result<Option<WhirlpoolRewardSchedule>> {
+    if !WhirlpoolRewardSchedule::is_allocated(whirlpool_rewards.data_len()) {
+        return Ok(None);
+    }
+
+    let data = whirlpool_rewards.try_borrow_data()?;
+    let reward_schedule = WhirlpoolRewardSchedule::deserialize(
+        &mut &data[WhirlpoolRewardSchedule::OFFSET
+            ..WhirlpoolRewardSchedule::OFFSET + WhirlpoolRewardSchedule::LEN],
+    )?;
+    Ok(Some(reward_schedule))
+}
+
+pub fn store_whirlpool_reward_schedule(
+    whirlpool_rewards: &AccountInfo<'_>,
+    reward_schedule: &WhirlpoolRewardSchedule,
+) -> Result<()> {
+    if !WhirlpoolRewardSchedule::is_allocated(whirlpool_rewards.data_len()) {
+        return Err(ErrorCode::InvalidRewardSchedule.into());
+    }
+
+    let mut data = whirlpool_rewards.try_borrow_mut_data()?;
+    reward_schedule.serialize(
+        &mut &mut data[WhirlpoolRewardSchedule::OFFSET
+            ..WhirlpoolRewardSchedule::OFFSET + WhirlpoolRewardSchedule::LEN],
+    )?;
+    Ok(())
+}
+
+/// Records the amount of reward tokens collected from the vault of the reward at the given index.
+/// No-op if the Whirlpool has no reward schedules to track the amounts in.
+pub fn record_reward_collected(
+    whirlpool_rewards: &AccountInfo<'_>,
+    index: usize,
+    amount: u64,
+) -> Result<()> {
+    if let Some(mut reward_schedule) = load_whirlpool_reward_schedule(whirlpool_rewards)? {
+        reward_schedule.record_collected(index, amount);
+        store_whirlpool_reward_schedule(whirlpool_rewards, &reward_schedule)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod reward_schedule_util_tests {
+    use super::*;
+    use crate::state::RewardLedger;
+    use crate::util::test_utils::leaked_account_info;
+
+    fn whirlpool_rewards_info(len: usize) -> AccountInfo<'static> {
+        leaked_account_info(Pubkey::new_unique(), crate::ID, false, true, vec![0; len])
+    }
+
+    #[test]
+    fn test_record_reward_collected() {
+        let whirlpool_rewards_info =
+            whirlpool_rewards_info(WhirlpoolRewardSchedule::OFFSET + WhirlpoolRewardSchedule::LEN);
+        let reward_schedule = WhirlpoolRewardSchedule {
+            ledgers: [
+                RewardLedger::new(100),
+                Default::default(),
+                Default::default(),
+            ],
+            ..Default::default()
+        };
+        store_whirlpool_reward_schedule(&whirlpool_rewards_info, &reward_schedule).unwrap();
+
+        record_reward_collected(&whirlpool_rewards_info, 0, 40).unwrap();
+
+        let reward_schedule = load_whirlpool_reward_schedule(&whirlpool_rewards_info)
+            .unwrap()
+            .unwrap();
+        assert_eq!(reward_schedule.ledgers[0].amount_collected, 40);
+        assert_eq!(reward_schedule.ledgers[0].amount_outstanding(), 60);
+    }
+
+    #[test]
+    fn test_record_reward_collected_without_schedule() {
+        // The WhirlpoolRewards PDA of a Whirlpool without schedules is not created
+        let whirlpool_rewards_info =
+            leaked_account_info(Pubkey::new_unique(), System::id(), false, true, vec![]);
+        record_reward_collected(&whirlpool_rewards_info, 0, 40).unwrap();
+        assert!(load_whirlpool_reward_schedule(&whirlpool_rewards_info)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_store_reward_schedule_not_allocated() {
+        let whirlpool_rewards_info = whirlpool_rewards_info(WhirlpoolRewards::LEN);
+        assert_eq!(
+            store_whirlpool_reward_schedule(
+                &whirlpool_rewards_info,
+                &WhirlpoolRewardSchedule::default()
+            )
+            .unwrap_err(),
+            ErrorCode::InvalidRewardSchedule.into()
+        );
+    }
+}
@@ -8,13 +8,15 @@ use solana_program::program_option::COption;
 use std::convert::TryFrom;
 
 use crate::errors::ErrorCode;
+use crate::state::{WhirlpoolPause, WhirlpoolsConfigExtension};
 
-pub fn verify_position_bundle_authority(
-    position_bundle_token_account: &TokenAccount,
+pub fn verify_position_bundle_authority_interface(
+    // position_bundle_token_account is owned by either TokenProgram or Token2022Program
+    position_bundle_token_account: &InterfaceAccount<'_, TokenAccountInterface>,
     position_bundle_authority: &Signer<'_>,
 ) -> Result<()> {
     // use same logic
-    verify_position_authority(position_bundle_token_account, position_bundle_authority)
+    verify_position_authority_interface(position_bundle_token_account, position_bundle_authority)
 }
 
 pub fn verify_position_authority(
@@ -58,6 +60,45 @@ pub fn verify_position_authority_interface(
     Ok(())
 }
 
+/// Unlike verify_position_authority_interface, a delegate of the position token is rejected.
+pub fn verify_position_owner_interface(
+    position_token_account: &InterfaceAccount<'_, TokenAccountInterface>,
+    position_owner: &Signer<'_>,
+) -> Result<()> {
+    validate_owner(
+        &position_token_account.owner,
+        &position_owner.to_account_info(),
+    )
+}
+
+/// Rejects the operation if the position is locked.
+/// Only lock_position freezes the token account of a position, through the Position account
+/// which is the freeze authority of the position mints created by open_position_with_token_extensions.
+pub fn verify_position_not_locked(
+    position_token_account: &InterfaceAccount<'_, TokenAccountInterface>,
+) -> Result<()> {
+    if position_token_account.is_frozen() {
+        return Err(ErrorCode::PositionLocked.into());
+    }
+    Ok(())
+}
+
+/// Rejects the operation if the current sqrt price of the Whirlpool is out of the given bounds (inclusive).
+/// This protects liquidity changes from price manipulation within the position range,
+/// which token_max_a/token_max_b or token_min_a/token_min_b alone do not bound.
+pub fn verify_sqrt_price_in_range(
+    sqrt_price: u128,
+    sqrt_price_min: Option<u128>,
+    sqrt_price_max: Option<u128>,
+) -> Result<()> {
+    if sqrt_price_min.is_some_and(|sqrt_price_min| sqrt_price < sqrt_price_min)
+        || sqrt_price_max.is_some_and(|sqrt_price_max| sqrt_price > sqrt_price_max)
+    {
+        return Err(ErrorCode::SqrtPriceOutOfRange.into());
+    }
+    Ok(())
+}
+
 fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) -> Result<()> {
     if expected_owner != owner_account_info.key || !owner_account_info.is_signer {
         return Err(ErrorCode::MissingOrInvalidDelegate.into());
@@ -69,3 +110,283 @@ fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) ->
 pub fn to_timestamp_u64(t: i64) -> Result<u64> {
     u64::try_from(t).or(Err(ErrorCode::InvalidTimestampConversion.into()))
 }
+
+/// Rejects the instruction if the Whirlpool is paused, either by its WhirlpoolPause or by the
+/// WhirlpoolsConfigExtension of its WhirlpoolsConfig.
+///
+/// Both accounts are required by the instructions which move the price or add liquidity, and their
+/// addresses are checked against the PDA seeds by the callers. A WhirlpoolPause which is not
+/// initialized (never paused) or a WhirlpoolsConfig without extension does not pause the Whirlpool.
+pub fn verify_whirlpool_not_paused(
+    whirlpools_config_extension: &AccountInfo<'_>,
+    whirlpool_pause: &AccountInfo<'_>,
+) -> Result<()> {
+    let config_paused = load_whirlpools_config_extension(whirlpools_config_extension)?
+        .is_some_and(|config_extension| config_extension.paused);
+    let whirlpool_paused = load_if_initialized::<WhirlpoolPause>(whirlpool_pause)?
+        .is_some_and(|whirlpool_pause| whirlpool_pause.paused);
+    if config_paused || whirlpool_paused {
+        return Err(ErrorCode::WhirlpoolPaused.into());
+    }
+    Ok(())
+}
+
+/// Returns the WhirlpoolsConfigExtension, or None if the WhirlpoolsConfig has no extension.
+/// The address of the account must be checked by the caller.
+pub fn load_whirlpools_config_extension(
+    whirlpools_config_extension: &AccountInfo<'_>,
+) -> Result<Option<WhirlpoolsConfigExtension>> {
+    load_if_initialized(whirlpools_config_extension)
+}
+
+fn load_if_initialized<T: AccountDeserialize>(account_info: &AccountInfo<'_>) -> Result<Option<T>> {
+    // PDAs which are not initialized are owned by the System program
+    if *account_info.owner != crate::ID {
+        return Ok(None);
+    }
+    Ok(Some(T::try_deserialize(
+        &mut account_info.try_borrow_data()?.as_ref(),
+    )?))
+}
+
+#[cfg(test)]
+mod shared_tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_sqrt_price_in_range() {
+        assert!(verify_sqrt_price_in_range(100, None, None).is_ok());
+        assert!(verify_sqrt_price_in_range(100, Some(100), Some(100)).is_ok());
+        assert!(verify_sqrt_price_in_range(100, Some(50), None).is_ok());
+        assert!(verify_sqrt_price_in_range(100, None, Some(150)).is_ok());
+
+        assert_eq!(
+            verify_sqrt_price_in_range(100, Some(101), None).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+        assert_eq!(
+            verify_sqrt_price_in_range(100, None, Some(99)).unwrap_err(),
+            ErrorCode::SqrtPriceOutOfRange.into()
+        );
+        // an empty range rejects any price
+        assert!(verify_sqrt_price_in_range(100, Some(150), Some(50)).is_err());
+    }
+
+    mod whirlpool_pause {
+        use super::*;
+        use crate::util::test_utils::{anchor_account_data, leaked_account_info};
+
+        fn config_extension(
+            owner: Pubkey,
+            whirlpools_config: Pubkey,
+            paused: bool,
+        ) -> AccountInfo<'static> {
+            let config_extension = WhirlpoolsConfigExtension {
+                whirlpools_config,
+                config_extension_authority: Pubkey::new_unique(),
+                token_badge_authority: Pubkey::new_unique(),
+                paused,
+                max_referral_fee_rate: 0,
+            };
+            leaked_account_info(
+                Pubkey::new_unique(),
+                owner,
+                false,
+                false,
+                anchor_account_data(&config_extension, WhirlpoolsConfigExtension::LEN),
+            )
+        }
+
+        fn whirlpool_pause(paused: bool) -> AccountInfo<'static> {
+            let whirlpool_pause = WhirlpoolPause {
+                whirlpool: Pubkey::new_unique(),
+                paused,
+            };
+            leaked_account_info(
+                Pubkey::new_unique(),
+                crate::ID,
+                false,
+                false,
+                anchor_account_data(&whirlpool_pause, WhirlpoolPause::LEN),
+            )
+        }
+
+        fn uninitialized() -> AccountInfo<'static> {
+            leaked_account_info(Pubkey::new_unique(), System::id(), false, false, vec![])
+        }
+
+        #[test]
+        fn test_not_paused() {
+            let whirlpools_config = Pubkey::new_unique();
+            assert!(verify_whirlpool_not_paused(&uninitialized(), &uninitialized()).is_ok());
+            assert!(verify_whirlpool_not_paused(
+                &config_extension(crate::ID, whirlpools_config, false),
+                &whirlpool_pause(false),
+            )
+            .is_ok());
+        }
+
+        #[test]
+        fn test_whirlpool_paused() {
+            for whirlpools_config_extension in [
+                uninitialized(),
+                config_extension(crate::ID, Pubkey::new_unique(), false),
+            ] {
+                assert_eq!(
+                    verify_whirlpool_not_paused(
+                        &whirlpools_config_extension,
+                        &whirlpool_pause(true)
+                    )
+                    .unwrap_err(),
+                    ErrorCode::WhirlpoolPaused.into()
+                );
+            }
+        }
+
+        #[test]
+        fn test_config_paused() {
+            for whirlpool_pause in [uninitialized(), whirlpool_pause(false)] {
+                assert_eq!(
+                    verify_whirlpool_not_paused(
+                        &config_extension(crate::ID, Pubkey::new_unique(), true),
+                        &whirlpool_pause,
+                    )
+                    .unwrap_err(),
+                    ErrorCode::WhirlpoolPaused.into()
+                );
+            }
+        }
+
+        #[test]
+        fn test_wrong_account_type() {
+            // a WhirlpoolPause passed as the WhirlpoolsConfigExtension
+            assert!(verify_whirlpool_not_paused(&whirlpool_pause(true), &uninitialized()).is_err());
+        }
+    }
+
+    mod position_bundle_authority {
+        use super::*;
+        use crate::util::test_utils::{leaked_account_info, token_account_data};
+        use anchor_spl::token::spl_token;
+        use anchor_spl::token_2022::spl_token_2022;
+
+        const TOKEN_PROGRAMS: [Pubkey; 2] = [spl_token::ID, spl_token_2022::ID];
+
+        fn position_bundle_token_account(
+            token_program: Pubkey,
+            owner: Pubkey,
+            delegate: Option<(Pubkey, u64)>,
+        ) -> InterfaceAccount<'static, TokenAccountInterface> {
+            let account_info = Box::leak(Box::new(leaked_account_info(
+                Pubkey::new_unique(),
+                token_program,
+                false,
+                false,
+                token_account_data(Pubkey::new_unique(), owner, 1, delegate),
+            )));
+            InterfaceAccount::try_from(account_info).unwrap()
+        }
+
+        fn signer(key: Pubkey) -> Signer<'static> {
+            let account_info = Box::leak(Box::new(leaked_account_info(
+                key,
+                System::id(),
+                true,
+                false,
+                vec![],
+            )));
+            Signer::try_from(account_info).unwrap()
+        }
+
+        #[test]
+        fn test_owner() {
+            for token_program in TOKEN_PROGRAMS {
+                let owner = Pubkey::new_unique();
+                let token_account = position_bundle_token_account(token_program, owner, None);
+                assert!(
+                    verify_position_bundle_authority_interface(&token_account, &signer(owner))
+                        .is_ok()
+                );
+            }
+        }
+
+        #[test]
+        fn test_delegate() {
+            for token_program in TOKEN_PROGRAMS {
+                let delegate = Pubkey::new_unique();
+                let token_account = position_bundle_token_account(
+                    token_program,
+                    Pubkey::new_unique(),
+                    Some((delegate, 1)),
+                );
+                assert!(verify_position_bundle_authority_interface(
+                    &token_account,
+                    &signer(delegate)
+                )
+                .is_ok());
+            }
+        }
+
+        #[test]
+        fn test_delegate_with_invalid_amount() {
+            for token_program in TOKEN_PROGRAMS {
+                let delegate = Pubkey::new_unique();
+                let token_account = position_bundle_token_account(
+                    token_program,
+                    Pubkey::new_unique(),
+                    Some((delegate, 2)),
+                );
+                assert_eq!(
+                    verify_position_bundle_authority_interface(&token_account, &signer(delegate))
+                        .unwrap_err(),
+                    ErrorCode::InvalidPositionTokenAmount.into()
+                );
+            }
+        }
+
+        #[test]
+        fn test_other_signer() {
+            for token_program in TOKEN_PROGRAMS {
+                let token_account = position_bundle_token_account(
+                    token_program,
+                    Pubkey::new_unique(),
+                    Some((Pubkey::new_unique(), 1)),
+                );
+                assert_eq!(
+                    verify_position_bundle_authority_interface(
+                        &token_account,
+                        &signer(Pubkey::new_unique())
+                    )
+                    .unwrap_err(),
+                    ErrorCode::MissingOrInvalidDelegate.into()
+                );
+            }
+        }
+
+        #[test]
+        fn test_position_owner() {
+            for token_program in TOKEN_PROGRAMS {
+                let (owner, delegate) = (Pubkey::new_unique(), Pubkey::new_unique());
+                let token_account =
+                    position_bundle_token_account(token_program, owner, Some((delegate, 1)));
+                assert!(verify_position_owner_interface(&token_account, &signer(owner)).is_ok());
+                assert_eq!(
+                    verify_position_owner_interface(&token_account, &signer(delegate)).unwrap_err(),
+                    ErrorCode::MissingOrInvalidDelegate.into()
+                );
+            }
+        }
+
+        #[test]
+        fn test_token_account_of_other_program() {
+            let account_info = Box::leak(Box::new(leaked_account_info(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                false,
+                false,
+                token_account_data(Pubkey::new_unique(), Pubkey::new_unique(), 1, None),
+            )));
+            assert!(InterfaceAccount::<TokenAccountInterface>::try_from(&*account_info).is_err());
+        }
+    }
+}
@@ -6,22 +6,44 @@ use std::{
 
 use crate::{
     errors::ErrorCode,
+    manager::tick_manager::next_reward_growths_outside_on_cross,
     state::{
-        Tick, TickArray, TickArrayType, TickUpdate, Whirlpool, ZeroedTickArray, TICK_ARRAY_SIZE,
+        DynamicTickArray, DynamicTickArrayMut, LimitOrderBook, LimitOrderLevel, Tick, TickArray,
+        TickArrayRewardExtension, TickArrayType, TickUpdate, Whirlpool, WhirlpoolRewardInfo,
+        ZeroedTickArray, NUM_EXTENSION_REWARDS, TICK_ARRAY_SIZE,
     },
     util::SwapTickSequence,
 };
 
 // In the case of an uninitialized TickArray, ZeroedTickArray is used to substitute TickArray behavior.
 // Since all Tick are not initialized, it can be substituted by returning Tick::default().
+// An initialized TickArray may carry a LimitOrderBook and a TickArrayRewardExtension appended to its account data.
+// A DynamicTickArray only stores its initialized ticks and never carries either of them.
 pub(crate) enum ProxiedTickArray<'a> {
-    Initialized(RefMut<'a, TickArray>),
+    Initialized(
+        RefMut<'a, TickArray>,
+        Option<RefMut<'a, LimitOrderBook>>,
+        Option<RefMut<'a, TickArrayRewardExtension>>,
+    ),
+    Dynamic(DynamicTickArrayMut<'a>),
     Uninitialized(ZeroedTickArray),
 }
 
 impl<'a> ProxiedTickArray<'a> {
     pub fn new_initialized(refmut: RefMut<'a, TickArray>) -> Self {
-        ProxiedTickArray::Initialized(refmut)
+        ProxiedTickArray::Initialized(refmut, None, None)
+    }
+
+    pub fn new_initialized_with_limit_order_book(
+        refmut: RefMut<'a, TickArray>,
+        limit_order_book: RefMut<'a, LimitOrderBook>,
+        reward_extension: Option<RefMut<'a, TickArrayRewardExtension>>,
+    ) -> Self {
+        ProxiedTickArray::Initialized(refmut, Some(limit_order_book), reward_extension)
+    }
+
+    pub fn new_dynamic(array: DynamicTickArrayMut<'a>) -> Self {
+        ProxiedTickArray::Dynamic(array)
     }
 
     pub fn new_uninitialized(start_tick_index: i32) -> Self {
@@ -38,8 +60,72 @@ impl<'a> ProxiedTickArray<'a> {
         tick_spacing: u16,
         a_to_b: bool,
     ) -> Result<Option<i32>> {
-        self.as_ref()
-            .get_next_init_tick_index(tick_index, tick_spacing, a_to_b)
+        let next_init_tick_index =
+            self.as_ref()
+                .get_next_init_tick_index(tick_index, tick_spacing, a_to_b)?;
+
+        // Ticks with fillable limit orders are also a stop of the swap, even if they have no liquidity
+        let limit_order_book = match self {
+            ProxiedTickArray::Initialized(_, Some(ref limit_order_book), _) => limit_order_book,
+            _ => return Ok(next_init_tick_index),
+        };
+        let offset = self.tick_offset(tick_index, tick_spacing)?;
+        let next_order_tick_index = limit_order_book
+            .get_next_fillable_offset(offset, a_to_b)
+            .map(|offset| offset as i32 * tick_spacing as i32 + self.start_tick_index());
+
+        Ok(match (next_init_tick_index, next_order_tick_index) {
+            (Some(init), Some(order)) if a_to_b => Some(init.max(order)),
+            (Some(init), Some(order)) => Some(init.min(order)),
+            (init, order) => init.or(order),
+        })
+    }
+
+    /// Get the limit orders at the given tick which can be filled by a swap in the given direction.
+    /// Returns None if this TickArray has no LimitOrderBook, the tick is not usable or has no orders to fill.
+    pub fn get_fillable_limit_orders_mut(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Option<&mut LimitOrderLevel> {
+        match self {
+            ProxiedTickArray::Initialized(ref array, Some(ref mut limit_order_book), _) => {
+                if !array.check_in_array_bounds(tick_index, tick_spacing)
+                    || !Tick::check_is_usable_tick(tick_index, tick_spacing)
+                {
+                    return None;
+                }
+                let offset = array.tick_offset(tick_index, tick_spacing).ok()?;
+                let level = limit_order_book.ticks[offset as usize].level_mut(!a_to_b);
+                // A level without remaining orders has nothing to fill
+                (level.amount_remaining > 0).then_some(level)
+            }
+            _ => None,
+        }
+    }
+
+    /// Update the growths outside of the given tick for the extension rewards when it is crossed.
+    ///
+    /// The growths outside of the ticks of TickArrays without a TickArrayRewardExtension
+    /// (including DynamicTickArrays) are not tracked, so there is nothing to update.
+    /// Positions can only accrue the extension rewards if both of their TickArrays carry one.
+    pub fn cross_reward_extension(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        reward_infos: &[WhirlpoolRewardInfo; NUM_EXTENSION_REWARDS],
+    ) -> Result<()> {
+        match self {
+            ProxiedTickArray::Initialized(ref array, _, Some(ref mut reward_extension)) => {
+                let offset = array.tick_offset(tick_index, tick_spacing)? as usize;
+                let reward_growths_outside = reward_extension.reward_growths_outside[offset];
+                reward_extension.reward_growths_outside[offset] =
+                    next_reward_growths_outside_on_cross(reward_infos, &reward_growths_outside);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
     }
 
     pub fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
@@ -71,7 +157,8 @@ impl<'a> ProxiedTickArray<'a> {
 impl<'a> AsRef<dyn TickArrayType + 'a> for ProxiedTickArray<'a> {
     fn as_ref(&self) -> &(dyn TickArrayType + 'a) {
         match self {
-            ProxiedTickArray::Initialized(ref array) => &**array,
+            ProxiedTickArray::Initialized(ref array, _, _) => &**array,
+            ProxiedTickArray::Dynamic(ref array) => array,
             ProxiedTickArray::Uninitialized(ref array) => array,
         }
     }
@@ -80,7 +167,8 @@ impl<'a> AsRef<dyn TickArrayType + 'a> for ProxiedTickArray<'a> {
 impl<'a> AsMut<dyn TickArrayType + 'a> for ProxiedTickArray<'a> {
     fn as_mut(&mut self) -> &mut (dyn TickArrayType + 'a) {
         match self {
-            ProxiedTickArray::Initialized(ref mut array) => &mut **array,
+            ProxiedTickArray::Initialized(ref mut array, _, _) => &mut **array,
+            ProxiedTickArray::Dynamic(ref mut array) => array,
             ProxiedTickArray::Uninitialized(ref mut array) => array,
         }
     }
@@ -126,8 +214,9 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
     /// - `AccountOwnedByWrongProgram` - If the provided initialized TickArray account is not owned by this program
     /// - `AccountDiscriminatorNotFound` - If the provided TickArray account does not have a discriminator
     /// - `AccountDiscriminatorMismatch` - If the provided TickArray account has a mismatched discriminator
+    /// - `AccountDidNotDeserialize` - If the provided DynamicTickArray account data is malformed
     pub fn try_from(
-        whirlpool: &Account<'info, Whirlpool>,
+        whirlpool: &Account<'_, Whirlpool>,
         a_to_b: bool,
         static_tick_array_account_infos: Vec<AccountInfo<'info>>,
         supplemental_tick_array_account_infos: Option<Vec<AccountInfo<'info>>>,
@@ -159,7 +248,7 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
 
                     // TickArray accounts in initialized have been verified as:
                     //   - Owned by this program
-                    //   - Initialized as TickArray account
+                    //   - Initialized as TickArray or DynamicTickArray account
                     //   - Writable account
                     //   - TickArray account for this whirlpool
                     // So we can safely use these accounts.
@@ -227,16 +316,60 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
         for tick_array_account in self.tick_array_accounts.iter() {
             match tick_array_account {
                 TickArrayAccount::Initialized { account_info, .. } => {
+                    use anchor_lang::Discriminator;
                     use std::ops::DerefMut;
 
                     let data = account_info.try_borrow_mut_data()?;
-                    let tick_array_refmut = RefMut::map(data, |data| {
-                        bytemuck::from_bytes_mut(
-                            &mut data.deref_mut()[8..std::mem::size_of::<TickArray>() + 8],
-                        )
-                    });
-                    proxied_tick_arrays
-                        .push_back(ProxiedTickArray::new_initialized(tick_array_refmut));
+                    if data[..8] == DynamicTickArray::discriminator() {
+                        proxied_tick_arrays.push_back(ProxiedTickArray::new_dynamic(
+                            DynamicTickArrayMut::load(data)?,
+                        ));
+                    } else if LimitOrderBook::is_allocated(data.len()) {
+                        let has_reward_extension =
+                            TickArrayRewardExtension::is_allocated(data.len());
+                        let (tick_array_refmut, tail_refmut) = RefMut::map_split(data, |data| {
+                            let (tick_array_data, tail_data) =
+                                data.deref_mut().split_at_mut(LimitOrderBook::OFFSET);
+                            (
+                                bytemuck::from_bytes_mut::<TickArray>(
+                                    &mut tick_array_data[8..std::mem::size_of::<TickArray>() + 8],
+                                ),
+                                tail_data,
+                            )
+                        });
+                        // The TickArrayRewardExtension is appended after the LimitOrderBook
+                        let (limit_order_book_refmut, reward_extension_refmut) =
+                            RefMut::map_split(tail_refmut, |tail_data| {
+                                let (limit_order_book_data, reward_extension_data) =
+                                    tail_data.split_at_mut(LimitOrderBook::LEN);
+                                (
+                                    bytemuck::from_bytes_mut::<LimitOrderBook>(
+                                        limit_order_book_data,
+                                    ),
+                                    reward_extension_data,
+                                )
+                            });
+                        let reward_extension_refmut = has_reward_extension.then(|| {
+                            RefMut::map(reward_extension_refmut, |data| {
+                                bytemuck::from_bytes_mut(&mut data[..TickArrayRewardExtension::LEN])
+                            })
+                        });
+                        proxied_tick_arrays.push_back(
+                            ProxiedTickArray::new_initialized_with_limit_order_book(
+                                tick_array_refmut,
+                                limit_order_book_refmut,
+                                reward_extension_refmut,
+                            ),
+                        );
+                    } else {
+                        let tick_array_refmut = RefMut::map(data, |data| {
+                            bytemuck::from_bytes_mut(
+                                &mut data.deref_mut()[8..std::mem::size_of::<TickArray>() + 8],
+                            )
+                        });
+                        proxied_tick_arrays
+                            .push_back(ProxiedTickArray::new_initialized(tick_array_refmut));
+                    }
                 }
                 TickArrayAccount::Uninitialized {
                     start_tick_index, ..
@@ -293,17 +426,22 @@ fn peek_tick_array(account_info: AccountInfo<'_>) -> Result<TickArrayAccount<'_>
     }
 
     let disc_bytes = arrayref::array_ref![data, 0, 8];
-    if disc_bytes != &TickArray::discriminator() {
+    let (start_tick_index, whirlpool) = if disc_bytes == &TickArray::discriminator() {
+        let tick_array: Ref<TickArray> = Ref::map(data, |data| {
+            bytemuck::from_bytes(&data[8..std::mem::size_of::<TickArray>() + 8])
+        });
+        (tick_array.start_tick_index, tick_array.whirlpool)
+    } else if disc_bytes == &DynamicTickArray::discriminator() {
+        if data.len() < DynamicTickArray::MIN_LEN {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+        let tick_array: Ref<DynamicTickArray> = Ref::map(data, |data| {
+            bytemuck::from_bytes(&data[8..DynamicTickArray::MIN_LEN])
+        });
+        (tick_array.start_tick_index, tick_array.whirlpool)
+    } else {
         return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
-    }
-
-    let tick_array: Ref<TickArray> = Ref::map(data, |data| {
-        bytemuck::from_bytes(&data[8..std::mem::size_of::<TickArray>() + 8])
-    });
-
-    let start_tick_index = tick_array.start_tick_index;
-    let whirlpool = tick_array.whirlpool;
-    drop(tick_array);
+    };
 
     Ok(TickArrayAccount::Initialized {
         tick_array_whirlpool: whirlpool,
@@ -370,6 +508,7 @@ fn derive_tick_array_pda(whirlpool: &Account<Whirlpool>, start_tick_index: i32)
 #[cfg(test)]
 mod sparse_swap_tick_sequence_tests {
     use super::*;
+    use crate::state::DynamicTick;
     use anchor_lang::solana_program::pubkey;
     use anchor_lang::Discriminator;
     use std::cell::RefCell;
@@ -425,6 +564,27 @@ mod sparse_swap_tick_sequence_tests {
             Self::new(key, data, owner.unwrap_or(TickArray::owner()))
         }
 
+        pub fn new_dynamic_tick_array(
+            key: Pubkey,
+            whirlpool: Pubkey,
+            start_tick_index: i32,
+            initialized_offsets: &[usize],
+        ) -> Self {
+            let mut data = vec![0u8; DynamicTickArray::MIN_LEN];
+            data[0..8].copy_from_slice(&DynamicTickArray::discriminator());
+            data[8..12].copy_from_slice(&start_tick_index.to_le_bytes());
+            data[12..44].copy_from_slice(&whirlpool.to_bytes());
+            for &offset in initialized_offsets {
+                data.resize(data.len() + DynamicTick::LEN, 0);
+                DynamicTickArray::insert_tick(&mut data, offset, Pubkey::default(), 0);
+            }
+            // mark the stored ticks as initialized
+            for i in 0..initialized_offsets.len() {
+                data[DynamicTickArray::MIN_LEN + i * DynamicTick::LEN] = 1;
+            }
+            Self::new(key, data, DynamicTickArray::owner())
+        }
+
         pub fn to_account_info(&mut self, is_writable: bool) -> AccountInfo<'_> {
             AccountInfo {
                 key: &self.key,
@@ -773,6 +933,34 @@ mod sparse_swap_tick_sequence_tests {
                 _ => panic!("unexpected state"),
             }
         }
+
+        #[test]
+        fn initialized_dynamic_tick_array() {
+            let tick_array_address = Pubkey::new_unique();
+            let whirlpool_address = Pubkey::new_unique();
+            let mut account_info_mock = AccountInfoMock::new_dynamic_tick_array(
+                tick_array_address,
+                whirlpool_address,
+                -5632,
+                &[3],
+            );
+            let account_info = account_info_mock.to_account_info(true);
+
+            let result = peek_tick_array(account_info);
+            assert!(result.is_ok());
+            match result.unwrap() {
+                TickArrayAccount::Initialized {
+                    start_tick_index,
+                    tick_array_whirlpool,
+                    account_info,
+                } => {
+                    assert_eq!(start_tick_index, -5632);
+                    assert_eq!(tick_array_whirlpool, whirlpool_address);
+                    assert_eq!(account_info.key(), tick_array_address);
+                }
+                _ => panic!("unexpected state"),
+            }
+        }
     }
 
     mod test_sparse_swap_tick_sequence_builder {
@@ -841,6 +1029,87 @@ mod sparse_swap_tick_sequence_tests {
             }
         }
 
+        #[test]
+        fn dynamic_tick_array() {
+            let whirlpool_address = Pubkey::new_unique();
+            let mut account_info_mock =
+                AccountInfoMock::new_whirlpool(whirlpool_address, 64, 5650, None);
+            let account_info = account_info_mock.to_account_info(false);
+            let whirlpool = Account::<Whirlpool>::try_from(&account_info).unwrap();
+
+            // dynamic, ticks at offset 10 and 40 are initialized
+            let ta0_address = derive_tick_array_pda(&whirlpool, 5632);
+            let mut ta0_mock = AccountInfoMock::new_dynamic_tick_array(
+                ta0_address,
+                whirlpool_address,
+                5632,
+                &[10, 40],
+            );
+            let ta0 = ta0_mock.to_account_info(true);
+
+            // initialized
+            let ta1_address = derive_tick_array_pda(&whirlpool, 11264);
+            let mut ta1_mock =
+                AccountInfoMock::new_tick_array(ta1_address, whirlpool_address, 11264, None);
+            let ta1 = ta1_mock.to_account_info(true);
+
+            let builder =
+                SparseSwapTickSequenceBuilder::try_from(&whirlpool, false, vec![ta0, ta1], None)
+                    .unwrap();
+            assert_eq!(builder.tick_array_accounts.len(), 2);
+
+            let mut swap_tick_sequence = builder.build().unwrap();
+            assert_eq!(
+                swap_tick_sequence
+                    .get_next_initialized_tick_index(5650, 64, false, 0)
+                    .unwrap(),
+                (0, 5632 + 10 * 64)
+            );
+            assert_eq!(
+                swap_tick_sequence
+                    .get_next_initialized_tick_index(5632 + 10 * 64, 64, false, 0)
+                    .unwrap(),
+                (0, 5632 + 40 * 64)
+            );
+            assert!(
+                !swap_tick_sequence
+                    .get_tick(0, 5632, 64)
+                    .unwrap()
+                    .initialized
+            );
+
+            // initialized ticks can be updated in place
+            swap_tick_sequence
+                .update_tick(
+                    0,
+                    5632 + 40 * 64,
+                    64,
+                    &TickUpdate {
+                        initialized: true,
+                        liquidity_gross: 100,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            let liquidity_gross = swap_tick_sequence
+                .get_tick(0, 5632 + 40 * 64, 64)
+                .unwrap()
+                .liquidity_gross;
+            assert_eq!(liquidity_gross, 100);
+
+            // DynamicTickArray and TickArray without a reward extension don't track the extension rewards
+            let reward_infos = [WhirlpoolRewardInfo {
+                growth_global_x64: 500,
+                ..Default::default()
+            }; NUM_EXTENSION_REWARDS];
+            swap_tick_sequence
+                .cross_reward_extension(0, 5632 + 40 * 64, 64, &reward_infos)
+                .unwrap();
+            swap_tick_sequence
+                .cross_reward_extension(1, 11264, 64, &reward_infos)
+                .unwrap();
+        }
+
         #[test]
         fn dedup_tick_array_account_infos() {
             let whirlpool_address = Pubkey::new_unique();
@@ -102,6 +102,58 @@ impl<'info> SwapTickSequence<'info> {
         }
     }
 
+    /// Get the limit orders at the given tick which can be filled by a swap in the given direction
+    ///
+    /// # Parameters
+    /// - `array_index` - the array index that the tick of this given tick-index would be stored in
+    /// - `tick_index` - the tick index of the limit orders
+    /// - `tick_spacing` - A u8 integer of the tick spacing for this whirlpool
+    /// - `a_to_b` - the direction of the swap
+    ///
+    /// # Returns
+    /// - `Some(&mut LimitOrderLevel)`: the limit orders in the opposite direction of the swap
+    /// - `None`: the TickArray has no LimitOrderBook, the tick is not usable or has no orders to fill
+    /// - `TickArrayIndexOutofBounds` - The provided array-index is out of bounds
+    pub fn get_fillable_limit_orders_mut(
+        &mut self,
+        array_index: usize,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<&mut LimitOrderLevel>> {
+        let array = self.arrays.get_mut(array_index);
+        match array {
+            Some(array) => {
+                Ok(array.get_fillable_limit_orders_mut(tick_index, tick_spacing, a_to_b))
+            }
+            _ => Err(ErrorCode::TickArrayIndexOutofBounds.into()),
+        }
+    }
+
+    /// Updates the growths outside of the given tick for the extension rewards when it is crossed
+    ///
+    /// # Parameters
+    /// - `array_index` - the array index that the tick of this given tick-index would be stored in
+    /// - `tick_index` - the tick index of the crossed tick
+    /// - `tick_spacing` - A u8 integer of the tick spacing for this whirlpool
+    /// - `reward_infos` - the extension rewards of the whirlpool at the time of the crossing
+    ///
+    /// # Errors
+    /// - `TickArrayIndexOutofBounds` - The provided array-index is out of bounds
+    pub fn cross_reward_extension(
+        &mut self,
+        array_index: usize,
+        tick_index: i32,
+        tick_spacing: u16,
+        reward_infos: &[WhirlpoolRewardInfo; NUM_EXTENSION_REWARDS],
+    ) -> Result<()> {
+        let array = self.arrays.get_mut(array_index);
+        match array {
+            Some(array) => array.cross_reward_extension(tick_index, tick_spacing, reward_infos),
+            _ => Err(ErrorCode::TickArrayIndexOutofBounds.into()),
+        }
+    }
+
     /// Get the next initialized tick in the provided tick range
     ///
     /// # Parameters
@@ -1,23 +1,38 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 
-use crate::{manager::swap_manager::PostSwapUpdate, state::Whirlpool};
+use crate::{
+    manager::swap_manager::PostSwapUpdate,
+    state::{OracleAccessor, Whirlpool},
+};
 
-use super::{transfer_from_owner_to_vault, transfer_from_vault_to_owner};
+use super::{
+    store_whirlpool_reward_extension, store_whirlpool_reward_schedule,
+    transfer_from_owner_to_vault, transfer_from_vault_to_owner,
+};
 
 #[allow(clippy::too_many_arguments)]
 pub fn update_and_swap_whirlpool<'info>(
     whirlpool: &mut Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
     token_authority: &Signer<'info>,
     token_owner_account_a: &Account<'info, TokenAccount>,
     token_owner_account_b: &Account<'info, TokenAccount>,
     token_vault_a: &Account<'info, TokenAccount>,
     token_vault_b: &Account<'info, TokenAccount>,
     token_program: &Program<'info, Token>,
+    oracle_accessor: &OracleAccessor<'info>,
     swap_update: PostSwapUpdate,
     is_token_fee_in_a: bool,
     reward_last_updated_timestamp: u64,
 ) -> Result<()> {
+    // Record the state of the Whirlpool before the swap
+    oracle_accessor.update_on_swap(
+        whirlpool,
+        &swap_update.next_adaptive_fee_info,
+        reward_last_updated_timestamp,
+    )?;
+
     whirlpool.update_after_swap(
         swap_update.next_liquidity,
         swap_update.next_tick_index,
@@ -29,6 +44,14 @@ pub fn update_and_swap_whirlpool<'info>(
         reward_last_updated_timestamp,
     );
 
+    if let Some(next_reward_schedule) = &swap_update.next_reward_schedule {
+        store_whirlpool_reward_schedule(whirlpool_rewards, next_reward_schedule)?;
+    }
+
+    if let Some(next_reward_extension) = &swap_update.next_reward_extension {
+        store_whirlpool_reward_extension(whirlpool_rewards, next_reward_extension)?;
+    }
+
     perform_swap(
         whirlpool,
         token_authority,
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use solana_program::program_option::COption;
+use solana_program::program_pack::Pack;
+
+/// Builds an AccountInfo whose key, lamports and data are leaked to live for the whole test.
+pub fn leaked_account_info(
+    key: Pubkey,
+    owner: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+    data: Vec<u8>,
+) -> AccountInfo<'static> {
+    AccountInfo::new(
+        Box::leak(Box::new(key)),
+        is_signer,
+        is_writable,
+        Box::leak(Box::new(1_000_000_000u64)),
+        Box::leak(data.into_boxed_slice()),
+        Box::leak(Box::new(owner)),
+        false,
+        0,
+    )
+}
+
+/// Builds the AccountInfo of an executable program.
+pub fn leaked_program_account_info(program_id: Pubkey) -> AccountInfo<'static> {
+    AccountInfo::new(
+        Box::leak(Box::new(program_id)),
+        false,
+        false,
+        Box::leak(Box::new(1u64)),
+        Box::leak(Vec::new().into_boxed_slice()),
+        Box::leak(Box::new(solana_program::bpf_loader_upgradeable::ID)),
+        true,
+        0,
+    )
+}
+
+/// Serializes an Anchor account with its discriminator.
+pub fn anchor_account_data<T: AccountSerialize>(account: &T, len: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(len);
+    account.try_serialize(&mut data).unwrap();
+    data.resize(len, 0);
+    data
+}
+
+/// Packs a token account. The layout is the same for the Token and Token-2022 programs.
+pub fn token_account_data(
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+    delegate: Option<(Pubkey, u64)>,
+) -> Vec<u8> {
+    let (delegate, delegated_amount) = match delegate {
+        Some((delegate, delegated_amount)) => (COption::Some(delegate), delegated_amount),
+        None => (COption::None, 0),
+    };
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        delegate,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+/// Packs a mint without extensions. The layout is the same for the Token and Token-2022 programs.
+pub fn mint_data(supply: u64) -> Vec<u8> {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::None,
+        supply,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+/// Packs a Token-2022 mint with the given TLV entries (extension type, value).
+/// The values are written as is, so extension types unknown to spl-token-2022 can be used.
+pub fn token_2022_mint_data(supply: u64, extensions: &[(u16, Vec<u8>)]) -> Vec<u8> {
+    let mut data = mint_data(supply);
+    if extensions.is_empty() {
+        return data;
+    }
+    // the account type follows the base account, padded to the length of a token account
+    data.resize(spl_token::state::Account::LEN, 0);
+    data.push(1); // AccountType::Mint
+    for (extension_type, value) in extensions {
+        data.extend_from_slice(&extension_type.to_le_bytes());
+        data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        data.extend_from_slice(value);
+    }
+    data
+}
@@ -110,8 +110,8 @@ impl LiquidityTestFixture {
 
     pub fn increment_whirlpool_reward_growths_by_time(&mut self, seconds: u64) {
         let next_timestamp = self.whirlpool.reward_last_updated_timestamp + seconds;
-        self.whirlpool.reward_infos =
-            next_whirlpool_reward_infos(&self.whirlpool, next_timestamp).unwrap();
+        (self.whirlpool.reward_infos, _) =
+            next_whirlpool_reward_infos(&self.whirlpool, &None, next_timestamp).unwrap();
         self.whirlpool.reward_last_updated_timestamp = next_timestamp;
     }
 
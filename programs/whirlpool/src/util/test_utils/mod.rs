@@ -1,5 +1,9 @@
+pub mod account_info_test_utils;
 pub mod liquidity_test_fixture;
+pub mod modify_liquidity_v2_test_fixture;
 pub mod swap_test_fixture;
 
+pub use account_info_test_utils::*;
 pub use liquidity_test_fixture::*;
+pub use modify_liquidity_v2_test_fixture::*;
 pub use swap_test_fixture::*;
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo;
+use anchor_spl::token::spl_token;
+
+use crate::instructions::v2::ModifyLiquidityV2;
+use crate::state::{Position, Whirlpool};
+
+use super::{
+    anchor_account_data, leaked_account_info, leaked_program_account_info, mint_data,
+    token_account_data,
+};
+
+pub const POSITION_TICK_LOWER_INDEX: i32 = -128;
+pub const POSITION_TICK_UPPER_INDEX: i32 = 128;
+
+fn leak(account_info: AccountInfo<'static>) -> &'static AccountInfo<'static> {
+    Box::leak(Box::new(account_info))
+}
+
+/// Builds the accounts of increase_liquidity_v2 and decrease_liquidity_v2 for a position of a
+/// Whirlpool at the given price, so that their handlers can be called off-chain.
+/// The handlers fail at the Clock sysvar, which is not available off-chain, once their arguments are checked.
+pub fn modify_liquidity_v2_accounts(
+    sqrt_price: u128,
+    tick_current_index: i32,
+) -> ModifyLiquidityV2<'static> {
+    let token_program = leak(leaked_program_account_info(spl_token::ID));
+    let token_mint_a = leak(leaked_account_info(
+        Pubkey::new_unique(),
+        spl_token::ID,
+        false,
+        false,
+        mint_data(1_000_000),
+    ));
+    let token_mint_b = leak(leaked_account_info(
+        Pubkey::new_unique(),
+        spl_token::ID,
+        false,
+        false,
+        mint_data(1_000_000),
+    ));
+    let (token_vault_a, token_vault_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    let whirlpool = leak(leaked_account_info(
+        Pubkey::new_unique(),
+        crate::ID,
+        false,
+        true,
+        anchor_account_data(
+            &Whirlpool {
+                tick_spacing: 64,
+                sqrt_price,
+                tick_current_index,
+                token_mint_a: *token_mint_a.key,
+                token_vault_a,
+                token_mint_b: *token_mint_b.key,
+                token_vault_b,
+                ..Default::default()
+            },
+            Whirlpool::LEN,
+        ),
+    ));
+    let position_mint = Pubkey::new_unique();
+    let position = leak(leaked_account_info(
+        Pubkey::new_unique(),
+        crate::ID,
+        false,
+        true,
+        anchor_account_data(
+            &Position {
+                whirlpool: *whirlpool.key,
+                position_mint,
+                liquidity: 1_000_000,
+                tick_lower_index: POSITION_TICK_LOWER_INDEX,
+                tick_upper_index: POSITION_TICK_UPPER_INDEX,
+                ..Default::default()
+            },
+            Position::LEN,
+        ),
+    ));
+    let position_authority = leak(leaked_account_info(
+        Pubkey::new_unique(),
+        System::id(),
+        true,
+        true,
+        vec![],
+    ));
+    let token_account = |key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64| {
+        Box::new(
+            InterfaceAccount::try_from(leak(leaked_account_info(
+                key,
+                spl_token::ID,
+                false,
+                true,
+                token_account_data(mint, owner, amount, None),
+            )))
+            .unwrap(),
+        )
+    };
+    let tick_array = || {
+        UncheckedAccount::try_from(leak(leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            true,
+            vec![],
+        )))
+    };
+
+    ModifyLiquidityV2 {
+        whirlpool: Account::try_from(whirlpool).unwrap(),
+        token_program_a: Interface::try_from(token_program).unwrap(),
+        token_program_b: Interface::try_from(token_program).unwrap(),
+        memo_program: Program::try_from(leak(leaked_program_account_info(memo::ID))).unwrap(),
+        position_authority: Signer::try_from(position_authority).unwrap(),
+        position: Account::try_from(position).unwrap(),
+        position_token_account: token_account(
+            Pubkey::new_unique(),
+            position_mint,
+            *position_authority.key,
+            1,
+        ),
+        token_mint_a: InterfaceAccount::try_from(token_mint_a).unwrap(),
+        token_mint_b: InterfaceAccount::try_from(token_mint_b).unwrap(),
+        token_owner_account_a: token_account(
+            Pubkey::new_unique(),
+            *token_mint_a.key,
+            *position_authority.key,
+            1_000_000,
+        ),
+        token_owner_account_b: token_account(
+            Pubkey::new_unique(),
+            *token_mint_b.key,
+            *position_authority.key,
+            1_000_000,
+        ),
+        token_vault_a: token_account(token_vault_a, *token_mint_a.key, *whirlpool.key, 0),
+        token_vault_b: token_account(token_vault_b, *token_mint_b.key, *whirlpool.key, 0),
+        tick_array_lower: tick_array(),
+        tick_array_upper: tick_array(),
+        oracle: tick_array(),
+        whirlpools_config_extension: UncheckedAccount::try_from(leak(leaked_account_info(
+            Pubkey::find_program_address(
+                &[b"config_extension", Pubkey::default().as_ref()],
+                &crate::ID,
+            )
+            .0,
+            System::id(),
+            false,
+            false,
+            vec![],
+        ))),
+        whirlpool_pause: UncheckedAccount::try_from(leak(leaked_account_info(
+            Pubkey::find_program_address(&[b"whirlpool_pause", whirlpool.key.as_ref()], &crate::ID)
+                .0,
+            System::id(),
+            false,
+            false,
+            vec![],
+        ))),
+        whirlpool_rewards: UncheckedAccount::try_from(leak(leaked_account_info(
+            Pubkey::find_program_address(
+                &[b"whirlpool_rewards", whirlpool.key.as_ref()],
+                &crate::ID,
+            )
+            .0,
+            System::id(),
+            false,
+            true,
+            vec![],
+        ))),
+        event_authority: leaked_account_info(
+            Pubkey::find_program_address(&[b"__event_authority"], &crate::ID).0,
+            System::id(),
+            false,
+            false,
+            vec![],
+        ),
+        program: leaked_program_account_info(crate::ID),
+    }
+}
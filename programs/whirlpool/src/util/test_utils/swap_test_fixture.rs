@@ -3,7 +3,7 @@ use crate::math::tick_math::*;
 use crate::state::{
     tick::*, tick_builder::TickBuilder, whirlpool_builder::WhirlpoolBuilder, TickArray, Whirlpool,
 };
-use crate::state::{WhirlpoolRewardInfo, NUM_REWARDS};
+use crate::state::{AdaptiveFeeInfo, WhirlpoolRewardInfo, NUM_REWARDS};
 use crate::util::SwapTickSequence;
 use anchor_lang::prelude::*;
 use std::cell::RefCell;
@@ -21,6 +21,7 @@ pub struct SwapTestFixture {
     pub amount_specified_is_input: bool,
     pub a_to_b: bool,
     pub reward_last_updated_timestamp: u64,
+    pub adaptive_fee_info: Option<AdaptiveFeeInfo>,
 }
 
 #[derive(Default)]
@@ -50,6 +51,7 @@ pub struct SwapTestFixtureInfo<'info> {
     pub array_3_ticks: Option<&'info Vec<TestTickInfo>>,
     pub fee_rate: u16,
     pub protocol_fee_rate: u16,
+    pub adaptive_fee_info: Option<AdaptiveFeeInfo>,
 }
 
 impl<'info> Default for SwapTestFixtureInfo<'info> {
@@ -76,6 +78,7 @@ impl<'info> Default for SwapTestFixtureInfo<'info> {
             array_3_ticks: None,
             fee_rate: 0,
             protocol_fee_rate: 0,
+            adaptive_fee_info: None,
         }
     }
 }
@@ -200,6 +203,7 @@ impl SwapTestFixture {
             amount_specified_is_input: info.amount_specified_is_input,
             a_to_b: info.a_to_b,
             reward_last_updated_timestamp: info.reward_last_updated_timestamp,
+            adaptive_fee_info: info.adaptive_fee_info,
         }
     }
 
@@ -212,6 +216,9 @@ impl SwapTestFixture {
             self.amount_specified_is_input,
             self.a_to_b,
             next_timestamp,
+            &self.adaptive_fee_info,
+            &None,
+            &None,
         )
         .unwrap()
     }
@@ -229,6 +236,9 @@ impl SwapTestFixture {
             self.amount_specified_is_input,
             self.a_to_b,
             next_timestamp,
+            &self.adaptive_fee_info,
+            &None,
+            &None,
         )
     }
 }
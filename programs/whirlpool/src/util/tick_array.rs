@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, transfer, CreateAccount, Transfer};
+use anchor_lang::Discriminator;
+use std::cell::RefMut;
+use std::ops::DerefMut;
+
+use crate::{
+    errors::ErrorCode,
+    state::{
+        DynamicTick, DynamicTickArray, DynamicTickArrayMut, LimitOrderBook, Tick, TickArray,
+        TickArrayRentPayers, TickArrayRewardExtension, TickArrayType, TickUpdate,
+    },
+};
+
+/// A TickArray or DynamicTickArray account loaded for modification.
+pub enum LoadedTickArrayMut<'a> {
+    Fixed(RefMut<'a, TickArray>),
+    Dynamic(DynamicTickArrayMut<'a>),
+}
+
+impl LoadedTickArrayMut<'_> {
+    pub fn whirlpool(&self) -> Pubkey {
+        match self {
+            LoadedTickArrayMut::Fixed(ref array) => array.whirlpool,
+            LoadedTickArrayMut::Dynamic(ref array) => array.whirlpool(),
+        }
+    }
+}
+
+impl<'a> AsRef<dyn TickArrayType + 'a> for LoadedTickArrayMut<'a> {
+    fn as_ref(&self) -> &(dyn TickArrayType + 'a) {
+        match self {
+            LoadedTickArrayMut::Fixed(ref array) => &**array,
+            LoadedTickArrayMut::Dynamic(ref array) => array,
+        }
+    }
+}
+
+impl<'a> AsMut<dyn TickArrayType + 'a> for LoadedTickArrayMut<'a> {
+    fn as_mut(&mut self) -> &mut (dyn TickArrayType + 'a) {
+        match self {
+            LoadedTickArrayMut::Fixed(ref mut array) => &mut **array,
+            LoadedTickArrayMut::Dynamic(ref mut array) => array,
+        }
+    }
+}
+
+/// Load a TickArray or DynamicTickArray account of the given whirlpool.
+/// This is the equivalent of AccountLoader::load_mut with a has_one = whirlpool constraint for both account types.
+///
+/// # Errors
+/// - `AccountOwnedByWrongProgram` - If the account is not owned by this program
+/// - `AccountDiscriminatorNotFound` - If the account does not have a discriminator
+/// - `AccountDiscriminatorMismatch` - If the account is neither a TickArray nor a DynamicTickArray
+/// - `AccountDidNotDeserialize` - If the DynamicTickArray data length does not match its initialized ticks
+/// - `DifferentWhirlpoolTickArrayAccount` - If the tick array is not for the whirlpool
+pub fn load_tick_array_mut<'a>(
+    account_info: &'a AccountInfo<'_>,
+    whirlpool: &Pubkey,
+) -> Result<LoadedTickArrayMut<'a>> {
+    if account_info.owner != &TickArray::owner() {
+        return Err(
+            Error::from(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram)
+                .with_pubkeys((*account_info.owner, TickArray::owner())),
+        );
+    }
+
+    let data = account_info.try_borrow_mut_data()?;
+    if data.len() < TickArray::discriminator().len() {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+
+    let disc_bytes = *arrayref::array_ref![data, 0, 8];
+    let tick_array = if disc_bytes == TickArray::discriminator() {
+        LoadedTickArrayMut::Fixed(RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut(&mut data.deref_mut()[8..std::mem::size_of::<TickArray>() + 8])
+        }))
+    } else if disc_bytes == DynamicTickArray::discriminator() {
+        LoadedTickArrayMut::Dynamic(DynamicTickArrayMut::load(data)?)
+    } else {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    };
+
+    // has_one constraint equivalent check
+    if tick_array.whirlpool() != *whirlpool {
+        return Err(ErrorCode::DifferentWhirlpoolTickArrayAccount.into());
+    }
+
+    Ok(tick_array)
+}
+
+/// Get a copy of the Tick at the given tick index from a TickArray or DynamicTickArray account.
+pub fn get_tick_from_tick_array(
+    account_info: &AccountInfo<'_>,
+    whirlpool: &Pubkey,
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<Tick> {
+    let tick_array = load_tick_array_mut(account_info, whirlpool)?;
+    let tick = *tick_array.as_ref().get_tick(tick_index, tick_spacing)?;
+    Ok(tick)
+}
+
+/// Pays the rent of the ticks initialized in a DynamicTickArray, and refunds the rent of the ticks
+/// it uninitializes to their recorded payers.
+///
+/// The funder is the position authority. It must be writable and the System program must be passed
+/// in the remaining accounts when a DynamicTickArray grows. It is recorded as the payer of the tick
+/// with the rent it paid. When the tick is uninitialized, the recorded rent is refunded to the
+/// recorded payer, which must be writable and be the position authority or in the remaining accounts.
+pub struct TickArrayRentFunder<'a, 'info> {
+    pub funder: &'a AccountInfo<'info>,
+    pub system_program: Option<&'a AccountInfo<'info>>,
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> TickArrayRentFunder<'a, 'info> {
+    pub fn new(
+        funder: &'a AccountInfo<'info>,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Self {
+        TickArrayRentFunder {
+            funder,
+            system_program: remaining_accounts
+                .iter()
+                .find(|account_info| account_info.key() == System::id()),
+            remaining_accounts,
+        }
+    }
+
+    fn fund(&self, account_info: &AccountInfo<'info>, lamports: u64) -> Result<()> {
+        let system_program = match self.system_program {
+            Some(system_program) if self.funder.is_writable => system_program,
+            _ => return Err(ErrorCode::TickArrayRentNotFunded.into()),
+        };
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: self.funder.clone(),
+                    to: account_info.clone(),
+                },
+            ),
+            lamports,
+        )
+    }
+
+    fn refund(
+        &self,
+        account_info: &AccountInfo<'info>,
+        rent_payer: &Pubkey,
+        lamports: u64,
+    ) -> Result<()> {
+        let rent_payer = std::iter::once(self.funder)
+            .chain(self.remaining_accounts.iter())
+            .find(|candidate| candidate.key == rent_payer && candidate.is_writable)
+            .ok_or(ErrorCode::TickArrayRentPayerMissing)?;
+        **account_info.try_borrow_mut_lamports()? -= lamports;
+        **rent_payer.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    }
+}
+
+/// Apply the update to the Tick at the given tick index of a TickArray or DynamicTickArray account.
+///
+/// A DynamicTickArray account grows by DynamicTick::LEN when the update initializes the Tick,
+/// and shrinks by DynamicTick::LEN when the update uninitializes it.
+/// The rent of the additional space is paid by the rent funder, and refunded to the recorded payer
+/// of the tick on shrink.
+///
+/// # Errors
+/// - `TickArrayRentNotFunded` - If a DynamicTickArray grows without enough lamports to stay rent exempt,
+///   and the funder is not writable or the System program is missing
+/// - `TickArrayRentPayerMissing` - If a DynamicTickArray shrinks and the recorded payer of the tick
+///   is not passed as a writable account
+pub fn update_tick_in_tick_array<'info>(
+    account_info: &AccountInfo<'info>,
+    whirlpool: &Pubkey,
+    tick_index: i32,
+    tick_spacing: u16,
+    update: &TickUpdate,
+    rent_funder: &TickArrayRentFunder<'_, 'info>,
+) -> Result<()> {
+    let resized_offset = {
+        let mut tick_array = load_tick_array_mut(account_info, whirlpool)?;
+        let initialized = tick_array
+            .as_ref()
+            .get_tick(tick_index, tick_spacing)?
+            .initialized;
+
+        match tick_array {
+            LoadedTickArrayMut::Dynamic(ref array) if initialized != update.initialized => {
+                array.tick_offset(tick_index, tick_spacing)? as usize
+            }
+            _ => {
+                return tick_array
+                    .as_mut()
+                    .update_tick(tick_index, tick_spacing, update);
+            }
+        }
+    };
+
+    let rent = Rent::get()?;
+    if update.initialized {
+        let space = account_info.data_len() + DynamicTick::LEN;
+        let required_lamports = rent
+            .minimum_balance(space)
+            .saturating_sub(account_info.lamports());
+        if required_lamports > 0 {
+            rent_funder.fund(account_info, required_lamports)?;
+        }
+
+        account_info.realloc(space, true)?;
+        DynamicTickArray::insert_tick(
+            &mut account_info.try_borrow_mut_data()?,
+            resized_offset,
+            rent_funder.funder.key(),
+            required_lamports,
+        );
+        load_tick_array_mut(account_info, whirlpool)?
+            .as_mut()
+            .update_tick(tick_index, tick_spacing, update)
+    } else {
+        let removed =
+            DynamicTickArray::remove_tick(&mut account_info.try_borrow_mut_data()?, resized_offset);
+        let space = account_info.data_len() - DynamicTick::LEN;
+        account_info.realloc(space, false)?;
+
+        // only the rent paid for the tick is refunded, the rest is refunded when the array is closed
+        let refund_lamports = account_info
+            .lamports()
+            .saturating_sub(rent.minimum_balance(space))
+            .min(removed.rent);
+        if refund_lamports > 0 {
+            rent_funder.refund(account_info, &removed.rent_payer, refund_lamports)?;
+        }
+        Ok(())
+    }
+}
+
+/// A section appended to the TickArray account data after the ticks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TickArraySection {
+    LimitOrderBook,
+    RewardExtension,
+}
+
+impl TickArraySection {
+    fn end(&self) -> usize {
+        match self {
+            TickArraySection::LimitOrderBook => LimitOrderBook::OFFSET + LimitOrderBook::LEN,
+            TickArraySection::RewardExtension => {
+                TickArrayRewardExtension::OFFSET + TickArrayRewardExtension::LEN
+            }
+        }
+    }
+}
+
+/// Appends a zeroed section to the TickArray account data if it is not allocated yet.
+/// The funder pays the rent for the additional space and is recorded as the payer of the section
+/// in the TickArrayRentPayers of the TickArray.
+///
+/// The TickArrayRentPayers of TickArrays initialized before the record was introduced is created
+/// here without a TickArray payer, and its rent is recorded with the rent of the section.
+pub fn allocate_tick_array_section<'info>(
+    tick_array: &AccountInfo<'info>,
+    section: TickArraySection,
+    rent_payers: &AccountInfo<'info>,
+    rent_payers_bump: u8,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if tick_array.data_len() >= section.end() {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?;
+    let (mut record, record_rent) = if *rent_payers.owner == crate::ID {
+        let record =
+            TickArrayRentPayers::try_deserialize(&mut rent_payers.try_borrow_data()?.as_ref())?;
+        (record, 0)
+    } else {
+        let record_rent = rent.minimum_balance(TickArrayRentPayers::LEN);
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: funder.to_account_info(),
+                    to: rent_payers.clone(),
+                },
+                &[&[
+                    b"tick_array_rent_payers",
+                    tick_array.key.as_ref(),
+                    &[rent_payers_bump],
+                ]],
+            ),
+            record_rent,
+            TickArrayRentPayers::LEN as u64,
+            &crate::ID,
+        )?;
+        let mut record = TickArrayRentPayers::default();
+        record.initialize(tick_array.key(), Pubkey::default());
+        (record, record_rent)
+    };
+
+    let space = section.end();
+    let required_lamports = rent
+        .minimum_balance(space)
+        .saturating_sub(tick_array.lamports());
+    if required_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: funder.to_account_info(),
+                    to: tick_array.clone(),
+                },
+            ),
+            required_lamports,
+        )?;
+    }
+
+    tick_array.realloc(space, true)?;
+
+    let section_rent = required_lamports + record_rent;
+    match section {
+        TickArraySection::LimitOrderBook => {
+            record.limit_order_book_payer = funder.key();
+            record.limit_order_book_rent = section_rent;
+        }
+        TickArraySection::RewardExtension => {
+            record.reward_extension_payer = funder.key();
+            record.reward_extension_rent = section_rent;
+        }
+    }
+
+    let mut data = rent_payers.try_borrow_mut_data()?;
+    let mut dst: &mut [u8] = &mut data;
+    record.try_serialize(&mut dst)
+}
+
+#[cfg(test)]
+mod tick_array_rent_funder_tests {
+    use super::*;
+    use crate::util::test_utils::leaked_account_info;
+
+    fn account(is_writable: bool) -> AccountInfo<'static> {
+        leaked_account_info(
+            Pubkey::new_unique(),
+            System::id(),
+            false,
+            is_writable,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_refund_to_recorded_payer() {
+        let tick_array = account(true);
+        let position_authority = account(true);
+        let remaining_accounts = vec![account(true)];
+        let rent_payer = &remaining_accounts[0];
+        let rent_funder = TickArrayRentFunder::new(&position_authority, &remaining_accounts);
+
+        rent_funder
+            .refund(&tick_array, rent_payer.key, 1_000)
+            .unwrap();
+        assert_eq!(rent_payer.lamports(), 1_000_001_000);
+        assert_eq!(position_authority.lamports(), 1_000_000_000);
+        assert_eq!(tick_array.lamports(), 999_999_000);
+    }
+
+    #[test]
+    fn test_refund_to_position_authority() {
+        let tick_array = account(true);
+        let position_authority = account(true);
+        let rent_funder = TickArrayRentFunder::new(&position_authority, &[]);
+
+        rent_funder
+            .refund(&tick_array, position_authority.key, 1_000)
+            .unwrap();
+        assert_eq!(position_authority.lamports(), 1_000_001_000);
+    }
+
+    #[test]
+    fn test_refund_rent_payer_missing() {
+        let tick_array = account(true);
+        let position_authority = account(true);
+        let remaining_accounts = vec![account(false)];
+        let rent_funder = TickArrayRentFunder::new(&position_authority, &remaining_accounts);
+
+        // not passed
+        let result = rent_funder.refund(&tick_array, &Pubkey::new_unique(), 1_000);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickArrayRentPayerMissing.into()
+        );
+
+        // not writable
+        let result = rent_funder.refund(&tick_array, remaining_accounts[0].key, 1_000);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickArrayRentPayerMissing.into()
+        );
+        assert_eq!(tick_array.lamports(), 1_000_000_000);
+    }
+}
@@ -10,6 +10,7 @@ use solana_program::program::{invoke, invoke_signed};
 use solana_program::system_instruction::{create_account, transfer};
 
 use crate::constants::{
+    WPB_2022_METADATA_NAME_PREFIX, WPB_2022_METADATA_SYMBOL, WPB_2022_METADATA_URI,
     WP_2022_METADATA_NAME_PREFIX, WP_2022_METADATA_SYMBOL,
     WP_2022_METADATA_URI_BASE,
 };
@@ -22,6 +23,41 @@ pub fn initialize_position_mint_2022<'info>(
     system_program: &Program<'info, System>,
     token_2022_program: &Program<'info, Token2022>,
     use_token_metadata_extension: bool,
+) -> Result<()> {
+    initialize_mint_2022(
+        position_mint,
+        funder,
+        &position.to_account_info(),
+        system_program,
+        token_2022_program,
+        use_token_metadata_extension,
+    )
+}
+
+pub fn initialize_position_bundle_mint_2022<'info>(
+    position_bundle_mint: &Signer<'info>,
+    funder: &Signer<'info>,
+    position_bundle: &Account<'info, PositionBundle>,
+    system_program: &Program<'info, System>,
+    token_2022_program: &Program<'info, Token2022>,
+) -> Result<()> {
+    initialize_mint_2022(
+        position_bundle_mint,
+        funder,
+        &position_bundle.to_account_info(),
+        system_program,
+        token_2022_program,
+        true,
+    )
+}
+
+fn initialize_mint_2022<'info>(
+    mint: &Signer<'info>,
+    funder: &Signer<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token_2022_program: &Program<'info, Token2022>,
+    use_token_metadata_extension: bool,
 ) -> Result<()> {
     let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
         if use_token_metadata_extension {
@@ -36,35 +72,33 @@ pub fn initialize_position_mint_2022<'info>(
 
     let lamports = Rent::get()?.minimum_balance(space);
 
-    let authority = position;
-
     // create account
     invoke(
         &create_account(
             funder.key,
-            position_mint.key,
+            mint.key,
             lamports,
             space as u64,
             token_2022_program.key,
         ),
         &[
             funder.to_account_info(),
-            position_mint.to_account_info(),
+            mint.to_account_info(),
             token_2022_program.to_account_info(),
             system_program.to_account_info(),
         ],
     )?;
 
     // initialize MintCloseAuthority extension
-    // authority: Position account (PDA)
+    // authority: Position or PositionBundle account (PDA)
     invoke(
         &spl_token_2022::instruction::initialize_mint_close_authority(
             token_2022_program.key,
-            position_mint.key,
+            mint.key,
             Some(&authority.key()),
         )?,
         &[
-            position_mint.to_account_info(),
+            mint.to_account_info(),
             authority.to_account_info(),
             token_2022_program.to_account_info(),
         ],
@@ -78,12 +112,12 @@ pub fn initialize_position_mint_2022<'info>(
         invoke(
             &spl_token_2022::extension::metadata_pointer::instruction::initialize(
                 token_2022_program.key,
-                position_mint.key,
+                mint.key,
                 None,
-                Some(position_mint.key()),
+                Some(mint.key()),
             )?,
             &[
-                position_mint.to_account_info(),
+                mint.to_account_info(),
                 authority.to_account_info(),
                 token_2022_program.to_account_info(),
             ],
@@ -91,18 +125,18 @@ pub fn initialize_position_mint_2022<'info>(
     }
 
     // initialize Mint
-    // mint authority: Position account (PDA) (will be removed in the transaction)
-    // freeze authority: Position account (PDA) (reserved for future improvements)
+    // mint authority: Position or PositionBundle account (PDA) (will be removed in the transaction)
+    // freeze authority: Position or PositionBundle account (PDA) (reserved for future improvements)
     invoke(
         &spl_token_2022::instruction::initialize_mint2(
             token_2022_program.key,
-            position_mint.key,
+            mint.key,
             &authority.key(),
             Some(&authority.key()),
             0,
         )?,
         &[
-            position_mint.to_account_info(),
+            mint.to_account_info(),
             authority.to_account_info(),
             token_2022_program.to_account_info(),
         ],
@@ -124,8 +158,60 @@ pub fn initialize_token_metadata_extension<'info>(
     token_2022_program: &Program<'info, Token2022>,
     position_seeds: &[&[u8]],
 ) -> Result<()> {
-    let mint_authority = position;
+    initialize_token_metadata(
+        name,
+        symbol,
+        uri,
+        position_mint,
+        &position.to_account_info(),
+        metadata_update_authority,
+        funder,
+        system_program,
+        token_2022_program,
+        position_seeds,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_position_bundle_token_metadata_extension<'info>(
+    name: String,
+    symbol: String,
+    uri: String,
+    position_bundle_mint: &Signer<'info>,
+    position_bundle: &Account<'info, PositionBundle>,
+    metadata_update_authority: &UncheckedAccount<'info>,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    token_2022_program: &Program<'info, Token2022>,
+    position_bundle_seeds: &[&[u8]],
+) -> Result<()> {
+    initialize_token_metadata(
+        name,
+        symbol,
+        uri,
+        position_bundle_mint,
+        &position_bundle.to_account_info(),
+        metadata_update_authority,
+        funder,
+        system_program,
+        token_2022_program,
+        position_bundle_seeds,
+    )
+}
 
+#[allow(clippy::too_many_arguments)]
+fn initialize_token_metadata<'info>(
+    name: String,
+    symbol: String,
+    uri: String,
+    mint: &Signer<'info>,
+    mint_authority: &AccountInfo<'info>,
+    metadata_update_authority: &UncheckedAccount<'info>,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    token_2022_program: &Program<'info, Token2022>,
+    mint_authority_seeds: &[&[u8]],
+) -> Result<()> {
     let metadata = spl_token_metadata_interface::state::TokenMetadata {
         name,
         symbol,
@@ -134,26 +220,26 @@ pub fn initialize_token_metadata_extension<'info>(
     };
 
     // we need to add rent for TokenMetadata extension to reallocate space
-    let token_mint_data = position_mint.try_borrow_data()?;
+    let token_mint_data = mint.try_borrow_data()?;
     let token_mint_unpacked =
         StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
     let new_account_len = token_mint_unpacked.try_get_new_account_len::<spl_token_metadata_interface::state::TokenMetadata>(
         &metadata,
     )?;
     let new_rent_exempt_minimum = Rent::get()?.minimum_balance(new_account_len);
-    let additional_rent = new_rent_exempt_minimum.saturating_sub(position_mint.lamports());
+    let additional_rent = new_rent_exempt_minimum.saturating_sub(mint.lamports());
     drop(token_mint_data); // CPI call will borrow the account data
 
     // transfer additional rent
     invoke(
         &transfer(
             funder.key,
-            position_mint.key,
+            mint.key,
             additional_rent,
         ),
         &[
             funder.to_account_info(),
-            position_mint.to_account_info(),
+            mint.to_account_info(),
             system_program.to_account_info(),
         ],
     )?;
@@ -163,21 +249,21 @@ pub fn initialize_token_metadata_extension<'info>(
     invoke_signed(
         &spl_token_metadata_interface::instruction::initialize(
             token_2022_program.key,
-            position_mint.key,
+            mint.key,
             metadata_update_authority.key,
-            position_mint.key,
-            &mint_authority.key(),
+            mint.key,
+            mint_authority.key,
             metadata.name,
             metadata.symbol,
             metadata.uri,
         ),
         &[
-            position_mint.to_account_info(),
+            mint.to_account_info(),
             mint_authority.to_account_info(),
             metadata_update_authority.to_account_info(),
             token_2022_program.to_account_info(),
         ],
-        &[position_seeds],
+        &[mint_authority_seeds],
     )?;
 
     Ok(())
@@ -212,43 +298,73 @@ pub fn mint_position_token_2022_and_remove_authority<'info>(
     token_2022_program: &Program<'info, Token2022>,
     position_seeds: &[&[u8]],
 ) -> Result<()> {
-    let authority = position;
+    mint_token_2022_and_remove_authority(
+        &position.to_account_info(),
+        position_mint,
+        position_token_account,
+        token_2022_program,
+        position_seeds,
+    )
+}
+
+pub fn mint_position_bundle_token_2022_and_remove_authority<'info>(
+    position_bundle: &Account<'info, PositionBundle>,
+    position_bundle_mint: &Signer<'info>,
+    position_bundle_token_account: &UncheckedAccount<'info>,
+    token_2022_program: &Program<'info, Token2022>,
+    position_bundle_seeds: &[&[u8]],
+) -> Result<()> {
+    mint_token_2022_and_remove_authority(
+        &position_bundle.to_account_info(),
+        position_bundle_mint,
+        position_bundle_token_account,
+        token_2022_program,
+        position_bundle_seeds,
+    )
+}
 
+fn mint_token_2022_and_remove_authority<'info>(
+    authority: &AccountInfo<'info>,
+    mint: &Signer<'info>,
+    token_account: &UncheckedAccount<'info>,
+    token_2022_program: &Program<'info, Token2022>,
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
     // mint
     invoke_signed(
         &spl_token_2022::instruction::mint_to(
             token_2022_program.key,
-            position_mint.to_account_info().key,
-            position_token_account.to_account_info().key,
+            mint.to_account_info().key,
+            token_account.to_account_info().key,
             authority.to_account_info().key,
             &[authority.to_account_info().key],
             1,
         )?,
         &[
-            position_mint.to_account_info(),
-            position_token_account.to_account_info(),
+            mint.to_account_info(),
+            token_account.to_account_info(),
             authority.to_account_info(),
             token_2022_program.to_account_info(),
         ],
-        &[position_seeds],
+        &[authority_seeds],
     )?;
 
     // remove mint authority
     invoke_signed(
         &spl_token_2022::instruction::set_authority(
             token_2022_program.key,
-            position_mint.to_account_info().key,
+            mint.to_account_info().key,
             Option::None,
             AuthorityType::MintTokens,
             authority.to_account_info().key,
             &[authority.to_account_info().key],
         )?,
         &[
-            position_mint.to_account_info(),
+            mint.to_account_info(),
             authority.to_account_info(),
             token_2022_program.to_account_info(),
         ],
-        &[position_seeds],
+        &[authority_seeds],
     )?;
 
     Ok(())
@@ -262,22 +378,62 @@ pub fn burn_and_close_user_position_token_2022<'info>(
     token_2022_program: &Program<'info, Token2022>,
     position: &Account<'info, Position>,
     position_seeds: &[&[u8]],
+) -> Result<()> {
+    burn_and_close_token_2022(
+        token_authority,
+        receiver,
+        position_mint,
+        position_token_account,
+        token_2022_program,
+        &position.to_account_info(),
+        position_seeds,
+    )
+}
+
+pub fn burn_and_close_position_bundle_token_2022<'info>(
+    position_bundle_authority: &Signer<'info>,
+    receiver: &UncheckedAccount<'info>,
+    position_bundle_mint: &InterfaceAccount<'info, Mint>,
+    position_bundle_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_2022_program: &Program<'info, Token2022>,
+    position_bundle: &Account<'info, PositionBundle>,
+    position_bundle_seeds: &[&[u8]],
+) -> Result<()> {
+    burn_and_close_token_2022(
+        position_bundle_authority,
+        receiver,
+        position_bundle_mint,
+        position_bundle_token_account,
+        token_2022_program,
+        &position_bundle.to_account_info(),
+        position_bundle_seeds,
+    )
+}
+
+fn burn_and_close_token_2022<'info>(
+    token_authority: &Signer<'info>,
+    receiver: &UncheckedAccount<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_2022_program: &Program<'info, Token2022>,
+    mint_close_authority: &AccountInfo<'info>,
+    mint_close_authority_seeds: &[&[u8]],
 ) -> Result<()> {
     // Burn a single token in user account
     invoke(
         &spl_token_2022::instruction::burn_checked(
             token_2022_program.key,
-            position_token_account.to_account_info().key,
-            position_mint.to_account_info().key,
+            token_account.to_account_info().key,
+            mint.to_account_info().key,
             token_authority.key,
             &[],
             1,
-            position_mint.decimals,
+            mint.decimals,
         )?,
         &[
             token_2022_program.to_account_info(),
-            position_token_account.to_account_info(),
-            position_mint.to_account_info(),
+            token_account.to_account_info(),
+            mint.to_account_info(),
             token_authority.to_account_info(),
         ],
     )?;
@@ -286,14 +442,14 @@ pub fn burn_and_close_user_position_token_2022<'info>(
     invoke(
         &spl_token_2022::instruction::close_account(
             token_2022_program.key,
-            position_token_account.to_account_info().key,
+            token_account.to_account_info().key,
             receiver.key,
             token_authority.key,
             &[],
         )?,
         &[
             token_2022_program.to_account_info(),
-            position_token_account.to_account_info(),
+            token_account.to_account_info(),
             receiver.to_account_info(),
             token_authority.to_account_info(),
         ],
@@ -303,18 +459,18 @@ pub fn burn_and_close_user_position_token_2022<'info>(
     invoke_signed(
         &spl_token_2022::instruction::close_account(
             token_2022_program.key,
-            position_mint.to_account_info().key,
+            mint.to_account_info().key,
             receiver.key,
-            &position.key(),
+            mint_close_authority.key,
             &[],
         )?,
         &[
             token_2022_program.to_account_info(),
-            position_mint.to_account_info(),
+            mint.to_account_info(),
             receiver.to_account_info(),
-            position.to_account_info(),
+            mint_close_authority.to_account_info(),
         ],
-        &[position_seeds],
+        &[mint_close_authority_seeds],
     )?;
 
     Ok(())
@@ -346,3 +502,107 @@ pub fn build_position_token_metadata<'info>(
 
     (name, WP_2022_METADATA_SYMBOL.to_string(), uri)
 }
+
+pub fn build_position_bundle_token_metadata<'info>(
+    position_bundle_mint: &Signer<'info>,
+) -> (String, String, String) {
+    // WPB_2022_METADATA_NAME_PREFIX + " xxxx...yyyy"
+    // xxxx and yyyy are the first and last 4 chars of mint address
+    let mint_address = position_bundle_mint.key().to_string();
+    let name = format!(
+        "{} {}...{}",
+        WPB_2022_METADATA_NAME_PREFIX,
+        &mint_address[0..4],
+        &mint_address[mint_address.len() - 4..],
+    );
+
+    (
+        name,
+        WPB_2022_METADATA_SYMBOL.to_string(),
+        WPB_2022_METADATA_URI.to_string(),
+    )
+}
+
+pub fn freeze_user_position_token_2022<'info>(
+    position_mint: &InterfaceAccount<'info, Mint>,
+    position_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_2022_program: &Program<'info, Token2022>,
+    position: &Account<'info, Position>,
+    position_seeds: &[&[u8]],
+) -> Result<()> {
+    // freeze authority: Position account (PDA)
+    invoke_signed(
+        &spl_token_2022::instruction::freeze_account(
+            token_2022_program.key,
+            position_token_account.to_account_info().key,
+            position_mint.to_account_info().key,
+            position.to_account_info().key,
+            &[],
+        )?,
+        &[
+            position_token_account.to_account_info(),
+            position_mint.to_account_info(),
+            position.to_account_info(),
+            token_2022_program.to_account_info(),
+        ],
+        &[position_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn thaw_user_position_token_2022<'info>(
+    position_mint: &InterfaceAccount<'info, Mint>,
+    position_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_2022_program: &Program<'info, Token2022>,
+    position: &Account<'info, Position>,
+    position_seeds: &[&[u8]],
+) -> Result<()> {
+    // freeze authority: Position account (PDA)
+    invoke_signed(
+        &spl_token_2022::instruction::thaw_account(
+            token_2022_program.key,
+            position_token_account.to_account_info().key,
+            position_mint.to_account_info().key,
+            position.to_account_info().key,
+            &[],
+        )?,
+        &[
+            position_token_account.to_account_info(),
+            position_mint.to_account_info(),
+            position.to_account_info(),
+            token_2022_program.to_account_info(),
+        ],
+        &[position_seeds],
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod token_2022_tests {
+    use super::*;
+    use crate::util::test_utils::leaked_account_info;
+
+    #[test]
+    fn test_build_position_bundle_token_metadata() {
+        let position_bundle_mint_info: &'static AccountInfo<'static> = Box::leak(Box::new(
+            leaked_account_info(Pubkey::new_unique(), System::id(), true, true, vec![]),
+        ));
+        let position_bundle_mint = Signer::try_from(position_bundle_mint_info).unwrap();
+        let mint_address = position_bundle_mint.key().to_string();
+
+        let (name, symbol, uri) = build_position_bundle_token_metadata(&position_bundle_mint);
+
+        assert_eq!(
+            name,
+            format!(
+                "OPB {}...{}",
+                &mint_address[0..4],
+                &mint_address[mint_address.len() - 4..]
+            )
+        );
+        assert_eq!(symbol, "OPB");
+        assert_eq!(uri, WPB_2022_METADATA_URI);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod remaining_accounts_utils;
 pub mod swap_utils;
 pub mod token;
+pub mod token_extension;
 
 pub use remaining_accounts_utils::*;
 pub use swap_utils::*;
@@ -14,6 +14,12 @@ pub enum AccountsType {
     SupplementalTickArrays,
     SupplementalTickArraysOne,
     SupplementalTickArraysTwo,
+    SwapHop,
+    IntermediateToken,
+    Reward,
+    ReferralFee,
+    FlashSwapCallback,
+    TokenBadges,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -28,7 +34,7 @@ pub struct RemainingAccountsInfo {
 }
 
 #[derive(Default)]
-pub struct ParsedRemainingAccounts<'info> {
+pub struct ParsedRemainingAccounts<'a, 'info> {
     pub transfer_hook_a: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_b: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_reward: Option<Vec<AccountInfo<'info>>>,
@@ -38,13 +44,19 @@ pub struct ParsedRemainingAccounts<'info> {
     pub supplemental_tick_arrays: Option<Vec<AccountInfo<'info>>>,
     pub supplemental_tick_arrays_one: Option<Vec<AccountInfo<'info>>>,
     pub supplemental_tick_arrays_two: Option<Vec<AccountInfo<'info>>>,
+    pub swap_hops: Vec<&'a [AccountInfo<'info>]>,
+    pub intermediate_tokens: Vec<&'a [AccountInfo<'info>]>,
+    pub rewards: Vec<&'a [AccountInfo<'info>]>,
+    pub referral_fee: Option<&'a AccountInfo<'info>>,
+    pub flash_swap_callback: Option<&'a [AccountInfo<'info>]>,
+    pub token_badges: Option<&'a [AccountInfo<'info>]>,
 }
 
-pub fn parse_remaining_accounts<'info>(
-    remaining_accounts: &[AccountInfo<'info>],
+pub fn parse_remaining_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
     remaining_accounts_info: &Option<RemainingAccountsInfo>,
     valid_accounts_type_list: &[AccountsType],
-) -> Result<ParsedRemainingAccounts<'info>> {
+) -> Result<ParsedRemainingAccounts<'a, 'info>> {
     let mut remaining_accounts_iter = remaining_accounts.iter();
     let mut parsed_remaining_accounts = ParsedRemainingAccounts::default();
 
@@ -62,6 +74,7 @@ pub fn parse_remaining_accounts<'info>(
             continue;
         }
 
+        let slice_accounts = remaining_accounts_iter.as_slice();
         let mut accounts: Vec<AccountInfo<'info>> = Vec::with_capacity(slice.length as usize);
         for _ in 0..slice.length {
             if let Some(account) = remaining_accounts_iter.next() {
@@ -147,8 +160,236 @@ pub fn parse_remaining_accounts<'info>(
                 }
                 parsed_remaining_accounts.supplemental_tick_arrays_two = Some(accounts);
             }
+            // SwapHop, IntermediateToken and Reward are repeatable, each slice is stored in order
+            AccountsType::SwapHop => {
+                parsed_remaining_accounts
+                    .swap_hops
+                    .push(&slice_accounts[..accounts.len()]);
+            }
+            AccountsType::IntermediateToken => {
+                parsed_remaining_accounts
+                    .intermediate_tokens
+                    .push(&slice_accounts[..accounts.len()]);
+            }
+            AccountsType::Reward => {
+                parsed_remaining_accounts
+                    .rewards
+                    .push(&slice_accounts[..accounts.len()]);
+            }
+            AccountsType::ReferralFee => {
+                if accounts.len() != 1 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+
+                if parsed_remaining_accounts.referral_fee.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.referral_fee = slice_accounts.first();
+            }
+            AccountsType::FlashSwapCallback => {
+                if parsed_remaining_accounts.flash_swap_callback.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.flash_swap_callback =
+                    Some(&slice_accounts[..accounts.len()]);
+            }
+            AccountsType::TokenBadges => {
+                if parsed_remaining_accounts.token_badges.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.token_badges = Some(&slice_accounts[..accounts.len()]);
+            }
         }
     }
 
     Ok(parsed_remaining_accounts)
 }
+
+#[cfg(test)]
+mod parse_remaining_accounts_tests {
+    use super::*;
+
+    fn slice(accounts_type: AccountsType, length: u8) -> RemainingAccountsSlice {
+        RemainingAccountsSlice {
+            accounts_type,
+            length,
+        }
+    }
+
+    #[test]
+    fn test_repeatable_slices_are_kept_in_order() {
+        let keys: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![vec![]; keys.len()];
+        let account_infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        let remaining_accounts_info = Some(RemainingAccountsInfo {
+            slices: vec![
+                slice(AccountsType::TransferHookInput, 1),
+                slice(AccountsType::SwapHop, 2),
+                slice(AccountsType::IntermediateToken, 1),
+                slice(AccountsType::SwapHop, 0),
+                slice(AccountsType::SwapHop, 2),
+            ],
+        });
+
+        let parsed = parse_remaining_accounts(
+            &account_infos,
+            &remaining_accounts_info,
+            &[
+                AccountsType::TransferHookInput,
+                AccountsType::SwapHop,
+                AccountsType::IntermediateToken,
+            ],
+        )
+        .unwrap();
+
+        let keys_of = |accounts: &[AccountInfo]| -> Vec<Pubkey> {
+            accounts.iter().map(|account| account.key()).collect()
+        };
+        assert_eq!(
+            keys_of(parsed.transfer_hook_input.as_ref().unwrap()),
+            keys[0..1]
+        );
+        assert_eq!(parsed.swap_hops.len(), 2);
+        assert_eq!(keys_of(parsed.swap_hops[0]), keys[1..3]);
+        assert_eq!(keys_of(parsed.swap_hops[1]), keys[4..6]);
+        assert_eq!(parsed.intermediate_tokens.len(), 1);
+        assert_eq!(keys_of(parsed.intermediate_tokens[0]), keys[3..4]);
+    }
+
+    #[test]
+    fn test_repeatable_slice_insufficient_accounts() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        let account_infos = vec![AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &key,
+            false,
+            0,
+        )];
+
+        let result = parse_remaining_accounts(
+            &account_infos,
+            &Some(RemainingAccountsInfo {
+                slices: vec![slice(AccountsType::SwapHop, 2)],
+            }),
+            &[AccountsType::SwapHop],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_flash_swap_callback_slice() {
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![vec![]; keys.len()];
+        let account_infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        let parsed = parse_remaining_accounts(
+            &account_infos,
+            &Some(RemainingAccountsInfo {
+                slices: vec![
+                    slice(AccountsType::TransferHookA, 1),
+                    slice(AccountsType::FlashSwapCallback, 2),
+                ],
+            }),
+            &[AccountsType::TransferHookA, AccountsType::FlashSwapCallback],
+        )
+        .unwrap();
+        let callback_keys: Vec<Pubkey> = parsed
+            .flash_swap_callback
+            .unwrap()
+            .iter()
+            .map(|account| account.key())
+            .collect();
+        assert_eq!(callback_keys, keys[1..3]);
+
+        let result = parse_remaining_accounts(
+            &account_infos,
+            &Some(RemainingAccountsInfo {
+                slices: vec![
+                    slice(AccountsType::FlashSwapCallback, 1),
+                    slice(AccountsType::FlashSwapCallback, 2),
+                ],
+            }),
+            &[AccountsType::FlashSwapCallback],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_optional_account_slices() {
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let owner = Pubkey::new_unique();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![vec![]; keys.len()];
+        let account_infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect();
+        let valid_accounts_type_list = [AccountsType::ReferralFee, AccountsType::TokenBadges];
+
+        let parsed =
+            parse_remaining_accounts(&account_infos, &None, &valid_accounts_type_list).unwrap();
+        assert!(parsed.referral_fee.is_none());
+        assert!(parsed.token_badges.is_none());
+
+        let parsed = parse_remaining_accounts(
+            &account_infos,
+            &Some(RemainingAccountsInfo {
+                slices: vec![
+                    slice(AccountsType::ReferralFee, 1),
+                    slice(AccountsType::TokenBadges, 2),
+                ],
+            }),
+            &valid_accounts_type_list,
+        )
+        .unwrap();
+        assert_eq!(parsed.referral_fee.unwrap().key(), keys[0]);
+        let token_badge_keys: Vec<Pubkey> = parsed
+            .token_badges
+            .unwrap()
+            .iter()
+            .map(|account| account.key())
+            .collect();
+        assert_eq!(token_badge_keys, keys[1..3]);
+
+        let result = parse_remaining_accounts(
+            &account_infos,
+            &Some(RemainingAccountsInfo {
+                slices: vec![
+                    slice(AccountsType::TokenBadges, 1),
+                    slice(AccountsType::TokenBadges, 1),
+                ],
+            }),
+            &valid_accounts_type_list,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -2,13 +2,100 @@ use anchor_lang::prelude::*;
 use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{manager::swap_manager::PostSwapUpdate, state::Whirlpool};
+use crate::{
+    errors::ErrorCode,
+    manager::swap_manager::PostSwapUpdate,
+    math::REFERRAL_FEE_RATE_MUL_VALUE,
+    state::{OracleAccessor, Whirlpool},
+};
 
 use super::{transfer_from_owner_to_vault_v2, transfer_from_vault_to_owner_v2};
+use crate::util::{store_whirlpool_reward_extension, store_whirlpool_reward_schedule};
+
+/// Referral fee charged by an integrator on a swap, transferred to the referral fee account.
+pub struct ReferralFee<'info> {
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // The fee is paid on top of the input amount if true, deducted from the output amount if false
+    pub is_input: bool,
+    pub amount: u64,
+}
+
+impl<'info> ReferralFee<'info> {
+    /// Calculates the referral fee of a swap from its input and output amounts (transfer fee included).
+    ///
+    /// The fee is charged in the token of the referral fee account: on top of the input amount for
+    /// the input token, or deducted from the output amount for the output token. The output token is
+    /// only accepted for exact input swaps, so that exact output swaps deliver the specified amount.
+    ///
+    /// # Returns
+    /// - `Ok`: None if the referral fee rate is zero
+    /// - `Err`: `ReferralFeeRateMaxExceeded` if the rate exceeds the cap of the WhirlpoolsConfig,
+    ///   `InvalidReferralFeeAccount` if the account is missing or holds neither token of the swap
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate(
+        referral_fee_account: Option<&'info AccountInfo<'info>>,
+        referral_fee_rate: u16,
+        max_referral_fee_rate: u16,
+        input_token_mint: &Pubkey,
+        output_token_mint: &Pubkey,
+        input_amount: u64,
+        output_amount: u64,
+        amount_specified_is_input: bool,
+    ) -> Result<Option<Self>> {
+        if referral_fee_rate == 0 {
+            return Ok(None);
+        }
+        if referral_fee_rate > max_referral_fee_rate {
+            return Err(ErrorCode::ReferralFeeRateMaxExceeded.into());
+        }
+
+        let token_account = Box::new(InterfaceAccount::<TokenAccount>::try_from(
+            referral_fee_account.ok_or(ErrorCode::InvalidReferralFeeAccount)?,
+        )?);
+        let is_input = if token_account.mint == *input_token_mint {
+            true
+        } else if token_account.mint == *output_token_mint && amount_specified_is_input {
+            false
+        } else {
+            return Err(ErrorCode::InvalidReferralFeeAccount.into());
+        };
+
+        let amount = if is_input {
+            input_amount
+        } else {
+            output_amount
+        };
+        Ok(Some(Self {
+            token_account,
+            is_input,
+            amount: calculate_referral_fee_amount(amount, referral_fee_rate),
+        }))
+    }
+
+    pub fn input_amount(referral_fee: &Option<Self>) -> u64 {
+        match referral_fee {
+            Some(referral_fee) if referral_fee.is_input => referral_fee.amount,
+            _ => 0,
+        }
+    }
+
+    pub fn output_amount(referral_fee: &Option<Self>) -> u64 {
+        match referral_fee {
+            Some(referral_fee) if !referral_fee.is_input => referral_fee.amount,
+            _ => 0,
+        }
+    }
+}
+
+fn calculate_referral_fee_amount(amount: u64, referral_fee_rate: u16) -> u64 {
+    // referral_fee_rate is capped by MAX_REFERRAL_FEE_RATE, so the result fits in u64
+    ((amount as u128) * (referral_fee_rate as u128) / REFERRAL_FEE_RATE_MUL_VALUE) as u64
+}
 
 #[allow(clippy::too_many_arguments)]
 pub fn update_and_swap_whirlpool_v2<'info>(
     whirlpool: &mut Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
     token_authority: &Signer<'info>,
     token_mint_a: &InterfaceAccount<'info, Mint>,
     token_mint_b: &InterfaceAccount<'info, Mint>,
@@ -21,21 +108,21 @@ pub fn update_and_swap_whirlpool_v2<'info>(
     token_program_a: &Interface<'info, TokenInterface>,
     token_program_b: &Interface<'info, TokenInterface>,
     memo_program: &Program<'info, Memo>,
+    oracle_accessor: &OracleAccessor<'info>,
     swap_update: PostSwapUpdate,
     is_token_fee_in_a: bool,
     reward_last_updated_timestamp: u64,
+    referral_fee: &Option<ReferralFee<'info>>,
     memo: &[u8],
 ) -> Result<()> {
-    whirlpool.update_after_swap(
-        swap_update.next_liquidity,
-        swap_update.next_tick_index,
-        swap_update.next_sqrt_price,
-        swap_update.next_fee_growth_global,
-        swap_update.next_reward_infos,
-        swap_update.next_protocol_fee,
+    update_whirlpool_after_swap_v2(
+        whirlpool,
+        whirlpool_rewards,
+        oracle_accessor,
+        &swap_update,
         is_token_fee_in_a,
         reward_last_updated_timestamp,
-    );
+    )?;
 
     perform_swap_v2(
         whirlpool,
@@ -54,10 +141,49 @@ pub fn update_and_swap_whirlpool_v2<'info>(
         swap_update.amount_a,
         swap_update.amount_b,
         is_token_fee_in_a,
+        referral_fee,
         memo,
     )
 }
 
+/// Applies the swap to the Whirlpool state, without transferring any tokens.
+pub fn update_whirlpool_after_swap_v2<'info>(
+    whirlpool: &mut Account<'info, Whirlpool>,
+    whirlpool_rewards: &AccountInfo<'info>,
+    oracle_accessor: &OracleAccessor<'info>,
+    swap_update: &PostSwapUpdate,
+    is_token_fee_in_a: bool,
+    reward_last_updated_timestamp: u64,
+) -> Result<()> {
+    // Record the state of the Whirlpool before the swap
+    oracle_accessor.update_on_swap(
+        whirlpool,
+        &swap_update.next_adaptive_fee_info,
+        reward_last_updated_timestamp,
+    )?;
+
+    whirlpool.update_after_swap(
+        swap_update.next_liquidity,
+        swap_update.next_tick_index,
+        swap_update.next_sqrt_price,
+        swap_update.next_fee_growth_global,
+        swap_update.next_reward_infos,
+        swap_update.next_protocol_fee,
+        is_token_fee_in_a,
+        reward_last_updated_timestamp,
+    );
+
+    if let Some(next_reward_schedule) = &swap_update.next_reward_schedule {
+        store_whirlpool_reward_schedule(whirlpool_rewards, next_reward_schedule)?;
+    }
+
+    if let Some(next_reward_extension) = &swap_update.next_reward_extension {
+        store_whirlpool_reward_extension(whirlpool_rewards, next_reward_extension)?;
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn perform_swap_v2<'info>(
     whirlpool: &Account<'info, Whirlpool>,
@@ -76,6 +202,7 @@ fn perform_swap_v2<'info>(
     amount_a: u64,
     amount_b: u64,
     a_to_b: bool,
+    referral_fee: &Option<ReferralFee<'info>>,
     memo: &[u8],
 ) -> Result<()> {
     // Transfer from user to pool
@@ -135,6 +262,7 @@ fn perform_swap_v2<'info>(
         deposit_amount,
     )?;
 
+    // The referral fee on the output is deducted from the amount sent to the user
     transfer_from_vault_to_owner_v2(
         whirlpool,
         withdrawal_mint,
@@ -143,10 +271,39 @@ fn perform_swap_v2<'info>(
         withdrawal_token_program,
         memo_program,
         withdrawal_transfer_hook_accounts,
-        withdrawal_amount,
+        withdrawal_amount - ReferralFee::output_amount(referral_fee),
         memo,
     )?;
 
+    match referral_fee {
+        Some(referral_fee) if referral_fee.is_input => {
+            transfer_from_owner_to_vault_v2(
+                token_authority,
+                deposit_mint,
+                deposit_account_user,
+                &referral_fee.token_account,
+                deposit_token_program,
+                memo_program,
+                deposit_transfer_hook_accounts,
+                referral_fee.amount,
+            )?;
+        }
+        Some(referral_fee) => {
+            transfer_from_vault_to_owner_v2(
+                whirlpool,
+                withdrawal_mint,
+                withdrawal_account_pool,
+                &referral_fee.token_account,
+                withdrawal_token_program,
+                memo_program,
+                withdrawal_transfer_hook_accounts,
+                referral_fee.amount,
+                memo,
+            )?;
+        }
+        None => {}
+    }
+
     Ok(())
 }
 
@@ -158,6 +315,11 @@ pub fn update_and_two_hop_swap_whirlpool_v2<'info>(
     // whirlpool
     whirlpool_one: &mut Account<'info, Whirlpool>,
     whirlpool_two: &mut Account<'info, Whirlpool>,
+    whirlpool_rewards_one: &AccountInfo<'info>,
+    whirlpool_rewards_two: &AccountInfo<'info>,
+    // oracle
+    oracle_accessor_one: &OracleAccessor<'info>,
+    oracle_accessor_two: &OracleAccessor<'info>,
     // direction
     is_token_fee_in_one_a: bool,
     is_token_fee_in_two_a: bool,
@@ -186,6 +348,18 @@ pub fn update_and_two_hop_swap_whirlpool_v2<'info>(
     reward_last_updated_timestamp: u64,
     memo: &[u8],
 ) -> Result<()> {
+    // Record the state of the Whirlpools before the swap
+    oracle_accessor_one.update_on_swap(
+        whirlpool_one,
+        &swap_update_one.next_adaptive_fee_info,
+        reward_last_updated_timestamp,
+    )?;
+    oracle_accessor_two.update_on_swap(
+        whirlpool_two,
+        &swap_update_two.next_adaptive_fee_info,
+        reward_last_updated_timestamp,
+    )?;
+
     whirlpool_one.update_after_swap(
         swap_update_one.next_liquidity,
         swap_update_one.next_tick_index,
@@ -197,6 +371,14 @@ pub fn update_and_two_hop_swap_whirlpool_v2<'info>(
         reward_last_updated_timestamp,
     );
 
+    if let Some(next_reward_schedule) = &swap_update_one.next_reward_schedule {
+        store_whirlpool_reward_schedule(whirlpool_rewards_one, next_reward_schedule)?;
+    }
+
+    if let Some(next_reward_extension) = &swap_update_one.next_reward_extension {
+        store_whirlpool_reward_extension(whirlpool_rewards_one, next_reward_extension)?;
+    }
+
     whirlpool_two.update_after_swap(
         swap_update_two.next_liquidity,
         swap_update_two.next_tick_index,
@@ -208,6 +390,14 @@ pub fn update_and_two_hop_swap_whirlpool_v2<'info>(
         reward_last_updated_timestamp,
     );
 
+    if let Some(next_reward_schedule) = &swap_update_two.next_reward_schedule {
+        store_whirlpool_reward_schedule(whirlpool_rewards_two, next_reward_schedule)?;
+    }
+
+    if let Some(next_reward_extension) = &swap_update_two.next_reward_extension {
+        store_whirlpool_reward_extension(whirlpool_rewards_two, next_reward_extension)?;
+    }
+
     // amount
     let (input_amount, intermediate_amount) = if is_token_fee_in_one_a {
         (swap_update_one.amount_a, swap_update_one.amount_b)
@@ -258,3 +448,47 @@ pub fn update_and_two_hop_swap_whirlpool_v2<'info>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod referral_fee_tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_referral_fee_amount() {
+        assert_eq!(calculate_referral_fee_amount(1_000_000, 0), 0);
+        assert_eq!(calculate_referral_fee_amount(1_000_000, 25), 2_500);
+        // rounded down
+        assert_eq!(calculate_referral_fee_amount(399, 25), 0);
+        assert_eq!(calculate_referral_fee_amount(400, 25), 1);
+        assert_eq!(
+            calculate_referral_fee_amount(u64::MAX, crate::math::MAX_REFERRAL_FEE_RATE),
+            u64::MAX / 10
+        );
+    }
+
+    #[test]
+    fn test_calculate_referral_fee_zero_rate() {
+        let mint = Pubkey::new_unique();
+        let referral_fee =
+            ReferralFee::calculate(None, 0, 0, &mint, &mint, 1_000, 1_000, true).unwrap();
+        assert!(referral_fee.is_none());
+        assert_eq!(ReferralFee::input_amount(&referral_fee), 0);
+        assert_eq!(ReferralFee::output_amount(&referral_fee), 0);
+    }
+
+    #[test]
+    fn test_calculate_referral_fee_invalid() {
+        let mint = Pubkey::new_unique();
+        let result = ReferralFee::calculate(None, 101, 100, &mint, &mint, 1_000, 1_000, true);
+        assert_eq!(
+            result.err().unwrap(),
+            ErrorCode::ReferralFeeRateMaxExceeded.into()
+        );
+
+        let result = ReferralFee::calculate(None, 100, 100, &mint, &mint, 1_000, 1_000, true);
+        assert_eq!(
+            result.err().unwrap(),
+            ErrorCode::InvalidReferralFeeAccount.into()
+        );
+    }
+}
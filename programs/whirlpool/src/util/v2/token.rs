@@ -16,6 +16,8 @@ use anchor_spl::token_2022::spl_token_2022::{
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use spl_transfer_hook_interface;
 
+use super::token_extension::{self, get_extension, get_extension_types, is_mint_paused};
+
 #[allow(clippy::too_many_arguments)]
 pub fn transfer_from_owner_to_vault_v2<'info>(
     authority: &Signer<'info>,
@@ -27,6 +29,16 @@ pub fn transfer_from_owner_to_vault_v2<'info>(
     transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
     amount: u64,
 ) -> Result<()> {
+    // Pausable extension
+    if is_token_mint_paused(token_mint)? {
+        // Token-2022 rejects even zero transfers of a paused mint,
+        // so skip them to keep the other token of the pool usable
+        if amount == 0 {
+            return Ok(());
+        }
+        return Err(ErrorCode::TokenMintPaused.into());
+    }
+
     // TransferFee extension
     if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
         // log applied transfer fee
@@ -104,6 +116,16 @@ pub fn transfer_from_vault_to_owner_v2<'info>(
     amount: u64,
     memo: &[u8],
 ) -> Result<()> {
+    // Pausable extension
+    if is_token_mint_paused(token_mint)? {
+        // Token-2022 rejects even zero transfers of a paused mint,
+        // so skip them to keep the other token of the pool usable
+        if amount == 0 {
+            return Ok(());
+        }
+        return Err(ErrorCode::TokenMintPaused.into());
+    }
+
     // TransferFee extension
     if let Some(epoch_transfer_fee) = get_epoch_transfer_fee(token_mint)? {
         // log applied transfer fee
@@ -183,9 +205,104 @@ fn get_transfer_hook_program_id(token_mint: &InterfaceAccount<'_, Mint>) -> Resu
     let token_mint_data = token_mint_info.try_borrow_data()?;
     let token_mint_unpacked =
         StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
-    Ok(extension::transfer_hook::get_program_id(
-        &token_mint_unpacked,
-    ))
+    get_transfer_hook_program_id_from_unpacked(&token_mint_unpacked)
+}
+
+fn get_transfer_hook_program_id_from_unpacked(
+    token_mint_unpacked: &StateWithExtensions<spl_token_2022::state::Mint>,
+) -> Result<Option<Pubkey>> {
+    Ok(
+        get_extension::<extension::transfer_hook::TransferHook>(
+            token_mint_unpacked.get_tlv_data(),
+        )?
+        .and_then(|transfer_hook| Option::<Pubkey>::from(transfer_hook.program_id)),
+    )
+}
+
+fn is_token_mint_paused(token_mint: &InterfaceAccount<'_, Mint>) -> Result<bool> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(false);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let token_mint_unpacked =
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
+    is_mint_paused(token_mint_unpacked.get_tlv_data())
+}
+
+fn get_permanent_delegate(token_mint: &InterfaceAccount<'_, Mint>) -> Result<Option<Pubkey>> {
+    let token_mint_info = token_mint.to_account_info();
+    if *token_mint_info.owner == Token::id() {
+        return Ok(None);
+    }
+
+    let token_mint_data = token_mint_info.try_borrow_data()?;
+    let token_mint_unpacked =
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
+    get_permanent_delegate_from_unpacked(&token_mint_unpacked)
+}
+
+fn get_permanent_delegate_from_unpacked(
+    token_mint_unpacked: &StateWithExtensions<spl_token_2022::state::Mint>,
+) -> Result<Option<Pubkey>> {
+    Ok(
+        get_extension::<extension::permanent_delegate::PermanentDelegate>(
+            token_mint_unpacked.get_tlv_data(),
+        )?
+        .and_then(|permanent_delegate| Option::<Pubkey>::from(permanent_delegate.delegate)),
+    )
+}
+
+/// Rejects the instruction if the transfer hook program or the permanent delegate of a mint is
+/// no longer allowed by the TokenBadge of the mint in the WhirlpoolsConfig.
+///
+/// The TokenBadges are passed in the TokenBadges slice of the remaining accounts, which must hold
+/// the TokenBadge PDA of each mint with a transfer hook or a permanent delegate. The slice may be
+/// left out only if none of the mints has either. A TokenBadge may be uninitialized if it has been
+/// deleted, then nothing is restricted.
+pub fn verify_token_badge_allowlists(
+    whirlpools_config_key: &Pubkey,
+    token_mints: &[&InterfaceAccount<'_, Mint>],
+    token_badges: Option<&[AccountInfo<'_>]>,
+) -> Result<()> {
+    let token_badges = token_badges.unwrap_or(&[]);
+
+    for token_mint in token_mints {
+        let hook_program_id = get_transfer_hook_program_id(token_mint)?;
+        let permanent_delegate = get_permanent_delegate(token_mint)?;
+        if hook_program_id.is_none() && permanent_delegate.is_none() {
+            continue;
+        }
+
+        let (token_badge_address, _) = Pubkey::find_program_address(
+            &[
+                b"token_badge",
+                whirlpools_config_key.as_ref(),
+                token_mint.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        let token_badge_info = token_badges
+            .iter()
+            .find(|account| account.key() == token_badge_address)
+            .ok_or(ErrorCode::MissingTokenBadge)?;
+
+        let token_badge =
+            match load_token_badge(*whirlpools_config_key, token_mint.key(), token_badge_info)? {
+                Some(token_badge) => token_badge,
+                None => continue,
+            };
+
+        if !token_badge.is_transfer_hook_program_allowed(hook_program_id) {
+            return Err(ErrorCode::TransferHookProgramNotAllowed.into());
+        }
+        if !token_badge.is_permanent_delegate_allowed(permanent_delegate) {
+            return Err(ErrorCode::PermanentDelegateNotAllowed.into());
+        }
+    }
+
+    Ok(())
 }
 
 fn is_transfer_memo_required(token_account: &InterfaceAccount<'_, TokenAccount>) -> Result<bool> {
@@ -197,10 +314,11 @@ fn is_transfer_memo_required(token_account: &InterfaceAccount<'_, TokenAccount>)
     let token_account_data = token_account_info.try_borrow_data()?;
     let token_account_unpacked =
         StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account_data)?;
-    let extension =
-        token_account_unpacked.get_extension::<extension::memo_transfer::MemoTransfer>();
+    let extension = get_extension::<extension::memo_transfer::MemoTransfer>(
+        token_account_unpacked.get_tlv_data(),
+    )?;
 
-    if let Ok(memo_transfer) = extension {
+    if let Some(memo_transfer) = extension {
         Ok(memo_transfer.require_incoming_transfer_memos.into())
     } else {
         Ok(false)
@@ -209,8 +327,9 @@ fn is_transfer_memo_required(token_account: &InterfaceAccount<'_, TokenAccount>)
 
 pub fn is_supported_token_mint(
     token_mint: &InterfaceAccount<'_, Mint>,
-    is_token_badge_initialized: bool,
+    token_badge: Option<&TokenBadge>,
 ) -> Result<bool> {
+    let is_token_badge_initialized = token_badge.is_some();
     let token_mint_info = token_mint.to_account_info();
 
     // if mint is owned by Token Program, it is supported (compatible to initialize_pool / initialize_reward)
@@ -234,8 +353,37 @@ pub fn is_supported_token_mint(
     let token_mint_unpacked =
         StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
 
-    let extensions = token_mint_unpacked.get_extension_types()?;
-    for extension in extensions {
+    let extension_types = get_extension_types(token_mint_unpacked.get_tlv_data())?;
+    for extension_type in extension_types {
+        // extensions unknown to spl-token-2022
+        match extension_type {
+            // supported
+            token_extension::EXTENSION_TYPE_SCALED_UI_AMOUNT => {
+                // Only the UI amount is scaled, the raw amounts transferred are not affected
+                continue;
+            }
+            token_extension::EXTENSION_TYPE_GROUP_POINTER
+            | token_extension::EXTENSION_TYPE_TOKEN_GROUP
+            | token_extension::EXTENSION_TYPE_GROUP_MEMBER_POINTER
+            | token_extension::EXTENSION_TYPE_TOKEN_GROUP_MEMBER => {
+                continue;
+            }
+            // supported if token badge is initialized
+            token_extension::EXTENSION_TYPE_PAUSABLE => {
+                // The pause authority can halt the transfers from and to the vaults
+                if !is_token_badge_initialized {
+                    return Ok(false);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let extension = match extension::ExtensionType::try_from(extension_type) {
+            Ok(extension) => extension,
+            // mint has unknown extensions
+            Err(_) => return Ok(false),
+        };
         match extension {
             // supported
             extension::ExtensionType::TransferFeeConfig => {}
@@ -258,14 +406,23 @@ pub fn is_supported_token_mint(
                 // ConfidentialTransferFeeConfig is also initialized to store encrypted transfer fee amount.
             }
             // supported if token badge is initialized
+            // and if the token badge allows the permanent delegate and the transfer hook program
             extension::ExtensionType::PermanentDelegate => {
-                if !is_token_badge_initialized {
-                    return Ok(false);
+                let permanent_delegate =
+                    get_permanent_delegate_from_unpacked(&token_mint_unpacked)?;
+                match token_badge {
+                    Some(token_badge)
+                        if token_badge.is_permanent_delegate_allowed(permanent_delegate) => {}
+                    _ => return Ok(false),
                 }
             }
             extension::ExtensionType::TransferHook => {
-                if !is_token_badge_initialized {
-                    return Ok(false);
+                let hook_program_id =
+                    get_transfer_hook_program_id_from_unpacked(&token_mint_unpacked)?;
+                match token_badge {
+                    Some(token_badge)
+                        if token_badge.is_transfer_hook_program_allowed(hook_program_id) => {}
+                    _ => return Ok(false),
                 }
             }
             extension::ExtensionType::MintCloseAuthority => {
@@ -279,9 +436,10 @@ pub fn is_supported_token_mint(
                 }
 
                 // reject if default state is not Initialized even if it has token badge
-                let default_state = token_mint_unpacked
-                    .get_extension::<extension::default_account_state::DefaultAccountState>(
-                )?;
+                let default_state = get_extension::<
+                    extension::default_account_state::DefaultAccountState,
+                >(token_mint_unpacked.get_tlv_data())?
+                .ok_or(ProgramError::InvalidAccountData)?;
                 let initialized: u8 = AccountState::Initialized.into();
                 if default_state.state != initialized {
                     return Ok(false);
@@ -301,19 +459,25 @@ pub fn is_supported_token_mint(
     Ok(true)
 }
 
-pub fn is_token_badge_initialized(
+/// Returns None if the TokenBadge of the mint in the WhirlpoolsConfig is not initialized.
+pub fn load_token_badge(
     whirlpools_config_key: Pubkey,
     token_mint_key: Pubkey,
-    token_badge: &UncheckedAccount<'_>,
-) -> Result<bool> {
+    token_badge: &AccountInfo<'_>,
+) -> Result<Option<TokenBadge>> {
     if *token_badge.owner != crate::id() {
-        return Ok(false);
+        return Ok(None);
     }
 
     let token_badge = TokenBadge::try_deserialize(&mut token_badge.data.borrow().as_ref())?;
 
-    Ok(token_badge.whirlpools_config == whirlpools_config_key
-        && token_badge.token_mint == token_mint_key)
+    if token_badge.whirlpools_config == whirlpools_config_key
+        && token_badge.token_mint == token_mint_key
+    {
+        Ok(Some(token_badge))
+    } else {
+        Ok(None)
+    }
 }
 
 #[derive(Debug)]
@@ -415,9 +579,9 @@ pub fn get_epoch_transfer_fee(
     let token_mint_data = token_mint_info.try_borrow_data()?;
     let token_mint_unpacked =
         StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&token_mint_data)?;
-    if let Ok(transfer_fee_config) =
-        token_mint_unpacked.get_extension::<extension::transfer_fee::TransferFeeConfig>()
-    {
+    if let Some(transfer_fee_config) = get_extension::<extension::transfer_fee::TransferFeeConfig>(
+        token_mint_unpacked.get_tlv_data(),
+    )? {
         let epoch = Clock::get()?.epoch;
         return Ok(Some(*transfer_fee_config.get_epoch_fee(epoch)));
     }
@@ -536,3 +700,400 @@ mod fuzz_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod token_badge_allowlist_tests {
+    use super::*;
+    use crate::util::test_utils::{
+        anchor_account_data, leaked_account_info, mint_data, token_2022_mint_data,
+    };
+    use anchor_spl::token::spl_token;
+    use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+
+    fn mint(
+        owner: Pubkey,
+        hook_program_id: Option<Pubkey>,
+        permanent_delegate: Option<Pubkey>,
+    ) -> InterfaceAccount<'static, Mint> {
+        let mut extensions = vec![];
+        if let Some(hook_program_id) = hook_program_id {
+            // authority, program_id
+            let mut value = Pubkey::new_unique().to_bytes().to_vec();
+            value.extend_from_slice(hook_program_id.as_ref());
+            extensions.push((u16::from(ExtensionType::TransferHook), value));
+        }
+        if let Some(permanent_delegate) = permanent_delegate {
+            extensions.push((
+                u16::from(ExtensionType::PermanentDelegate),
+                permanent_delegate.to_bytes().to_vec(),
+            ));
+        }
+        let data = if owner == spl_token::ID {
+            mint_data(0)
+        } else {
+            token_2022_mint_data(0, &extensions)
+        };
+        let account_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            owner,
+            false,
+            false,
+            data,
+        )));
+        InterfaceAccount::try_from(&*account_info).unwrap()
+    }
+
+    fn token_badge_address(whirlpools_config: &Pubkey, token_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"token_badge",
+                whirlpools_config.as_ref(),
+                token_mint.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    fn token_badge(
+        whirlpools_config: Pubkey,
+        token_mint: Pubkey,
+        transfer_hook_program_id: Pubkey,
+        permanent_delegate: Pubkey,
+    ) -> AccountInfo<'static> {
+        let token_badge = TokenBadge {
+            whirlpools_config,
+            token_mint,
+            transfer_hook_program_id,
+            permanent_delegate,
+        };
+        leaked_account_info(
+            token_badge_address(&whirlpools_config, &token_mint),
+            crate::ID,
+            false,
+            false,
+            anchor_account_data(&token_badge, TokenBadge::LEN),
+        )
+    }
+
+    #[test]
+    fn test_without_token_badges_slice() {
+        let token_mints = [
+            mint(spl_token_2022::ID, Some(Pubkey::new_unique()), None),
+            mint(spl_token_2022::ID, None, Some(Pubkey::new_unique())),
+        ];
+        for token_mint in token_mints.iter() {
+            assert_eq!(
+                verify_token_badge_allowlists(&Pubkey::new_unique(), &[token_mint], None)
+                    .unwrap_err(),
+                ErrorCode::MissingTokenBadge.into()
+            );
+        }
+
+        let token_mint = mint(spl_token_2022::ID, None, None);
+        assert!(verify_token_badge_allowlists(&Pubkey::new_unique(), &[&token_mint], None).is_ok());
+    }
+
+    #[test]
+    fn test_mints_without_hook_or_delegate() {
+        let token_mints = [
+            mint(spl_token::ID, None, None),
+            mint(spl_token_2022::ID, None, None),
+        ];
+        assert!(verify_token_badge_allowlists(
+            &Pubkey::new_unique(),
+            &[&token_mints[0], &token_mints[1]],
+            Some(&[]),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_missing_token_badge() {
+        let token_mint = mint(spl_token_2022::ID, Some(Pubkey::new_unique()), None);
+        assert_eq!(
+            verify_token_badge_allowlists(&Pubkey::new_unique(), &[&token_mint], Some(&[]))
+                .unwrap_err(),
+            ErrorCode::MissingTokenBadge.into()
+        );
+    }
+
+    #[test]
+    fn test_transfer_hook_program() {
+        let whirlpools_config = Pubkey::new_unique();
+        let hook_program_id = Pubkey::new_unique();
+        let token_mint = mint(spl_token_2022::ID, Some(hook_program_id), None);
+
+        let allowed = [token_badge(
+            whirlpools_config,
+            token_mint.key(),
+            hook_program_id,
+            Pubkey::default(),
+        )];
+        assert!(
+            verify_token_badge_allowlists(&whirlpools_config, &[&token_mint], Some(&allowed))
+                .is_ok()
+        );
+
+        let not_allowed = [token_badge(
+            whirlpools_config,
+            token_mint.key(),
+            Pubkey::new_unique(),
+            Pubkey::default(),
+        )];
+        assert_eq!(
+            verify_token_badge_allowlists(&whirlpools_config, &[&token_mint], Some(&not_allowed))
+                .unwrap_err(),
+            ErrorCode::TransferHookProgramNotAllowed.into()
+        );
+    }
+
+    #[test]
+    fn test_permanent_delegate_without_transfer_hook() {
+        let whirlpools_config = Pubkey::new_unique();
+        let permanent_delegate = Pubkey::new_unique();
+        let token_mint = mint(spl_token_2022::ID, None, Some(permanent_delegate));
+
+        let allowed = [token_badge(
+            whirlpools_config,
+            token_mint.key(),
+            Pubkey::default(),
+            permanent_delegate,
+        )];
+        assert!(
+            verify_token_badge_allowlists(&whirlpools_config, &[&token_mint], Some(&allowed))
+                .is_ok()
+        );
+
+        let not_allowed = [token_badge(
+            whirlpools_config,
+            token_mint.key(),
+            Pubkey::default(),
+            Pubkey::new_unique(),
+        )];
+        assert_eq!(
+            verify_token_badge_allowlists(&whirlpools_config, &[&token_mint], Some(&not_allowed))
+                .unwrap_err(),
+            ErrorCode::PermanentDelegateNotAllowed.into()
+        );
+    }
+
+    #[test]
+    fn test_deleted_token_badge() {
+        let whirlpools_config = Pubkey::new_unique();
+        let token_mint = mint(spl_token_2022::ID, Some(Pubkey::new_unique()), None);
+        let deleted = [leaked_account_info(
+            token_badge_address(&whirlpools_config, &token_mint.key()),
+            System::id(),
+            false,
+            false,
+            vec![],
+        )];
+        assert!(
+            verify_token_badge_allowlists(&whirlpools_config, &[&token_mint], Some(&deleted))
+                .is_ok()
+        );
+    }
+}
+
+#[cfg(test)]
+mod is_supported_token_mint_tests {
+    use super::*;
+    use crate::util::test_utils::{leaked_account_info, token_2022_mint_data};
+    use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+    use token_extension::{EXTENSION_TYPE_PAUSABLE, EXTENSION_TYPE_SCALED_UI_AMOUNT};
+
+    fn mint(extensions: &[(u16, Vec<u8>)]) -> InterfaceAccount<'static, Mint> {
+        let account_info = Box::leak(Box::new(leaked_account_info(
+            Pubkey::new_unique(),
+            spl_token_2022::ID,
+            false,
+            false,
+            token_2022_mint_data(0, extensions),
+        )));
+        InterfaceAccount::try_from(&*account_info).unwrap()
+    }
+
+    fn pausable_config(paused: bool) -> Vec<u8> {
+        // authority, paused
+        let mut value = Pubkey::new_unique().to_bytes().to_vec();
+        value.push(paused as u8);
+        value
+    }
+
+    fn scaled_ui_amount_config() -> Vec<u8> {
+        // authority, multiplier, new_multiplier_effective_timestamp, new_multiplier
+        let mut value = Pubkey::new_unique().to_bytes().to_vec();
+        value.extend_from_slice(&1f64.to_le_bytes());
+        value.extend_from_slice(&0i64.to_le_bytes());
+        value.extend_from_slice(&2f64.to_le_bytes());
+        value
+    }
+
+    #[test]
+    fn test_pausable_requires_token_badge() {
+        let token_badge = TokenBadge::default();
+        for paused in [false, true] {
+            let token_mint = mint(&[(EXTENSION_TYPE_PAUSABLE, pausable_config(paused))]);
+            assert!(!is_supported_token_mint(&token_mint, None).unwrap());
+            assert!(is_supported_token_mint(&token_mint, Some(&token_badge)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_scaled_ui_amount_is_supported() {
+        let token_mint = mint(&[(EXTENSION_TYPE_SCALED_UI_AMOUNT, scaled_ui_amount_config())]);
+        assert!(is_supported_token_mint(&token_mint, None).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_extension_types_with_known_ones() {
+        // MemoTransfer is a token account extension, so it is not supported on a mint
+        let token_mint = mint(&[
+            (EXTENSION_TYPE_SCALED_UI_AMOUNT, scaled_ui_amount_config()),
+            (u16::from(ExtensionType::MemoTransfer), vec![1]),
+        ]);
+        assert!(!is_supported_token_mint(&token_mint, None).unwrap());
+
+        let token_mint = mint(&[
+            (EXTENSION_TYPE_PAUSABLE, pausable_config(false)),
+            (u16::from(ExtensionType::MetadataPointer), vec![0; 64]),
+            (EXTENSION_TYPE_SCALED_UI_AMOUNT, scaled_ui_amount_config()),
+        ]);
+        assert!(!is_supported_token_mint(&token_mint, None).unwrap());
+        assert!(is_supported_token_mint(&token_mint, Some(&TokenBadge::default())).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_extension_type() {
+        let token_mint = mint(&[(u16::MAX, vec![0; 8])]);
+        assert!(!is_supported_token_mint(&token_mint, Some(&TokenBadge::default())).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod paused_token_mint_transfer_tests {
+    use super::*;
+    use crate::util::test_utils::{
+        anchor_account_data, leaked_account_info, leaked_program_account_info,
+        token_2022_mint_data, token_account_data,
+    };
+    use token_extension::EXTENSION_TYPE_PAUSABLE;
+
+    struct Accounts {
+        whirlpool: Account<'static, Whirlpool>,
+        authority: Signer<'static>,
+        token_mint: InterfaceAccount<'static, Mint>,
+        token_owner_account: InterfaceAccount<'static, TokenAccount>,
+        token_vault: InterfaceAccount<'static, TokenAccount>,
+        token_program: Interface<'static, TokenInterface>,
+        memo_program: Program<'static, Memo>,
+    }
+
+    fn leak(account_info: AccountInfo<'static>) -> &'static AccountInfo<'static> {
+        Box::leak(Box::new(account_info))
+    }
+
+    fn accounts(paused: bool) -> Accounts {
+        // authority, paused
+        let mut pausable_config = Pubkey::new_unique().to_bytes().to_vec();
+        pausable_config.push(paused as u8);
+
+        let token_mint = leak(leaked_account_info(
+            Pubkey::new_unique(),
+            spl_token_2022::ID,
+            false,
+            false,
+            token_2022_mint_data(1000, &[(EXTENSION_TYPE_PAUSABLE, pausable_config)]),
+        ));
+        let whirlpool = leak(leaked_account_info(
+            Pubkey::new_unique(),
+            crate::ID,
+            false,
+            true,
+            anchor_account_data(&Whirlpool::default(), Whirlpool::LEN),
+        ));
+        let authority = leak(leaked_account_info(
+            Pubkey::new_unique(),
+            System::id(),
+            true,
+            false,
+            vec![],
+        ));
+        let token_account = |owner: Pubkey| {
+            leak(leaked_account_info(
+                Pubkey::new_unique(),
+                spl_token_2022::ID,
+                false,
+                true,
+                token_account_data(*token_mint.key, owner, 500, None),
+            ))
+        };
+
+        Accounts {
+            whirlpool: Account::try_from(whirlpool).unwrap(),
+            authority: Signer::try_from(authority).unwrap(),
+            token_mint: InterfaceAccount::try_from(token_mint).unwrap(),
+            token_owner_account: InterfaceAccount::try_from(token_account(*authority.key)).unwrap(),
+            token_vault: InterfaceAccount::try_from(token_account(*whirlpool.key)).unwrap(),
+            token_program: Interface::try_from(leak(leaked_program_account_info(
+                spl_token_2022::ID,
+            )))
+            .unwrap(),
+            memo_program: Program::try_from(leak(leaked_program_account_info(memo::ID))).unwrap(),
+        }
+    }
+
+    fn transfer_from_owner_to_vault(accounts: &Accounts, amount: u64) -> Result<()> {
+        transfer_from_owner_to_vault_v2(
+            &accounts.authority,
+            &accounts.token_mint,
+            &accounts.token_owner_account,
+            &accounts.token_vault,
+            &accounts.token_program,
+            &accounts.memo_program,
+            &None,
+            amount,
+        )
+    }
+
+    fn transfer_from_vault_to_owner(accounts: &Accounts, amount: u64) -> Result<()> {
+        transfer_from_vault_to_owner_v2(
+            &accounts.whirlpool,
+            &accounts.token_mint,
+            &accounts.token_vault,
+            &accounts.token_owner_account,
+            &accounts.token_program,
+            &accounts.memo_program,
+            &None,
+            amount,
+            b"memo",
+        )
+    }
+
+    #[test]
+    fn test_zero_amount_is_skipped() {
+        let accounts = accounts(true);
+        assert!(transfer_from_owner_to_vault(&accounts, 0).is_ok());
+        assert!(transfer_from_vault_to_owner(&accounts, 0).is_ok());
+    }
+
+    #[test]
+    fn test_non_zero_amount_is_rejected() {
+        let accounts = accounts(true);
+        assert_eq!(
+            transfer_from_owner_to_vault(&accounts, 1).unwrap_err(),
+            ErrorCode::TokenMintPaused.into()
+        );
+        assert_eq!(
+            transfer_from_vault_to_owner(&accounts, 1).unwrap_err(),
+            ErrorCode::TokenMintPaused.into()
+        );
+    }
+
+    #[test]
+    fn test_is_token_mint_paused() {
+        assert!(is_token_mint_paused(&accounts(true).token_mint).unwrap());
+        assert!(!is_token_mint_paused(&accounts(false).token_mint).unwrap());
+    }
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{Extension, ExtensionType};
+use bytemuck::Pod;
+
+// Extension types introduced after the spl-token-2022 version this program is built with.
+// spl-token-2022 fails to iterate over the TLV data once it reaches one of them,
+// so the TLV data of mints and token accounts is read with the functions below.
+pub const EXTENSION_TYPE_GROUP_POINTER: u16 = 20;
+pub const EXTENSION_TYPE_TOKEN_GROUP: u16 = 21;
+pub const EXTENSION_TYPE_GROUP_MEMBER_POINTER: u16 = 22;
+pub const EXTENSION_TYPE_TOKEN_GROUP_MEMBER: u16 = 23;
+pub const EXTENSION_TYPE_SCALED_UI_AMOUNT: u16 = 25;
+pub const EXTENSION_TYPE_PAUSABLE: u16 = 26;
+
+// PausableConfig: authority (32) + paused (1)
+const PAUSABLE_CONFIG_PAUSED_OFFSET: usize = 32;
+
+const TLV_TYPE_LEN: usize = 2;
+const TLV_LENGTH_LEN: usize = 2;
+
+/// Iterates over the TLV entries, returning the extension type and the value of each entry.
+fn get_tlv_entries(tlv_data: &[u8]) -> Result<Vec<(u16, &[u8])>> {
+    let mut entries = vec![];
+    let mut start_index = 0;
+    while start_index + TLV_TYPE_LEN + TLV_LENGTH_LEN <= tlv_data.len() {
+        let length_start = start_index + TLV_TYPE_LEN;
+        let value_start = length_start + TLV_LENGTH_LEN;
+
+        let extension_type = u16::from_le_bytes([tlv_data[start_index], tlv_data[start_index + 1]]);
+        // nothing is written after an Uninitialized entry
+        if extension_type == u16::from(ExtensionType::Uninitialized) {
+            break;
+        }
+
+        let length =
+            u16::from_le_bytes([tlv_data[length_start], tlv_data[length_start + 1]]) as usize;
+        let value_end = value_start + length;
+        if value_end > tlv_data.len() {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+
+        entries.push((extension_type, &tlv_data[value_start..value_end]));
+        start_index = value_end;
+    }
+    Ok(entries)
+}
+
+/// Returns the types of all extensions in the TLV data, including the types unknown to spl-token-2022.
+pub fn get_extension_types(tlv_data: &[u8]) -> Result<Vec<u16>> {
+    Ok(get_tlv_entries(tlv_data)?
+        .into_iter()
+        .map(|(extension_type, _)| extension_type)
+        .collect())
+}
+
+/// Returns the extension if present in the TLV data.
+/// Unlike BaseStateWithExtensions::get_extension, the extension types unknown to spl-token-2022 are skipped.
+pub fn get_extension<V: Extension + Pod>(tlv_data: &[u8]) -> Result<Option<&V>> {
+    let extension_type = u16::from(V::TYPE);
+    match get_tlv_entries(tlv_data)?
+        .into_iter()
+        .find(|(entry_type, _)| *entry_type == extension_type)
+    {
+        Some((_, value)) => Ok(Some(
+            bytemuck::try_from_bytes::<V>(value).map_err(|_| ProgramError::InvalidAccountData)?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Returns true if the mint has the Pausable extension and is currently paused.
+/// Token-2022 rejects any transfer of a paused mint, even of zero tokens.
+pub fn is_mint_paused(tlv_data: &[u8]) -> Result<bool> {
+    match get_tlv_entries(tlv_data)?
+        .into_iter()
+        .find(|(entry_type, _)| *entry_type == EXTENSION_TYPE_PAUSABLE)
+    {
+        Some((_, value)) => match value.get(PAUSABLE_CONFIG_PAUSED_OFFSET) {
+            Some(paused) => Ok(*paused != 0),
+            None => Err(ProgramError::InvalidAccountData.into()),
+        },
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod token_extension_tests {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::extension::memo_transfer::MemoTransfer;
+
+    fn tlv_entry(extension_type: u16, value: &[u8]) -> Vec<u8> {
+        let mut entry = vec![];
+        entry.extend_from_slice(&extension_type.to_le_bytes());
+        entry.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        entry.extend_from_slice(value);
+        entry
+    }
+
+    fn pausable_config(paused: bool) -> Vec<u8> {
+        let mut value = vec![1u8; 32];
+        value.push(paused as u8);
+        value
+    }
+
+    #[test]
+    fn test_get_extension_types_with_unknown_types() {
+        let mut tlv_data = tlv_entry(EXTENSION_TYPE_PAUSABLE, &pausable_config(false));
+        tlv_data.extend(tlv_entry(u16::from(ExtensionType::MemoTransfer), &[1]));
+        tlv_data.extend(tlv_entry(EXTENSION_TYPE_SCALED_UI_AMOUNT, &[0; 56]));
+        // trailing Uninitialized entry
+        tlv_data.extend([0; 4]);
+
+        assert_eq!(
+            get_extension_types(&tlv_data).unwrap(),
+            vec![
+                EXTENSION_TYPE_PAUSABLE,
+                u16::from(ExtensionType::MemoTransfer),
+                EXTENSION_TYPE_SCALED_UI_AMOUNT,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_extension_skips_unknown_types() {
+        let mut tlv_data = tlv_entry(EXTENSION_TYPE_PAUSABLE, &pausable_config(false));
+        tlv_data.extend(tlv_entry(u16::from(ExtensionType::MemoTransfer), &[1]));
+
+        let memo_transfer = get_extension::<MemoTransfer>(&tlv_data).unwrap().unwrap();
+        assert!(bool::from(memo_transfer.require_incoming_transfer_memos));
+
+        let tlv_data = tlv_entry(EXTENSION_TYPE_PAUSABLE, &pausable_config(false));
+        assert!(get_extension::<MemoTransfer>(&tlv_data).unwrap().is_none());
+        assert!(get_extension::<MemoTransfer>(&[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_get_extension_types_malformed() {
+        let mut tlv_data = tlv_entry(u16::from(ExtensionType::MemoTransfer), &[1]);
+        tlv_data.truncate(tlv_data.len() - 1);
+        assert!(get_extension_types(&tlv_data).is_err());
+    }
+
+    #[test]
+    fn test_is_mint_paused() {
+        assert!(!is_mint_paused(&[]).unwrap());
+
+        let tlv_data = tlv_entry(EXTENSION_TYPE_PAUSABLE, &pausable_config(false));
+        assert!(!is_mint_paused(&tlv_data).unwrap());
+
+        let mut tlv_data = tlv_entry(EXTENSION_TYPE_TOKEN_GROUP, &[0; 80]);
+        tlv_data.extend(tlv_entry(EXTENSION_TYPE_PAUSABLE, &pausable_config(true)));
+        assert!(is_mint_paused(&tlv_data).unwrap());
+    }
+}